@@ -2,7 +2,7 @@ use std::{path::PathBuf, str::FromStr as _};
 
 use anyhow::anyhow;
 use fj_host::Parameters;
-use fj_kernel::algorithms::Tolerance;
+use fj_kernel::algorithms::{Quality, Tolerance};
 use fj_math::Scalar;
 
 /// Fornjot - Experimental CAD System
@@ -17,13 +17,40 @@ pub struct Args {
     #[clap(short, long)]
     pub export: Option<PathBuf>,
 
+    /// Write exported STL files as text, instead of binary
+    #[clap(long)]
+    pub ascii: bool,
+
+    /// Display the mesh in this STL or OBJ file next to the model
+    #[clap(long)]
+    pub reference: Option<PathBuf>,
+
     /// Parameters for the model, each in the form `key=value`
     #[clap(short, long, parse(try_from_str = parse_parameters))]
     pub parameters: Option<Parameters>,
 
+    /// List the parameters the model declares, then exit
+    #[clap(long)]
+    pub list_parameters: bool,
+
     /// Model deviation tolerance
+    ///
+    /// If neither a tolerance nor a quality is specified, the viewer derives
+    /// the tolerance from the size of the model on screen.
     #[clap[short, long, parse(try_from_str = parse_tolerance)]]
     pub tolerance: Option<Tolerance>,
+
+    /// Model approximation quality (draft, normal, high, or ultra)
+    ///
+    /// Ignored, if a tolerance is specified.
+    #[clap(short, long, parse(try_from_str = parse_quality))]
+    pub quality: Option<Quality>,
+
+    /// Approximate curves relative to their curvature
+    ///
+    /// Small curves get as many segments as large ones, instead of fewer.
+    #[clap(long)]
+    pub adaptive: bool,
 }
 
 impl Args {
@@ -66,3 +93,15 @@ fn parse_tolerance(input: &str) -> anyhow::Result<Tolerance> {
 
     Ok(tolerance)
 }
+
+fn parse_quality(input: &str) -> anyhow::Result<Quality> {
+    let quality = match input {
+        "draft" => Quality::Draft,
+        "normal" => Quality::Normal,
+        "high" => Quality::High,
+        "ultra" => Quality::Ultra,
+        _ => return Err(anyhow!("Unknown quality preset: {input}")),
+    };
+
+    Ok(quality)
+}
@@ -15,14 +15,20 @@
 mod args;
 mod config;
 
-use std::path::PathBuf;
+use std::{
+    fs::File,
+    io::{BufWriter, Write as _},
+    path::PathBuf,
+};
 
 use anyhow::{anyhow, Context as _};
-use fj_export::export;
+use fj_export::{export_step, export_with_options, ExportOptions, StlFormat};
 use fj_host::{Model, Parameters};
+use fj_import::import_mesh;
 use fj_interop::status_report::StatusReport;
 use fj_operations::shape_processor::ShapeProcessor;
 use fj_window::run::run;
+use tracing::warn;
 use tracing_subscriber::fmt::format;
 use tracing_subscriber::EnvFilter;
 
@@ -59,21 +65,80 @@ fn main() -> anyhow::Result<()> {
         .with_context(|| format!("Failed to load model: {}", path.display()))?;
     let parameters = args.parameters.unwrap_or_else(Parameters::empty);
 
+    if args.list_parameters {
+        for argument in model.argument_metadata(&mut status)? {
+            println!("{argument}");
+        }
+
+        return Ok(());
+    }
+
     let shape_processor = ShapeProcessor {
         tolerance: args.tolerance,
+        quality: args.quality.unwrap_or_default(),
+        adaptive: args.adaptive,
     };
 
     if let Some(path) = args.export {
         let shape = model.load_once(&parameters, &mut status)?;
+
+        // STEP is written from the boundary representation, all other formats
+        // from the triangle mesh.
+        let is_step = path.extension().map_or(false, |extension| {
+            let extension = extension.to_ascii_uppercase();
+            extension == "STEP" || extension == "STP"
+        });
+        if is_step {
+            let faces = shape_processor.compute_faces(&shape)?;
+
+            let mut file = BufWriter::new(File::create(&path)?);
+            export_step(&faces, &mut file)?;
+            file.flush()?;
+
+            return Ok(());
+        }
+
         let shape = shape_processor.process(&shape)?;
 
-        export(&shape.mesh, &path)?;
+        // Most consumers of exported meshes, 3D printers in particular, can't
+        // deal with holes in them. Still export, but let the user know.
+        if let Err(defects) = shape.mesh.check_watertight() {
+            warn!("Exporting mesh that might not print correctly.\n{defects}");
+        }
+
+        let options = ExportOptions {
+            stl_format: if args.ascii {
+                StlFormat::Ascii
+            } else {
+                StlFormat::Binary
+            },
+            ..ExportOptions::default()
+        };
+        export_with_options(&shape.mesh, &options, &path)?;
 
         return Ok(());
     }
 
+    let reference = args
+        .reference
+        .map(|path| {
+            import_mesh(&path).with_context(|| {
+                format!("Failed to load reference: {}", path.display())
+            })
+        })
+        .transpose()?;
+
+    // Unless the user asked for a specific tolerance or quality, choose the
+    // tolerance that looks smooth from where the model is viewed.
+    let screen_error = (args.tolerance.is_none() && args.quality.is_none())
+        .then_some(MAX_SCREEN_ERROR);
+
     let watcher = model.load_and_watch(parameters)?;
-    run(watcher, shape_processor, status)?;
+    run(watcher, shape_processor, status, reference, screen_error)?;
 
     Ok(())
 }
+
+/// The maximum deviation of the model from its approximation on screen, in
+/// pixels
+const MAX_SCREEN_ERROR: f64 = 0.5;
@@ -0,0 +1,331 @@
+use std::io::Write;
+
+use fj_interop::mesh::Mesh;
+use fj_math::{Point, Triangle, Vector};
+use serde_json::json;
+
+use crate::Error;
+
+/// Export the provided mesh as binary glTF 2.0 (`.glb`)
+///
+/// The mesh is written as a single primitive, without indices. Vertices are
+/// not shared between triangles, so each triangle can have its own normal and
+/// color. Normals are computed from the triangles; colors are written to
+/// `COLOR_0`, as linear RGBA values.
+///
+/// An empty mesh results in a valid file that doesn't contain any meshes.
+pub fn export_gltf(
+    mesh: &Mesh<Point<3>>,
+    writer: &mut impl Write,
+) -> Result<(), Error> {
+    let (json, buffer) = gltf(mesh);
+
+    let mut json = serde_json::to_vec(&json)?;
+    pad(&mut json, b' ');
+
+    let mut buffer = buffer;
+    pad(&mut buffer, 0);
+
+    let mut length = HEADER_LENGTH + CHUNK_HEADER_LENGTH + json.len();
+    if !buffer.is_empty() {
+        length += CHUNK_HEADER_LENGTH + buffer.len();
+    }
+
+    writer.write_all(b"glTF")?;
+    writer.write_all(&2u32.to_le_bytes())?;
+    writer.write_all(&chunk_length(length)?.to_le_bytes())?;
+
+    write_chunk(writer, CHUNK_TYPE_JSON, &json)?;
+    if !buffer.is_empty() {
+        write_chunk(writer, CHUNK_TYPE_BIN, &buffer)?;
+    }
+
+    Ok(())
+}
+
+/// Export the provided mesh as glTF 2.0 with JSON encoding (`.gltf`)
+///
+/// Writes the same data as [`export_gltf`], but as a JSON document. The
+/// binary buffer is embedded as a base64 data URI, so the file is
+/// self-contained.
+pub fn export_gltf_embedded(
+    mesh: &Mesh<Point<3>>,
+    writer: &mut impl Write,
+) -> Result<(), Error> {
+    let (mut json, buffer) = gltf(mesh);
+
+    if !buffer.is_empty() {
+        json["buffers"][0]["uri"] = format!(
+            "data:application/octet-stream;base64,{}",
+            base64::encode(&buffer)
+        )
+        .into();
+    }
+
+    serde_json::to_writer(writer, &json)?;
+
+    Ok(())
+}
+
+/// Build the JSON document and binary buffer for a mesh
+fn gltf(mesh: &Mesh<Point<3>>) -> (serde_json::Value, Vec<u8>) {
+    let mut positions = Vec::new();
+    let mut normals = Vec::new();
+    let mut colors = Vec::new();
+
+    for triangle in mesh.triangles() {
+        let points = triangle.inner.points();
+        let normal = Triangle::<3>::from_points(points)
+            .and_then(|triangle| triangle.normal())
+            .unwrap_or_else(|| Vector::from([0., 0., 0.]));
+
+        for point in points {
+            positions.push(point.coords.components.map(|s| s.into_f32()));
+            normals.push(normal.components.map(|s| s.into_f32()));
+            colors.push(triangle.color.to_linear());
+        }
+    }
+
+    if positions.is_empty() {
+        (json!({ "asset": asset() }), Vec::new())
+    } else {
+        gltf_mesh(&positions, &normals, &colors)
+    }
+}
+
+/// Build the JSON document and binary buffer for a non-empty mesh
+fn gltf_mesh(
+    positions: &[[f32; 3]],
+    normals: &[[f32; 3]],
+    colors: &[[f32; 4]],
+) -> (serde_json::Value, Vec<u8>) {
+    let mut buffer = Vec::new();
+    let mut buffer_views = Vec::new();
+
+    // All elements are made of `f32`s, so every buffer view starts at a
+    // multiple of 4, as the spec requires.
+    let mut push_view = |data: &[f32]| {
+        let offset = buffer.len();
+        for value in data {
+            buffer.extend(value.to_le_bytes());
+        }
+
+        buffer_views.push(json!({
+            "buffer": 0,
+            "byteOffset": offset,
+            "byteLength": buffer.len() - offset,
+            "target": ARRAY_BUFFER,
+        }));
+    };
+    push_view(positions.concat().as_slice());
+    push_view(normals.concat().as_slice());
+    push_view(colors.concat().as_slice());
+
+    // The spec requires bounds for positions. They must match the `f32`
+    // values exactly, so compute them after the conversion.
+    let mut min = [f32::INFINITY; 3];
+    let mut max = [f32::NEG_INFINITY; 3];
+    for position in positions {
+        for i in 0..3 {
+            min[i] = min[i].min(position[i]);
+            max[i] = max[i].max(position[i]);
+        }
+    }
+
+    let count = positions.len();
+    let json = json!({
+        "asset": asset(),
+        "scene": 0,
+        "scenes": [{ "nodes": [0] }],
+        "nodes": [{ "mesh": 0 }],
+        "meshes": [{
+            "primitives": [{
+                "attributes": { "POSITION": 0, "NORMAL": 1, "COLOR_0": 2 },
+                "mode": TRIANGLES,
+            }],
+        }],
+        "buffers": [{ "byteLength": buffer.len() }],
+        "bufferViews": buffer_views,
+        "accessors": [
+            {
+                "bufferView": 0,
+                "componentType": FLOAT,
+                "count": count,
+                "type": "VEC3",
+                "min": min,
+                "max": max,
+            },
+            {
+                "bufferView": 1,
+                "componentType": FLOAT,
+                "count": count,
+                "type": "VEC3",
+            },
+            {
+                "bufferView": 2,
+                "componentType": FLOAT,
+                "count": count,
+                "type": "VEC4",
+            },
+        ],
+    });
+
+    (json, buffer)
+}
+
+/// Pad the data of a chunk to a multiple of 4 bytes
+fn pad(data: &mut Vec<u8>, padding: u8) {
+    while data.len() % 4 != 0 {
+        data.push(padding);
+    }
+}
+
+fn write_chunk(
+    writer: &mut impl Write,
+    chunk_type: u32,
+    data: &[u8],
+) -> Result<(), Error> {
+    writer.write_all(&chunk_length(data.len())?.to_le_bytes())?;
+    writer.write_all(&chunk_type.to_le_bytes())?;
+    writer.write_all(data)?;
+    Ok(())
+}
+
+fn chunk_length(length: usize) -> Result<u32, Error> {
+    length.try_into().map_err(|_| Error::InvalidTriangleCount)
+}
+
+fn asset() -> serde_json::Value {
+    json!({ "version": "2.0", "generator": "Fornjot" })
+}
+
+const HEADER_LENGTH: usize = 12;
+const CHUNK_HEADER_LENGTH: usize = 8;
+
+const CHUNK_TYPE_JSON: u32 = 0x4E4F534A;
+const CHUNK_TYPE_BIN: u32 = 0x004E4942;
+
+const ARRAY_BUFFER: u32 = 34962;
+const FLOAT: u32 = 5126;
+const TRIANGLES: u32 = 4;
+
+#[cfg(test)]
+mod tests {
+    use fj_interop::mesh::{Color, Mesh};
+    use fj_math::Point;
+
+    use super::{export_gltf, export_gltf_embedded};
+
+    fn parse(glb: &[u8]) -> (serde_json::Value, &[u8]) {
+        let u32_at = |offset: usize| {
+            u32::from_le_bytes(glb[offset..offset + 4].try_into().unwrap())
+                as usize
+        };
+
+        assert_eq!(&glb[0..4], b"glTF");
+        assert_eq!(u32_at(4), 2);
+        assert_eq!(u32_at(8), glb.len());
+
+        let json_length = u32_at(12);
+        assert_eq!(json_length % 4, 0);
+        assert_eq!(&glb[16..20], b"JSON");
+        let json = serde_json::from_slice(&glb[20..20 + json_length]).unwrap();
+
+        let bin = 20 + json_length;
+        let bin_length = u32_at(bin);
+        assert_eq!(bin_length % 4, 0);
+        assert_eq!(&glb[bin + 4..bin + 8], b"BIN\0");
+        assert_eq!(bin + 8 + bin_length, glb.len());
+
+        (json, &glb[bin + 8..])
+    }
+
+    #[test]
+    fn export_glb() {
+        let mut mesh = Mesh::new();
+        let [a, b, c, d] =
+            [[0., 0., 0.], [1., 0., 0.], [0., 1., 0.], [0., 0., 2.]]
+                .map(Point::from);
+        mesh.push_triangle([a, c, b], Color::rgb(255, 0, 0));
+        mesh.push_triangle([a, b, d], Color::rgb(0, 255, 0));
+
+        let mut glb = Vec::new();
+        export_gltf(&mesh, &mut glb).unwrap();
+
+        let (json, bin) = parse(&glb);
+
+        assert_eq!(json["meshes"].as_array().unwrap().len(), 1);
+        let primitives = json["meshes"][0]["primitives"].as_array().unwrap();
+        assert_eq!(primitives.len(), 1);
+
+        let position = &json["accessors"][primitives[0]["attributes"]
+            ["POSITION"]
+            .as_u64()
+            .unwrap() as usize];
+        assert_eq!(position["count"], 6);
+        assert_eq!(position["min"], serde_json::json!([0., 0., 0.]));
+        assert_eq!(position["max"], serde_json::json!([1., 1., 2.]));
+
+        // Every buffer view must be aligned and within the buffer.
+        assert_eq!(json["buffers"][0]["byteLength"], bin.len());
+        for view in json["bufferViews"].as_array().unwrap() {
+            let offset = view["byteOffset"].as_u64().unwrap() as usize;
+            let length = view["byteLength"].as_u64().unwrap() as usize;
+
+            assert_eq!(offset % 4, 0);
+            assert!(offset + length <= bin.len());
+        }
+
+        // Colors are per triangle.
+        let colors = &json["bufferViews"][2];
+        let offset = colors["byteOffset"].as_u64().unwrap() as usize;
+        let color = |vertex: usize| {
+            let start = offset + vertex * 16;
+            bin[start..start + 16]
+                .chunks(4)
+                .map(|c| f32::from_le_bytes(c.try_into().unwrap()))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(color(2), [1., 0., 0., 1.]);
+        assert_eq!(color(3), [0., 1., 0., 1.]);
+    }
+
+    #[test]
+    fn export_empty_mesh() {
+        let mut glb = Vec::new();
+        export_gltf(&Mesh::new(), &mut glb).unwrap();
+
+        assert_eq!(&glb[0..4], b"glTF");
+        assert_eq!(glb.len() % 4, 0);
+
+        let json: serde_json::Value =
+            serde_json::from_slice(&glb[20..]).unwrap();
+        assert_eq!(json["asset"]["version"], "2.0");
+        assert!(json.get("meshes").is_none());
+    }
+
+    #[test]
+    fn export_embedded() {
+        let mut mesh = Mesh::new();
+        let [a, b, c] =
+            [[0., 0., 0.], [1., 0., 0.], [0., 1., 0.]].map(Point::from);
+        mesh.push_triangle([a, b, c], Color::rgb(0, 0, 255));
+
+        let mut glb = Vec::new();
+        export_gltf(&mesh, &mut glb).unwrap();
+        let (_, bin) = parse(&glb);
+
+        let mut gltf = Vec::new();
+        export_gltf_embedded(&mesh, &mut gltf).unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&gltf).unwrap();
+
+        let uri = json["buffers"][0]["uri"].as_str().unwrap();
+        let data = uri
+            .strip_prefix("data:application/octet-stream;base64,")
+            .unwrap();
+        let data = base64::decode(data).unwrap();
+
+        assert_eq!(json["buffers"][0]["byteLength"], data.len());
+        assert_eq!(data, bin);
+    }
+}
@@ -14,26 +14,92 @@
 
 #![warn(missing_docs)]
 
-use std::{fs::File, path::Path};
+mod gltf;
+mod obj;
+mod options;
+mod step;
+mod stl;
+mod svg;
+mod three_mf;
+
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    path::Path,
+};
 
 use thiserror::Error;
 
 use fj_interop::mesh::Mesh;
-use fj_math::{Point, Triangle};
+use fj_math::Point;
+
+pub use self::{
+    gltf::{export_gltf, export_gltf_embedded},
+    obj::{export_obj, export_obj_with_options, SMOOTHING_ANGLE},
+    options::{weld_vertices, ExportOptions},
+    step::export_step,
+    stl::{export_stl, StlFormat},
+    svg::{export_svg, export_svg_with_stroke_width, DEFAULT_STROKE_WIDTH},
+    three_mf::{export_3mf, Unit},
+};
 
 /// Export the provided mesh to the file at the given path.
 ///
 /// This function will create a file if it does not exist, and will truncate it if it does.
 ///
-/// Currently 3MF & STL file types are supported. The case insensitive file extension of
-/// the provided path is used to switch between supported types.
+/// Currently 3MF, STL, OBJ, GLB (binary glTF) & glTF file types are supported. The case insensitive file extension of
+/// the provided path is used to switch between supported types. STL files are
+/// written in binary; use [`export_with_options`] to write ASCII STL instead.
 pub fn export(mesh: &Mesh<Point<3>>, path: &Path) -> Result<(), Error> {
+    export_with_options(mesh, &ExportOptions::default(), path)
+}
+
+/// Export the provided mesh to the file at the given path, using the options
+///
+/// See [`export`].
+pub fn export_with_options(
+    mesh: &Mesh<Point<3>>,
+    options: &ExportOptions,
+    path: &Path,
+) -> Result<(), Error> {
+    let mesh = &options.prepare(mesh);
+
     match path.extension() {
         Some(extension) if extension.to_ascii_uppercase() == "3MF" => {
-            export_3mf(mesh, path)
+            let file = BufWriter::new(File::create(path)?);
+            export_3mf(mesh, options.unit, file)
         }
         Some(extension) if extension.to_ascii_uppercase() == "STL" => {
-            export_stl(mesh, path)
+            let mut file = BufWriter::new(File::create(path)?);
+            export_stl(mesh, options.stl_format, &mut file)?;
+
+            file.flush()?;
+            Ok(())
+        }
+        Some(extension) if extension.to_ascii_uppercase() == "OBJ" => {
+            let mut file = BufWriter::new(File::create(path)?);
+
+            // The mesh is already prepared, so only the formatting options
+            // are left to apply.
+            let options = ExportOptions {
+                weld_tolerance: None,
+                ..*options
+            };
+            export_obj_with_options(mesh, &options, &mut file)?;
+
+            file.flush()?;
+            Ok(())
+        }
+        Some(extension) if extension.to_ascii_uppercase() == "GLB" => {
+            let mut file = File::create(path)?;
+            export_gltf(mesh, &mut file)
+        }
+        Some(extension) if extension.to_ascii_uppercase() == "GLTF" => {
+            let mut file = BufWriter::new(File::create(path)?);
+            export_gltf_embedded(mesh, &mut file)?;
+
+            file.flush()?;
+            Ok(())
         }
         Some(extension) => Err(Error::InvalidExtension(
             extension.to_string_lossy().into_owned(),
@@ -42,76 +108,6 @@ pub fn export(mesh: &Mesh<Point<3>>, path: &Path) -> Result<(), Error> {
     }
 }
 
-fn export_3mf(mesh: &Mesh<Point<3>>, path: &Path) -> Result<(), Error> {
-    let vertices = mesh.vertices().map(|vertex| vertex.into()).collect();
-
-    let indices: Vec<_> = mesh.indices().collect();
-    let triangles = indices
-        .chunks(3)
-        .map(|triangle| {
-            [
-                triangle[0] as usize,
-                triangle[1] as usize,
-                triangle[2] as usize,
-            ]
-        })
-        .collect();
-
-    let mesh = threemf::TriangleMesh {
-        vertices,
-        triangles,
-    };
-
-    threemf::write(path, &mesh)?;
-
-    Ok(())
-}
-
-fn export_stl(mesh: &Mesh<Point<3>>, path: &Path) -> Result<(), Error> {
-    let points = mesh
-        .triangles()
-        .map(|triangle| triangle.inner.points())
-        .collect::<Vec<_>>();
-
-    let vertices = points.iter().map(|points| {
-        points.map(|point| point.coords.components.map(|s| s.into_f32()))
-    });
-
-    let normals = points
-        .iter()
-        .map(|&points| points.into())
-        .map(|triangle: Triangle<3>| triangle.normal())
-        .map(|vector| vector.components.map(|s| s.into_f32()));
-
-    let triangles = vertices
-        .zip(normals)
-        .map(|([v1, v2, v3], normal)| stl::Triangle {
-            normal,
-            v1,
-            v2,
-            v3,
-            attr_byte_count: 0,
-        })
-        .collect::<Vec<_>>();
-
-    let mut file = File::create(path)?;
-
-    let binary_stl_file = stl::BinaryStlFile {
-        header: stl::BinaryStlHeader {
-            header: [0u8; 80],
-            num_triangles: triangles
-                .len()
-                .try_into()
-                .map_err(|_| Error::InvalidTriangleCount)?,
-        },
-        triangles,
-    };
-
-    stl::write_stl(&mut file, &binary_stl_file)?;
-
-    Ok(())
-}
-
 /// An error that can occur while exporting
 #[derive(Debug, Error)]
 pub enum Error {
@@ -131,7 +127,11 @@ pub enum Error {
     #[error("maximum triangle count exceeded")]
     InvalidTriangleCount,
 
-    /// Threemf error whilst exporting to 3MF file
-    #[error("threemf error whilst exporting to 3MF file")]
-    ThreeMF(#[from] threemf::Error),
+    /// Error serializing the JSON part of a glTF file
+    #[error("error serializing glTF JSON")]
+    Json(#[from] serde_json::Error),
+
+    /// Error writing the ZIP archive of a 3MF file
+    #[error("error writing 3MF archive")]
+    Zip(#[from] zip::result::ZipError),
 }
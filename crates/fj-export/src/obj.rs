@@ -0,0 +1,272 @@
+use std::{collections::HashMap, io::Write};
+
+use fj_interop::mesh::Mesh;
+use fj_math::{Point, Scalar, Vector};
+
+use crate::{Error, ExportOptions};
+
+/// Export the provided mesh as Wavefront OBJ
+///
+/// Uses the default [`ExportOptions`]. See [`export_obj_with_options`].
+pub fn export_obj(
+    mesh: &Mesh<Point<3>>,
+    writer: &mut impl Write,
+) -> Result<(), Error> {
+    export_obj_with_options(mesh, &ExportOptions::default(), writer)
+}
+
+/// Export the provided mesh as Wavefront OBJ
+///
+/// Writes one `v` line per unique vertex and one `vn` line per unique vertex
+/// normal, followed by one `f` line per triangle. Colors are not written.
+///
+/// The vertex normals are smoothed: Where triangles meet at a vertex, their
+/// normals are averaged, weighted by area. Triangles that meet at an angle
+/// larger than [`SMOOTHING_ANGLE`] keep separate normals, so the edges of a
+/// cube stay sharp, while a cylinder looks round.
+pub fn export_obj_with_options(
+    mesh: &Mesh<Point<3>>,
+    options: &ExportOptions,
+    writer: &mut impl Write,
+) -> Result<(), Error> {
+    let mesh = options.prepare(mesh);
+
+    for vertex in mesh.vertices() {
+        let [x, y, z] = vertex.coords.components;
+
+        writeln!(
+            writer,
+            "v {} {} {}",
+            format_coordinate(x, options.decimal_places),
+            format_coordinate(y, options.decimal_places),
+            format_coordinate(z, options.decimal_places),
+        )?;
+    }
+
+    let (normals, triangle_normals) = vertex_normals(&mesh);
+    for normal in normals {
+        let [x, y, z] = normal.components;
+
+        writeln!(
+            writer,
+            "vn {} {} {}",
+            format_coordinate(x, options.decimal_places),
+            format_coordinate(y, options.decimal_places),
+            format_coordinate(z, options.decimal_places),
+        )?;
+    }
+
+    let indices: Vec<_> = mesh.indices().collect();
+    for (triangle, normals) in indices.chunks(3).zip(triangle_normals) {
+        // OBJ indices start at one.
+        let [a, b, c] = [0, 1, 2].map(|i| triangle[i] + 1);
+        let [na, nb, nc] = normals.map(|i| i + 1);
+        writeln!(writer, "f {a}//{na} {b}//{nb} {c}//{nc}")?;
+    }
+
+    Ok(())
+}
+
+/// The largest angle, in degrees, between triangles that are smoothed
+///
+/// See [`export_obj_with_options`].
+pub const SMOOTHING_ANGLE: f64 = 60.;
+
+/// Compute the smoothed normals at the corners of the mesh's triangles
+///
+/// Returns the unique normals, and the indices of the normals at each corner of
+/// each triangle.
+fn vertex_normals(mesh: &Mesh<Point<3>>) -> (Vec<Vector<3>>, Vec<[usize; 3]>) {
+    let indices: Vec<_> = mesh.indices().collect();
+    let adjacency = mesh.adjacency();
+
+    // The magnitude of these is twice the area of the triangle, which is what
+    // weighs them when they're added up.
+    let triangle_normals: Vec<_> = mesh
+        .triangles()
+        .map(|triangle| {
+            let [a, b, c] = triangle.inner.points();
+            (b - a).cross(&(c - a))
+        })
+        .collect();
+
+    let min_cos = Scalar::from_f64(SMOOTHING_ANGLE.to_radians().cos());
+    let is_smooth = |a: Vector<3>, b: Vector<3>| {
+        // Degenerate triangles have no direction of their own, so they are
+        // smoothed with everything.
+        a.magnitude() == Scalar::ZERO
+            || b.magnitude() == Scalar::ZERO
+            || a.normalize().dot(&b.normalize()) >= min_cos
+    };
+
+    let mut normals = Vec::new();
+    let mut normal_indices = HashMap::new();
+
+    let corner_normals = indices
+        .chunks(3)
+        .enumerate()
+        .map(|(t, triangle)| {
+            [0, 1, 2].map(|i| {
+                let normal = adjacency
+                    .incident_triangles(triangle[i])
+                    .iter()
+                    .map(|&n| triangle_normals[n])
+                    .filter(|&n| is_smooth(triangle_normals[t], n))
+                    .fold(Vector::from([0., 0., 0.]), |sum, n| sum + n);
+                let normal = if normal.magnitude() == Scalar::ZERO {
+                    normal
+                } else {
+                    normal.normalize()
+                };
+
+                *normal_indices.entry(normal).or_insert_with(|| {
+                    normals.push(normal);
+                    normals.len() - 1
+                })
+            })
+        })
+        .collect();
+
+    (normals, corner_normals)
+}
+
+fn format_coordinate(value: Scalar, decimal_places: Option<usize>) -> String {
+    let value = value.into_f64();
+
+    // `Display` for floating-point numbers never uses scientific notation.
+    let formatted = match decimal_places {
+        Some(decimal_places) => format!("{value:.decimal_places$}"),
+        None => format!("{value}"),
+    };
+
+    // Rounding might turn a small negative value into `-0`.
+    match formatted.strip_prefix('-') {
+        Some(rest) if rest.chars().all(|c| c == '0' || c == '.') => {
+            rest.to_string()
+        }
+        _ => formatted,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_interop::mesh::{Color, Mesh};
+    use fj_math::{Point, Scalar};
+
+    use crate::ExportOptions;
+
+    use super::{export_obj, export_obj_with_options};
+
+    fn export(mesh: &Mesh<Point<3>>, options: &ExportOptions) -> String {
+        let mut obj = Vec::new();
+        export_obj_with_options(mesh, options, &mut obj).unwrap();
+        String::from_utf8(obj).unwrap()
+    }
+
+    fn lines<'a>(obj: &'a str, prefix: &str) -> Vec<&'a str> {
+        obj.lines()
+            .filter(|line| line.starts_with(prefix))
+            .collect()
+    }
+
+    #[test]
+    fn vertices_and_faces() {
+        let mut mesh = Mesh::new();
+        let [a, b, c, d] =
+            [[0., 0., 0.], [1., 0., 0.], [0., 1., 0.], [0., 0., 1e-7]]
+                .map(Point::from);
+        mesh.push_triangle([a, b, c], Color::default());
+        mesh.push_triangle([a, c, d], Color::default());
+
+        let mut obj = Vec::new();
+        export_obj(&mesh, &mut obj).unwrap();
+        let obj = String::from_utf8(obj).unwrap();
+
+        assert_eq!(
+            obj,
+            "v 0 0 0\n\
+             v 1 0 0\n\
+             v 0 1 0\n\
+             v 0 0 0.0000001\n\
+             vn 0 0 1\n\
+             vn 1 0 0\n\
+             f 1//1 2//1 3//1\n\
+             f 1//2 3//2 4//2\n"
+        );
+    }
+
+    #[test]
+    fn decimal_places() {
+        let mut mesh = Mesh::new();
+        let [a, b, c] = [
+            [0.123456, -0.0001, 2.],
+            [1.0005, 0., 0.],
+            [0., 1. / 3., 1e-12],
+        ]
+        .map(Point::from);
+        mesh.push_triangle([a, b, c], Color::default());
+
+        let options = ExportOptions {
+            decimal_places: Some(3),
+            ..ExportOptions::default()
+        };
+        let obj = export(&mesh, &options);
+
+        assert_eq!(
+            lines(&obj, "v "),
+            [
+                "v 0.123 0.000 2.000",
+                "v 1.000 0.000 0.000",
+                "v 0.000 0.333 0.000",
+            ]
+        );
+    }
+
+    #[test]
+    fn weld_near_duplicates() {
+        // Two triangles that share an edge, except that the vertices of that
+        // edge are slightly off in one of them.
+        let mut mesh = Mesh::new();
+        let [a, b, c, d] =
+            [[0., 0., 0.], [1., 0., 0.], [0., 1., 0.], [1., 1., 0.]]
+                .map(Point::from);
+        let [b2, c2] = [[1. + 1e-9, 0., 0.], [0., 1., 1e-9]].map(Point::from);
+        mesh.push_triangle([a, b, c], Color::default());
+        mesh.push_triangle([b2, d, c2], Color::default());
+
+        let unwelded = export(&mesh, &ExportOptions::default());
+        assert_eq!(lines(&unwelded, "v ").len(), 6);
+
+        let options = ExportOptions {
+            weld_tolerance: Some(Scalar::from_f64(1e-6)),
+            ..ExportOptions::default()
+        };
+        let welded = export(&mesh, &options);
+
+        assert_eq!(lines(&welded, "v ").len(), 4);
+        assert_eq!(
+            lines(&welded, "f "),
+            ["f 1//1 2//1 3//1", "f 2//1 4//1 3//1"]
+        );
+    }
+
+    #[test]
+    fn smoothed_normals() {
+        // Two triangles that meet at a shallow angle
+        let mut mesh = Mesh::new();
+        let [a, b, c, d] =
+            [[0., 0., 0.], [1., 0., 0.], [0., 1., 0.], [1., 1., 0.1]]
+                .map(Point::from);
+        mesh.push_triangle([a, b, c], Color::default());
+        mesh.push_triangle([b, d, c], Color::default());
+
+        let obj = export(&mesh, &ExportOptions::default());
+
+        // The shared vertices get the same normal, between those of the two
+        // triangles.
+        let normals = lines(&obj, "vn ");
+        assert_eq!(normals.len(), 3);
+        assert_eq!(normals[0], "vn 0 0 1");
+        assert_eq!(lines(&obj, "f "), ["f 1//1 2//2 3//2", "f 2//2 4//3 3//2"]);
+    }
+}
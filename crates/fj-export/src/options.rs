@@ -0,0 +1,111 @@
+use std::collections::HashMap;
+
+use fj_interop::mesh::Mesh;
+use fj_math::{Point, Scalar};
+
+use crate::{StlFormat, Unit};
+
+/// Options that control how a mesh is written
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ExportOptions {
+    /// The number of decimal places that coordinates are written with
+    ///
+    /// Only affects text-based formats. Coordinates are always written in
+    /// fixed-point notation, never in scientific notation. If `None`, they are
+    /// written with as many decimal places as required to represent them
+    /// exactly.
+    pub decimal_places: Option<usize>,
+
+    /// Merge vertices that are closer to each other than this, before writing
+    ///
+    /// See [`weld_vertices`].
+    pub weld_tolerance: Option<Scalar>,
+
+    /// The encoding that STL files are written with
+    pub stl_format: StlFormat,
+
+    /// The unit that coordinates are written in
+    ///
+    /// Only affects formats that record units. Coordinates are never scaled.
+    pub unit: Unit,
+}
+
+impl ExportOptions {
+    /// Apply the options that modify the mesh itself
+    pub(crate) fn prepare(&self, mesh: &Mesh<Point<3>>) -> Mesh<Point<3>> {
+        match self.weld_tolerance {
+            Some(tolerance) => weld_vertices(mesh, tolerance),
+            None => mesh.clone(),
+        }
+    }
+}
+
+/// Merge vertices that are within `tolerance` of each other
+///
+/// Vertices are visited in order. A vertex that is within the tolerance of an
+/// earlier vertex, which was kept, is replaced by that vertex. Triangles that
+/// collapse, because two of their vertices are merged, are removed.
+///
+/// A tolerance of zero or less leaves the mesh unchanged.
+pub fn weld_vertices(
+    mesh: &Mesh<Point<3>>,
+    tolerance: Scalar,
+) -> Mesh<Point<3>> {
+    if tolerance <= Scalar::ZERO {
+        return mesh.clone();
+    }
+
+    // Vertices are sorted into a grid with cells the size of the tolerance, so
+    // only the neighboring cells need to be searched for a vertex to merge
+    // with.
+    let cell = |point: Point<3>| {
+        point
+            .coords
+            .components
+            .map(|c| (c / tolerance).into_f64().floor() as i64)
+    };
+
+    let mut grid: HashMap<[i64; 3], Vec<Point<3>>> = HashMap::new();
+    let mut welded = Vec::new();
+
+    for vertex in mesh.vertices() {
+        let [x, y, z] = cell(vertex);
+
+        let mut existing = None;
+        'search: for dx in -1..=1 {
+            for dy in -1..=1 {
+                for dz in -1..=1 {
+                    let neighbors = grid.get(&[x + dx, y + dy, z + dz]);
+                    let close =
+                        neighbors.into_iter().flatten().find(|&&other| {
+                            Point::distance(&vertex, &other) <= tolerance
+                        });
+
+                    if let Some(&close) = close {
+                        existing = Some(close);
+                        break 'search;
+                    }
+                }
+            }
+        }
+
+        welded.push(existing.unwrap_or_else(|| {
+            grid.entry([x, y, z]).or_default().push(vertex);
+            vertex
+        }));
+    }
+
+    let indices: Vec<_> = mesh.indices().collect();
+
+    let mut result = Mesh::new();
+    for (triangle, indices) in mesh.triangles().zip(indices.chunks(3)) {
+        let [a, b, c] = [0, 1, 2].map(|i| welded[indices[i] as usize]);
+        if a == b || b == c || c == a {
+            continue;
+        }
+
+        result.push_triangle([a, b, c], triangle.color);
+    }
+
+    result
+}
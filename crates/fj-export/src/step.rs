@@ -0,0 +1,594 @@
+use std::{collections::HashMap, io::Write};
+
+use fj_kernel::{
+    geometry::NurbsSurface,
+    objects::{CurveKind, Cycle, Face, GlobalCurve, Surface},
+};
+use fj_math::{Point, Scalar, Triangle, Vector};
+
+use crate::Error;
+
+/// Export the provided faces as a STEP (AP214) file
+///
+/// Unlike the other exporters, this one works on the boundary representation,
+/// so planes, lines, circles, and NURBS surfaces are written exactly, instead of
+/// as triangles.
+/// Edges and vertices that are shared between faces are written once, and
+/// referenced from all faces they bound. Lengths are written in millimeters.
+///
+/// If every edge is shared by exactly two faces, the faces are written as a
+/// solid. Otherwise, they are written as a surface model.
+///
+/// # Limitations
+///
+/// Faces that still use the obsolete triangle representation are written as
+/// one planar face per triangle. Those triangles don't share edges with the
+/// exact faces next to them, so a shape that has such faces is written as a
+/// surface model.
+pub fn export_step(
+    faces: &[Face],
+    writer: &mut impl Write,
+) -> Result<(), Error> {
+    let mut step = StepWriter::default();
+
+    let mut advanced_faces = Vec::new();
+    for face in faces {
+        match face.triangles() {
+            Some(triangles) => {
+                for (triangle, _) in triangles {
+                    advanced_faces.extend(step.triangle(triangle));
+                }
+            }
+            None => advanced_faces.push(step.face(face)),
+        }
+    }
+
+    let is_closed = step.edge_uses.values().all(|&uses| uses == 2);
+    let representation = if is_closed {
+        let shell =
+            step.add(format!("CLOSED_SHELL('',{})", list(&advanced_faces)));
+        let solid = step.add(format!("MANIFOLD_SOLID_BREP('',#{shell})"));
+        ("ADVANCED_BREP_SHAPE_REPRESENTATION", solid)
+    } else {
+        let shell =
+            step.add(format!("OPEN_SHELL('',{})", list(&advanced_faces)));
+        let model =
+            step.add(format!("SHELL_BASED_SURFACE_MODEL('',(#{shell}))"));
+        ("MANIFOLD_SURFACE_SHAPE_REPRESENTATION", model)
+    };
+
+    step.product(representation);
+    step.write(writer)
+}
+
+#[derive(Default)]
+struct StepWriter {
+    entities: Vec<String>,
+    vertices: HashMap<Point<3>, usize>,
+    edges: HashMap<EdgeKey, (usize, Point<3>)>,
+    edge_uses: HashMap<EdgeKey, usize>,
+}
+
+impl StepWriter {
+    /// Add an entity and return its ID
+    fn add(&mut self, entity: String) -> usize {
+        self.entities.push(entity);
+        self.entities.len()
+    }
+
+    fn point(&mut self, point: Point<3>) -> usize {
+        let [x, y, z] = point.coords.components.map(real);
+        self.add(format!("CARTESIAN_POINT('',({x},{y},{z}))"))
+    }
+
+    fn direction(&mut self, direction: Vector<3>) -> usize {
+        let [x, y, z] = direction.normalize().components.map(real);
+        self.add(format!("DIRECTION('',({x},{y},{z}))"))
+    }
+
+    fn placement(
+        &mut self,
+        origin: Point<3>,
+        axis: Vector<3>,
+        x: Vector<3>,
+    ) -> usize {
+        let origin = self.point(origin);
+        let axis = self.direction(axis);
+        let x = self.direction(x);
+        self.add(format!("AXIS2_PLACEMENT_3D('',#{origin},#{axis},#{x})"))
+    }
+
+    fn vertex(&mut self, position: Point<3>) -> usize {
+        if let Some(&vertex) = self.vertices.get(&position) {
+            return vertex;
+        }
+
+        let point = self.point(position);
+        let vertex = self.add(format!("VERTEX_POINT('',#{point})"));
+        self.vertices.insert(position, vertex);
+
+        vertex
+    }
+
+    fn curve(&mut self, curve: &CurveKind<3>) -> usize {
+        match curve {
+            CurveKind::Line(line) => {
+                let origin = self.point(line.origin());
+                let direction = self.direction(line.direction());
+                let magnitude = real(line.direction().magnitude());
+                let vector =
+                    self.add(format!("VECTOR('',#{direction},{magnitude})"));
+                self.add(format!("LINE('',#{origin},#{vector})"))
+            }
+            CurveKind::Circle(circle) => {
+                let axis = circle.a().cross(&circle.b());
+                let placement =
+                    self.placement(circle.center(), axis, circle.a());
+                let radius = real(circle.a().magnitude());
+                self.add(format!("CIRCLE('',#{placement},{radius})"))
+            }
+            CurveKind::Ellipse(ellipse) => {
+                let axis = ellipse.a().cross(&ellipse.b());
+                let placement =
+                    self.placement(ellipse.center(), axis, ellipse.a());
+                let [a, b] = [ellipse.a(), ellipse.b()]
+                    .map(|axis| real(axis.magnitude()));
+                self.add(format!("ELLIPSE('',#{placement},{a},{b})"))
+            }
+            CurveKind::Parabola(parabola) => {
+                // STEP defines a parabola in terms of its vertex and focal
+                // distance, as `F * (u² * x + 2 * u * y)`.
+                let b = parabola.b();
+                let vertex = parabola.vertex();
+                let t = parabola.point_to_parabola_coords(vertex).t;
+                let y = parabola.tangent_from_parabola_coords([t]);
+                let focal_distance = y.dot(&y) / (b.magnitude() * 4.);
+
+                let placement = self.placement(vertex, b.cross(&y), b);
+                let focal_distance = real(focal_distance);
+                self.add(format!("PARABOLA('',#{placement},{focal_distance})"))
+            }
+        }
+    }
+
+    /// Write the edge between two points, if it hasn't been written yet
+    ///
+    /// Returns the ID of the `ORIENTED_EDGE` that runs from `start` to `end`.
+    fn edge(
+        &mut self,
+        start: Point<3>,
+        end: Point<3>,
+        curve: &GlobalCurve,
+    ) -> usize {
+        let key = EdgeKey::new(start, end, curve);
+        *self.edge_uses.entry(key).or_default() += 1;
+
+        let (edge, edge_start) = match self.edges.get(&key) {
+            Some(&edge) => edge,
+            None => {
+                let [a, b] = [start, end].map(|point| self.vertex(point));
+                let curve = self.curve(curve.kind());
+                let edge =
+                    self.add(format!("EDGE_CURVE('',#{a},#{b},#{curve},.T.)"));
+                self.edges.insert(key, (edge, start));
+                (edge, start)
+            }
+        };
+
+        let orientation = bool(edge_start == start);
+        self.add(format!("ORIENTED_EDGE('',*,*,#{edge},{orientation})"))
+    }
+
+    fn edge_loop(&mut self, cycle: &Cycle) -> usize {
+        let mut edges = Vec::new();
+
+        for edge in cycle.edges() {
+            let curve = edge.curve().global();
+            let [start, end] = match edge.vertices().get() {
+                Some(vertices) => {
+                    vertices.map(|vertex| vertex.global().position())
+                }
+                None => {
+                    // An edge without vertices is closed. STEP needs a vertex
+                    // anyway, so use the start of the curve.
+                    let point =
+                        curve.kind().point_from_curve_coords([Scalar::ZERO]);
+                    [point, point]
+                }
+            };
+
+            edges.push(self.edge(start, end, curve));
+        }
+
+        self.add(format!("EDGE_LOOP('',{})", list(&edges)))
+    }
+
+    fn face(&mut self, face: &Face) -> usize {
+        let mut bounds = Vec::new();
+        for cycle in face.exteriors() {
+            let edge_loop = self.edge_loop(cycle);
+            bounds.push(
+                self.add(format!("FACE_OUTER_BOUND('',#{edge_loop},.T.)")),
+            );
+        }
+        for cycle in face.interiors() {
+            let edge_loop = self.edge_loop(cycle);
+            bounds.push(self.add(format!("FACE_BOUND('',#{edge_loop},.T.)")));
+        }
+
+        let surface = self.surface(face.surface());
+        self.add(format!(
+            "ADVANCED_FACE('',{},#{surface},.T.)",
+            list(&bounds)
+        ))
+    }
+
+    fn surface(&mut self, surface: &Surface) -> usize {
+        let surface = match surface {
+            Surface::SweptCurve(surface) => surface,
+            Surface::Nurbs(surface) => return self.nurbs_surface(surface),
+        };
+
+        match &surface.curve {
+            CurveKind::Line(line) => {
+                let normal = line.direction().cross(&surface.path);
+                let placement =
+                    self.placement(line.origin(), normal, line.direction());
+                self.add(format!("PLANE('',#{placement})"))
+            }
+            curve => {
+                let curve = self.curve(curve);
+                let direction = self.direction(surface.path);
+                let magnitude = real(surface.path.magnitude());
+                let vector =
+                    self.add(format!("VECTOR('',#{direction},{magnitude})"));
+                self.add(format!(
+                    "SURFACE_OF_LINEAR_EXTRUSION('',#{curve},#{vector})"
+                ))
+            }
+        }
+    }
+
+    fn nurbs_surface(&mut self, surface: &NurbsSurface) -> usize {
+        let control_points: Vec<_> = surface
+            .control_points()
+            .iter()
+            .map(|row| {
+                let points: Vec<_> =
+                    row.iter().map(|&point| self.point(point)).collect();
+                list(&points)
+            })
+            .collect();
+        let weights: Vec<_> = surface
+            .weights()
+            .iter()
+            .map(|row| {
+                let weights: Vec<_> = row.iter().map(|&w| real(w)).collect();
+                format!("({})", weights.join(","))
+            })
+            .collect();
+
+        let [degree_u, degree_v] = surface.degree();
+        let [(multiplicities_u, knots_u), (multiplicities_v, knots_v)] =
+            [0, 1].map(|i| knots(&surface.knots()[i]));
+
+        // STEP has no single entity for a rational B-spline surface. It is a
+        // complex entity instead, with the partial entities sorted by name.
+        self.add(format!(
+            "(BOUNDED_SURFACE()\
+             B_SPLINE_SURFACE({degree_u},{degree_v},({}),.UNSPECIFIED.,.F.,\
+             .F.,.F.)\
+             B_SPLINE_SURFACE_WITH_KNOTS({multiplicities_u},\
+             {multiplicities_v},{knots_u},{knots_v},.UNSPECIFIED.)\
+             GEOMETRIC_REPRESENTATION_ITEM()\
+             RATIONAL_B_SPLINE_SURFACE(({}))\
+             REPRESENTATION_ITEM('')SURFACE())",
+            control_points.join(","),
+            weights.join(","),
+        ))
+    }
+
+    fn triangle(&mut self, triangle: &Triangle<3>) -> Option<usize> {
+        let normal = triangle.normal()?;
+        let [a, b, c] = triangle.points();
+
+        let mut edges = Vec::new();
+        for [start, end] in [[a, b], [b, c], [c, a]] {
+            let curve = GlobalCurve::from_kind(CurveKind::line_from_points([
+                start, end,
+            ]));
+            edges.push(self.edge(start, end, &curve));
+        }
+        let edge_loop = self.add(format!("EDGE_LOOP('',{})", list(&edges)));
+        let bound = self.add(format!("FACE_OUTER_BOUND('',#{edge_loop},.T.)"));
+
+        let placement = self.placement(a, normal, b - a);
+        let plane = self.add(format!("PLANE('',#{placement})"));
+
+        Some(self.add(format!("ADVANCED_FACE('',(#{bound}),#{plane},.T.)")))
+    }
+
+    /// Add the entities that describe the product that the shape belongs to
+    fn product(&mut self, (representation, item): (&str, usize)) {
+        let context =
+            self.add("APPLICATION_CONTEXT('automotive design')".to_owned());
+        self.add(format!(
+            "APPLICATION_PROTOCOL_DEFINITION('international standard',\
+             'automotive_design',2000,#{context})"
+        ));
+        let product_context =
+            self.add(format!("PRODUCT_CONTEXT('',#{context},'mechanical')"));
+        let product = self
+            .add(format!("PRODUCT('model','model','',(#{product_context}))"));
+        let formation =
+            self.add(format!("PRODUCT_DEFINITION_FORMATION('','',#{product})"));
+        let definition_context = self.add(format!(
+            "PRODUCT_DEFINITION_CONTEXT('part definition',#{context},'design')"
+        ));
+        let definition = self.add(format!(
+            "PRODUCT_DEFINITION('design','',#{formation},#{definition_context})"
+        ));
+        let shape =
+            self.add(format!("PRODUCT_DEFINITION_SHAPE('','',#{definition})"));
+
+        let length = self.add(
+            "(LENGTH_UNIT()NAMED_UNIT(*)SI_UNIT(.MILLI.,.METRE.))".to_owned(),
+        );
+        let angle = self.add(
+            "(NAMED_UNIT(*)PLANE_ANGLE_UNIT()SI_UNIT($,.RADIAN.))".to_owned(),
+        );
+        let solid_angle = self.add(
+            "(NAMED_UNIT(*)SI_UNIT($,.STERADIAN.)SOLID_ANGLE_UNIT())"
+                .to_owned(),
+        );
+        let uncertainty = self.add(format!(
+            "UNCERTAINTY_MEASURE_WITH_UNIT(LENGTH_MEASURE(1.E-07),#{length},\
+             'distance_accuracy_value','')"
+        ));
+        let representation_context = self.add(format!(
+            "(GEOMETRIC_REPRESENTATION_CONTEXT(3)\
+             GLOBAL_UNCERTAINTY_ASSIGNED_CONTEXT((#{uncertainty}))\
+             GLOBAL_UNIT_ASSIGNED_CONTEXT((#{length},#{angle},#{solid_angle}))\
+             REPRESENTATION_CONTEXT('',''))"
+        ));
+
+        let representation = self.add(format!(
+            "{representation}('',(#{item}),#{representation_context})"
+        ));
+        self.add(format!(
+            "SHAPE_DEFINITION_REPRESENTATION(#{shape},#{representation})"
+        ));
+    }
+
+    fn write(&self, writer: &mut impl Write) -> Result<(), Error> {
+        writeln!(writer, "ISO-10303-21;")?;
+        writeln!(writer, "HEADER;")?;
+        writeln!(writer, "FILE_DESCRIPTION(('Fornjot model'),'2;1');")?;
+        writeln!(
+            writer,
+            "FILE_NAME('model.step','',(''),(''),'Fornjot','Fornjot','');"
+        )?;
+        writeln!(
+            writer,
+            "FILE_SCHEMA(('AUTOMOTIVE_DESIGN {{ 1 0 10303 214 1 1 1 1 }}'));"
+        )?;
+        writeln!(writer, "ENDSEC;")?;
+
+        writeln!(writer, "DATA;")?;
+        for (i, entity) in self.entities.iter().enumerate() {
+            writeln!(writer, "#{}={};", i + 1, entity)?;
+        }
+        writeln!(writer, "ENDSEC;")?;
+        writeln!(writer, "END-ISO-10303-21;")?;
+
+        Ok(())
+    }
+}
+
+/// Identifies an edge, regardless of its direction
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+enum EdgeKey {
+    /// A straight edge is defined by its end points
+    Line([Point<3>; 2]),
+
+    /// Other edges also need their curve, as there can be more than one
+    /// between the same points
+    Curve([Point<3>; 2], GlobalCurve),
+}
+
+impl EdgeKey {
+    fn new(start: Point<3>, end: Point<3>, curve: &GlobalCurve) -> Self {
+        let mut points = [start, end];
+        points.sort();
+
+        match curve.kind() {
+            CurveKind::Line(_) => Self::Line(points),
+            CurveKind::Circle(_)
+            | CurveKind::Ellipse(_)
+            | CurveKind::Parabola(_) => Self::Curve(points, *curve),
+        }
+    }
+}
+
+/// Format a real number, as required by STEP
+///
+/// STEP requires a decimal point in every real, including those in
+/// scientific notation.
+fn real(value: Scalar) -> String {
+    let value = format!("{:?}", value.into_f64());
+
+    match value.split_once('e') {
+        Some((mantissa, exponent)) if !mantissa.contains('.') => {
+            format!("{mantissa}.E{exponent}")
+        }
+        Some((mantissa, exponent)) => format!("{mantissa}E{exponent}"),
+        None => value,
+    }
+}
+
+fn bool(value: bool) -> &'static str {
+    if value {
+        ".T."
+    } else {
+        ".F."
+    }
+}
+
+/// Write a knot vector as its distinct knots, and their multiplicities
+///
+/// Returns the multiplicities first, as that's the order STEP expects.
+fn knots(knots: &[Scalar]) -> (String, String) {
+    let mut values: Vec<Scalar> = Vec::new();
+    let mut multiplicities: Vec<usize> = Vec::new();
+
+    for &knot in knots {
+        match (values.last(), multiplicities.last_mut()) {
+            (Some(&last), Some(multiplicity)) if last == knot => {
+                *multiplicity += 1;
+            }
+            _ => {
+                values.push(knot);
+                multiplicities.push(1);
+            }
+        }
+    }
+
+    let values: Vec<_> = values.into_iter().map(real).collect();
+    let multiplicities: Vec<_> =
+        multiplicities.iter().map(ToString::to_string).collect();
+
+    (
+        format!("({})", multiplicities.join(",")),
+        format!("({})", values.join(",")),
+    )
+}
+
+fn list(ids: &[usize]) -> String {
+    let ids: Vec<_> = ids.iter().map(|id| format!("#{id}")).collect();
+    format!("({})", ids.join(","))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use fj_kernel::{
+        geometry::NurbsSurface,
+        objects::{Face, Solid, Surface},
+    };
+    use fj_math::Scalar;
+
+    use super::{export_step, knots, real};
+
+    fn export(faces: &[Face]) -> String {
+        let mut step = Vec::new();
+        export_step(faces, &mut step).unwrap();
+        String::from_utf8(step).unwrap()
+    }
+
+    fn count(step: &str, entity: &str) -> usize {
+        step.lines()
+            .filter(|line| {
+                line.split_once('=')
+                    .map(|(_, rest)| rest.starts_with(&format!("{entity}(")))
+                    .unwrap_or(false)
+            })
+            .count()
+    }
+
+    #[test]
+    fn cube() {
+        let faces: Vec<_> = Solid::build()
+            .cube_from_edge_length(2.)
+            .into_faces()
+            .collect();
+        let step = export(&faces);
+
+        assert!(step.starts_with("ISO-10303-21;\nHEADER;\n"));
+        assert!(step.ends_with("ENDSEC;\nEND-ISO-10303-21;\n"));
+
+        // Vertices and edges are shared between the faces.
+        assert_eq!(count(&step, "VERTEX_POINT"), 8);
+        assert_eq!(count(&step, "EDGE_CURVE"), 12);
+        assert_eq!(count(&step, "ORIENTED_EDGE"), 24);
+        assert_eq!(count(&step, "PLANE"), 6);
+        assert_eq!(count(&step, "ADVANCED_FACE"), 6);
+        assert_eq!(count(&step, "CLOSED_SHELL"), 1);
+        assert_eq!(count(&step, "MANIFOLD_SOLID_BREP"), 1);
+        assert_eq!(count(&step, "ADVANCED_BREP_SHAPE_REPRESENTATION"), 1);
+
+        // Every referenced entity is defined.
+        let defined: HashSet<_> = step
+            .lines()
+            .filter_map(|line| line.split_once('='))
+            .map(|(id, _)| id)
+            .collect();
+        for line in step.lines() {
+            let references =
+                line.split('#')
+                    .skip(if line.starts_with('#') { 2 } else { 1 });
+            for reference in references {
+                let digits: String = reference
+                    .chars()
+                    .take_while(char::is_ascii_digit)
+                    .collect();
+                assert!(defined.contains(format!("#{digits}").as_str()));
+            }
+        }
+    }
+
+    #[test]
+    fn open_shell() {
+        let faces: Vec<_> = Solid::build()
+            .cube_from_edge_length(2.)
+            .into_faces()
+            .take(5)
+            .collect();
+        let step = export(&faces);
+
+        assert_eq!(count(&step, "OPEN_SHELL"), 1);
+        assert_eq!(count(&step, "SHELL_BASED_SURFACE_MODEL"), 1);
+        assert_eq!(count(&step, "MANIFOLD_SOLID_BREP"), 0);
+    }
+
+    #[test]
+    fn nurbs_surface() {
+        let surface = NurbsSurface::from_control_points(
+            [
+                [[0., 0., 0.], [0., 1., 0.]],
+                [[1., 0., 1.], [1., 1., 1.]],
+                [[2., 0., 0.], [2., 1., 0.]],
+            ],
+            [2, 1],
+        );
+        let face = Face::build(Surface::nurbs(surface))
+            .polygon_from_points([[0., 0.], [1., 0.], [1., 1.], [0., 1.]])
+            .into_face();
+        let step = export(&[face]);
+
+        assert_eq!(count(&step, "PLANE"), 0);
+        assert!(step.contains(
+            "B_SPLINE_SURFACE_WITH_KNOTS((3,3),(2,2),(0.0,1.0),(0.0,1.0),\
+             .UNSPECIFIED.)"
+        ));
+        assert!(step.contains(
+            "RATIONAL_B_SPLINE_SURFACE(((1.0,1.0),(1.0,1.0),(1.0,1.0)))"
+        ));
+    }
+
+    #[test]
+    fn knot_multiplicities() {
+        let knots = knots(&[0., 0., 0.5, 1., 1., 1.].map(Scalar::from_f64));
+        assert_eq!(knots, ("(2,1,3)".to_owned(), "(0.0,0.5,1.0)".to_owned()));
+    }
+
+    #[test]
+    fn reals() {
+        let real = |value: f64| real(Scalar::from_f64(value));
+
+        assert_eq!(real(1.), "1.0");
+        assert_eq!(real(-0.25), "-0.25");
+        assert_eq!(real(1e-7), "1.E-7");
+        assert_eq!(real(1.5e20), "1.5E20");
+    }
+}
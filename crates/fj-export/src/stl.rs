@@ -0,0 +1,159 @@
+use std::io::Write;
+
+use fj_interop::mesh::Mesh;
+use fj_math::{Point, Triangle, Vector};
+
+use crate::Error;
+
+/// The encoding of an STL file
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum StlFormat {
+    /// Binary STL
+    ///
+    /// This is the default, as it's much more compact than ASCII STL.
+    Binary,
+
+    /// ASCII STL
+    Ascii,
+}
+
+impl Default for StlFormat {
+    fn default() -> Self {
+        Self::Binary
+    }
+}
+
+/// Export the provided mesh as STL
+pub fn export_stl(
+    mesh: &Mesh<Point<3>>,
+    format: StlFormat,
+    writer: &mut impl Write,
+) -> Result<(), Error> {
+    let triangles = mesh
+        .triangles()
+        .map(|triangle| {
+            let points = triangle.inner.points();
+            let vertices = points
+                .map(|point| point.coords.components.map(|s| s.into_f32()));
+
+            // STL readers are expected to compute the normal themselves, if
+            // it's all zeros.
+            let normal = Triangle::from(points)
+                .normal()
+                .unwrap_or_else(|| Vector::from([0., 0., 0.]))
+                .components
+                .map(|s| s.into_f32());
+
+            let [v1, v2, v3] = vertices;
+            stl::Triangle {
+                normal,
+                v1,
+                v2,
+                v3,
+                attr_byte_count: 0,
+            }
+        })
+        .collect::<Vec<_>>();
+
+    match format {
+        StlFormat::Binary => write_binary(triangles, writer),
+        StlFormat::Ascii => write_ascii(&triangles, writer),
+    }
+}
+
+fn write_binary(
+    triangles: Vec<stl::Triangle>,
+    writer: &mut impl Write,
+) -> Result<(), Error> {
+    let binary_stl_file = stl::BinaryStlFile {
+        header: stl::BinaryStlHeader {
+            header: [0u8; 80],
+            num_triangles: triangles
+                .len()
+                .try_into()
+                .map_err(|_| Error::InvalidTriangleCount)?,
+        },
+        triangles,
+    };
+
+    stl::write_stl(writer, &binary_stl_file)?;
+
+    Ok(())
+}
+
+fn write_ascii(
+    triangles: &[stl::Triangle],
+    writer: &mut impl Write,
+) -> Result<(), Error> {
+    let vector = |[x, y, z]: [f32; 3]| format!("{x:e} {y:e} {z:e}");
+
+    writeln!(writer, "solid fornjot")?;
+    for triangle in triangles {
+        writeln!(writer, "  facet normal {}", vector(triangle.normal))?;
+        writeln!(writer, "    outer loop")?;
+        for vertex in [triangle.v1, triangle.v2, triangle.v3] {
+            writeln!(writer, "      vertex {}", vector(vertex))?;
+        }
+        writeln!(writer, "    endloop")?;
+        writeln!(writer, "  endfacet")?;
+    }
+    writeln!(writer, "endsolid fornjot")?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_interop::mesh::{Color, Mesh};
+    use fj_math::Point;
+
+    use super::{export_stl, StlFormat};
+
+    fn mesh() -> Mesh<Point<3>> {
+        let mut mesh = Mesh::new();
+        let [a, b, c, d] =
+            [[0., 0., 0.], [1., 0., 0.], [0., 1., 0.], [0., 0., 1.]]
+                .map(Point::from);
+        mesh.push_triangle([a, b, c], Color::default());
+        mesh.push_triangle([a, d, b], Color::default());
+        mesh
+    }
+
+    #[test]
+    fn binary() {
+        let mut stl = Vec::new();
+        export_stl(&mesh(), StlFormat::Binary, &mut stl).unwrap();
+
+        // 80 byte header, triangle count, and 50 bytes per triangle
+        assert_eq!(stl.len(), 80 + 4 + 2 * 50);
+
+        let read = stl::read_stl(&mut stl.as_slice()).unwrap();
+        assert_eq!(read.header.num_triangles, 2);
+        assert_eq!(read.triangles[0].normal, [0., 0., 1.]);
+        assert_eq!(read.triangles[1].v2, [0., 0., 1.]);
+    }
+
+    #[test]
+    fn ascii() {
+        let mut stl = Vec::new();
+        export_stl(&mesh(), StlFormat::Ascii, &mut stl).unwrap();
+        let stl = String::from_utf8(stl).unwrap();
+
+        let lines: Vec<_> = stl.lines().map(str::trim).collect();
+        assert_eq!(
+            lines[..8],
+            [
+                "solid fornjot",
+                "facet normal 0e0 0e0 1e0",
+                "outer loop",
+                "vertex 0e0 0e0 0e0",
+                "vertex 1e0 0e0 0e0",
+                "vertex 0e0 1e0 0e0",
+                "endloop",
+                "endfacet",
+            ]
+        );
+        assert_eq!(lines[8], "facet normal 0e0 1e0 0e0");
+        assert_eq!(lines.last(), Some(&"endsolid fornjot"));
+    }
+}
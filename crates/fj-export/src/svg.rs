@@ -0,0 +1,110 @@
+use std::io::{self, Write};
+
+use fj_math::{Aabb, Point, Scalar, Segment};
+
+/// The stroke width that [`export_svg`] uses
+pub const DEFAULT_STROKE_WIDTH: f64 = 0.1;
+
+/// Export the provided 2D segments as an SVG document
+///
+/// Uses [`DEFAULT_STROKE_WIDTH`]. See [`export_svg_with_stroke_width`].
+pub fn export_svg<W: Write>(
+    segments: &[Segment<2>],
+    writer: &mut W,
+) -> io::Result<()> {
+    export_svg_with_stroke_width(segments, DEFAULT_STROKE_WIDTH, writer)
+}
+
+/// Export the provided 2D segments as an SVG document
+///
+/// All segments are written into a single `<path>`, with one move and one line
+/// command per segment. The `viewBox` covers the bounds of all segments, plus
+/// half the stroke width on each side, so the outermost strokes aren't clipped.
+///
+/// SVG has its y-axis pointing down, so the y-coordinates are flipped to keep
+/// the sketch the right way up.
+///
+/// An empty list of segments results in an empty, but valid, document.
+pub fn export_svg_with_stroke_width<W: Write>(
+    segments: &[Segment<2>],
+    stroke_width: f64,
+    writer: &mut W,
+) -> io::Result<()> {
+    if segments.is_empty() {
+        return writeln!(
+            writer,
+            r#"<svg xmlns="http://www.w3.org/2000/svg"/>"#
+        );
+    }
+
+    let points = segments
+        .iter()
+        .flat_map(|segment| segment.points())
+        .map(flip);
+    let aabb = Aabb::<2>::from_points(points);
+
+    let padding = stroke_width / 2.;
+    let [min_x, min_y] =
+        (aabb.min.coords.components).map(|s| s.into_f64() - padding);
+    let [width, height] = (aabb.max - aabb.min)
+        .components
+        .map(|s| s.into_f64() + stroke_width);
+
+    writeln!(
+        writer,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="{min_x} {min_y} {width} {height}">"#
+    )?;
+
+    write!(writer, r#"  <path d=""#)?;
+    for (i, segment) in segments.iter().enumerate() {
+        let [a, b] = segment.points().map(flip);
+
+        if i > 0 {
+            write!(writer, " ")?;
+        }
+        write!(writer, "M {} {} L {} {}", a.u, a.v, b.u, b.v)?;
+    }
+    writeln!(
+        writer,
+        r#"" fill="none" stroke="black" stroke-width="{stroke_width}"/>"#
+    )?;
+
+    writeln!(writer, "</svg>")?;
+
+    Ok(())
+}
+
+fn flip(point: Point<2>) -> Point<2> {
+    // Subtracting from zero, instead of negating, avoids writing `-0`.
+    Point::from([point.u, Scalar::ZERO - point.v])
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::Segment;
+
+    use super::export_svg_with_stroke_width;
+
+    #[test]
+    fn square() {
+        let [a, b, c, d] = [[0., 0.], [2., 0.], [2., 1.], [0., 1.]];
+        let segments = [[a, b], [b, c], [c, d], [d, a]].map(Segment::from);
+
+        let mut svg = Vec::new();
+        export_svg_with_stroke_width(&segments, 0.5, &mut svg).unwrap();
+        let svg = String::from_utf8(svg).unwrap();
+
+        assert_eq!(svg.matches(" L ").count(), 4);
+        assert!(svg.contains(r#"viewBox="-0.25 -1.25 2.5 1.5""#));
+    }
+
+    #[test]
+    fn empty() {
+        let mut svg = Vec::new();
+        export_svg_with_stroke_width(&[], 0.5, &mut svg).unwrap();
+        let svg = String::from_utf8(svg).unwrap();
+
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.trim_end().ends_with("/>"));
+    }
+}
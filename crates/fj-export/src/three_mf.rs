@@ -0,0 +1,232 @@
+use std::{
+    collections::HashMap,
+    io::{Seek, Write},
+};
+
+use fj_interop::mesh::{Color, Mesh};
+use fj_math::Point;
+use zip::{write::FileOptions, ZipWriter};
+
+use crate::Error;
+
+/// The unit that the coordinates of a mesh are interpreted in
+///
+/// Only affects formats that record units, currently 3MF.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Unit {
+    /// Micrometers
+    Micron,
+
+    /// Millimeters
+    ///
+    /// This is the default, as it's what most CAD and slicing software expects.
+    Millimeter,
+
+    /// Centimeters
+    Centimeter,
+
+    /// Meters
+    Meter,
+
+    /// Inches
+    Inch,
+
+    /// Feet
+    Foot,
+}
+
+impl Unit {
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Micron => "micron",
+            Self::Millimeter => "millimeter",
+            Self::Centimeter => "centimeter",
+            Self::Meter => "meter",
+            Self::Inch => "inch",
+            Self::Foot => "foot",
+        }
+    }
+}
+
+impl Default for Unit {
+    fn default() -> Self {
+        Self::Millimeter
+    }
+}
+
+const CONTENT_TYPES: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types">
+  <Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/>
+  <Default Extension="model" ContentType="application/vnd.ms-package.3dmanufacturing-3dmodel+xml"/>
+</Types>
+"#;
+
+const RELATIONSHIPS: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+  <Relationship Target="/3D/3dmodel.model" Id="rel0" Type="http://schemas.microsoft.com/3dmanufacturing/2013/01/3dmodel"/>
+</Relationships>
+"#;
+
+/// Export the provided mesh as 3MF
+///
+/// The color of each triangle is written as a base material, so slicers that
+/// support multiple materials can tell the differently colored parts apart.
+pub fn export_3mf(
+    mesh: &Mesh<Point<3>>,
+    unit: Unit,
+    writer: impl Write + Seek,
+) -> Result<(), Error> {
+    let mut zip = ZipWriter::new(writer);
+    let options = FileOptions::default();
+
+    zip.start_file("[Content_Types].xml", options)?;
+    zip.write_all(CONTENT_TYPES.as_bytes())?;
+
+    zip.start_file("_rels/.rels", options)?;
+    zip.write_all(RELATIONSHIPS.as_bytes())?;
+
+    zip.start_file("3D/3dmodel.model", options)?;
+    write_model(mesh, unit, &mut zip)?;
+
+    zip.finish()?;
+
+    Ok(())
+}
+
+fn write_model(
+    mesh: &Mesh<Point<3>>,
+    unit: Unit,
+    writer: &mut impl Write,
+) -> Result<(), Error> {
+    // Resource IDs
+    const MATERIALS: u32 = 1;
+    const OBJECT: u32 = 2;
+
+    let mut colors = Vec::new();
+    let mut color_indices = HashMap::new();
+    let triangle_colors: Vec<_> = mesh
+        .triangles()
+        .map(|triangle| {
+            *color_indices.entry(triangle.color).or_insert_with(|| {
+                colors.push(triangle.color);
+                colors.len() - 1
+            })
+        })
+        .collect();
+
+    writeln!(writer, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(
+        writer,
+        r#"<model unit="{}" xml:lang="en-US" xmlns="http://schemas.microsoft.com/3dmanufacturing/core/2015/02">"#,
+        unit.name()
+    )?;
+    writeln!(writer, "  <resources>")?;
+
+    writeln!(writer, r#"    <basematerials id="{MATERIALS}">"#)?;
+    for (i, color) in colors.iter().enumerate() {
+        writeln!(
+            writer,
+            r#"      <base name="Color {i}" displaycolor="{}"/>"#,
+            hex(*color)
+        )?;
+    }
+    writeln!(writer, "    </basematerials>")?;
+
+    writeln!(
+        writer,
+        r#"    <object id="{OBJECT}" type="model" pid="{MATERIALS}" pindex="0">"#
+    )?;
+    writeln!(writer, "      <mesh>")?;
+
+    writeln!(writer, "        <vertices>")?;
+    for vertex in mesh.vertices() {
+        let [x, y, z] = vertex.coords.components.map(|s| s.into_f64());
+        writeln!(writer, r#"          <vertex x="{x}" y="{y}" z="{z}"/>"#)?;
+    }
+    writeln!(writer, "        </vertices>")?;
+
+    writeln!(writer, "        <triangles>")?;
+    let indices: Vec<_> = mesh.indices().collect();
+    for (triangle, color) in indices.chunks(3).zip(triangle_colors) {
+        let [v1, v2, v3] = [0, 1, 2].map(|i| triangle[i]);
+        writeln!(
+            writer,
+            r#"          <triangle v1="{v1}" v2="{v2}" v3="{v3}" pid="{MATERIALS}" p1="{color}"/>"#
+        )?;
+    }
+    writeln!(writer, "        </triangles>")?;
+
+    writeln!(writer, "      </mesh>")?;
+    writeln!(writer, "    </object>")?;
+    writeln!(writer, "  </resources>")?;
+    writeln!(writer, "  <build>")?;
+    writeln!(writer, r#"    <item objectid="{OBJECT}"/>"#)?;
+    writeln!(writer, "  </build>")?;
+    writeln!(writer, "</model>")?;
+
+    Ok(())
+}
+
+fn hex(Color([r, g, b, a]): Color) -> String {
+    format!("#{r:02X}{g:02X}{b:02X}{a:02X}")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Cursor, Read as _};
+
+    use fj_interop::mesh::{Color, Mesh};
+    use fj_math::Point;
+    use zip::ZipArchive;
+
+    use super::{export_3mf, Unit};
+
+    #[test]
+    fn mesh_units_and_colors() {
+        let mut mesh = Mesh::new();
+        let [a, b, c, d] =
+            [[0., 0., 0.], [1., 0., 0.], [0., 1., 0.], [0., 0., 0.5]]
+                .map(Point::from);
+        let red = Color::rgb(255, 0, 0);
+        mesh.push_triangle([a, b, c], red);
+        mesh.push_triangle([a, d, b], Color::rgba(0, 0, 255, 128));
+        mesh.push_triangle([a, c, d], red);
+
+        let mut file = Cursor::new(Vec::new());
+        export_3mf(&mesh, Unit::Inch, &mut file).unwrap();
+
+        let mut archive = ZipArchive::new(file).unwrap();
+        for name in ["[Content_Types].xml", "_rels/.rels"] {
+            assert!(archive.by_name(name).is_ok());
+        }
+
+        let mut model = String::new();
+        archive
+            .by_name("3D/3dmodel.model")
+            .unwrap()
+            .read_to_string(&mut model)
+            .unwrap();
+        let lines: Vec<_> = model.lines().map(str::trim).collect();
+
+        assert!(lines[1].starts_with(r#"<model unit="inch""#));
+        assert!(lines.contains(&r#"<vertex x="0" y="0" z="0.5"/>"#));
+        assert!(lines
+            .contains(&r##"<base name="Color 0" displaycolor="#FF0000FF"/>"##));
+        assert!(lines
+            .contains(&r##"<base name="Color 1" displaycolor="#0000FF80"/>"##));
+
+        let triangles: Vec<_> = lines
+            .iter()
+            .copied()
+            .filter(|line| line.starts_with("<triangle "))
+            .collect();
+        assert_eq!(
+            triangles,
+            [
+                r#"<triangle v1="0" v2="1" v3="2" pid="1" p1="0"/>"#,
+                r#"<triangle v1="0" v2="3" v3="1" pid="1" p1="1"/>"#,
+                r#"<triangle v1="0" v2="2" v3="3" pid="1" p1="0"/>"#,
+            ]
+        );
+    }
+}
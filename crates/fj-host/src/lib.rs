@@ -21,12 +21,13 @@ use fj_interop::status_report::StatusReport;
 use std::{
     collections::{HashMap, HashSet},
     ffi::OsStr,
-    io,
+    fs, io, mem,
     ops::{Deref, DerefMut},
     path::{Path, PathBuf},
     process::Command,
     sync::mpsc,
     thread,
+    time::SystemTime,
 };
 
 use fj::abi;
@@ -86,6 +87,13 @@ impl Model {
         arguments: &Parameters,
         status: &mut StatusReport,
     ) -> Result<fj::Shape, Error> {
+        self.build(status)?;
+        let (shape, _) = self.evaluate(arguments)?;
+
+        Ok(shape)
+    }
+
+    fn build(&self, status: &mut StatusReport) -> Result<(), Error> {
         let manifest_path = self.manifest_path.display().to_string();
 
         let mut command_root = Command::new("cargo");
@@ -115,6 +123,49 @@ impl Model {
             return Err(Error::Compile);
         }
 
+        Ok(())
+    }
+
+    /// Build the model, and return the metadata of the arguments it expects
+    ///
+    /// The model isn't run, so this works without any arguments.
+    pub fn argument_metadata(
+        &self,
+        status: &mut StatusReport,
+    ) -> Result<Vec<fj::models::ArgumentMetadata>, Error> {
+        self.build(status)?;
+
+        let arguments = Parameters::empty();
+        self.with_model(&arguments, |model, _| Ok(model.metadata().arguments))
+    }
+
+    /// Run the model, without building it first
+    ///
+    /// Arguments are checked against the metadata the model declares, before
+    /// it is run. Returns the shape, and that metadata.
+    fn evaluate(
+        &self,
+        arguments: &Parameters,
+    ) -> Result<(fj::Shape, Vec<fj::models::ArgumentMetadata>), Error> {
+        self.with_model(arguments, |model, host| {
+            let metadata = model.metadata().arguments;
+
+            let mut errors = fj::models::ContextErrors::new();
+            errors.validate(host, &metadata);
+            errors.into_result().map_err(Error::InvalidArguments)?;
+
+            let shape = model.shape(host).map_err(Error::Shape)?;
+
+            Ok((shape, metadata))
+        })
+    }
+
+    /// Load the model from its library, and pass it to `f`
+    fn with_model<T>(
+        &self,
+        arguments: &Parameters,
+        f: impl FnOnce(&dyn fj::models::Model, &Host) -> Result<T, Error>,
+    ) -> Result<T, Error> {
         // So, strictly speaking this is all unsound:
         // - `Library::new` requires us to abide by the arbitrary requirements
         //   of any library initialization or termination routines.
@@ -131,7 +182,7 @@ impl Model {
         // I don't know of a way to fix this. We should take this as motivation
         // to switch to a better technique:
         // https://github.com/hannobraun/Fornjot/issues/71
-        let shape = unsafe {
+        unsafe {
             let lib = libloading::Library::new(&self.lib_path)?;
             let init: libloading::Symbol<abi::InitFunction> =
                 lib.get(abi::INIT_FUNCTION_NAME.as_bytes())?;
@@ -150,15 +201,15 @@ impl Model {
 
             let model = host.model.take().ok_or(Error::NoModelRegistered)?;
 
-            model.shape(&host).map_err(Error::Shape)?
-        };
-
-        Ok(shape)
+            f(&*model, &host)
+        }
     }
 
     /// Load the model, then watch it for changes
     ///
-    /// Whenever a change is detected, the model is being reloaded.
+    /// Whenever a change is detected, the model is being reloaded. That happens,
+    /// if its source code changes, or if its library is rebuilt from outside,
+    /// for example by running `cargo build` manually.
     ///
     /// Consumes this instance of `Model` and returns a [`Watcher`], which can
     /// be queried for changes to the model.
@@ -171,12 +222,42 @@ impl Model {
 
         let watch_path = self.src_path.clone();
 
+        // Cargo replaces the library when rebuilding it, so watching the file
+        // itself wouldn't work. Watch the directory it's in instead.
+        let lib_path = self.lib_path.clone();
+        let lib_dir = lib_path
+            .parent()
+            .expect("Library path must have a parent")
+            .to_path_buf();
+        fs::create_dir_all(&lib_dir)?;
+        let watch_lib_dir = lib_dir.clone();
+
         let mut watcher = notify::recommended_watcher(
             move |event: notify::Result<notify::Event>| {
                 // Unfortunately the `notify` documentation doesn't say when
                 // this might happen, so no idea if it needs to be handled.
                 let event = event.expect("Error handling watch event");
 
+                if event
+                    .paths
+                    .iter()
+                    .any(|path| path.parent() == Some(&lib_dir))
+                {
+                    let is_library_change = event.paths.contains(&lib_path)
+                        && matches!(
+                            event.kind,
+                            notify::EventKind::Create(_)
+                                | notify::EventKind::Modify(_)
+                        );
+
+                    if is_library_change {
+                        tx.send(Change::Library)
+                            .expect("Channel is disconnected");
+                    }
+
+                    return;
+                }
+
                 // Various acceptable ModifyKind kinds. Varies across platforms
                 // (e.g. MacOs vs. Windows10)
                 if let notify::EventKind::Modify(
@@ -216,12 +297,13 @@ impl Model {
                     // application is being shut down.
                     //
                     // Either way, not much we can do about it here.
-                    tx.send(()).expect("Channel is disconnected");
+                    tx.send(Change::Source).expect("Channel is disconnected");
                 }
             },
         )?;
 
         watcher.watch(&watch_path, notify::RecursiveMode::Recursive)?;
+        watcher.watch(&watch_lib_dir, notify::RecursiveMode::NonRecursive)?;
 
         // To prevent a race condition between the initial load and the start of
         // watching, we'll trigger the initial load here, after having started
@@ -229,13 +311,18 @@ impl Model {
         //
         // Will panic, if the receiving end has panicked. Not much we can do
         // about that, if it happened.
-        thread::spawn(move || tx2.send(()).expect("Channel is disconnected"));
+        thread::spawn(move || {
+            tx2.send(Change::Source).expect("Channel is disconnected")
+        });
 
         Ok(Watcher {
             _watcher: Box::new(watcher),
             channel: rx,
             model: self,
             parameters,
+            parameters_changed: false,
+            argument_metadata: Vec::new(),
+            loaded_library: None,
         })
     }
 }
@@ -287,9 +374,16 @@ fn ambiguous_path_error(
 /// Watches a model for changes, reloading it continually
 pub struct Watcher {
     _watcher: Box<dyn notify::Watcher>,
-    channel: mpsc::Receiver<()>,
+    channel: mpsc::Receiver<Change>,
     model: Model,
     parameters: Parameters,
+    parameters_changed: bool,
+
+    /// The metadata of the model's arguments, when it was last run
+    argument_metadata: Vec<fj::models::ArgumentMetadata>,
+
+    /// The modification time of the library, when it was last loaded
+    loaded_library: Option<SystemTime>,
 }
 
 impl Watcher {
@@ -297,28 +391,19 @@ impl Watcher {
     ///
     /// Returns `None`, if the model has not changed since the last time this
     /// method was called.
-    pub fn receive(&self, status: &mut StatusReport) -> Option<fj::Shape> {
-        match self.channel.try_recv() {
-            Ok(()) => {
-                let shape = match self.model.load_once(&self.parameters, status)
-                {
-                    Ok(shape) => shape,
-                    Err(Error::Compile) => {
-                        // It would be better to display an error in the UI,
-                        // where the user can actually see it. Issue:
-                        // https://github.com/hannobraun/fornjot/issues/30
-                        return None;
-                    }
-                    Err(err) => {
-                        panic!("Error reloading model: {:?}", err);
-                    }
-                };
-
-                Some(shape)
-            }
+    ///
+    /// A reloaded model is run with the same arguments as before, unless they
+    /// were changed using [`Watcher::set_parameters`].
+    pub fn receive(&mut self, status: &mut StatusReport) -> Option<fj::Shape> {
+        let change = match self.channel.try_recv() {
+            Ok(change) => change,
             Err(mpsc::TryRecvError::Empty) => {
                 // Nothing to receive from the channel.
-                None
+                if !mem::take(&mut self.parameters_changed) {
+                    return None;
+                }
+
+                Change::Parameters
             }
             Err(mpsc::TryRecvError::Disconnected) => {
                 // The other end has disconnected. This is probably the result
@@ -326,8 +411,104 @@ impl Watcher {
                 // progress. In any case, not much we can do here.
                 panic!();
             }
+        };
+
+        // Loading the model rebuilds the library, if necessary. Don't reload it
+        // again, because of that.
+        if change == Change::Library
+            && modified(&self.model.lib_path) == self.loaded_library
+        {
+            return None;
+        }
+
+        // The library hasn't changed, if only the parameters have.
+        if change != Change::Parameters {
+            let built = self.model.build(status);
+            self.loaded_library = modified(&self.model.lib_path);
+
+            match built {
+                Ok(()) => {}
+                Err(Error::Compile) => {
+                    // It would be better to display an error in the UI, where
+                    // the user can actually see it. Issue:
+                    // https://github.com/hannobraun/fornjot/issues/30
+                    return None;
+                }
+                Err(err) => {
+                    panic!("Error reloading model: {:?}", err);
+                }
+            }
+        }
+
+        match self.model.evaluate(&self.parameters) {
+            Ok((shape, argument_metadata)) => {
+                self.argument_metadata = argument_metadata;
+                Some(shape)
+            }
+            Err(Error::InvalidArguments(errors)) => {
+                // Keep the previous shape, until the user fixes the arguments.
+                status.update_status(&format!(
+                    "Failed to evaluate model: {}",
+                    errors
+                ));
+                None
+            }
+            Err(Error::Shape(err)) => {
+                // Most likely, the model was run with an invalid argument.
+                // Keep the previous shape, until the user fixes that.
+                status.update_status(&format!(
+                    "Failed to evaluate model: {}",
+                    err
+                ));
+                None
+            }
+            Err(err) => {
+                panic!("Error reloading model: {:?}", err);
+            }
+        }
+    }
+
+    /// The arguments that the model is run with
+    pub fn parameters(&self) -> &Parameters {
+        &self.parameters
+    }
+
+    /// Change the arguments that the model is run with
+    ///
+    /// The model is run with the new arguments on the next call to
+    /// [`Watcher::receive`], without rebuilding it.
+    pub fn set_parameters(&mut self, parameters: Parameters) {
+        if parameters != self.parameters {
+            self.parameters = parameters;
+            self.parameters_changed = true;
         }
     }
+
+    /// The metadata of the arguments that the model expects
+    ///
+    /// Updated, whenever the model is reloaded.
+    pub fn argument_metadata(&self) -> &[fj::models::ArgumentMetadata] {
+        &self.argument_metadata
+    }
+}
+
+/// A change that requires a model to be reloaded
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Change {
+    /// The source code of the model has changed
+    Source,
+
+    /// The library of the model has been rebuilt
+    Library,
+
+    /// The arguments the model is run with have changed
+    Parameters,
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
 }
 
 /// Parameters that are passed to a model.
@@ -390,6 +571,10 @@ pub enum Error {
     #[error("No model was registered")]
     NoModelRegistered,
 
+    /// The arguments don't match the parameters the model declares
+    #[error("Invalid arguments for the model")]
+    InvalidArguments(#[source] fj::models::ContextErrors),
+
     /// An error was returned from [`fj::models::Model::shape()`].
     #[error("Unable to determine the model's geometry")]
     Shape(#[source] fj::models::Error),
@@ -436,4 +621,7 @@ impl<'a> fj::models::Context for Host<'a> {
     fn get_argument(&self, name: &str) -> Option<&str> {
         self.args.get(name).map(|s| s.as_str())
     }
+    fn arguments(&self) -> Box<dyn Iterator<Item = (&str, &str)> + '_> {
+        fj::models::Context::arguments(&self.args.0)
+    }
 }
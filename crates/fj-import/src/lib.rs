@@ -0,0 +1,124 @@
+//! # Fornjot Importer
+//!
+//! This library is part of the [Fornjot] ecosystem. Fornjot is an open-source,
+//! code-first CAD application; and collection of libraries that make up the CAD
+//! application, but can be used independently.
+//!
+//! This library is an internal component of Fornjot. It is not relevant to end
+//! users that just want to create CAD models.
+//!
+//! The purpose of this library is to import models from external file formats
+//! into the Fornjot kernel.
+//!
+//! [Fornjot]: https://www.fornjot.app/
+
+#![warn(missing_docs)]
+
+mod mesh;
+mod step;
+
+use std::{fs, path::Path};
+
+use fj_interop::mesh::Mesh;
+use fj_kernel::objects::Face;
+use fj_math::Point;
+use thiserror::Error;
+
+pub use self::{
+    mesh::{import_obj, import_stl},
+    step::import_step,
+};
+
+/// Import the faces of the model in the file at the given path
+///
+/// Currently only STEP files are supported. The case insensitive file extension
+/// of the provided path is used to detect the file type.
+pub fn import(path: &Path) -> Result<Vec<Face>, Error> {
+    match path.extension() {
+        Some(extension)
+            if extension.to_ascii_uppercase() == "STEP"
+                || extension.to_ascii_uppercase() == "STP" =>
+        {
+            let source = fs::read_to_string(path)?;
+            import_step(&source)
+        }
+        Some(extension) => Err(Error::InvalidExtension(
+            extension.to_string_lossy().into_owned(),
+        )),
+        None => Err(Error::NoExtension),
+    }
+}
+
+/// Import the triangle mesh in the file at the given path
+///
+/// Currently STL and OBJ files are supported. The case insensitive file
+/// extension of the provided path is used to detect the file type.
+///
+/// Unlike [`import`], this doesn't result in faces that the kernel can work
+/// with. It's meant for displaying things like 3D scans next to a model.
+pub fn import_mesh(path: &Path) -> Result<Mesh<Point<3>>, Error> {
+    match path.extension() {
+        Some(extension) if extension.to_ascii_uppercase() == "STL" => {
+            let data = fs::read(path)?;
+            import_stl(&data)
+        }
+        Some(extension) if extension.to_ascii_uppercase() == "OBJ" => {
+            let source = fs::read_to_string(path)?;
+            import_obj(&source)
+        }
+        Some(extension) => Err(Error::InvalidExtension(
+            extension.to_string_lossy().into_owned(),
+        )),
+        None => Err(Error::NoExtension),
+    }
+}
+
+/// An error that can occur while importing
+#[derive(Debug, Error)]
+pub enum Error {
+    /// No extension specified
+    #[error("no extension specified")]
+    NoExtension,
+
+    /// Unrecognized extension found
+    #[error("unrecognized extension found `{0:?}`")]
+    InvalidExtension(String),
+
+    /// I/O error whilst importing from file
+    #[error("I/O error whilst importing from file")]
+    Io(#[from] std::io::Error),
+
+    /// The file is not syntactically valid
+    #[error("syntax error in line {line}: {message}")]
+    Syntax {
+        /// The line that the error was found in
+        line: usize,
+
+        /// A description of the error
+        message: String,
+    },
+
+    /// An entity is referenced, but not defined
+    #[error("entity #{0} is referenced, but not defined")]
+    MissingEntity(usize),
+
+    /// An entity doesn't have the type or arguments it is expected to have
+    #[error("entity #{id} is invalid: {message}")]
+    InvalidEntity {
+        /// The ID of the entity
+        id: usize,
+
+        /// A description of the problem
+        message: String,
+    },
+
+    /// The file contains geometry that the kernel can't represent yet
+    #[error("unsupported geometry in entity #{id}: {message}")]
+    Unsupported {
+        /// The ID of the entity
+        id: usize,
+
+        /// A description of the geometry
+        message: String,
+    },
+}
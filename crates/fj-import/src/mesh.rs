@@ -0,0 +1,261 @@
+use fj_interop::mesh::{Color, Mesh};
+use fj_math::{Point, Triangle};
+
+use crate::Error;
+
+/// Import a mesh from the contents of an STL file
+///
+/// Both binary and ASCII STL are supported. The normals in the file are
+/// ignored; the triangles' orientation is defined by the order of their points.
+///
+/// Degenerate facets, which don't span any area, are skipped.
+pub fn import_stl(data: &[u8]) -> Result<Mesh<Point<3>>, Error> {
+    let mut mesh = Mesh::new();
+
+    // ASCII files start with `solid`, but some binary files do too. A binary
+    // file has exactly the size that its triangle count implies.
+    let is_binary = data.len() >= 84 && {
+        let count =
+            u32::from_le_bytes([data[80], data[81], data[82], data[83]]);
+        data.len() as u64 == 84 + 50 * u64::from(count)
+    };
+
+    if is_binary {
+        let stl = stl::read_stl(&mut &data[..])?;
+        for triangle in stl.triangles {
+            let points = [triangle.v1, triangle.v2, triangle.v3]
+                .map(|vertex| Point::from(vertex.map(f64::from)));
+            push_triangle(&mut mesh, points);
+        }
+
+        return Ok(mesh);
+    }
+
+    let source = String::from_utf8_lossy(data);
+    let mut points = Vec::new();
+
+    for (i, line) in source.lines().enumerate() {
+        let mut tokens = line.split_whitespace();
+        match tokens.next() {
+            Some("vertex") => points.push(coordinates(tokens, i + 1)?),
+            Some("endloop") => {
+                let triangle: [_; 3] =
+                    points.split_off(0).try_into().map_err(|_| {
+                        syntax_error(i + 1, "expected 3 vertices per facet")
+                    })?;
+                push_triangle(&mut mesh, triangle);
+            }
+            _ => {}
+        }
+    }
+
+    Ok(mesh)
+}
+
+/// Import a mesh from the contents of a Wavefront OBJ file
+///
+/// Only vertices and faces are read. Faces with more than three vertices are
+/// split into triangles around their first vertex, which is only correct for
+/// convex faces. Degenerate triangles, which don't span any area, are skipped.
+pub fn import_obj(source: &str) -> Result<Mesh<Point<3>>, Error> {
+    let mut mesh = Mesh::new();
+    let mut vertices = Vec::new();
+
+    for (i, line) in source.lines().enumerate() {
+        let line_number = i + 1;
+
+        let mut tokens = line.split_whitespace();
+        match tokens.next() {
+            Some("v") => vertices.push(coordinates(tokens, line_number)?),
+            Some("f") => {
+                let face = tokens
+                    .map(|token| {
+                        // A vertex can also reference texture coordinates and
+                        // a normal, like `1/2/3`. Only the position is needed.
+                        let index = token.split('/').next().unwrap_or(token);
+                        let index: i64 = index.parse().map_err(|_| {
+                            syntax_error(
+                                line_number,
+                                format!("invalid index `{token}`"),
+                            )
+                        })?;
+
+                        // Indices start at one. Negative indices count back
+                        // from the last vertex.
+                        let index = if index < 0 {
+                            vertices.len() as i64 + index
+                        } else {
+                            index - 1
+                        };
+                        usize::try_from(index)
+                            .ok()
+                            .and_then(|index| vertices.get(index).copied())
+                            .ok_or_else(|| {
+                                syntax_error(
+                                    line_number,
+                                    format!("index `{token}` is out of range"),
+                                )
+                            })
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                if face.len() < 3 {
+                    return Err(syntax_error(
+                        line_number,
+                        "expected at least 3 vertices per face",
+                    ));
+                }
+
+                for i in 1..face.len() - 1 {
+                    push_triangle(&mut mesh, [face[0], face[i], face[i + 1]]);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(mesh)
+}
+
+fn push_triangle(mesh: &mut Mesh<Point<3>>, points: [Point<3>; 3]) {
+    // Files in the wild contain facets without any area. They don't contribute
+    // anything to the shape, but `Mesh` can't represent them.
+    if let Some(triangle) = Triangle::from_points(points) {
+        mesh.push_triangle(triangle, Color::default());
+    }
+}
+
+fn coordinates<'a>(
+    tokens: impl Iterator<Item = &'a str>,
+    line: usize,
+) -> Result<Point<3>, Error> {
+    let coordinates = tokens
+        .take(3)
+        .map(|token| {
+            token.parse::<f64>().map_err(|_| {
+                syntax_error(line, format!("invalid coordinate `{token}`"))
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let coordinates: [f64; 3] = coordinates
+        .try_into()
+        .map_err(|_| syntax_error(line, "expected 3 coordinates"))?;
+
+    Ok(Point::from(coordinates))
+}
+
+fn syntax_error(line: usize, message: impl Into<String>) -> Error {
+    Error::Syntax {
+        line,
+        message: message.into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_export::{export_stl, StlFormat};
+    use fj_interop::mesh::{Color, Mesh};
+    use fj_math::Point;
+
+    use crate::Error;
+
+    use super::{import_obj, import_stl};
+
+    fn tetrahedron() -> Mesh<Point<3>> {
+        let mut mesh = Mesh::new();
+        let [a, b, c, d] =
+            [[0., 0., 0.], [1., 0., 0.], [0., 1., 0.], [0., 0., 1.]]
+                .map(Point::from);
+        for triangle in [[a, c, b], [a, b, d], [b, c, d], [a, d, c]] {
+            mesh.push_triangle(triangle, Color::default());
+        }
+        mesh
+    }
+
+    #[test]
+    fn stl() {
+        let mesh = tetrahedron();
+
+        for format in [StlFormat::Binary, StlFormat::Ascii] {
+            let mut stl = Vec::new();
+            export_stl(&mesh, format, &mut stl).unwrap();
+
+            let imported = import_stl(&stl).unwrap();
+            assert_eq!(
+                imported.vertices().collect::<Vec<_>>(),
+                mesh.vertices().collect::<Vec<_>>(),
+                "{format:?}"
+            );
+            assert_eq!(
+                imported.indices().collect::<Vec<_>>(),
+                mesh.indices().collect::<Vec<_>>(),
+                "{format:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn stl_degenerate_facet() {
+        let mut stl = Vec::new();
+        export_stl(&tetrahedron(), StlFormat::Ascii, &mut stl).unwrap();
+
+        // Add a facet whose vertices are collinear.
+        let stl = String::from_utf8(stl).unwrap().replace(
+            "endsolid",
+            "facet normal 0 0 0\n\
+             outer loop\n\
+             vertex 0 0 0\n\
+             vertex 1 1 1\n\
+             vertex 2 2 2\n\
+             endloop\n\
+             endfacet\n\
+             endsolid",
+        );
+
+        let imported = import_stl(stl.as_bytes()).unwrap();
+        assert_eq!(imported.triangles().count(), 4);
+    }
+
+    #[test]
+    fn obj() {
+        let obj = "# A square and a triangle\n\
+                   v 0 0 0\n\
+                   v 1 0 0\n\
+                   v 1 1 0\n\
+                   v 0 1 0\n\
+                   vn 0 0 1\n\
+                   f 1//1 2//1 3//1 4//1\n\
+                   f -4 -2 -1\n";
+
+        let mesh = import_obj(obj).unwrap();
+        let triangles: Vec<_> =
+            mesh.triangles().map(|triangle| triangle.inner).collect();
+
+        let [a, b, c, d] =
+            [[0., 0., 0.], [1., 0., 0.], [1., 1., 0.], [0., 1., 0.]]
+                .map(Point::from);
+        assert_eq!(
+            triangles,
+            [[a, b, c].into(), [a, c, d].into(), [a, c, d].into()]
+        );
+    }
+
+    #[test]
+    fn obj_index_out_of_range() {
+        let result = import_obj("v 0 0 0\nv 1 0 0\nv 0 1 0\nf 1 2 4\n");
+        assert!(matches!(result, Err(Error::Syntax { line: 4, .. })));
+    }
+
+    #[test]
+    fn obj_degenerate_face() {
+        let obj = "v 0 0 0\n\
+                   v 1 0 0\n\
+                   v 0 1 0\n\
+                   f 1 2 3\n\
+                   f 1 1 2\n";
+
+        let mesh = import_obj(obj).unwrap();
+        assert_eq!(mesh.triangles().count(), 1);
+    }
+}
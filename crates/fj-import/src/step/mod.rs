@@ -0,0 +1,587 @@
+mod parser;
+
+use fj_kernel::objects::{
+    Curve, CurveKind, Cycle, Edge, Face, GlobalCurve, GlobalVertex, Surface,
+    Vertex, VerticesOfEdge,
+};
+use fj_math::{Circle, Line, Point, Scalar, Vector};
+
+use crate::Error;
+
+use self::parser::{Entities, Value};
+
+/// Import the faces of a STEP (AP203/AP214) file
+///
+/// Every `ADVANCED_FACE` in the file is converted into a [`Face`]. Edges and
+/// vertices that the file shares between faces end up at the same positions,
+/// so the faces can be used with the kernel's other algorithms, like booleans.
+/// Lengths are expected to be in millimeters.
+///
+/// # Limitations
+///
+/// Only the geometry that the kernel can represent is supported: Faces must be
+/// planar, and bounded by straight edges and full circles. Anything else
+/// results in [`Error::Unsupported`].
+pub fn import_step(source: &str) -> Result<Vec<Face>, Error> {
+    let entities = parser::parse(source)?;
+    let importer = Importer {
+        entities: &entities,
+    };
+
+    let mut faces = Vec::new();
+    for (&id, records) in &entities {
+        if records.iter().any(|record| record.name == "ADVANCED_FACE") {
+            faces.push(importer.face(id)?);
+        }
+    }
+
+    Ok(faces)
+}
+
+struct Importer<'e> {
+    entities: &'e Entities,
+}
+
+impl Importer<'_> {
+    /// Access the arguments of the entity with the given ID and type
+    fn args(&self, id: usize, name: &str) -> Result<&[Value], Error> {
+        let records = self.entities.get(&id).ok_or(Error::MissingEntity(id))?;
+
+        records
+            .iter()
+            .find(|record| record.name == name)
+            .map(|record| record.args.as_slice())
+            .ok_or_else(|| {
+                invalid(id, format!("expected `{name}`, found `{records:?}`"))
+            })
+    }
+
+    /// Access the name of the entity with the given ID
+    fn name(&self, id: usize) -> Result<&str, Error> {
+        let records = self.entities.get(&id).ok_or(Error::MissingEntity(id))?;
+        Ok(records
+            .first()
+            .map(|record| record.name.as_str())
+            .unwrap_or_default())
+    }
+
+    fn coordinates(&self, id: usize, name: &str) -> Result<[f64; 3], Error> {
+        let args = self.args(id, name)?;
+        let coordinates = list(id, args.get(1))?
+            .iter()
+            .map(|value| number(id, Some(value)))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        coordinates
+            .try_into()
+            .map_err(|_| invalid(id, "expected 3 coordinates"))
+    }
+
+    fn point(&self, id: usize) -> Result<Point<3>, Error> {
+        self.coordinates(id, "CARTESIAN_POINT").map(Point::from)
+    }
+
+    fn direction(&self, id: usize) -> Result<Vector<3>, Error> {
+        let direction = Vector::from(self.coordinates(id, "DIRECTION")?);
+        if direction.magnitude() == Scalar::ZERO {
+            return Err(invalid(id, "direction has zero length"));
+        }
+
+        Ok(direction.normalize())
+    }
+
+    /// Read an `AXIS2_PLACEMENT_3D` as an origin and orthonormal axes
+    ///
+    /// Returns the origin, the z axis, and the x axis.
+    fn placement(
+        &self,
+        id: usize,
+    ) -> Result<(Point<3>, Vector<3>, Vector<3>), Error> {
+        let args = self.args(id, "AXIS2_PLACEMENT_3D")?;
+
+        let origin = self.point(reference(id, args.get(1))?)?;
+        let axis = match args.get(2) {
+            Some(Value::Omitted) => Vector::from([0., 0., 1.]),
+            value => self.direction(reference(id, value)?)?,
+        };
+        let x = match args.get(3) {
+            Some(Value::Omitted) => {
+                if axis.x.abs() < Scalar::from_f64(0.9) {
+                    Vector::from([1., 0., 0.])
+                } else {
+                    Vector::from([0., 1., 0.])
+                }
+            }
+            value => self.direction(reference(id, value)?)?,
+        };
+
+        // The x axis is only required to not be parallel to the z axis. Make
+        // it perpendicular.
+        let x = x - axis * x.dot(&axis);
+        if x.magnitude() == Scalar::ZERO {
+            return Err(invalid(id, "axes are parallel"));
+        }
+
+        Ok((origin, axis, x.normalize()))
+    }
+
+    fn face(&self, id: usize) -> Result<Face, Error> {
+        let args = self.args(id, "ADVANCED_FACE")?;
+
+        let surface = reference(id, args.get(2))?;
+        if self.name(surface)? != "PLANE" {
+            return Err(Error::Unsupported {
+                id: surface,
+                message: format!("surface type `{}`", self.name(surface)?),
+            });
+        }
+        let placement =
+            reference(surface, self.args(surface, "PLANE")?.get(1))?;
+        let (origin, mut normal, u) = self.placement(placement)?;
+        if !boolean(id, args.get(3))? {
+            normal = -normal;
+        }
+
+        let plane = Plane {
+            origin,
+            u,
+            v: normal.cross(&u),
+        };
+
+        let mut exteriors = Vec::new();
+        let mut interiors = Vec::new();
+        for bound in list(id, args.get(1))? {
+            let bound = reference(id, Some(bound))?;
+            let is_outer = self.name(bound)? == "FACE_OUTER_BOUND";
+            let args = self.args(
+                bound,
+                if is_outer {
+                    "FACE_OUTER_BOUND"
+                } else {
+                    "FACE_BOUND"
+                },
+            )?;
+
+            let mut edges = self.edge_loop(reference(bound, args.get(1))?)?;
+            if !boolean(bound, args.get(2))? {
+                edges.reverse();
+                for edge in &mut edges {
+                    edge.reverse();
+                }
+            }
+
+            if is_outer {
+                exteriors.push(edges);
+            } else {
+                interiors.push(edges);
+            }
+        }
+
+        // Many files don't mark the outer bound. The one that encloses the
+        // largest area must be it.
+        if exteriors.is_empty() {
+            let largest = interiors
+                .iter()
+                .enumerate()
+                .max_by_key(|(_, edges)| plane.area(edges))
+                .map(|(i, _)| i)
+                .ok_or_else(|| invalid(id, "face has no bounds"))?;
+            exteriors.push(interiors.remove(largest));
+        }
+
+        let [exteriors, interiors] = [exteriors, interiors].map(|cycles| {
+            cycles
+                .into_iter()
+                .map(|edges| plane.cycle(edges, id))
+                .collect::<Result<Vec<_>, _>>()
+        });
+
+        Ok(Face::new(plane.surface())
+            .with_exteriors(exteriors?)
+            .with_interiors(interiors?))
+    }
+
+    fn edge_loop(&self, id: usize) -> Result<Vec<StepEdge>, Error> {
+        let args = self.args(id, "EDGE_LOOP")?;
+
+        list(id, args.get(1))?
+            .iter()
+            .map(|edge| self.oriented_edge(reference(id, Some(edge))?))
+            .collect()
+    }
+
+    fn oriented_edge(&self, id: usize) -> Result<StepEdge, Error> {
+        let args = self.args(id, "ORIENTED_EDGE")?;
+        let orientation = boolean(id, args.get(4))?;
+
+        let edge = reference(id, args.get(3))?;
+        let args = self.args(edge, "EDGE_CURVE")?;
+        let [start, end] = [1, 2].map(|i| {
+            let vertex = reference(edge, args.get(i))?;
+            let point = self.args(vertex, "VERTEX_POINT")?.get(1);
+            self.point(reference(vertex, point)?)
+        });
+        let (start, end) = (start?, end?);
+        let same_sense = boolean(edge, args.get(4))?;
+
+        let curve = reference(edge, args.get(3))?;
+        let mut step_edge = match self.name(curve)? {
+            "LINE" => StepEdge::Line {
+                points: [start, end],
+            },
+            "CIRCLE" => {
+                if start != end {
+                    return Err(Error::Unsupported {
+                        id: edge,
+                        message: "circular arc".into(),
+                    });
+                }
+
+                let args = self.args(curve, "CIRCLE")?;
+                let (center, mut axis, _) =
+                    self.placement(reference(curve, args.get(1))?)?;
+                let radius = number(curve, args.get(2))?;
+                if !same_sense {
+                    axis = -axis;
+                }
+
+                StepEdge::Circle {
+                    center,
+                    axis,
+                    radius: Scalar::from_f64(radius),
+                    id: edge,
+                }
+            }
+            name => {
+                return Err(Error::Unsupported {
+                    id: curve,
+                    message: format!("curve type `{name}`"),
+                })
+            }
+        };
+
+        if !orientation {
+            step_edge.reverse();
+        }
+
+        Ok(step_edge)
+    }
+}
+
+/// An edge, as it is bounding a face
+#[derive(Clone, Copy)]
+enum StepEdge {
+    /// A line segment, from the first to the second point
+    Line { points: [Point<3>; 2] },
+
+    /// A full circle, counterclockwise around the axis
+    Circle {
+        center: Point<3>,
+        axis: Vector<3>,
+        radius: Scalar,
+        id: usize,
+    },
+}
+
+impl StepEdge {
+    fn reverse(&mut self) {
+        match self {
+            Self::Line { points } => points.reverse(),
+            Self::Circle { axis, .. } => *axis = -*axis,
+        }
+    }
+}
+
+/// The plane of a face, with an orthonormal coordinate system
+struct Plane {
+    origin: Point<3>,
+    u: Vector<3>,
+    v: Vector<3>,
+}
+
+impl Plane {
+    fn normal(&self) -> Vector<3> {
+        self.u.cross(&self.v)
+    }
+
+    fn surface(&self) -> Surface {
+        Surface::plane_from_points([
+            self.origin,
+            self.origin + self.u,
+            self.origin + self.v,
+        ])
+    }
+
+    fn project(&self, point: Point<3>) -> Point<2> {
+        let offset = point - self.origin;
+        Point::from([offset.dot(&self.u), offset.dot(&self.v)])
+    }
+
+    /// The area that the edges enclose, ignoring their orientation
+    fn area(&self, edges: &[StepEdge]) -> Scalar {
+        let mut area = Scalar::ZERO;
+
+        for edge in edges {
+            match edge {
+                StepEdge::Line { points } => {
+                    let [a, b] = points.map(|point| self.project(point));
+                    area += (a.u * b.v - b.u * a.v) / 2.;
+                }
+                StepEdge::Circle { radius, .. } => {
+                    area += Scalar::PI * *radius * *radius;
+                }
+            }
+        }
+
+        area.abs()
+    }
+
+    fn cycle(&self, edges: Vec<StepEdge>, face: usize) -> Result<Cycle, Error> {
+        let edges = edges
+            .into_iter()
+            .map(|edge| self.edge(edge, face))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Cycle::new(self.surface()).with_edges(edges))
+    }
+
+    fn edge(&self, edge: StepEdge, face: usize) -> Result<Edge, Error> {
+        match edge {
+            StepEdge::Line { points } => {
+                let local = points.map(|point| self.project(point));
+
+                // The global form of the curve is defined by the exact points,
+                // so the vertices are the same in every face they are part of.
+                let curve = Curve::new(
+                    CurveKind::Line(Line::from_points(local)),
+                    GlobalCurve::from_kind(CurveKind::Line(Line::from_points(
+                        points,
+                    ))),
+                );
+                let [a, b] = points.map(GlobalVertex::from_position);
+                let vertices = VerticesOfEdge::from_vertices([
+                    Vertex::new(Point::from([0.]), a),
+                    Vertex::new(Point::from([1.]), b),
+                ]);
+
+                Ok(Edge::new(curve, vertices))
+            }
+            StepEdge::Circle {
+                center,
+                axis,
+                radius,
+                id,
+            } => {
+                let alignment = axis.dot(&self.normal());
+                if alignment.abs() < Scalar::ONE - Scalar::from_f64(1e-9) {
+                    return Err(Error::Unsupported {
+                        id,
+                        message: format!(
+                            "circle that is not parallel to face #{face}"
+                        ),
+                    });
+                }
+                let direction = if alignment > Scalar::ZERO {
+                    Scalar::ONE
+                } else {
+                    -Scalar::ONE
+                };
+
+                // A full circle has no vertices, so where it starts doesn't
+                // matter. Aligning it with the plane's coordinate system keeps
+                // the local form simple.
+                let local = Circle::new(
+                    self.project(center),
+                    Vector::from([radius, Scalar::ZERO]),
+                    Vector::from([Scalar::ZERO, radius * direction]),
+                );
+                let global = Circle::new(
+                    center,
+                    self.u * radius,
+                    self.v * radius * direction,
+                );
+
+                Ok(Edge::new(
+                    Curve::new(
+                        CurveKind::Circle(local),
+                        GlobalCurve::from_kind(CurveKind::Circle(global)),
+                    ),
+                    VerticesOfEdge::none(),
+                ))
+            }
+        }
+    }
+}
+
+fn invalid(id: usize, message: impl Into<String>) -> Error {
+    Error::InvalidEntity {
+        id,
+        message: message.into(),
+    }
+}
+
+fn reference(id: usize, value: Option<&Value>) -> Result<usize, Error> {
+    match value {
+        Some(Value::Reference(reference)) => Ok(*reference),
+        value => {
+            Err(invalid(id, format!("expected reference, found {value:?}")))
+        }
+    }
+}
+
+fn number(id: usize, value: Option<&Value>) -> Result<f64, Error> {
+    match value {
+        Some(Value::Number(number)) => Ok(*number),
+        value => Err(invalid(id, format!("expected number, found {value:?}"))),
+    }
+}
+
+fn boolean(id: usize, value: Option<&Value>) -> Result<bool, Error> {
+    match value {
+        Some(Value::Enumeration(value)) if value == "T" => Ok(true),
+        Some(Value::Enumeration(value)) if value == "F" => Ok(false),
+        value => Err(invalid(id, format!("expected boolean, found {value:?}"))),
+    }
+}
+
+fn list(id: usize, value: Option<&Value>) -> Result<&[Value], Error> {
+    match value {
+        Some(Value::List(values)) => Ok(values),
+        value => Err(invalid(id, format!("expected list, found {value:?}"))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_export::export_step;
+    use fj_interop::{debug::DebugInfo, mesh::Mesh};
+    use fj_kernel::{
+        algorithms::{triangulate, union, Tolerance},
+        objects::{CurveKind, Face, Solid},
+    };
+    use fj_math::{Point, Scalar, Triangle};
+
+    use crate::Error;
+
+    use super::import_step;
+
+    const SQUARE_WITH_HOLE: &str = "ISO-10303-21;
+HEADER;
+ENDSEC;
+DATA;
+#1=CARTESIAN_POINT('',(0.,0.,0.));
+#2=DIRECTION('',(0.,0.,1.));
+#3=DIRECTION('',(1.,0.,0.));
+#4=AXIS2_PLACEMENT_3D('',#1,#2,#3);
+#5=PLANE('',#4);
+#10=CARTESIAN_POINT('',(0.,0.,0.));
+#11=CARTESIAN_POINT('',(2.,0.,0.));
+#12=CARTESIAN_POINT('',(2.,2.,0.));
+#13=CARTESIAN_POINT('',(0.,2.,0.));
+#20=VERTEX_POINT('',#10);
+#21=VERTEX_POINT('',#11);
+#22=VERTEX_POINT('',#12);
+#23=VERTEX_POINT('',#13);
+#30=LINE('',#10,$);
+#40=EDGE_CURVE('',#20,#21,#30,.T.);
+#41=EDGE_CURVE('',#21,#22,#30,.T.);
+#42=EDGE_CURVE('',#22,#23,#30,.T.);
+#43=EDGE_CURVE('',#23,#20,#30,.T.);
+#50=ORIENTED_EDGE('',*,*,#40,.T.);
+#51=ORIENTED_EDGE('',*,*,#41,.T.);
+#52=ORIENTED_EDGE('',*,*,#42,.T.);
+#53=ORIENTED_EDGE('',*,*,#43,.T.);
+#60=EDGE_LOOP('',(#50,#51,#52,#53));
+#70=CARTESIAN_POINT('',(1.,1.,0.));
+#71=AXIS2_PLACEMENT_3D('',#70,#2,#3);
+#72=CIRCLE('',#71,0.5);
+#73=CARTESIAN_POINT('',(1.5,1.,0.));
+#74=VERTEX_POINT('',#73);
+#75=EDGE_CURVE('',#74,#74,#72,.T.);
+#76=ORIENTED_EDGE('',*,*,#75,.F.);
+#77=EDGE_LOOP('',(#76));
+#80=FACE_BOUND('',#77,.T.);
+#81=FACE_BOUND('',#60,.T.);
+#90=ADVANCED_FACE('',(#80,#81),#5,.T.);
+ENDSEC;
+END-ISO-10303-21;
+";
+
+    fn triangulated(faces: Vec<Face>) -> Mesh<Point<3>> {
+        let tolerance = Tolerance::from_scalar(0.001).unwrap();
+        triangulate(faces, tolerance, &mut DebugInfo::new())
+    }
+
+    fn area(mesh: &Mesh<Point<3>>) -> f64 {
+        mesh.triangles()
+            .map(|triangle| Triangle::from(triangle.inner.points()))
+            .map(|triangle| {
+                let [a, b, c] = triangle.points();
+                ((b - a).cross(&(c - a))).magnitude().into_f64() / 2.
+            })
+            .sum()
+    }
+
+    #[test]
+    fn face_with_hole() {
+        let faces = import_step(SQUARE_WITH_HOLE).unwrap();
+        assert_eq!(faces.len(), 1);
+
+        // The outer bound isn't marked, so it must have been detected.
+        let face = &faces[0];
+        assert_eq!(face.exteriors().count(), 1);
+        assert_eq!(face.exteriors().next().unwrap().edges().count(), 4);
+
+        let interior: Vec<_> = face.interiors().collect();
+        assert_eq!(interior.len(), 1);
+        let hole = interior[0].edges().next().unwrap();
+        match hole.curve().global().kind() {
+            CurveKind::Circle(circle) => {
+                assert_eq!(circle.center(), Point::from([1., 1., 0.]));
+                assert_eq!(circle.a().magnitude(), Scalar::from_f64(0.5));
+            }
+            curve => panic!("Expected circle, got {curve:?}"),
+        }
+
+        let area = area(&triangulated(faces));
+        let expected = 4. - std::f64::consts::PI * 0.25;
+        assert!((area - expected).abs() < 0.01, "{area} != {expected}");
+    }
+
+    #[test]
+    fn exported_cube() {
+        let cube: Vec<_> = Solid::build()
+            .cube_from_edge_length(1.)
+            .into_faces()
+            .collect();
+
+        let mut step = Vec::new();
+        export_step(&cube, &mut step).unwrap();
+        let imported = import_step(&String::from_utf8(step).unwrap()).unwrap();
+        assert_eq!(imported.len(), 6);
+
+        let mut vertices: Vec<_> = imported
+            .iter()
+            .flat_map(|face| face.all_cycles())
+            .flat_map(|cycle| cycle.vertices())
+            .map(|vertex| vertex.global().position())
+            .collect();
+        vertices.sort();
+        vertices.dedup();
+        assert_eq!(vertices.len(), 8);
+
+        // Imported faces can be combined with faces from the kernel.
+        let tolerance = Tolerance::from_scalar(0.001).unwrap();
+        let mesh = triangulated(union(&imported, &cube, tolerance));
+        assert!((area(&mesh) - 6.).abs() < 1e-9);
+    }
+
+    #[test]
+    fn unsupported_surface() {
+        let source = SQUARE_WITH_HOLE
+            .replace("#5=PLANE('',#4);", "#5=CYLINDRICAL_SURFACE('',#4,1.);");
+
+        let result = import_step(&source);
+        assert!(matches!(result, Err(Error::Unsupported { id: 5, .. })));
+    }
+}
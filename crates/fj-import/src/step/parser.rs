@@ -0,0 +1,381 @@
+//! Parser for the data section of STEP (ISO 10303-21) files
+
+use std::{collections::BTreeMap, iter::Peekable, str::Chars};
+
+use crate::Error;
+
+/// The entities of a STEP file, by ID
+pub type Entities = BTreeMap<usize, Vec<Record>>;
+
+/// A typed list of values
+///
+/// A simple entity consists of one record. Complex entities consist of one
+/// record for each of the types that they combine.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Record {
+    pub name: String,
+    pub args: Vec<Value>,
+}
+
+/// A value in a STEP file
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    /// A reference to another entity, like `#12`
+    Reference(usize),
+
+    /// An integer or real number
+    Number(f64),
+
+    /// A string, like `'name'`
+    String(String),
+
+    /// An enumeration value, like `.T.`
+    Enumeration(String),
+
+    /// A list of values, like `(1.,2.,3.)`
+    List(Vec<Value>),
+
+    /// A typed value, like `LENGTH_MEASURE(1.)`
+    Typed(Record),
+
+    /// An unset (`$`) or derived (`*`) value
+    Omitted,
+}
+
+/// Parse the entities in the data section of a STEP file
+pub fn parse(source: &str) -> Result<Entities, Error> {
+    let mut parser = Parser {
+        chars: source.chars().peekable(),
+        line: 1,
+    };
+
+    // Everything before the data section is the header, which isn't needed.
+    loop {
+        parser.skip_whitespace();
+        match parser.peek() {
+            Some('\'') => {
+                // Skip strings, in case they contain something that looks
+                // like the start of the data section.
+                parser.value()?;
+            }
+            Some(c) if c.is_ascii_alphabetic() => {
+                if parser.keyword().as_deref() == Some("DATA") {
+                    parser.expect(';')?;
+                    break;
+                }
+            }
+            Some(_) => {
+                parser.next();
+            }
+            None => return Err(parser.error("expected data section")),
+        }
+    }
+
+    let mut entities = Entities::new();
+    loop {
+        parser.skip_whitespace();
+        match parser.peek() {
+            Some('#') => {
+                let (id, records) = parser.instance()?;
+                entities.insert(id, records);
+            }
+            Some(_) => match parser.keyword().as_deref() {
+                Some("ENDSEC") => break,
+                _ => return Err(parser.error("expected entity instance")),
+            },
+            None => return Err(parser.error("unexpected end of file")),
+        }
+    }
+
+    Ok(entities)
+}
+
+struct Parser<'s> {
+    chars: Peekable<Chars<'s>>,
+    line: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&mut self) -> Option<char> {
+        self.chars.peek().copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.chars.next();
+        if c == Some('\n') {
+            self.line += 1;
+        }
+        c
+    }
+
+    fn error(&self, message: impl Into<String>) -> Error {
+        Error::Syntax {
+            line: self.line,
+            message: message.into(),
+        }
+    }
+
+    /// Skip whitespace and comments
+    fn skip_whitespace(&mut self) {
+        loop {
+            match self.peek() {
+                Some(c) if c.is_whitespace() => {
+                    self.next();
+                }
+                Some('/') => {
+                    self.next();
+                    if self.peek() != Some('*') {
+                        continue;
+                    }
+                    self.next();
+
+                    let mut previous = None;
+                    while let Some(c) = self.next() {
+                        if previous == Some('*') && c == '/' {
+                            break;
+                        }
+                        previous = Some(c);
+                    }
+                }
+                _ => break,
+            }
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), Error> {
+        self.skip_whitespace();
+        match self.next() {
+            Some(c) if c == expected => Ok(()),
+            Some(c) => {
+                Err(self.error(format!("expected `{expected}`, found `{c}`")))
+            }
+            None => Err(self.error(format!("expected `{expected}`"))),
+        }
+    }
+
+    /// Parse a keyword, if there is one
+    fn keyword(&mut self) -> Option<String> {
+        self.skip_whitespace();
+
+        let mut keyword = String::new();
+        while let Some(c) = self.peek() {
+            if !(c.is_ascii_alphanumeric() || c == '_' || c == '-') {
+                break;
+            }
+            keyword.push(c);
+            self.next();
+        }
+
+        (!keyword.is_empty()).then_some(keyword)
+    }
+
+    fn integer(&mut self) -> Result<usize, Error> {
+        let mut digits = String::new();
+        while let Some(c) = self.peek().filter(char::is_ascii_digit) {
+            digits.push(c);
+            self.next();
+        }
+
+        digits
+            .parse()
+            .map_err(|_| self.error(format!("invalid integer `{digits}`")))
+    }
+
+    /// Parse an entity instance, like `#1=NAME(...);`
+    fn instance(&mut self) -> Result<(usize, Vec<Record>), Error> {
+        self.expect('#')?;
+        let id = self.integer()?;
+        self.expect('=')?;
+        self.skip_whitespace();
+
+        let records = if self.peek() == Some('(') {
+            self.next();
+
+            let mut records = Vec::new();
+            loop {
+                self.skip_whitespace();
+                if self.peek() == Some(')') {
+                    self.next();
+                    break;
+                }
+                records.push(self.record()?);
+            }
+            records
+        } else {
+            vec![self.record()?]
+        };
+
+        self.expect(';')?;
+
+        Ok((id, records))
+    }
+
+    /// Parse a record, like `NAME(...)`
+    fn record(&mut self) -> Result<Record, Error> {
+        let name = self
+            .keyword()
+            .ok_or_else(|| self.error("expected entity name"))?;
+        let args = self.list()?;
+
+        Ok(Record { name, args })
+    }
+
+    /// Parse a parenthesized list of values
+    fn list(&mut self) -> Result<Vec<Value>, Error> {
+        self.expect('(')?;
+
+        let mut values = Vec::new();
+        loop {
+            self.skip_whitespace();
+            if self.peek() == Some(')') && values.is_empty() {
+                self.next();
+                break;
+            }
+
+            values.push(self.value()?);
+
+            self.skip_whitespace();
+            match self.next() {
+                Some(',') => {}
+                Some(')') => break,
+                _ => return Err(self.error("expected `,` or `)`")),
+            }
+        }
+
+        Ok(values)
+    }
+
+    fn value(&mut self) -> Result<Value, Error> {
+        self.skip_whitespace();
+
+        let value = match self.peek() {
+            Some('#') => {
+                self.next();
+                Value::Reference(self.integer()?)
+            }
+            Some('$' | '*') => {
+                self.next();
+                Value::Omitted
+            }
+            Some('(') => Value::List(self.list()?),
+            Some('\'') => {
+                self.next();
+
+                let mut string = String::new();
+                loop {
+                    match self.next() {
+                        // A quote is escaped by doubling it.
+                        Some('\'') if self.peek() == Some('\'') => {
+                            self.next();
+                            string.push('\'');
+                        }
+                        Some('\'') => break,
+                        Some(c) => string.push(c),
+                        None => return Err(self.error("unterminated string")),
+                    }
+                }
+
+                Value::String(string)
+            }
+            Some('.') => {
+                self.next();
+                let value = self
+                    .keyword()
+                    .ok_or_else(|| self.error("expected enumeration value"))?;
+                self.expect('.')?;
+
+                Value::Enumeration(value)
+            }
+            Some(c) if c.is_ascii_digit() || c == '-' || c == '+' => {
+                let mut number = String::new();
+                while let Some(c) = self.peek() {
+                    if !(c.is_ascii_digit() || "+-.eE".contains(c)) {
+                        break;
+                    }
+                    number.push(c);
+                    self.next();
+                }
+
+                let value = number.parse().map_err(|_| {
+                    self.error(format!("invalid number `{number}`"))
+                })?;
+                Value::Number(value)
+            }
+            Some(c) if c.is_ascii_alphabetic() => Value::Typed(self.record()?),
+            Some(c) => {
+                return Err(self.error(format!("unexpected character `{c}`")))
+            }
+            None => return Err(self.error("unexpected end of file")),
+        };
+
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Error;
+
+    use super::{parse, Record, Value};
+
+    #[test]
+    fn entities() {
+        let entities = parse(
+            "ISO-10303-21;\n\
+             HEADER;\n\
+             FILE_NAME('a;b','',(''),(''),'','','');\n\
+             ENDSEC;\n\
+             DATA;\n\
+             /* comment */\n\
+             #1=CARTESIAN_POINT('it''s',(1.,-2.5E-1,3));\n\
+             #2 = ORIENTED_EDGE('',*,*,#1,.T.);\n\
+             #3=(LENGTH_UNIT() NAMED_UNIT(*) SI_UNIT(.MILLI.,.METRE.));\n\
+             #4=MEASURE(LENGTH_MEASURE(1.),$);\n\
+             ENDSEC;\n\
+             END-ISO-10303-21;\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            entities[&1],
+            [Record {
+                name: "CARTESIAN_POINT".into(),
+                args: vec![
+                    Value::String("it's".into()),
+                    Value::List(vec![
+                        Value::Number(1.),
+                        Value::Number(-0.25),
+                        Value::Number(3.),
+                    ]),
+                ],
+            }]
+        );
+        assert_eq!(
+            entities[&2][0].args[3..],
+            [Value::Reference(1), Value::Enumeration("T".into())]
+        );
+
+        let names: Vec<_> = entities[&3]
+            .iter()
+            .map(|record| record.name.as_str())
+            .collect();
+        assert_eq!(names, ["LENGTH_UNIT", "NAMED_UNIT", "SI_UNIT"]);
+
+        assert_eq!(
+            entities[&4][0].args,
+            [
+                Value::Typed(Record {
+                    name: "LENGTH_MEASURE".into(),
+                    args: vec![Value::Number(1.)],
+                }),
+                Value::Omitted,
+            ]
+        );
+    }
+
+    #[test]
+    fn syntax_error() {
+        let result = parse("DATA;\n#1=POINT((1.,2.);\nENDSEC;");
+        assert!(matches!(result, Err(Error::Syntax { line: 2, .. })));
+    }
+}
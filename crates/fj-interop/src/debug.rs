@@ -7,10 +7,16 @@
 use fj_math::{Point, Segment};
 
 /// Debug info from the CAD kernel that can be visualized
-#[derive(Default)]
+#[derive(Clone, Default)]
 pub struct DebugInfo {
     /// Rays being used during face triangulation
     pub triangle_edge_checks: Vec<TriangleEdgeCheck>,
+
+    /// Approximations of the face boundaries that were triangulated
+    pub approximations: Vec<Approximation>,
+
+    /// Triangles that were removed from a triangulation, for being too thin
+    pub degenerate_triangles: Vec<[Point<3>; 3]>,
 }
 
 impl DebugInfo {
@@ -26,10 +32,13 @@ impl DebugInfo {
     /// allocations.
     pub fn clear(&mut self) {
         self.triangle_edge_checks.clear();
+        self.approximations.clear();
+        self.degenerate_triangles.clear();
     }
 }
 
 /// Record of a check to determine if a triangle edge is within a face
+#[derive(Clone)]
 pub struct TriangleEdgeCheck {
     /// The origin of the ray used to perform the check
     pub origin: Point<3>,
@@ -47,3 +56,12 @@ impl TriangleEdgeCheck {
         }
     }
 }
+
+/// The points that the CAD kernel approximated a curve or cycle with
+#[derive(Clone)]
+pub struct Approximation {
+    /// The points of the approximation, in order
+    ///
+    /// Each point is connected to the next one by a straight line.
+    pub points: Vec<Point<3>>,
+}
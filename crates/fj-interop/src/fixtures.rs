@@ -0,0 +1,37 @@
+//! Meshes for tests
+//!
+//! Available in this crate's tests and, for the tests of other crates, with
+//! the `fixtures` feature.
+
+use fj_math::{Point, Vector};
+
+use crate::mesh::{Color, Mesh};
+
+/// A cube with the given edge length, with its minimum corner at `min`
+///
+/// The triangles are wound counter-clockwise, seen from outside. Each side is
+/// a fan of two triangles around its first corner.
+pub fn cube(min: [f64; 3], size: f64) -> Mesh<Point<3>> {
+    let min = Point::from(min);
+    let corner = |[x, y, z]: [f64; 3]| {
+        min + Vector::from([x * size, y * size, z * size])
+    };
+
+    let sides = [
+        [[0., 0., 0.], [0., 1., 0.], [1., 1., 0.], [1., 0., 0.]],
+        [[0., 0., 1.], [1., 0., 1.], [1., 1., 1.], [0., 1., 1.]],
+        [[0., 0., 0.], [1., 0., 0.], [1., 0., 1.], [0., 0., 1.]],
+        [[0., 1., 0.], [0., 1., 1.], [1., 1., 1.], [1., 1., 0.]],
+        [[0., 0., 0.], [0., 0., 1.], [0., 1., 1.], [0., 1., 0.]],
+        [[1., 0., 0.], [1., 1., 0.], [1., 1., 1.], [1., 0., 1.]],
+    ];
+
+    let mut mesh = Mesh::new();
+    for side in sides {
+        let [a, b, c, d] = side.map(corner);
+        mesh.push_triangle([a, b, c], Color::default());
+        mesh.push_triangle([a, c, d], Color::default());
+    }
+
+    mesh
+}
@@ -0,0 +1,115 @@
+//! A k-d tree over the vertices of a mesh
+
+use std::cmp::Ordering;
+
+use fj_math::Point;
+
+use crate::mesh::Index;
+
+/// A k-d tree that stores indices into a list of points
+///
+/// The tree is implicit: The median of each subtree is stored in the middle of
+/// its slice of `indices`, with the lower half of the subtree to its left, and
+/// the upper half to its right.
+#[derive(Clone, Debug)]
+pub struct KdTree {
+    indices: Vec<Index>,
+}
+
+impl KdTree {
+    /// Build a tree over the provided points
+    pub fn new(points: &[Point<3>]) -> Self {
+        let mut indices: Vec<_> = (0..points.len() as Index).collect();
+        build(points, &mut indices, 0);
+
+        Self { indices }
+    }
+
+    /// Find the point closest to `target`
+    ///
+    /// Returns the index of the point and its squared distance to `target`.
+    /// Of multiple points that are equally close, the one with the lowest
+    /// index is returned.
+    pub fn nearest(
+        &self,
+        points: &[Point<3>],
+        target: Point<3>,
+    ) -> Option<(Index, f64)> {
+        let mut best = None;
+        nearest(points, &self.indices, 0, target, &mut best);
+        best
+    }
+}
+
+fn build(points: &[Point<3>], indices: &mut [Index], depth: usize) {
+    if indices.len() <= 1 {
+        return;
+    }
+
+    let axis = depth % 3;
+    let mid = indices.len() / 2;
+
+    indices.select_nth_unstable_by(mid, |&a, &b| {
+        coordinate(points, a, axis)
+            .partial_cmp(&coordinate(points, b, axis))
+            .unwrap_or(Ordering::Equal)
+    });
+
+    let (lower, upper) = indices.split_at_mut(mid);
+    build(points, lower, depth + 1);
+    build(points, &mut upper[1..], depth + 1);
+}
+
+fn nearest(
+    points: &[Point<3>],
+    indices: &[Index],
+    depth: usize,
+    target: Point<3>,
+    best: &mut Option<(Index, f64)>,
+) {
+    if indices.is_empty() {
+        return;
+    }
+
+    let axis = depth % 3;
+    let mid = indices.len() / 2;
+    let index = indices[mid];
+
+    let distance = (points[index as usize] - target)
+        .components
+        .iter()
+        .map(|c| c.into_f64().powi(2))
+        .sum::<f64>();
+    let is_better = match *best {
+        Some((best_index, best_distance)) => {
+            distance < best_distance
+                || distance == best_distance && index < best_index
+        }
+        None => true,
+    };
+    if is_better {
+        *best = Some((index, distance));
+    }
+
+    let offset = target.coords.components[axis].into_f64()
+        - coordinate(points, index, axis);
+    let (near, far) = if offset < 0. {
+        (&indices[..mid], &indices[mid + 1..])
+    } else {
+        (&indices[mid + 1..], &indices[..mid])
+    };
+
+    nearest(points, near, depth + 1, target, best);
+
+    // Points on the other side of the splitting plane can only be closer (or
+    // equally close, with a lower index), if the plane itself is close enough.
+    if let Some((_, best_distance)) = *best {
+        if offset.powi(2) <= best_distance {
+            nearest(points, far, depth + 1, target, best);
+        }
+    }
+}
+
+fn coordinate(points: &[Point<3>], index: Index, axis: usize) -> f64 {
+    points[index as usize].coords.components[axis].into_f64()
+}
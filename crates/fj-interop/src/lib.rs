@@ -14,6 +14,11 @@
 
 #![warn(missing_docs)]
 
+mod kd_tree;
+
+#[cfg(any(test, feature = "fixtures"))]
+pub mod fixtures;
+
 pub mod debug;
 pub mod mesh;
 pub mod processed_shape;
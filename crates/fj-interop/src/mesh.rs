@@ -1,16 +1,29 @@
 //! A triangle mesh
 
-use std::{collections::HashMap, hash::Hash};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+    hash::Hash,
+};
 
-use fj_math::Point;
+use fj_math::{Aabb, Point};
+use once_cell::sync::OnceCell;
+#[cfg(feature = "serde")]
+use serde::{de, ser, Deserialize, Serialize};
+
+use crate::kd_tree::KdTree;
 
 /// A triangle mesh
+#[derive(Clone)]
 pub struct Mesh<V> {
     vertices: Vec<V>,
     indices: Vec<Index>,
 
     indices_by_vertex: HashMap<V, Index>,
     triangles: Vec<Triangle>,
+
+    // Built on the first query, and reset whenever a new vertex is added.
+    kd_tree: OnceCell<KdTree>,
 }
 
 impl<V> Mesh<V>
@@ -28,6 +41,7 @@ where
             *self.indices_by_vertex.entry(vertex).or_insert_with(|| {
                 let index = self.vertices.len();
                 self.vertices.push(vertex);
+                self.kd_tree.take();
                 index as u32
             });
 
@@ -68,6 +82,39 @@ where
     pub fn triangles(&self) -> impl Iterator<Item = Triangle> + '_ {
         self.triangles.iter().copied()
     }
+
+    /// Compute which triangles and vertices of the mesh are adjacent
+    ///
+    /// Triangles are identified by their position in [`Mesh::triangles`].
+    pub fn adjacency(&self) -> Adjacency {
+        let mut edges: BTreeMap<_, Vec<_>> = BTreeMap::new();
+        let mut vertex_triangles = vec![Vec::new(); self.vertices.len()];
+
+        for (t, triangle) in self.indices.chunks(3).enumerate() {
+            for (i, &a) in triangle.iter().enumerate() {
+                let b = triangle[(i + 1) % 3];
+                edges.entry([a.min(b), a.max(b)]).or_default().push(t);
+
+                let incident: &mut Vec<_> = &mut vertex_triangles[a as usize];
+                if incident.last() != Some(&t) {
+                    incident.push(t);
+                }
+            }
+        }
+
+        let mut neighbors = vec![Vec::new(); self.indices.len() / 3];
+        for triangles in edges.values() {
+            for &t in triangles {
+                neighbors[t].extend(triangles.iter().filter(|&&n| n != t));
+            }
+        }
+
+        Adjacency {
+            edges,
+            neighbors,
+            vertex_triangles,
+        }
+    }
 }
 
 impl Mesh<Point<3>> {
@@ -88,6 +135,247 @@ impl Mesh<Point<3>> {
             color,
         });
     }
+
+    /// Compute the axis-aligned bounding box of the mesh
+    ///
+    /// The AABB of an empty mesh has its `min` and `max` points at the origin.
+    pub fn aabb(&self) -> Aabb<3> {
+        if self.vertices.is_empty() {
+            let origin = Point::origin();
+            return Aabb {
+                min: origin,
+                max: origin,
+            };
+        }
+
+        Aabb::<3>::from_points(self.vertices())
+    }
+
+    /// Compute a bounding sphere of the mesh
+    ///
+    /// Returns the center and radius of the sphere. Uses Ritter's algorithm,
+    /// which is fast, but only approximate: The sphere encloses all vertices,
+    /// but is usually somewhat larger than the smallest one that does.
+    ///
+    /// The bounding sphere of an empty mesh is at the origin, with a radius of
+    /// zero.
+    pub fn bounding_sphere(&self) -> (Point<3>, f64) {
+        let first = match self.vertices.first() {
+            Some(&first) => first,
+            None => return (Point::origin(), 0.),
+        };
+
+        let farthest_from = |point: Point<3>| {
+            self.vertices()
+                .max_by(|a, b| {
+                    Point::distance(&point, a).cmp(&Point::distance(&point, b))
+                })
+                .unwrap_or(point)
+        };
+
+        // Start with a sphere around two vertices that are far apart, then grow
+        // it to include every vertex that is still outside.
+        let a = farthest_from(first);
+        let b = farthest_from(a);
+
+        let mut center = a + (b - a) / 2.;
+        let mut radius = Point::distance(&a, &b) / 2.;
+
+        for vertex in self.vertices() {
+            let distance = Point::distance(&center, &vertex);
+
+            if distance > radius {
+                let new_radius = (radius + distance) / 2.;
+                center = center
+                    + (vertex - center) * ((new_radius - radius) / distance);
+                radius = new_radius;
+            }
+        }
+
+        (center, radius.into_f64())
+    }
+
+    /// Find the vertex that is closest to the provided point
+    ///
+    /// Returns the index of the vertex, and its distance to the point. If
+    /// multiple vertices are equally close, the one with the lowest index is
+    /// returned.
+    ///
+    /// The first query builds a spatial index of the vertices, which is reused
+    /// until the mesh is modified.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the mesh has no vertices.
+    pub fn nearest_vertex(&self, point: Point<3>) -> (usize, f64) {
+        let (index, distance_squared) = self
+            .kd_tree
+            .get_or_init(|| KdTree::new(&self.vertices))
+            .nearest(&self.vertices, point)
+            .expect("Can't find nearest vertex of mesh without vertices");
+
+        (index as usize, distance_squared.sqrt())
+    }
+
+    /// Check whether the mesh is watertight
+    ///
+    /// A watertight mesh is a closed 2-manifold: Every edge is shared by
+    /// exactly two triangles, and the triangles around every vertex form a
+    /// single fan. Only such meshes unambiguously bound a volume, which is what
+    /// 3D printers and many other consumers of exported meshes expect.
+    ///
+    /// Returns the defects that violate this, if any.
+    pub fn check_watertight(&self) -> Result<(), MeshDefects> {
+        let adjacency = self.adjacency();
+        let position = |index: Index| self.vertices[index as usize];
+
+        let mut defects = MeshDefects::default();
+
+        for ([a, b], triangles) in adjacency.edges() {
+            let edge = [a, b].map(position);
+
+            match triangles.len() {
+                1 => defects.open_edges.push(edge),
+                2 => {}
+                _ => defects.non_manifold_edges.push(edge),
+            }
+        }
+
+        // The triangles around a vertex form a fan, if they're all connected
+        // through edges that contain the vertex. Count the connected groups.
+        for (vertex, &position) in self.vertices.iter().enumerate() {
+            let vertex = vertex as Index;
+            let incident = adjacency.incident_triangles(vertex);
+
+            let other_vertices = |t: usize| {
+                self.indices[t * 3..t * 3 + 3]
+                    .iter()
+                    .copied()
+                    .filter(move |&index| index != vertex)
+            };
+
+            let mut visited = vec![false; incident.len()];
+            let mut num_fans = 0;
+            for start in 0..incident.len() {
+                if visited[start] {
+                    continue;
+                }
+                num_fans += 1;
+
+                let mut stack = vec![start];
+                visited[start] = true;
+                while let Some(i) = stack.pop() {
+                    for other in other_vertices(incident[i]) {
+                        for &t in adjacency.edge_triangles(vertex, other) {
+                            if let Some(j) =
+                                incident.iter().position(|&u| u == t)
+                            {
+                                if !visited[j] {
+                                    visited[j] = true;
+                                    stack.push(j);
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
+            if num_fans > 1 {
+                defects.non_manifold_vertices.push(position);
+            }
+        }
+
+        if defects.open_edges.is_empty()
+            && defects.non_manifold_edges.is_empty()
+            && defects.non_manifold_vertices.is_empty()
+        {
+            return Ok(());
+        }
+
+        Err(defects)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl ser::Serialize for Mesh<Point<3>> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        let serde_mesh = MeshSerde {
+            vertices: self.vertices().map(Into::into).collect(),
+            indices: self.indices.clone(),
+            colors: self.triangles().map(|triangle| triangle.color.0).collect(),
+        };
+
+        serde_mesh.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> de::Deserialize<'de> for Mesh<Point<3>> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        use de::Error as _;
+
+        let serde_mesh = MeshSerde::deserialize(deserializer)?;
+
+        if serde_mesh.indices.len() != serde_mesh.colors.len() * 3 {
+            return Err(D::Error::custom(format!(
+                "expected {} indices for {} triangles, found {}",
+                serde_mesh.colors.len() * 3,
+                serde_mesh.colors.len(),
+                serde_mesh.indices.len(),
+            )));
+        }
+
+        let mut mesh = Self::new();
+
+        let triangles = serde_mesh.indices.chunks(3).zip(serde_mesh.colors);
+        for (indices, color) in triangles {
+            let mut points = [Point::origin(); 3];
+            for (point, &index) in points.iter_mut().zip(indices) {
+                let vertex = serde_mesh
+                    .vertices
+                    .get(index as usize)
+                    .ok_or_else(|| {
+                        D::Error::custom(format!(
+                            "index {index} is out of range"
+                        ))
+                    })?;
+
+                *point = Point::from(*vertex);
+            }
+
+            let triangle =
+                fj_math::Triangle::from_points(points).ok_or_else(|| {
+                    D::Error::custom(format!(
+                        "indices {indices:?} don't form a valid triangle"
+                    ))
+                })?;
+
+            // Pushing the triangles in their original order restores the
+            // original vertices and indices too.
+            mesh.push_triangle(triangle, Color(color));
+        }
+
+        Ok(mesh)
+    }
+}
+
+/// The serialized form of [`Mesh`]
+///
+/// Every vertex is only stored once. Triangles are stored as three indices into
+/// the vertices, plus one color per triangle.
+#[cfg(feature = "serde")]
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "Mesh")]
+struct MeshSerde {
+    vertices: Vec<[f64; 3]>,
+    indices: Vec<Index>,
+    colors: Vec<[u8; 4]>,
 }
 
 // This needs to be a manual implementation. Deriving `Default` would require
@@ -99,6 +387,7 @@ impl<V> Default for Mesh<V> {
             indices: Default::default(),
             indices_by_vertex: Default::default(),
             triangles: Default::default(),
+            kd_tree: Default::default(),
         }
     }
 }
@@ -106,6 +395,97 @@ impl<V> Default for Mesh<V> {
 /// An index that refers to a vertex in a mesh
 pub type Index = u32;
 
+/// The adjacency of the triangles and vertices of a [`Mesh`]
+///
+/// Returned by [`Mesh::adjacency`]. Non-manifold edges, which are shared by
+/// more than two triangles, are kept: Each of those triangles is a neighbor of
+/// all the others.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Adjacency {
+    edges: BTreeMap<[Index; 2], Vec<usize>>,
+    neighbors: Vec<Vec<usize>>,
+    vertex_triangles: Vec<Vec<usize>>,
+}
+
+impl Adjacency {
+    /// Access the triangles that share an edge with the given triangle
+    ///
+    /// A triangle that shares multiple edges with the given one is listed once
+    /// per edge.
+    pub fn neighbors(&self, triangle: usize) -> &[usize] {
+        &self.neighbors[triangle]
+    }
+
+    /// Access the triangles that the given vertex is part of
+    pub fn incident_triangles(&self, vertex: Index) -> &[usize] {
+        &self.vertex_triangles[vertex as usize]
+    }
+
+    /// Access the triangles that share the edge between two vertices
+    ///
+    /// The order of the vertices doesn't matter. Returns an empty slice, if no
+    /// triangle has this edge.
+    pub fn edge_triangles(&self, a: Index, b: Index) -> &[usize] {
+        self.edges
+            .get(&[a.min(b), a.max(b)])
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Iterate over all edges, with the triangles that share each of them
+    ///
+    /// Each edge is identified by its two vertices, the lower index first.
+    pub fn edges(&self) -> impl Iterator<Item = ([Index; 2], &[usize])> {
+        self.edges
+            .iter()
+            .map(|(&edge, triangles)| (edge, triangles.as_slice()))
+    }
+}
+
+/// Defects that keep a [`Mesh`] from being watertight
+///
+/// Returned by [`Mesh::check_watertight`]. Edges are given by the positions of
+/// their two vertices.
+#[derive(Clone, Debug, Default, PartialEq, thiserror::Error)]
+pub struct MeshDefects {
+    /// Edges that only belong to one triangle, leaving a hole in the mesh
+    pub open_edges: Vec<[Point<3>; 2]>,
+
+    /// Edges that are shared by more than two triangles
+    pub non_manifold_edges: Vec<[Point<3>; 2]>,
+
+    /// Vertices where separate parts of the mesh touch
+    pub non_manifold_vertices: Vec<Point<3>>,
+}
+
+impl fmt::Display for MeshDefects {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Mesh is not watertight:")?;
+
+        for (description, edges) in [
+            ("Open edges", &self.open_edges),
+            ("Non-manifold edges", &self.non_manifold_edges),
+        ] {
+            if !edges.is_empty() {
+                writeln!(f, "- {description}:")?;
+
+                for [a, b] in edges {
+                    writeln!(f, "  - from {a:?} to {b:?}")?;
+                }
+            }
+        }
+        if !self.non_manifold_vertices.is_empty() {
+            writeln!(f, "- Non-manifold vertices:")?;
+
+            for vertex in &self.non_manifold_vertices {
+                writeln!(f, "  - {vertex:?}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
 /// A triangle
 ///
 /// Extension of [`fj_math::Triangle`] that also includes a color.
@@ -122,9 +502,378 @@ pub struct Triangle {
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
 pub struct Color(pub [u8; 4]);
 
+impl Color {
+    /// Construct an opaque color from its red, green, and blue components
+    pub fn rgb(r: u8, g: u8, b: u8) -> Self {
+        Self::rgba(r, g, b, 255)
+    }
+
+    /// Construct a color from its red, green, blue, and alpha components
+    pub fn rgba(r: u8, g: u8, b: u8, a: u8) -> Self {
+        Self([r, g, b, a])
+    }
+
+    /// Parse a color from a hex string
+    ///
+    /// Accepts the forms `#rgb`, `#rrggbb`, and `#rrggbbaa`. The leading `#` is
+    /// optional. Colors without an alpha component are opaque.
+    pub fn from_hex(hex: &str) -> Result<Self, ParseColorError> {
+        let digits = hex.strip_prefix('#').unwrap_or(hex);
+
+        let mut values = Vec::with_capacity(digits.len());
+        for digit in digits.chars() {
+            let value = digit
+                .to_digit(16)
+                .ok_or(ParseColorError::InvalidDigit(digit))?;
+            values.push(value as u8);
+        }
+
+        let color = match values.as_slice() {
+            // Each digit of the short form is repeated, so `#f80` is the same
+            // as `#ff8800`.
+            &[r, g, b] => Self::rgb(r * 17, g * 17, b * 17),
+            &[r1, r2, g1, g2, b1, b2] => {
+                Self::rgb(r1 << 4 | r2, g1 << 4 | g2, b1 << 4 | b2)
+            }
+            &[r1, r2, g1, g2, b1, b2, a1, a2] => Self::rgba(
+                r1 << 4 | r2,
+                g1 << 4 | g2,
+                b1 << 4 | b2,
+                a1 << 4 | a2,
+            ),
+            values => return Err(ParseColorError::InvalidLength(values.len())),
+        };
+
+        Ok(color)
+    }
+
+    /// Construct a color from normalized components
+    ///
+    /// This is the inverse of [`Color::to_linear`]. Components are clamped to
+    /// the range `0.0..=1.0`, then rounded to the nearest representable value.
+    pub fn from_linear(components: [f32; 4]) -> Self {
+        Self(components.map(|c| (c.clamp(0., 1.) * 255.).round() as u8))
+    }
+
+    /// Convert the color to normalized components, as expected by the GPU
+    ///
+    /// The components are mapped to the range `0.0..=1.0` without any gamma
+    /// correction, which means they are treated as linear.
+    pub fn to_linear(self) -> [f32; 4] {
+        self.0.map(|c| f32::from(c) / 255.)
+    }
+}
+
 impl Default for Color {
     fn default() -> Self {
         // The default color is red. This is an arbitrary choice.
         Self([255, 0, 0, 255])
     }
 }
+
+/// Error parsing a [`Color`] from a hex string
+#[derive(Debug, Eq, PartialEq, thiserror::Error)]
+pub enum ParseColorError {
+    /// The string has the wrong number of digits
+    #[error("Expected 3, 6, or 8 hex digits, found {0}")]
+    InvalidLength(usize),
+
+    /// The string contains a character that is not a hex digit
+    #[error("Invalid hex digit `{0}`")]
+    InvalidDigit(char),
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Aabb, Point};
+
+    use crate::fixtures;
+
+    use super::{Color, Mesh, MeshDefects, ParseColorError};
+
+    fn test_mesh() -> Mesh<Point<3>> {
+        let [a, b, c, d] =
+            [[0., 0., 0.], [1., 0., 0.], [1., 1., 0.], [0., 0., 1.]]
+                .map(Point::from);
+
+        let mut mesh = Mesh::new();
+        mesh.push_triangle([a, b, c], Color([255, 0, 0, 255]));
+        mesh.push_triangle([a, b, d], Color([0, 255, 0, 255]));
+        mesh.push_triangle([b, c, d], Color([0, 0, 255, 128]));
+
+        mesh
+    }
+
+    #[cfg(feature = "serde")]
+    fn assert_same_mesh(a: &Mesh<Point<3>>, b: &Mesh<Point<3>>) {
+        assert_eq!(
+            a.vertices().collect::<Vec<_>>(),
+            b.vertices().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            a.indices().collect::<Vec<_>>(),
+            b.indices().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            a.triangles().collect::<Vec<_>>(),
+            b.triangles().collect::<Vec<_>>()
+        );
+    }
+
+    /// A cube with an edge length of one, centered at the origin
+    fn cube() -> Mesh<Point<3>> {
+        fixtures::cube([-0.5, -0.5, -0.5], 1.)
+    }
+
+    #[test]
+    fn aabb() {
+        assert_eq!(
+            cube().aabb(),
+            Aabb {
+                min: Point::from([-0.5, -0.5, -0.5]),
+                max: Point::from([0.5, 0.5, 0.5]),
+            }
+        );
+
+        let empty = Mesh::<Point<3>>::new().aabb();
+        assert_eq!(empty.min, empty.max);
+    }
+
+    #[test]
+    fn adjacency() {
+        let [a, b, c, d] =
+            [[0., 0., 0.], [1., 0., 0.], [0., 1., 0.], [0., 0., 1.]]
+                .map(Point::from);
+
+        let mut tetrahedron = Mesh::new();
+        for triangle in [[a, c, b], [a, b, d], [b, c, d], [a, d, c]] {
+            tetrahedron.push_triangle(triangle, Color::default());
+        }
+
+        let adjacency = tetrahedron.adjacency();
+        for t in 0..4 {
+            let mut neighbors = adjacency.neighbors(t).to_vec();
+            neighbors.sort_unstable();
+
+            let expected: Vec<_> = (0..4).filter(|&n| n != t).collect();
+            assert_eq!(neighbors, expected);
+        }
+        for vertex in 0..4 {
+            assert_eq!(adjacency.incident_triangles(vertex).len(), 3);
+        }
+        assert_eq!(adjacency.edges().count(), 6);
+
+        // Three triangles around the same edge, like the pages of a book.
+        let mut book = Mesh::new();
+        for page in [c, d, Point::from([0., -1., 0.])] {
+            book.push_triangle([a, b, page], Color::default());
+        }
+
+        let adjacency = book.adjacency();
+        assert_eq!(adjacency.edge_triangles(1, 0), [0, 1, 2]);
+        assert_eq!(adjacency.neighbors(1), [0, 2]);
+        assert_eq!(adjacency.edge_triangles(2, 3), [] as [usize; 0]);
+    }
+
+    #[test]
+    fn bounding_sphere() {
+        let cube = cube();
+        let (center, radius) = cube.bounding_sphere();
+
+        for vertex in cube.vertices() {
+            let distance = Point::distance(&center, &vertex).into_f64();
+            assert!(distance <= radius + 1e-12);
+        }
+
+        let half_diagonal = 3_f64.sqrt() / 2.;
+        assert!((radius - half_diagonal).abs() < 1e-9);
+
+        let empty = Mesh::<Point<3>>::new().bounding_sphere();
+        assert_eq!(empty, (Point::origin(), 0.));
+    }
+
+    #[test]
+    fn check_watertight() {
+        let mut cube = cube();
+        assert_eq!(cube.check_watertight(), Ok(()));
+
+        // Two cubes that only touch at a corner.
+        let other = cube.clone();
+        for triangle in other.triangles() {
+            let points =
+                triangle.inner.points().map(|point| point + [1., 1., 1.]);
+            cube.push_triangle(points, triangle.color);
+        }
+        assert_eq!(
+            cube.check_watertight(),
+            Err(MeshDefects {
+                non_manifold_vertices: vec![Point::from([0.5, 0.5, 0.5])],
+                ..MeshDefects::default()
+            })
+        );
+
+        let [a, b, c] =
+            [[0., 0., 0.], [1., 0., 0.], [0., 1., 0.]].map(Point::from);
+        let mut book = Mesh::new();
+        for page in [c, Point::from([0., 0., 1.]), Point::from([0., -1., 0.])] {
+            book.push_triangle([a, b, page], Color::default());
+        }
+        let defects = book.check_watertight().unwrap_err();
+        assert_eq!(defects.non_manifold_edges, vec![[a, b]]);
+        assert_eq!(defects.open_edges.len(), 6);
+    }
+
+    #[test]
+    fn nearest_vertex() {
+        // A wavy grid of 20x20 quads.
+        let vertex = |i: usize, j: usize| {
+            let [x, y] = [i, j].map(|c| c as f64 * 0.5);
+            Point::from([x, y, (x * 1.3).sin() * (y * 0.7).cos()])
+        };
+
+        let mut mesh = Mesh::new();
+        for i in 0..20 {
+            for j in 0..20 {
+                let [a, b, c, d] =
+                    [(i, j), (i + 1, j), (i + 1, j + 1), (i, j + 1)]
+                        .map(|(i, j)| vertex(i, j));
+                mesh.push_triangle([a, b, c], Color::default());
+                mesh.push_triangle([a, c, d], Color::default());
+            }
+        }
+
+        let brute_force = |mesh: &Mesh<Point<3>>, point: Point<3>| {
+            mesh.vertices()
+                .map(|vertex| (vertex - point).magnitude().into_f64())
+                .enumerate()
+                .fold((usize::MAX, f64::INFINITY), |best, (i, distance)| {
+                    if distance < best.1 {
+                        (i, distance)
+                    } else {
+                        best
+                    }
+                })
+        };
+
+        for i in 0..100 {
+            let t = i as f64;
+            let point = Point::from([
+                (t * 0.37) % 11. - 0.5,
+                (t * 0.73) % 11. - 0.5,
+                (t * 0.11).sin() * 2.,
+            ]);
+
+            let (index, distance) = mesh.nearest_vertex(point);
+            let (expected_index, expected_distance) = brute_force(&mesh, point);
+
+            assert_eq!(index, expected_index);
+            assert!((distance - expected_distance).abs() < 1e-12);
+        }
+
+        // The center of a flat square is equally close to its four corners.
+        let mut square = Mesh::new();
+        let [a, b, c, d] =
+            [[1., 1., 0.], [0., 1., 0.], [0., 0., 0.], [1., 0., 0.]]
+                .map(Point::from);
+        square.push_triangle([a, b, c], Color::default());
+        square.push_triangle([a, c, d], Color::default());
+
+        let (index, distance) =
+            square.nearest_vertex(Point::from([0.5, 0.5, 0.]));
+        assert_eq!(index, 0);
+        assert!((distance - 0.5_f64.sqrt()).abs() < 1e-12);
+
+        // Adding vertices after a query is reflected by the next one.
+        square.push_triangle(
+            [d, Point::from([0.5, 0.5, 0.1]), a],
+            Color::default(),
+        );
+        assert_eq!(square.nearest_vertex(Point::from([0.5, 0.5, 0.])).0, 4);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serialize_loopback_json() {
+        let mesh = test_mesh();
+
+        let json =
+            serde_json::to_string(&mesh).expect("failed to serialize mesh");
+        let mesh_de: Mesh<Point<3>> =
+            serde_json::from_str(&json).expect("failed to deserialize mesh");
+
+        assert_same_mesh(&mesh, &mesh_de);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serialize_loopback_bincode() {
+        let mesh = test_mesh();
+
+        let bytes =
+            bincode::serialize(&mesh).expect("failed to serialize mesh");
+        let mesh_de: Mesh<Point<3>> =
+            bincode::deserialize(&bytes).expect("failed to deserialize mesh");
+
+        assert_same_mesh(&mesh, &mesh_de);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialize_invalid_index() {
+        let json = r#"{"vertices":[[0,0,0]],"indices":[0,0,1],"colors":[[0,0,0,255]]}"#;
+
+        assert!(serde_json::from_str::<Mesh<Point<3>>>(json).is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialize_degenerate_triangle() {
+        let json = r#"{"vertices":[[0,0,0],[1,0,0]],"indices":[0,0,1],"colors":[[0,0,0,255]]}"#;
+
+        assert!(serde_json::from_str::<Mesh<Point<3>>>(json).is_err());
+    }
+
+    #[test]
+    fn push_triangle_deduplicates_vertices() {
+        let mesh = test_mesh();
+
+        assert_eq!(mesh.vertices().count(), 4);
+        assert_eq!(mesh.indices().count(), 9);
+    }
+
+    #[test]
+    fn color_from_hex() {
+        assert_eq!(Color::from_hex("#f80"), Ok(Color::rgb(255, 136, 0)));
+        assert_eq!(Color::from_hex("#aabbcc"), Ok(Color::rgb(170, 187, 204)));
+        assert_eq!(Color::from_hex("AABBCC"), Ok(Color::rgb(170, 187, 204)));
+        assert_eq!(
+            Color::from_hex("#aabbcc80"),
+            Ok(Color::rgba(170, 187, 204, 128))
+        );
+
+        assert_eq!(
+            Color::from_hex("#aabb"),
+            Err(ParseColorError::InvalidLength(4))
+        );
+        assert_eq!(Color::from_hex(""), Err(ParseColorError::InvalidLength(0)));
+        assert_eq!(
+            Color::from_hex("#aabbgg"),
+            Err(ParseColorError::InvalidDigit('g'))
+        );
+    }
+
+    #[test]
+    fn color_linear_loopback() {
+        for color in [
+            Color::rgb(0, 0, 0),
+            Color::rgb(255, 255, 255),
+            Color::rgb(12, 128, 251),
+            Color::rgba(1, 2, 3, 4),
+        ] {
+            let linear = color.to_linear();
+            assert!(linear.iter().all(|c| (0. ..=1.).contains(c)));
+
+            assert_eq!(Color::from_linear(linear), color);
+        }
+    }
+}
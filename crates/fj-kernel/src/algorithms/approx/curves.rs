@@ -1,6 +1,6 @@
 use std::cmp::max;
 
-use fj_math::{Circle, Point, Scalar};
+use fj_math::{Circle, Ellipse, Parabola, Point, Scalar};
 
 use crate::objects::{CurveKind, GlobalCurve};
 
@@ -11,23 +11,30 @@ use super::{Local, Tolerance};
 /// `tolerance` defines how far the approximation is allowed to deviate from the
 /// actual edge.
 ///
-/// # Implementation Note
-///
-/// This only works as it is, because edges are severely limited and don't
-/// define which section of the curve they inhabit. Once they do that, we need
-/// an `approximate_between(a, b)` method instead, where `a` and `b` are the
-/// vertices that bound the edge on the curve.
-///
-/// The `approximate_between` methods of the curves then need to make sure to
-/// only return points in between those vertices, not the vertices themselves.
+/// If `range` is provided, only the section of the curve between those curve
+/// coordinates is approximated. The points at either end of the range are not
+/// part of the approximation, as they are the vertices of the edge.
 pub fn approx_curve(
     curve: &GlobalCurve,
+    range: Option<[Point<1>; 2]>,
     tolerance: Tolerance,
     out: &mut Vec<Local<Point<1>>>,
 ) {
-    match curve.kind() {
-        CurveKind::Circle(curve) => approx_circle(curve, tolerance, out),
-        CurveKind::Line(_) => {}
+    match (curve.kind(), range) {
+        (CurveKind::Circle(curve), Some(range)) => {
+            approx_arc(curve, range, tolerance, out)
+        }
+        (CurveKind::Circle(curve), None) => {
+            approx_circle(curve, tolerance, out)
+        }
+        (CurveKind::Ellipse(curve), range) => {
+            approx_ellipse(curve, range, tolerance, out)
+        }
+        (CurveKind::Parabola(curve), Some(range)) => {
+            approx_parabola(curve, range, tolerance, out)
+        }
+        // Parabolas are unbounded, so edges on them always have vertices.
+        (CurveKind::Line(_), _) | (CurveKind::Parabola(_), None) => {}
     }
 }
 
@@ -42,23 +49,112 @@ pub fn approx_circle(
 ) {
     let radius = circle.a().magnitude();
 
-    // To approximate the circle, we use a regular polygon for which
-    // the circle is the circumscribed circle. The `tolerance`
-    // parameter is the maximum allowed distance between the polygon
-    // and the circle. This is the same as the difference between
-    // the circumscribed circle and the incircle.
+    for angle in angles(radius, None, tolerance) {
+        let point = circle.point_from_circle_coords([angle]);
+        out.push(Local::new([angle], point));
+    }
+}
 
-    let n = number_of_vertices_for_circle(tolerance, radius);
+/// Approximate the section of the circle between the given circle coordinates
+///
+/// The section goes from the first to the second coordinate, which can be
+/// smaller than the first, or more than a full turn away from it.
+fn approx_arc(
+    circle: &Circle<3>,
+    range: [Point<1>; 2],
+    tolerance: Tolerance,
+    out: &mut Vec<Local<Point<1>>>,
+) {
+    let radius = circle.a().magnitude();
 
-    for i in 0..n {
-        let angle = Scalar::PI * 2. / n as f64 * i as f64;
+    for angle in angles(radius, Some(range), tolerance) {
         let point = circle.point_from_circle_coords([angle]);
         out.push(Local::new([angle], point));
     }
 }
 
+/// Approximate the ellipse, or the section of it within the range
+fn approx_ellipse(
+    ellipse: &Ellipse<3>,
+    range: Option<[Point<1>; 2]>,
+    tolerance: Tolerance,
+    out: &mut Vec<Local<Point<1>>>,
+) {
+    // An ellipse is a circle that has been squashed along one axis, which
+    // can only bring the approximation closer to the curve. The angles that
+    // approximate a circle with the larger radius are good enough.
+    let radius = ellipse.a().magnitude().max(ellipse.b().magnitude());
+
+    for angle in angles(radius, range, tolerance) {
+        let point = ellipse.point_from_ellipse_coords([angle]);
+        out.push(Local::new([angle], point));
+    }
+}
+
+/// Approximate the section of the parabola between the given coordinates
+fn approx_parabola(
+    parabola: &Parabola<3>,
+    range: [Point<1>; 2],
+    tolerance: Tolerance,
+    out: &mut Vec<Local<Point<1>>>,
+) {
+    let [start, end] = range.map(|point| point.t);
+
+    // The midpoint of a parabola section with length `2 * h` in parabola
+    // coordinates deviates from the midpoint of its chord by `b * h²`.
+    //
+    // The parabola is most curved at its vertex. That's where its radius of
+    // curvature is the smallest.
+    let [a, b] = [parabola.a(), parabola.b()];
+    let a_perpendicular = a - b * (a.dot(&b) / b.dot(&b));
+    let min_radius =
+        a_perpendicular.dot(&a_perpendicular) / (b.magnitude() * 2.);
+    let tolerance = tolerance.for_radius(min_radius);
+
+    let max_length =
+        (tolerance / parabola.b().magnitude()).into_f64().sqrt() * 2.;
+    let n = ((end - start).abs() / max_length).ceil().into_u64();
+
+    for i in 1..n {
+        let t = start + (end - start) / n as f64 * i as f64;
+        let point = parabola.point_from_parabola_coords([t]);
+        out.push(Local::new([t], point));
+    }
+}
+
+/// Compute the angles that approximate a circle with the given radius
+///
+/// Without a range, the angles cover the full circle, starting at zero.
+/// Otherwise, they are between the ends of the range, which are not included.
+fn angles(
+    radius: Scalar,
+    range: Option<[Point<1>; 2]>,
+    tolerance: Tolerance,
+) -> Vec<Scalar> {
+    let n = number_of_vertices_for_circle(tolerance, radius);
+
+    match range {
+        None => (0..n)
+            .map(|i| Scalar::PI * 2. / n as f64 * i as f64)
+            .collect(),
+        Some(range) => {
+            let [start, end] = range.map(|point| point.t);
+
+            // Use the same spacing as for a full circle, so arcs and circles of
+            // the same radius look alike.
+            let step = Scalar::PI * 2. / n as f64;
+            let n = ((end - start).abs() / step).ceil().into_u64();
+
+            (1..n)
+                .map(|i| start + (end - start) / n as f64 * i as f64)
+                .collect()
+        }
+    }
+}
+
 fn number_of_vertices_for_circle(tolerance: Tolerance, radius: Scalar) -> u64 {
-    let n = (Scalar::PI / (Scalar::ONE - (tolerance.inner() / radius)).acos())
+    let tolerance = tolerance.for_radius(radius);
+    let n = (Scalar::PI / (Scalar::ONE - (tolerance / radius)).acos())
         .ceil()
         .into_u64();
 
@@ -67,9 +163,76 @@ fn number_of_vertices_for_circle(tolerance: Tolerance, radius: Scalar) -> u64 {
 
 #[cfg(test)]
 mod tests {
-    use fj_math::Scalar;
+    use fj_math::{Circle, Ellipse, Parabola, Point, Scalar};
 
-    use crate::algorithms::Tolerance;
+    use crate::algorithms::{approx::Local, Tolerance};
+
+    #[test]
+    fn approx_arc() {
+        let circle = Circle::new([0., 0., 0.], [1., 0., 0.], [0., 1., 0.]);
+        let tolerance = Tolerance::from_scalar(0.01).unwrap();
+
+        for range in [[0., 1.5], [1.5, -3.]] {
+            let mut points = Vec::new();
+            super::approx_arc(
+                &circle,
+                range.map(|t| Point::from([t])),
+                tolerance,
+                &mut points,
+            );
+
+            let [start, end] = range;
+            assert!(!points.is_empty());
+            assert!(points.windows(2).all(|pair| {
+                let [a, b] = [pair[0], pair[1]].map(|p| p.local_form().t);
+                (b - a) * (end - start) > Scalar::ZERO
+            }));
+            assert!(points.iter().all(|point| {
+                let t = point.local_form().t.into_f64();
+                t > start.min(end) && t < start.max(end)
+            }));
+        }
+    }
+
+    #[test]
+    fn approx_conics() {
+        let ellipse = Ellipse::new([0., 0., 0.], [3., 0., 0.], [0., 1., 0.]);
+        let parabola = Parabola::new([0., 0., 0.], [1., 0., 0.], [0., 2., 0.]);
+        let tolerance = Tolerance::from_scalar(0.01).unwrap();
+
+        let range = [-1., 2.].map(|t| Point::from([t]));
+
+        let mut points = Vec::new();
+        super::approx_ellipse(&ellipse, Some(range), tolerance, &mut points);
+        check(&points, range, |t| ellipse.point_from_ellipse_coords([t]));
+
+        let mut points = Vec::new();
+        super::approx_parabola(&parabola, range, tolerance, &mut points);
+        check(&points, range, |t| parabola.point_from_parabola_coords([t]));
+
+        fn check(
+            points: &[Local<Point<1>>],
+            range: [Point<1>; 2],
+            curve: impl Fn(Scalar) -> Point<3>,
+        ) {
+            let [start, end] = range.map(|point| point.t);
+            let coords: Vec<_> = [start]
+                .into_iter()
+                .chain(points.iter().map(|point| point.local_form().t))
+                .chain([end])
+                .collect();
+
+            for pair in coords.windows(2) {
+                let [a, b] = [pair[0], pair[1]];
+                assert!(b > a);
+
+                let chord_midpoint = curve(a) + (curve(b) - curve(a)) / 2.;
+                let deviation =
+                    (curve((a + b) / 2.) - chord_midpoint).magnitude();
+                assert!(deviation <= Scalar::from(0.01), "{deviation:?}");
+            }
+        }
+    }
 
     #[test]
     fn number_of_vertices_for_circle() {
@@ -21,8 +21,17 @@ impl CycleApprox {
 
         for edge in cycle.edges() {
             let mut edge_points = Vec::new();
-            approx_curve(edge.curve().global(), tolerance, &mut edge_points);
-            approx_edge(*edge.vertices(), &mut edge_points);
+            let range = edge
+                .vertices()
+                .get()
+                .map(|vertices| vertices.map(|vertex| vertex.position()));
+            approx_curve(
+                edge.curve().global(),
+                range,
+                tolerance,
+                &mut edge_points,
+            );
+            approx_edge(*edge.vertices(), tolerance, &mut edge_points);
 
             points.extend(edge_points.into_iter().map(|point| {
                 let local = edge
@@ -2,10 +2,11 @@ use fj_math::Point;
 
 use crate::objects::VerticesOfEdge;
 
-use super::Local;
+use super::{Local, Tolerance};
 
 pub fn approx_edge(
     vertices: VerticesOfEdge,
+    tolerance: Tolerance,
     points: &mut Vec<Local<Point<1>>>,
 ) {
     // Insert the exact vertices of this edge into the approximation. This means
@@ -28,8 +29,20 @@ pub fn approx_edge(
         // The edge has no vertices, which means it connects to itself. We need
         // to reflect that in the approximation.
 
-        if let Some(&point) = points.first() {
-            points.push(point);
+        if let Some(&first) = points.first() {
+            // If the curve approximation already closed the loop, pushing the
+            // first point again would result in a degenerate segment. Replace
+            // the last point instead, so the loop closes exactly.
+            let is_closed = points.len() > 1
+                && points
+                    .last()
+                    .map(|last| last.coincides_with(&first, tolerance))
+                    .unwrap_or(false);
+
+            if is_closed {
+                points.pop();
+            }
+            points.push(first);
         }
     }
 }
@@ -39,7 +52,7 @@ mod test {
     use fj_math::Point;
 
     use crate::{
-        algorithms::approx::Local,
+        algorithms::{approx::Local, Tolerance},
         objects::{GlobalVertex, Vertex, VerticesOfEdge},
     };
 
@@ -63,14 +76,22 @@ mod test {
         let c = Local::new([0.75], c);
         let d = Local::new([1.0], d);
 
+        let tolerance = Tolerance::from_scalar(1e-3).unwrap();
+
         // Regular edge
         let mut points = vec![b, c];
-        super::approx_edge(vertices, &mut points);
+        super::approx_edge(vertices, tolerance, &mut points);
         assert_eq!(points, vec![a, b, c, d]);
 
         // Continuous edge
         let mut points = vec![b, c];
-        super::approx_edge(VerticesOfEdge::none(), &mut points);
+        super::approx_edge(VerticesOfEdge::none(), tolerance, &mut points);
+        assert_eq!(points, vec![b, c, b]);
+
+        // Continuous edge, already closed by the curve approximation
+        let b_approx = Local::new([0.25 + 1e-6], [2., 3. + 1e-6, 5.]);
+        let mut points = vec![b, c, b_approx];
+        super::approx_edge(VerticesOfEdge::none(), tolerance, &mut points);
         assert_eq!(points, vec![b, c, b]);
     }
 }
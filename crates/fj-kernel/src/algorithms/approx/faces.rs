@@ -1,10 +1,13 @@
-use std::collections::HashSet;
+use std::{cmp::max, collections::HashSet};
 
-use fj_math::Point;
+use fj_math::{point_in_polygon, point_on_polygon_boundary, Point, Scalar};
 
-use crate::objects::Face;
+use crate::{
+    geometry::NurbsSurface,
+    objects::{Face, Surface},
+};
 
-use super::{CycleApprox, Local, Tolerance};
+use super::{approx_nurbs_surface, CycleApprox, Local, Tolerance};
 
 /// An approximation of a [`Face`]
 #[derive(Debug, PartialEq)]
@@ -28,35 +31,43 @@ impl FaceApprox {
     /// `tolerance` defines how far the approximation is allowed to deviate from
     /// the actual face.
     pub fn new(face: &Face, tolerance: Tolerance) -> Self {
-        // Curved faces whose curvature is not fully defined by their edges
-        // are not supported yet. For that reason, we can fully ignore `face`'s
-        // `surface` field and just pass the edges to `Self::for_edges`.
+        // The curvature of a swept curve is fully defined by the edges that
+        // bound its faces. A cylinder, for example, is bordered by circles, and
+        // their approximations are sufficient to triangulate the surface.
         //
-        // An example of a curved face that is supported, is the cylinder. Its
-        // curvature is fully defined be the edges (circles) that border it. The
-        // circle approximations are sufficient to triangulate the surface.
-        //
-        // An example of a curved face that is currently not supported, and thus
-        // doesn't need to be handled here, is a sphere. A spherical face would
-        // would need to provide its own approximation, as the edges that bound
-        // it have nothing to do with its curvature.
+        // This is not the case for NURBS surfaces, which can be curved in both
+        // directions. Their faces are approximated from the surface itself, in
+        // addition to their edges.
+
+        let mut exteriors: Vec<_> = face
+            .exteriors()
+            .map(|cycle| CycleApprox::new(cycle, tolerance))
+            .collect();
+        let mut interiors: Vec<_> = face
+            .interiors()
+            .map(|cycle| CycleApprox::new(cycle, tolerance))
+            .collect();
 
         let mut points = HashSet::new();
-        let mut exteriors = Vec::new();
-        let mut interiors = HashSet::new();
 
-        for cycle in face.exteriors() {
-            let cycle = CycleApprox::new(cycle, tolerance);
+        if let Surface::Nurbs(surface) = face.surface() {
+            let grid = approx_nurbs_surface(surface, tolerance);
 
-            points.extend(cycle.points.iter().copied());
-            exteriors.push(cycle);
+            for cycle in exteriors.iter_mut().chain(&mut interiors) {
+                subdivide_cycle(cycle, surface, &grid);
+            }
+
+            points.extend(points_inside_face(
+                surface,
+                &grid,
+                exteriors.iter().chain(&interiors),
+            ));
         }
-        for cycle in face.interiors() {
-            let cycle = CycleApprox::new(cycle, tolerance);
 
+        for cycle in exteriors.iter().chain(&interiors) {
             points.extend(cycle.points.iter().copied());
-            interiors.insert(cycle);
         }
+        let interiors: HashSet<_> = interiors.into_iter().collect();
 
         // Only polygons with exactly one exterior cycle are supported.
         //
@@ -78,6 +89,91 @@ impl FaceApprox {
     }
 }
 
+/// Subdivide the segments of a cycle on a NURBS surface
+///
+/// The segments are split, until none of them is longer than the spacing of
+/// the grid that approximates the surface. The new points are on the surface,
+/// which the straight segments between them wouldn't be.
+fn subdivide_cycle(
+    cycle: &mut CycleApprox,
+    surface: &NurbsSurface,
+    grid: &[Vec<Scalar>; 2],
+) {
+    let spacing = grid_spacing(grid);
+    let mut points = Vec::new();
+
+    for segment in cycle.points.windows(2) {
+        let [a, b] = [segment[0], segment[1]];
+        points.push(a);
+
+        let [start, end] = [a, b].map(|point| *point.local_form());
+        let n = max(
+            ((end.u - start.u).abs() / spacing[0]).ceil().into_u64(),
+            ((end.v - start.v).abs() / spacing[1]).ceil().into_u64(),
+        );
+
+        for i in 1..n {
+            let point = start + (end - start) * (i as f64 / n as f64);
+            points.push(Local::new(
+                point,
+                surface.point_from_surface_coords(point),
+            ));
+        }
+    }
+    points.extend(cycle.points.last().copied());
+
+    cycle.points = points;
+}
+
+/// Find the points of the grid that are inside of the face
+///
+/// Points on the boundary of the face are left out, as the approximations of
+/// its cycles already take care of it. The same goes for the outermost grid
+/// lines, which are on the boundary of the surface's domain.
+fn points_inside_face<'r>(
+    surface: &'r NurbsSurface,
+    grid: &'r [Vec<Scalar>; 2],
+    cycles: impl Iterator<Item = &'r CycleApprox>,
+) -> impl Iterator<Item = Local<Point<2>>> + 'r {
+    let [us, vs] = [0, 1].map(|i| &grid[i][1..grid[i].len() - 1]);
+    let polygons: Vec<Vec<_>> = cycles
+        .map(|cycle| {
+            cycle
+                .points
+                .iter()
+                .map(|point| *point.local_form())
+                .collect()
+        })
+        .collect();
+
+    us.iter()
+        .flat_map(move |&u| vs.iter().map(move |&v| Point::from([u, v])))
+        .filter(move |point| {
+            // Since holes don't overlap each other or the exterior, a point is
+            // inside of the face, if it's inside of an odd number of cycles.
+            let mut contains = false;
+            for polygon in &polygons {
+                if point_on_polygon_boundary(point, polygon) {
+                    return false;
+                }
+                contains ^= point_in_polygon(point, polygon);
+            }
+
+            contains
+        })
+        .map(|point| {
+            Local::new(point, surface.point_from_surface_coords(point))
+        })
+}
+
+/// The distance between neighboring grid lines, along `u` and `v`
+fn grid_spacing(grid: &[Vec<Scalar>; 2]) -> [Scalar; 2] {
+    [0, 1].map(|i| {
+        let lines = &grid[i];
+        (lines[lines.len() - 1] - lines[0]) / (lines.len() - 1) as f64
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use fj_math::{Point, Scalar};
@@ -1,6 +1,8 @@
 //! Infrastructure for types that have a local and a global form
 
-use fj_math::Point;
+use fj_math::{Point, Scalar};
+
+use super::Tolerance;
 
 /// A wrapper around the local and global forms of a type
 ///
@@ -40,6 +42,38 @@ impl<T: LocalForm> Local<T> {
     }
 }
 
+impl<const D: usize> Local<Point<D>>
+where
+    Point<D>: LocalForm<GlobalForm = Point<3>>,
+{
+    /// Determine whether this point coincides with another
+    ///
+    /// Points coincide, if both their local and their global forms are within
+    /// `tolerance` of each other.
+    pub fn coincides_with(&self, other: &Self, tolerance: Tolerance) -> bool {
+        Point::distance(&self.local, &other.local) <= tolerance.inner()
+            && Point::distance(&self.global, &other.global) <= tolerance.inner()
+    }
+}
+
+impl<T> approx::AbsDiffEq for Local<T>
+where
+    T: LocalForm + approx::AbsDiffEq<Epsilon = Scalar>,
+    T::GlobalForm: approx::AbsDiffEq<Epsilon = Scalar>,
+{
+    type Epsilon = Scalar;
+
+    fn default_epsilon() -> Self::Epsilon {
+        Scalar::default_epsilon()
+    }
+
+    /// Compares both the local and the global form
+    fn abs_diff_eq(&self, other: &Self, epsilon: Self::Epsilon) -> bool {
+        self.local.abs_diff_eq(&other.local, epsilon)
+            && self.global.abs_diff_eq(&other.global, epsilon)
+    }
+}
+
 /// Implemented for types that are the local form of a global type
 ///
 /// See [`Local`] for more information.
@@ -55,3 +89,49 @@ impl LocalForm for Point<1> {
 impl LocalForm for Point<2> {
     type GlobalForm = Point<3>;
 }
+
+#[cfg(test)]
+mod tests {
+    use approx::{assert_abs_diff_eq, assert_abs_diff_ne};
+    use fj_math::{Point, Scalar};
+
+    use crate::algorithms::Tolerance;
+
+    use super::Local;
+
+    #[test]
+    fn abs_diff_eq() {
+        let a = Local::<Point<1>>::new(
+            Point::from([1.]),
+            Point::from([1., 0., 0.]),
+        );
+        let b = Local::<Point<1>>::new(
+            Point::from([1. + 1e-9]),
+            Point::from([1., 0., 0.]),
+        );
+        let c = Local::<Point<1>>::new(
+            Point::from([1.]),
+            Point::from([1., 1e-3, 0.]),
+        );
+
+        assert_abs_diff_eq!(a, b, epsilon = Scalar::from(1e-6));
+        assert_abs_diff_ne!(a, c, epsilon = Scalar::from(1e-6));
+    }
+
+    #[test]
+    fn coincides_with() {
+        let tolerance = Tolerance::from_scalar(1e-3).unwrap();
+
+        let a = Local::<Point<1>>::new([1.], [1., 0., 0.]);
+        let b = Local::<Point<1>>::new([1. + 1e-4], [1., 1e-4, 0.]);
+        let c = Local::<Point<1>>::new([1.], [1., 1e-2, 0.]);
+        let d = Local::<Point<1>>::new([1.1], [1., 0., 0.]);
+
+        assert!(a.coincides_with(&b, tolerance));
+        assert!(b.coincides_with(&a, tolerance));
+
+        // Both forms must coincide.
+        assert!(!a.coincides_with(&c, tolerance));
+        assert!(!a.coincides_with(&d, tolerance));
+    }
+}
@@ -3,11 +3,13 @@ mod cycles;
 mod edges;
 mod faces;
 mod local;
+mod surfaces;
 mod tolerance;
 
 pub use self::{
     cycles::CycleApprox,
     faces::FaceApprox,
     local::{Local, LocalForm},
-    tolerance::{InvalidTolerance, Tolerance},
+    surfaces::approx_nurbs_surface,
+    tolerance::{InvalidTolerance, Quality, Tolerance},
 };
@@ -0,0 +1,210 @@
+use fj_math::{Point, Scalar};
+
+use crate::geometry::NurbsSurface;
+
+use super::Tolerance;
+
+/// Compute an approximation of a NURBS surface
+///
+/// `tolerance` defines how far the approximation is allowed to deviate from the
+/// surface.
+///
+/// The surface is sampled on a grid in surface coordinates, which is refined
+/// until the triangles between the samples are within the tolerance. Returns
+/// the coordinates of the grid lines, along `u` and `v`, in that order.
+///
+/// The grid covers the whole domain of the surface. [`FaceApprox`] uses it to
+/// approximate faces on the surface.
+///
+/// [`FaceApprox`]: super::FaceApprox
+pub fn approx_nurbs_surface(
+    surface: &NurbsSurface,
+    tolerance: Tolerance,
+) -> [Vec<Scalar>; 2] {
+    // Subdivision stops here, even if the tolerance is not met yet. This can
+    // only happen with surfaces that are huge compared to the tolerance.
+    const MAX_SEGMENTS: usize = 1024;
+
+    let [spans_u, spans_v] = surface.num_spans();
+    let [degree_u, degree_v] = surface.degree();
+
+    // Start with enough segments per span to follow the polynomial pieces.
+    let mut num_u = spans_u * degree_u;
+    let mut num_v = spans_v * degree_v;
+
+    let grid = loop {
+        let grid = Grid::new(surface, num_u, num_v);
+        let [error_u, error_v] = grid.max_error(surface);
+
+        let refine_u = error_u > tolerance.inner() && num_u < MAX_SEGMENTS;
+        let refine_v = error_v > tolerance.inner() && num_v < MAX_SEGMENTS;
+
+        if !refine_u && !refine_v {
+            break grid;
+        }
+
+        if refine_u {
+            num_u *= 2;
+        }
+        if refine_v {
+            num_v *= 2;
+        }
+    };
+
+    grid.coords
+}
+
+struct Grid {
+    coords: [Vec<Scalar>; 2],
+    points: Vec<Vec<Point<3>>>,
+}
+
+impl Grid {
+    fn new(surface: &NurbsSurface, num_u: usize, num_v: usize) -> Self {
+        let [domain_u, domain_v] = surface.domain();
+        let coords = [(domain_u, num_u), (domain_v, num_v)].map(
+            |([start, end], num)| {
+                (0..=num)
+                    .map(|i| start + (end - start) * (i as f64 / num as f64))
+                    .collect::<Vec<_>>()
+            },
+        );
+
+        let points = coords[0]
+            .iter()
+            .map(|&u| {
+                coords[1]
+                    .iter()
+                    .map(|&v| surface.point_from_surface_coords([u, v]))
+                    .collect()
+            })
+            .collect();
+
+        Self { coords, points }
+    }
+
+    /// Compute the maximum error in the `u` and `v` directions
+    ///
+    /// The error of a direction is measured at the midpoints of the grid lines
+    /// that run along it. The centers of the grid cells count towards both
+    /// directions.
+    fn max_error(&self, surface: &NurbsSurface) -> [Scalar; 2] {
+        let [us, vs] = &self.coords;
+        let mut error = [Scalar::ZERO; 2];
+
+        for (i, u) in us.windows(2).enumerate() {
+            let u_mid = (u[0] + u[1]) / 2.;
+
+            for (j, v) in vs.windows(2).enumerate() {
+                let v_mid = (v[0] + v[1]) / 2.;
+
+                let p00 = self.points[i][j];
+                let p10 = self.points[i + 1][j];
+                let p01 = self.points[i][j + 1];
+                let p11 = self.points[i + 1][j + 1];
+
+                let error_u = deviation(surface, [u_mid, v[0]], [p00, p10]);
+                let error_v = deviation(surface, [u[0], v_mid], [p00, p01]);
+                let error_center =
+                    deviation(surface, [u_mid, v_mid], [p00, p11]);
+
+                error[0] = error[0].max(error_u).max(error_center);
+                error[1] = error[1].max(error_v).max(error_center);
+            }
+
+            // The grid line at the end of the `v` range isn't covered by the
+            // loop above.
+            let j = vs.len() - 1;
+            let error_u = deviation(
+                surface,
+                [u_mid, vs[j]],
+                [self.points[i][j], self.points[i + 1][j]],
+            );
+            error[0] = error[0].max(error_u);
+        }
+
+        // Same for the grid line at the end of the `u` range.
+        let i = us.len() - 1;
+        for (j, v) in vs.windows(2).enumerate() {
+            let error_v = deviation(
+                surface,
+                [us[i], (v[0] + v[1]) / 2.],
+                [self.points[i][j], self.points[i][j + 1]],
+            );
+            error[1] = error[1].max(error_v);
+        }
+
+        error
+    }
+}
+
+/// Distance of a surface point from the midpoint of a grid segment
+fn deviation(
+    surface: &NurbsSurface,
+    coords: [Scalar; 2],
+    segment: [Point<3>; 2],
+) -> Scalar {
+    let [a, b] = segment;
+    let midpoint = a + (b - a) / 2.;
+
+    (surface.point_from_surface_coords(coords) - midpoint).magnitude()
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_interop::debug::DebugInfo;
+    use fj_math::{Point, Scalar, Vector};
+
+    use crate::{
+        algorithms::{triangulate, Tolerance},
+        geometry::NurbsSurface,
+        objects::{Face, Surface},
+    };
+
+    #[test]
+    fn approx_nurbs_surface() {
+        // A quarter of a cylinder with radius 1, around the z-axis
+        let weight = Scalar::from(0.5_f64.sqrt());
+        let surface = NurbsSurface::new(
+            vec![
+                vec![Point::from([1., 0., 0.]), Point::from([1., 0., 1.])],
+                vec![Point::from([1., 1., 0.]), Point::from([1., 1., 1.])],
+                vec![Point::from([0., 1., 0.]), Point::from([0., 1., 1.])],
+            ],
+            vec![
+                vec![Scalar::ONE, Scalar::ONE],
+                vec![weight, weight],
+                vec![Scalar::ONE, Scalar::ONE],
+            ],
+            [2, 1],
+            [
+                [0., 0., 0., 1., 1., 1.].map(Scalar::from).to_vec(),
+                [0., 0., 1., 1.].map(Scalar::from).to_vec(),
+            ],
+        );
+
+        let tolerance = Tolerance::from_scalar(0.001).unwrap();
+
+        // A face that covers the whole domain, with a hole in the middle.
+        let face = Face::build(Surface::nurbs(surface))
+            .polygon_from_points([[0., 0.], [1., 0.], [1., 1.], [0., 1.]])
+            .with_hole([[0.25, 0.25], [0.25, 0.75], [0.75, 0.75], [0.75, 0.25]])
+            .into_face();
+        let mesh = triangulate(vec![face], tolerance, &mut DebugInfo::new());
+
+        // A flat strip with a hole would need only eight triangles.
+        assert!(mesh.triangles().count() > 8);
+
+        for triangle in mesh.triangles() {
+            let triangle = triangle.inner;
+            let centroid = triangle.centroid();
+            let radius = Vector::from([centroid.x, centroid.y, Scalar::ZERO]);
+
+            assert!(radius.magnitude() >= Scalar::ONE - tolerance.inner());
+            assert!(radius.magnitude() <= Scalar::ONE);
+
+            // The normals point away from the axis, like the surface's.
+            assert!(triangle.normal().unwrap().dot(&radius) > Scalar::ZERO);
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use fj_math::Scalar;
+use fj_math::{Aabb, Scalar};
 
 /// A tolerance value
 ///
@@ -8,6 +8,14 @@ use fj_math::Scalar;
 /// The `Tolerance` type enforces that the tolerance value is always larger than
 /// zero, which is an attribute that the approximation code relies on.
 ///
+/// # Adaptive tolerance
+///
+/// A single tolerance doesn't suit curves of all sizes: Tiny curves end up
+/// with very few segments, while huge ones end up with a lot of them. An
+/// adaptive tolerance (see [`Tolerance::adaptive`]) scales with the radius of
+/// curvature of whatever curve is being approximated instead, so the number of
+/// segments per curve only depends on how far the curve turns.
+///
 /// # Failing [`From`]/[`Into`] implementation
 ///
 /// The [`From`]/[`Into`] implementations of tolerance are fallible, which goes
@@ -18,7 +26,10 @@ use fj_math::Scalar;
 /// documentation doesn't provide any actual reasoning for this requirement, I'm
 /// feeling free to just ignore it.
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
-pub struct Tolerance(Scalar);
+pub struct Tolerance {
+    inner: Scalar,
+    relative: Option<Scalar>,
+}
 
 impl Tolerance {
     /// Construct a `Tolerance` from a [`Scalar`]
@@ -33,12 +44,55 @@ impl Tolerance {
             return Err(InvalidTolerance(scalar));
         }
 
-        Ok(Self(scalar))
+        Ok(Self {
+            inner: scalar,
+            relative: None,
+        })
+    }
+
+    /// Construct a `Tolerance` that is appropriate for a model of a given size
+    ///
+    /// The tolerance is relative to the smallest non-zero extent of the
+    /// bounding box. How small it is depends on the `quality` preset.
+    pub fn from_quality(
+        quality: Quality,
+        aabb: &Aabb<3>,
+    ) -> Result<Self, InvalidTolerance> {
+        let mut min_extent = Scalar::MAX;
+        for extent in aabb.size().components {
+            if extent > Scalar::ZERO && extent < min_extent {
+                min_extent = extent;
+            }
+        }
+
+        Self::from_scalar(min_extent * quality.relative_tolerance())
+    }
+
+    /// Make the tolerance adapt to the curvature of curves
+    ///
+    /// Curves are approximated with a tolerance relative to their radius of
+    /// curvature, as defined by the `quality` preset. Everything else is still
+    /// approximated with the tolerance this method is called on.
+    pub fn adaptive(self, quality: Quality) -> Self {
+        Self {
+            relative: Some(Scalar::from(quality.relative_tolerance())),
+            ..self
+        }
     }
 
     /// Return the [`Scalar`] that defines the tolerance
     pub fn inner(&self) -> Scalar {
-        self.0
+        self.inner
+    }
+
+    /// Return the tolerance for a curve with the given radius of curvature
+    ///
+    /// Returns [`Tolerance::inner`], unless the tolerance is adaptive.
+    pub fn for_radius(&self, radius: Scalar) -> Scalar {
+        match self.relative {
+            Some(relative) => radius * relative,
+            None => self.inner,
+        }
     }
 }
 
@@ -52,7 +106,126 @@ where
     }
 }
 
+/// A preset for the quality of approximations
+///
+/// See [`Tolerance::from_quality`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum Quality {
+    /// Coarse, but fast approximations
+    Draft,
+
+    /// The default
+    Normal,
+
+    /// Fine approximations
+    High,
+
+    /// Very fine approximations, that might take a while to compute
+    Ultra,
+}
+
+impl Quality {
+    /// All quality presets, from lowest to highest
+    pub const ALL: [Self; 4] =
+        [Self::Draft, Self::Normal, Self::High, Self::Ultra];
+
+    /// The tolerance, relative to the size of the model
+    pub fn relative_tolerance(&self) -> f64 {
+        match self {
+            Self::Draft => 1e-2,
+            Self::Normal => 1e-3,
+            Self::High => 1e-4,
+            Self::Ultra => 1e-5,
+        }
+    }
+
+    /// The next-higher quality preset
+    ///
+    /// Wraps around to [`Quality::Draft`] after [`Quality::Ultra`].
+    pub fn next(&self) -> Self {
+        match self {
+            Self::Draft => Self::Normal,
+            Self::Normal => Self::High,
+            Self::High => Self::Ultra,
+            Self::Ultra => Self::Draft,
+        }
+    }
+}
+
+impl Default for Quality {
+    fn default() -> Self {
+        Self::Normal
+    }
+}
+
 /// Error converting scalar to tolerance
 #[derive(Debug, thiserror::Error)]
 #[error("Invalid tolerance ({0}); must be above zero")]
 pub struct InvalidTolerance(Scalar);
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Aabb, Circle, Point, Scalar, Vector};
+
+    use crate::algorithms::approx::curves::approx_circle;
+
+    use super::{Quality, Tolerance};
+
+    #[test]
+    fn quality_presets() {
+        let circle = Circle::new(
+            Point::origin(),
+            Vector::from([1., 0., 0.]),
+            Vector::from([0., 1., 0.]),
+        );
+        let aabb = Aabb::<3>::from_points([
+            Point::from([-1., -1., 0.]),
+            Point::from([1., 1., 0.]),
+        ]);
+
+        let mut previous: Option<(f64, usize)> = None;
+        for quality in Quality::ALL {
+            let relative_tolerance = quality.relative_tolerance();
+
+            let tolerance = Tolerance::from_quality(quality, &aabb).unwrap();
+            let mut points = Vec::new();
+            approx_circle(&circle, tolerance, &mut points);
+
+            if let Some((relative_tolerance_before, points_before)) = previous {
+                assert!(relative_tolerance < relative_tolerance_before);
+                assert!(points.len() > points_before);
+            }
+
+            previous = Some((relative_tolerance, points.len()));
+        }
+    }
+
+    #[test]
+    fn adaptive() {
+        let tolerance = Tolerance::from_scalar(0.01).unwrap();
+        let adaptive = tolerance.adaptive(Quality::Normal);
+
+        let num_points = |radius: f64, tolerance: Tolerance| {
+            let circle = Circle::new(
+                Point::origin(),
+                Vector::from([radius, 0., 0.]),
+                Vector::from([0., radius, 0.]),
+            );
+
+            let mut points = Vec::new();
+            approx_circle(&circle, tolerance, &mut points);
+            points.len()
+        };
+
+        // The adaptive tolerance approximates circles of all sizes alike.
+        assert_eq!(num_points(0.01, adaptive), num_points(100., adaptive));
+        assert!(num_points(0.01, adaptive) > num_points(0.01, tolerance));
+        assert!(num_points(100., adaptive) < num_points(100., tolerance));
+
+        assert_eq!(
+            adaptive.for_radius(Scalar::from(2.)),
+            Scalar::from(2. * Quality::Normal.relative_tolerance())
+        );
+        assert_eq!(adaptive.inner(), tolerance.inner());
+    }
+}
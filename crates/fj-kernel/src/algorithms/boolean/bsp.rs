@@ -0,0 +1,310 @@
+use std::mem;
+
+use fj_interop::mesh::Color;
+use fj_math::{Point, Scalar, Vector};
+
+/// Points closer to a plane than this are considered to be on the plane
+const PLANE_EPSILON: f64 = 1e-9;
+
+/// A convex, planar polygon that is part of the boundary of a solid
+///
+/// The points are ordered counter-clockwise, when looking at the polygon from
+/// the outside of the solid.
+#[derive(Clone, Debug)]
+pub struct Fragment {
+    pub points: Vec<Point<3>>,
+    pub plane: Plane,
+    pub color: Color,
+}
+
+impl Fragment {
+    /// Construct a fragment from a triangle
+    ///
+    /// Returns `None`, if the triangle has no area.
+    pub fn from_triangle(points: [Point<3>; 3], color: Color) -> Option<Self> {
+        let plane = Plane::from_points(points)?;
+
+        Some(Self {
+            points: points.into(),
+            plane,
+            color,
+        })
+    }
+
+    fn flip(&mut self) {
+        self.points.reverse();
+        self.plane = self.plane.flip();
+    }
+}
+
+/// An oriented plane
+///
+/// Points on the side that the normal points to are in front of the plane.
+#[derive(Clone, Copy, Debug)]
+pub struct Plane {
+    pub normal: Vector<3>,
+    distance: Scalar,
+}
+
+impl Plane {
+    fn from_points([a, b, c]: [Point<3>; 3]) -> Option<Self> {
+        let normal = (b - a).cross(&(c - a));
+        if normal.magnitude() == Scalar::ZERO {
+            return None;
+        }
+
+        let normal = normal.normalize();
+        let distance = normal.dot(&a.coords);
+
+        Some(Self { normal, distance })
+    }
+
+    fn flip(self) -> Self {
+        Self {
+            normal: -self.normal,
+            distance: -self.distance,
+        }
+    }
+
+    fn signed_distance(&self, point: &Point<3>) -> Scalar {
+        self.normal.dot(&point.coords) - self.distance
+    }
+
+    /// Split a fragment into the parts in front of and behind the plane
+    fn split(&self, fragment: Fragment) -> Split {
+        let epsilon = Scalar::from_f64(PLANE_EPSILON);
+
+        let sides: Vec<_> = fragment
+            .points
+            .iter()
+            .map(|point| {
+                let distance = self.signed_distance(point);
+                if distance > epsilon {
+                    Side::Front
+                } else if distance < -epsilon {
+                    Side::Back
+                } else {
+                    Side::On
+                }
+            })
+            .collect();
+
+        let in_front = sides.contains(&Side::Front);
+        let behind = sides.contains(&Side::Back);
+
+        match (in_front, behind) {
+            (false, false) => {
+                let facing_same_way =
+                    self.normal.dot(&fragment.plane.normal) > Scalar::ZERO;
+                Split::Coplanar {
+                    fragment,
+                    facing_same_way,
+                }
+            }
+            (true, false) => Split::Parts {
+                front: Some(fragment),
+                back: None,
+            },
+            (false, true) => Split::Parts {
+                front: None,
+                back: Some(fragment),
+            },
+            (true, true) => {
+                let mut front = Vec::new();
+                let mut back = Vec::new();
+
+                let n = fragment.points.len();
+                for i in 0..n {
+                    let j = (i + 1) % n;
+                    let [a, b] = [fragment.points[i], fragment.points[j]];
+
+                    if sides[i] != Side::Back {
+                        front.push(a);
+                    }
+                    if sides[i] != Side::Front {
+                        back.push(a);
+                    }
+
+                    let crosses = matches!(
+                        (sides[i], sides[j]),
+                        (Side::Front, Side::Back) | (Side::Back, Side::Front)
+                    );
+                    if crosses {
+                        let t = self.signed_distance(&a)
+                            / (self.signed_distance(&a)
+                                - self.signed_distance(&b));
+                        let intersection = a + (b - a) * t;
+
+                        front.push(intersection);
+                        back.push(intersection);
+                    }
+                }
+
+                let part = |points: Vec<Point<3>>| {
+                    (points.len() >= 3).then(|| Fragment {
+                        points,
+                        ..fragment.clone()
+                    })
+                };
+
+                Split::Parts {
+                    front: part(front),
+                    back: part(back),
+                }
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Side {
+    Front,
+    Back,
+    On,
+}
+
+enum Split {
+    Coplanar {
+        fragment: Fragment,
+        facing_same_way: bool,
+    },
+    Parts {
+        front: Option<Fragment>,
+        back: Option<Fragment>,
+    },
+}
+
+/// A binary space partitioning tree of the boundary of a solid
+///
+/// Each node splits space along the plane of one of the fragments. Everything
+/// behind the planes of the leaf nodes is inside of the solid.
+#[derive(Debug, Default)]
+pub struct Bsp {
+    plane: Option<Plane>,
+    fragments: Vec<Fragment>,
+    front: Option<Box<Bsp>>,
+    back: Option<Box<Bsp>>,
+}
+
+impl Bsp {
+    pub fn new(fragments: Vec<Fragment>) -> Self {
+        let mut bsp = Self::default();
+        bsp.insert(fragments);
+        bsp
+    }
+
+    /// Insert fragments into the tree, splitting them where necessary
+    pub fn insert(&mut self, fragments: Vec<Fragment>) {
+        let plane = match (self.plane, fragments.first()) {
+            (Some(plane), _) => plane,
+            (None, Some(fragment)) => *self.plane.insert(fragment.plane),
+            (None, None) => return,
+        };
+
+        let mut front = Vec::new();
+        let mut back = Vec::new();
+
+        for fragment in fragments {
+            match plane.split(fragment) {
+                Split::Coplanar { fragment, .. } => {
+                    self.fragments.push(fragment)
+                }
+                Split::Parts {
+                    front: front_part,
+                    back: back_part,
+                } => {
+                    front.extend(front_part);
+                    back.extend(back_part);
+                }
+            }
+        }
+
+        if !front.is_empty() {
+            self.front
+                .get_or_insert_with(Default::default)
+                .insert(front);
+        }
+        if !back.is_empty() {
+            self.back.get_or_insert_with(Default::default).insert(back);
+        }
+    }
+
+    /// Swap inside and outside of the solid
+    pub fn invert(&mut self) {
+        for fragment in &mut self.fragments {
+            fragment.flip();
+        }
+        self.plane = self.plane.map(Plane::flip);
+
+        for node in [&mut self.front, &mut self.back].into_iter().flatten() {
+            node.invert();
+        }
+        mem::swap(&mut self.front, &mut self.back);
+    }
+
+    /// Remove the parts of `fragments` that are inside of this solid
+    ///
+    /// Fragments that are coplanar with the boundary of the solid are kept, if
+    /// they face the same way as the boundary.
+    fn clip(&self, fragments: Vec<Fragment>) -> Vec<Fragment> {
+        let plane = match self.plane {
+            Some(plane) => plane,
+            None => return fragments,
+        };
+
+        let mut front = Vec::new();
+        let mut back = Vec::new();
+
+        for fragment in fragments {
+            match plane.split(fragment) {
+                Split::Coplanar {
+                    fragment,
+                    facing_same_way,
+                } => {
+                    if facing_same_way {
+                        front.push(fragment);
+                    } else {
+                        back.push(fragment);
+                    }
+                }
+                Split::Parts {
+                    front: front_part,
+                    back: back_part,
+                } => {
+                    front.extend(front_part);
+                    back.extend(back_part);
+                }
+            }
+        }
+
+        let mut fragments = match &self.front {
+            Some(node) => node.clip(front),
+            None => front,
+        };
+        if let Some(node) = &self.back {
+            fragments.extend(node.clip(back));
+        }
+
+        fragments
+    }
+
+    /// Remove the parts of this tree's fragments that are inside of `other`
+    pub fn clip_to(&mut self, other: &Bsp) {
+        self.fragments = other.clip(mem::take(&mut self.fragments));
+
+        for node in [&mut self.front, &mut self.back].into_iter().flatten() {
+            node.clip_to(other);
+        }
+    }
+
+    /// Collect all fragments of the tree
+    pub fn into_fragments(self) -> Vec<Fragment> {
+        let mut fragments = self.fragments;
+
+        for node in [self.front, self.back].into_iter().flatten() {
+            fragments.extend(node.into_fragments());
+        }
+
+        fragments
+    }
+}
@@ -0,0 +1,654 @@
+//! Boolean operations on solids
+//!
+//! The faces of both solids are triangulated and sorted into a binary space
+//! partitioning tree each. Clipping the fragments of one tree against the
+//! other splits them along the intersections of the solids and classifies them
+//! as inside or outside, which is all that a boolean operation needs.
+//!
+//! Faces that share a surface can also be subtracted from each other, which
+//! works on polygons instead. See [`difference_2d`].
+//!
+//! # Limitations
+//!
+//! The solids must be closed, but can have faces of any kind. Curved faces are
+//! approximated within the tolerance that the operation is called with, before
+//! anything else happens. The result only consists of planar faces, so a curve
+//! stays approximated at that tolerance, even if the result is approximated at
+//! a finer one later.
+//!
+//! Vertices that are closer to each other than the tolerance are merged. Parts
+//! of the result that are thinner than the tolerance collapse, which can leave
+//! parts of the solid that only touch along an edge.
+//!
+//! Every fragment becomes a face of its own. Faces that end up next to each
+//! other in the same plane are not merged, so the result has many more faces
+//! than necessary.
+
+mod bsp;
+mod polygon;
+
+use std::collections::HashMap;
+
+use fj_interop::debug::DebugInfo;
+use fj_math::{Line, Point, Scalar, Vector};
+
+use crate::objects::{
+    Curve, CurveKind, Cycle, Edge, Face, GlobalCurve, GlobalVertex, Surface,
+    Vertex, VerticesOfEdge,
+};
+
+pub use self::polygon::difference_2d;
+
+use self::bsp::{Bsp, Fragment};
+
+use super::{orient_consistently, triangulate, volume, Tolerance};
+
+/// Compute the union of two solids
+///
+/// Both solids are given by the faces that bound them, and must be closed. The
+/// result is made up of planar faces, which approximate the faces of the
+/// original solids within the tolerance. Where the solids intersect, the faces
+/// of both are cut along the intersection and the parts that end up inside the
+/// other solid are removed.
+///
+/// The faces share their vertices and edges where they meet, so the result is
+/// watertight when triangulated. Vertices that are closer to each other than
+/// the tolerance are merged.
+pub fn union(a: &[Face], b: &[Face], tolerance: Tolerance) -> Vec<Face> {
+    let mut a = Bsp::new(fragments(a, tolerance));
+    let b = Bsp::new(fragments(b, tolerance));
+
+    merge(&mut a, b);
+
+    into_faces(a.into_fragments(), tolerance)
+}
+
+/// Subtract one solid from another
+///
+/// Removes the volume of `b` from `a`, which can be used to cut holes and
+/// pockets into `a`. The parts of the faces of `b` that are inside of `a`
+/// become faces of the result, facing the other way.
+///
+/// See [`union`] for the requirements on the solids and the properties of the
+/// result.
+pub fn difference(a: &[Face], b: &[Face], tolerance: Tolerance) -> Vec<Face> {
+    let mut a = Bsp::new(fragments(a, tolerance));
+    let b = Bsp::new(fragments(b, tolerance));
+
+    // Everything outside of `a` or inside of `b` is the union of the inverse of
+    // `a` and `b`. Inverting that union leaves the difference.
+    a.invert();
+    merge(&mut a, b);
+    a.invert();
+
+    into_faces(a.into_fragments(), tolerance)
+}
+
+/// Compute the intersection of two solids
+///
+/// Keeps only the volume that is inside of both solids. If they don't overlap,
+/// the result has no faces.
+///
+/// See [`union`] for the requirements on the solids and the properties of the
+/// result.
+pub fn intersection(a: &[Face], b: &[Face], tolerance: Tolerance) -> Vec<Face> {
+    let mut a = Bsp::new(fragments(a, tolerance));
+    let mut b = Bsp::new(fragments(b, tolerance));
+
+    // Everything outside of either solid is the union of their inverses.
+    // Inverting that union leaves the intersection.
+    a.invert();
+    b.invert();
+    merge(&mut a, b);
+    a.invert();
+
+    into_faces(a.into_fragments(), tolerance)
+}
+
+/// Merge `b` into `a`, so `a` becomes the union of both
+fn merge(a: &mut Bsp, mut b: Bsp) {
+    a.clip_to(&b);
+    b.clip_to(a);
+
+    // Remove the parts of `b` that coincide with the boundary of `a`, which
+    // would otherwise be duplicated.
+    b.invert();
+    b.clip_to(a);
+    b.invert();
+
+    a.insert(b.into_fragments());
+}
+
+fn fragments(faces: &[Face], tolerance: Tolerance) -> Vec<Fragment> {
+    let mut mesh =
+        triangulate(faces.to_vec(), tolerance, &mut DebugInfo::new());
+
+    // Classifying fragments as inside or outside relies on all of them facing
+    // outwards, which the faces of a solid don't guarantee.
+    orient_consistently(&mut mesh);
+    let is_inside_out = volume(&mesh) < Scalar::ZERO;
+
+    mesh.triangles()
+        .filter_map(|triangle| {
+            let [a, b, c] = triangle.inner.points();
+            let points = if is_inside_out { [a, c, b] } else { [a, b, c] };
+
+            Fragment::from_triangle(points, triangle.color)
+        })
+        .collect()
+}
+
+/// Convert fragments into faces that connect without gaps
+fn into_faces(mut fragments: Vec<Fragment>, tolerance: Tolerance) -> Vec<Face> {
+    let mut welder = Welder::new(tolerance.inner());
+    for fragment in &mut fragments {
+        for point in &mut fragment.points {
+            *point = welder.weld(*point);
+        }
+        fragment.points.dedup();
+        if fragment.points.first() == fragment.points.last() {
+            fragment.points.pop();
+        }
+    }
+    fragments.retain(|fragment| fragment.points.len() >= 3);
+
+    // Splitting a fragment adds vertices to its edges, that the fragments next
+    // to it don't necessarily have. Add those vertices to all edges they are
+    // on, or the triangulated faces won't connect there.
+    //
+    // Sorting by all coordinates, not just the x-coordinate that
+    // `points_on_edge` needs, makes the result independent of the order that
+    // the welder returns the points in.
+    let mut vertices = welder.into_points();
+    vertices.sort();
+
+    fragments
+        .into_iter()
+        .flat_map(|fragment| {
+            let mut points = Vec::new();
+
+            let n = fragment.points.len();
+            for i in 0..n {
+                let [a, b] = [fragment.points[i], fragment.points[(i + 1) % n]];
+                points.push(a);
+                points.extend(points_on_edge(
+                    [a, b],
+                    &vertices,
+                    tolerance.inner(),
+                ));
+            }
+
+            // A fragment that is thinner than the tolerance can have one of
+            // its own vertices added to its edges. Its boundary then touches
+            // itself there, or turns back on itself. Each loop of the boundary
+            // becomes a face of its own, unless it doesn't enclose any area.
+            split_into_loops(points)
+                .into_iter()
+                .filter(|points| encloses_area(points, &fragment))
+                .map(|points| {
+                    face_from_fragment(Fragment {
+                        points,
+                        ..fragment.clone()
+                    })
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// Split a polygon into loops, wherever it visits a point more than once
+///
+/// Loops that only go back and forth between points are returned too.
+fn split_into_loops(points: Vec<Point<3>>) -> Vec<Vec<Point<3>>> {
+    let mut loops = Vec::new();
+    let mut current: Vec<Point<3>> = Vec::new();
+
+    for point in points {
+        // The loop starts and ends at the point that is visited again.
+        if let Some(i) = current.iter().position(|&other| other == point) {
+            loops.push(current.split_off(i));
+        }
+        current.push(point);
+    }
+
+    loops.push(current);
+    loops
+}
+
+/// Determine whether a loop of points encloses an area
+///
+/// Only counts, if the loop goes around the area in the same direction as the
+/// fragment it was split from.
+fn encloses_area(points: &[Point<3>], fragment: &Fragment) -> bool {
+    let origin = match points.first() {
+        Some(&origin) => origin,
+        None => return false,
+    };
+
+    // This is twice the vector area of the loop, computed as the sum of the
+    // triangles that fan out from its first point.
+    let area = points
+        .windows(2)
+        .map(|pair| (pair[0] - origin).cross(&(pair[1] - origin)))
+        .fold(Vector::from([0., 0., 0.]), |sum, area| sum + area);
+
+    area.dot(&fragment.plane.normal) > Scalar::ZERO
+}
+
+/// Find the vertices that are on an edge, excluding its end points
+///
+/// `vertices` must be sorted by their x-coordinate. The vertices are returned
+/// in order, from `a` to `b`.
+fn points_on_edge(
+    [a, b]: [Point<3>; 2],
+    vertices: &[Point<3>],
+    tolerance: Scalar,
+) -> Vec<Point<3>> {
+    let min_x = a.x.min(b.x) - tolerance;
+    let max_x = a.x.max(b.x) + tolerance;
+
+    let direction = b - a;
+    let length = direction.magnitude();
+
+    let start = vertices.partition_point(|point| point.x < min_x);
+    let mut points: Vec<_> = vertices[start..]
+        .iter()
+        .take_while(|point| point.x <= max_x)
+        .filter_map(|&point| {
+            let along = (point - a).dot(&direction) / length;
+            if along <= tolerance || along >= length - tolerance {
+                return None;
+            }
+
+            let closest = a + direction * (along / length);
+            (Point::distance(&point, &closest) <= tolerance)
+                .then_some((along, point))
+        })
+        .collect();
+
+    points.sort_by_key(|&(along, _)| along);
+    points.into_iter().map(|(_, point)| point).collect()
+}
+
+/// Build a planar face, whose vertices are exactly the points of the fragment
+fn face_from_fragment(fragment: Fragment) -> Face {
+    let points = fragment.points;
+
+    let origin = points[0];
+    let u = (points[1] - origin).normalize();
+    let v = fragment.plane.normal.cross(&u);
+    let surface = Surface::plane_from_points([origin, origin + u, origin + v]);
+
+    let mut edges = Vec::new();
+    for i in 0..points.len() {
+        let global = [points[i], points[(i + 1) % points.len()]];
+        let local = global.map(|point| {
+            let offset = point - origin;
+            Point::from([offset.dot(&u), offset.dot(&v)])
+        });
+
+        // The global form of the curve is defined by the exact points, so the
+        // vertices are the same in every face they are part of.
+        let curve = Curve::new(
+            CurveKind::Line(Line::from_points(local)),
+            GlobalCurve::from_kind(CurveKind::Line(Line::from_points(global))),
+        );
+        let [a, b] = global.map(GlobalVertex::from_position);
+        let vertices = VerticesOfEdge::from_vertices([
+            Vertex::new(Point::from([0.]), a),
+            Vertex::new(Point::from([1.]), b),
+        ]);
+
+        edges.push(Edge::new(curve, vertices));
+    }
+
+    Face::new(surface.clone())
+        .with_exteriors([Cycle::new(surface).with_edges(edges)])
+        .with_color(fragment.color)
+}
+
+/// Merges points that are within a tolerance of each other
+struct Welder {
+    tolerance: Scalar,
+    grid: HashMap<[i64; 3], Vec<Point<3>>>,
+}
+
+impl Welder {
+    fn new(tolerance: Scalar) -> Self {
+        Self {
+            tolerance,
+            grid: HashMap::new(),
+        }
+    }
+
+    /// Return a previously welded point that is close, or the point itself
+    fn weld(&mut self, point: Point<3>) -> Point<3> {
+        // Points are sorted into cells the size of the tolerance, so only the
+        // neighboring cells need to be searched.
+        let cell = point
+            .coords
+            .components
+            .map(|c| (c / self.tolerance).into_f64().floor() as i64);
+
+        for dx in -1..=1 {
+            for dy in -1..=1 {
+                for dz in -1..=1 {
+                    let [x, y, z] = cell;
+                    let neighbors = self.grid.get(&[x + dx, y + dy, z + dz]);
+
+                    let close = neighbors.into_iter().flatten().find(|other| {
+                        Point::distance(&point, other) <= self.tolerance
+                    });
+                    if let Some(&close) = close {
+                        return close;
+                    }
+                }
+            }
+        }
+
+        self.grid.entry(cell).or_default().push(point);
+        point
+    }
+
+    fn into_points(self) -> Vec<Point<3>> {
+        self.grid.into_values().flatten().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use fj_interop::{
+        debug::DebugInfo,
+        mesh::{Color, Mesh},
+    };
+    use fj_math::{Point, Scalar, Transform};
+
+    use crate::{
+        algorithms::{
+            reverse_cycle, sweep, transform_faces, triangulate, volume,
+            Tolerance, TransformObject,
+        },
+        objects::{Cycle, Edge, Face, Sketch, Solid, Surface},
+        validation::{validate, ValidationConfig},
+    };
+
+    use super::{difference, intersection, union};
+
+    fn cube(edge_length: f64, offset: [f64; 3]) -> Vec<Face> {
+        let mut faces = Solid::build()
+            .cube_from_edge_length(edge_length)
+            .into_faces()
+            .collect();
+        transform_faces(&mut faces, &Transform::translation(offset));
+        faces
+    }
+
+    fn cuboid(
+        [x0, y0, z0]: [f64; 3],
+        [x1, y1, z1]: [f64; 3],
+        tolerance: Tolerance,
+    ) -> Vec<Face> {
+        let surface = Surface::xy_plane().translate([0., 0., z0]);
+        let bottom = Face::build(surface)
+            .polygon_from_points([[x0, y0], [x1, y0], [x1, y1], [x0, y1]])
+            .into_face();
+
+        sweep(
+            Sketch::new().with_faces([bottom]),
+            [0., 0., z1 - z0],
+            tolerance,
+            Color::default(),
+        )
+        .into_faces()
+        .collect()
+    }
+
+    /// A cylinder along the z-axis, with a hole through its middle, unless the
+    /// radius of that is zero
+    fn cylinder(
+        [radius, hole_radius]: [f64; 2],
+        [x, y, z]: [f64; 3],
+        height: f64,
+        tolerance: Tolerance,
+    ) -> Vec<Face> {
+        let surface = Surface::xy_plane();
+        let circle = |radius| {
+            Cycle::new(surface.clone()).with_edges([
+                Edge::build().circle_from_radius(Scalar::from_f64(radius))
+            ])
+        };
+
+        let mut bottom =
+            Face::new(surface.clone()).with_exteriors([circle(radius)]);
+        if hole_radius > 0. {
+            bottom =
+                bottom.with_interiors([reverse_cycle(&circle(hole_radius))]);
+        }
+
+        let mut faces = sweep(
+            Sketch::new().with_faces([bottom]),
+            [0., 0., height],
+            tolerance,
+            Color::default(),
+        )
+        .into_faces()
+        .collect();
+        transform_faces(&mut faces, &Transform::translation([x, y, z]));
+        faces
+    }
+
+    fn triangulated(faces: Vec<Face>, tolerance: Tolerance) -> Mesh<Point<3>> {
+        triangulate(faces, tolerance, &mut DebugInfo::new())
+    }
+
+    fn assert_watertight(mesh: &Mesh<Point<3>>) {
+        let adjacency = mesh.adjacency();
+        for (edge, triangles) in adjacency.edges() {
+            assert_eq!(triangles.len(), 2, "Edge {edge:?} is not shared");
+        }
+    }
+
+    fn assert_volume(mesh: &Mesh<Point<3>>, expected: f64) {
+        let volume = volume(mesh).into_f64();
+        assert!((volume - expected).abs() < 1e-9, "{volume} != {expected}");
+    }
+
+    /// Curved solids are approximated, so their volume is not exact
+    fn assert_volume_near(mesh: &Mesh<Point<3>>, expected: f64) {
+        let volume = volume(mesh).into_f64();
+        assert!((volume - expected).abs() < 0.05, "{volume} != {expected}");
+    }
+
+    fn assert_valid(faces: &[Face]) {
+        if let Err(err) = validate(faces.to_vec(), &ValidationConfig::default())
+        {
+            panic!("Result is not valid: {err}");
+        }
+    }
+
+    #[test]
+    fn union_overlapping() {
+        let tolerance = Tolerance::from_scalar(0.001).unwrap();
+
+        let faces =
+            union(&cube(2., [0., 0., 0.]), &cube(2., [1., 1., 1.]), tolerance);
+        let mesh = triangulated(faces, tolerance);
+
+        assert_watertight(&mesh);
+        assert_volume(&mesh, 8. + 8. - 1.);
+
+        let aabb = mesh.aabb();
+        assert_eq!(aabb.min, Point::from([-1., -1., -1.]));
+        assert_eq!(aabb.max, Point::from([2., 2., 2.]));
+    }
+
+    #[test]
+    fn union_touching() {
+        let tolerance = Tolerance::from_scalar(0.001).unwrap();
+
+        // The cubes share half of a face, which ends up inside the union.
+        let faces =
+            union(&cube(2., [0., 0., 0.]), &cube(2., [2., 1., 0.]), tolerance);
+        let mesh = triangulated(faces, tolerance);
+
+        assert_watertight(&mesh);
+        assert_volume(&mesh, 16.);
+
+        for triangle in mesh.triangles() {
+            let center = triangle.inner.centroid();
+            let on_shared_face = center.x == Scalar::ONE
+                && center.y > Scalar::ZERO
+                && center.y < Scalar::ONE;
+            assert!(!on_shared_face);
+        }
+    }
+
+    #[test]
+    fn union_contained_and_disjoint() {
+        let tolerance = Tolerance::from_scalar(0.001).unwrap();
+
+        let outer = cube(4., [0., 0., 0.]);
+        let faces = union(&outer, &cube(1., [0.5, 0., 0.]), tolerance);
+        let mesh = triangulated(faces, tolerance);
+        assert_watertight(&mesh);
+        assert_volume(&mesh, 64.);
+
+        let faces = union(&outer, &cube(1., [5., 0., 0.]), tolerance);
+        let mesh = triangulated(faces, tolerance);
+        assert_watertight(&mesh);
+        assert_volume(&mesh, 65.);
+    }
+
+    #[test]
+    fn difference_overlapping() {
+        let tolerance = Tolerance::from_scalar(0.001).unwrap();
+
+        let faces = difference(
+            &cube(2., [0., 0., 0.]),
+            &cube(2., [1., 1., 1.]),
+            tolerance,
+        );
+        let mesh = triangulated(faces, tolerance);
+
+        assert_watertight(&mesh);
+        assert_volume(&mesh, 8. - 1.);
+    }
+
+    #[test]
+    fn difference_hole_and_pocket() {
+        let tolerance = Tolerance::from_scalar(0.001).unwrap();
+
+        let a = cube(2., [0., 0., 0.]);
+
+        // A square hole, all the way through.
+        let b = cuboid([-0.5, -0.5, -2.], [0.5, 0.5, 2.], tolerance);
+        let mesh = triangulated(difference(&a, &b, tolerance), tolerance);
+        assert_watertight(&mesh);
+        assert_volume(&mesh, 8. - 2.);
+
+        // A pocket in the top face.
+        let b = cube(1., [0., 0., 1.]);
+        let mesh = triangulated(difference(&a, &b, tolerance), tolerance);
+        assert_watertight(&mesh);
+        assert_volume(&mesh, 8. - 0.5);
+
+        // Subtracting a disjoint solid changes nothing.
+        let b = cube(1., [3., 0., 0.]);
+        let mesh = triangulated(difference(&a, &b, tolerance), tolerance);
+        assert_watertight(&mesh);
+        assert_volume(&mesh, 8.);
+    }
+
+    #[test]
+    fn intersection_of_solids() {
+        let tolerance = Tolerance::from_scalar(0.001).unwrap();
+
+        let a = cube(2., [0., 0., 0.]);
+
+        let faces = intersection(&a, &cube(2., [1., 1., 1.]), tolerance);
+        let mesh = triangulated(faces, tolerance);
+        assert_watertight(&mesh);
+        assert_volume(&mesh, 1.);
+
+        let aabb = mesh.aabb();
+        assert_eq!(aabb.min, Point::from([0., 0., 0.]));
+        assert_eq!(aabb.max, Point::from([1., 1., 1.]));
+
+        // The part of the cuboid that sticks out of the cube is cut off.
+        let b = cuboid([-0.5, -0.5, -2.], [0.5, 0.5, 0.5], tolerance);
+        let faces = intersection(&a, &b, tolerance);
+        let mesh = triangulated(faces, tolerance);
+        assert_watertight(&mesh);
+        assert_volume(&mesh, 1.5);
+
+        let faces = intersection(&a, &cube(1., [3., 0., 0.]), tolerance);
+        assert!(faces.is_empty());
+    }
+
+    #[test]
+    fn difference_with_curved_solids() {
+        let tolerance = Tolerance::from_scalar(0.0002).unwrap();
+
+        let a = cube(2., [0., 0., 0.]);
+
+        // A hole drilled all the way through.
+        let b = cylinder([0.5, 0.], [0., 0., -2.], 4., tolerance);
+        let faces = difference(&a, &b, tolerance);
+        assert_valid(&faces);
+        let mesh = triangulated(faces, tolerance);
+        assert_watertight(&mesh);
+        assert_volume_near(&mesh, 8. - PI * 0.25 * 2.);
+
+        // A blind hole in the top face.
+        let b = cylinder([0.5, 0.], [0., 0., 0.], 2., tolerance);
+        let faces = difference(&a, &b, tolerance);
+        assert_valid(&faces);
+        let mesh = triangulated(faces, tolerance);
+        assert_watertight(&mesh);
+        assert_volume_near(&mesh, 8. - PI * 0.25);
+
+        // A ring that touches the sides of the cube, cut out of its top. What
+        // is left of the cube's walls there is thinner than the tolerance, so
+        // the corners of the result only touch along some edges.
+        let tolerance = Tolerance::from_scalar(0.002).unwrap();
+        let b = cylinder([1., 0.5], [0., 0., 0.5], 1., tolerance);
+        let faces = difference(&a, &b, tolerance);
+        assert_valid(&faces);
+        let mesh = triangulated(faces, tolerance);
+        assert_volume_near(&mesh, 8. - PI * (1. - 0.25) * 0.5);
+    }
+
+    #[test]
+    fn union_with_curved_solids() {
+        let tolerance = Tolerance::from_scalar(0.0002).unwrap();
+
+        let a = cube(2., [0., 0., 0.]);
+
+        // A cylinder on the edge of the cube. A quarter of it overlaps.
+        let b = cylinder([0.5, 0.], [1., 0., 0.], 2., tolerance);
+        let faces = union(&a, &b, tolerance);
+        assert_valid(&faces);
+        let mesh = triangulated(faces, tolerance);
+        assert_watertight(&mesh);
+        assert_volume_near(&mesh, 8. + PI * 0.25 * 2. * 0.75);
+
+        // A cylinder that was poked through the cube.
+        let b = cylinder([0.5, 0.], [0., 0., -2.], 4., tolerance);
+        let faces = union(&a, &b, tolerance);
+        assert_valid(&faces);
+        let mesh = triangulated(faces, tolerance);
+        assert_watertight(&mesh);
+        assert_volume_near(&mesh, 8. + PI * 0.25 * 2.);
+
+        // A ring that touches the sides of the cube, sticking out of its top.
+        let tolerance = Tolerance::from_scalar(0.002).unwrap();
+        let b = cylinder([1., 0.5], [0., 0., 0.5], 1., tolerance);
+        let faces = union(&a, &b, tolerance);
+        assert_valid(&faces);
+        let mesh = triangulated(faces, tolerance);
+        assert_watertight(&mesh);
+        assert_volume_near(&mesh, 8. + PI * (1. - 0.25) * 0.5);
+    }
+}
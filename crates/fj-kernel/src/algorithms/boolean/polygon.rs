@@ -0,0 +1,577 @@
+//! Boolean operations on faces that share a surface
+//!
+//! The cycles of the faces are approximated as polygons. Every polygon edge is
+//! split wherever it touches another one, and each of the resulting segments is
+//! kept, if it separates the result from the rest of the surface. Linking the
+//! kept segments back up yields the cycles of the result.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use fj_math::{Point, Scalar, Vector};
+
+use crate::{
+    algorithms::{reverse_cycle, CycleApprox, PointMerger, Tolerance},
+    objects::{Cycle, Face},
+    validation::ValidationConfig,
+};
+
+/// Subtract faces from other faces, within their shared surface
+///
+/// If the faces of `b` are fully contained in the faces of `a`, without
+/// touching their boundaries, they are turned into holes in `a`, keeping the
+/// curves that bound them. Otherwise, the boundaries are approximated within
+/// the tolerance, and the resulting faces are bounded by line segments.
+///
+/// # Panics
+///
+/// Panics, if the faces don't all share the same surface.
+pub fn difference_2d(
+    a: &[Face],
+    b: &[Face],
+    tolerance: Tolerance,
+) -> Vec<Face> {
+    let (surface, color) = match a.first() {
+        Some(face) => (face.surface().clone(), face.color()),
+        None => return Vec::new(),
+    };
+    for face in a.iter().chain(b) {
+        assert_eq!(
+            &surface,
+            face.surface(),
+            "Trying to subtract faces with different surfaces.",
+        );
+    }
+
+    // Points that validation wouldn't consider distinct are the same point.
+    // Merging them makes sure they compare as equal.
+    let mut merger =
+        PointMerger::new(ValidationConfig::default().distinct_min_distance);
+
+    let a_polygons = Polygons::new(a, tolerance, &mut merger);
+    let b_polygons = Polygons::new(b, tolerance, &mut merger);
+
+    if let Some(holes) = holes_in(&a_polygons, &b_polygons) {
+        let mut exteriors = Vec::new();
+        let mut interiors = Vec::new();
+
+        for face in a {
+            exteriors.extend(face.exteriors().cloned());
+            interiors.extend(face.interiors().cloned());
+        }
+        interiors.extend(
+            b.iter()
+                .flat_map(|face| face.exteriors())
+                .zip(holes)
+                .filter(|(_, is_hole)| *is_hole)
+                .map(|(cycle, _)| reverse_cycle(cycle)),
+        );
+
+        return vec![Face::new(surface)
+            .with_exteriors(exteriors)
+            .with_interiors(interiors)
+            .with_color(color)];
+    }
+
+    let segments =
+        boundary(&a_polygons, &b_polygons, &mut merger, |a, b| a && !b);
+
+    let faces = faces_from_cycles(link(segments));
+    let is_ccw = a_polygons.is_ccw;
+
+    faces
+        .into_iter()
+        .map(|(exterior, interiors)| {
+            let cycle = |mut points: Vec<Point<2>>| {
+                if !is_ccw {
+                    points.reverse();
+                }
+                Cycle::build(surface.clone()).polygon_from_points(points)
+            };
+
+            Face::new(surface.clone())
+                .with_exteriors([cycle(exterior)])
+                .with_interiors(interiors.into_iter().map(cycle))
+                .with_color(color)
+        })
+        .collect()
+}
+
+/// The approximated cycles of some faces
+struct Polygons {
+    /// The approximated exteriors, in counterclockwise order
+    exteriors: Vec<Vec<Point<2>>>,
+
+    /// The approximated interiors, in clockwise order
+    interiors: Vec<Vec<Point<2>>>,
+
+    /// Whether the exteriors of the faces were counterclockwise originally
+    is_ccw: bool,
+}
+
+impl Polygons {
+    fn new(
+        faces: &[Face],
+        tolerance: Tolerance,
+        merger: &mut PointMerger<2>,
+    ) -> Self {
+        let mut approx = |cycle: &Cycle| {
+            let mut points: Vec<_> = CycleApprox::new(cycle, tolerance)
+                .points
+                .into_iter()
+                .map(|point| merger.merge(*point.local_form()))
+                .collect();
+            points.dedup();
+
+            if points.len() > 1 && points.first() == points.last() {
+                points.pop();
+            }
+            points
+        };
+
+        let mut is_ccw = true;
+        let mut exteriors = Vec::new();
+        let mut interiors = Vec::new();
+
+        for face in faces {
+            for cycle in face.exteriors() {
+                let mut points = approx(cycle);
+                if signed_area(&points) < Scalar::ZERO {
+                    is_ccw = false;
+                    points.reverse();
+                }
+                exteriors.push(points);
+            }
+            for cycle in face.interiors() {
+                let mut points = approx(cycle);
+                if signed_area(&points) > Scalar::ZERO {
+                    points.reverse();
+                }
+                interiors.push(points);
+            }
+        }
+
+        Self {
+            exteriors,
+            interiors,
+            is_ccw,
+        }
+    }
+
+    fn cycles(&self) -> impl Iterator<Item = &Vec<Point<2>>> + '_ {
+        self.exteriors.iter().chain(&self.interiors)
+    }
+
+    fn edges(&self) -> impl Iterator<Item = [Point<2>; 2]> + '_ {
+        self.cycles().flat_map(|cycle| edges(cycle))
+    }
+
+    fn contains(&self, point: Point<2>) -> bool {
+        let winding: i32 = self
+            .cycles()
+            .map(|cycle| winding_number(cycle, point))
+            .sum();
+        winding > 0
+    }
+}
+
+/// Check whether the exteriors of `b` can simply become holes in `a`
+///
+/// Returns whether each exterior of `b` is a hole in `a`, or lies completely
+/// outside of it. Returns `None`, if the faces overlap in any other way.
+fn holes_in(a: &Polygons, b: &Polygons) -> Option<Vec<bool>> {
+    if !b.interiors.is_empty() {
+        return None;
+    }
+
+    let cycles: Vec<_> = a.cycles().chain(b.cycles()).collect();
+    for (i, cycle) in cycles.iter().enumerate() {
+        for other in &cycles[i + 1..] {
+            let touch = edges(cycle).any(|edge| {
+                edges(other).any(|other| {
+                    let [on_edge, on_other] = split_points(edge, other);
+                    !on_edge.is_empty() || !on_other.is_empty()
+                })
+            });
+            if touch {
+                return None;
+            }
+        }
+    }
+
+    // Since no cycles touch, checking a single point of each is enough.
+    let a_in_b = a.cycles().any(|cycle| b.contains(cycle[0]));
+    let b_nested = b.exteriors.iter().enumerate().any(|(i, cycle)| {
+        b.exteriors
+            .iter()
+            .enumerate()
+            .any(|(j, other)| i != j && winding_number(other, cycle[0]) != 0)
+    });
+    if a_in_b || b_nested {
+        return None;
+    }
+
+    Some(
+        b.exteriors
+            .iter()
+            .map(|cycle| a.contains(cycle[0]))
+            .collect(),
+    )
+}
+
+/// Compute the boundary of the region that `op` selects
+///
+/// `op` is called with whether a point is in `a` and in `b`. The returned
+/// segments have the region to their left.
+fn boundary(
+    a: &Polygons,
+    b: &Polygons,
+    merger: &mut PointMerger<2>,
+    op: impl Fn(bool, bool) -> bool,
+) -> Vec<[Point<2>; 2]> {
+    let edges: Vec<_> = a.edges().chain(b.edges()).collect();
+
+    let mut points: Vec<_> =
+        edges.iter().map(|&[start, end]| vec![start, end]).collect();
+    for i in 0..edges.len() {
+        for j in i + 1..edges.len() {
+            let [on_i, on_j] = split_points(edges[i], edges[j]);
+            points[i].extend(on_i.into_iter().map(|point| merger.merge(point)));
+            points[j].extend(on_j.into_iter().map(|point| merger.merge(point)));
+        }
+    }
+
+    // Edges of `a` and `b` can coincide, so the same segment can come up more
+    // than once. Only look at each of them once.
+    let mut segments = BTreeSet::new();
+    for (&[start, end], mut points) in edges.iter().zip(points) {
+        let direction = end - start;
+        points.sort_by_key(|point| (*point - start).dot(&direction));
+        points.dedup();
+
+        for pair in points.windows(2) {
+            let [a, b] = [pair[0], pair[1]];
+            segments.insert(if a < b { [a, b] } else { [b, a] });
+        }
+    }
+    let segments: Vec<_> = segments.into_iter().collect();
+
+    let in_region = |point| op(a.contains(point), b.contains(point));
+
+    let mut boundary = Vec::new();
+    for (i, &[start, end]) in segments.iter().enumerate() {
+        let direction = end - start;
+        let normal = Vector::from([-direction.v, direction.u]).normalize();
+        let middle = start + direction / 2.;
+
+        // Look at the points right next to the segment on both sides. They
+        // need to be closer than any other segment, so nothing is in between.
+        let clearance = segments
+            .iter()
+            .enumerate()
+            .filter(|(j, _)| *j != i)
+            .map(|(_, &segment)| distance_to_segment(middle, segment))
+            .fold(direction.magnitude(), Scalar::min);
+        let offset = normal * (clearance / 2.);
+
+        match (in_region(middle + offset), in_region(middle - offset)) {
+            (true, false) => boundary.push([start, end]),
+            (false, true) => boundary.push([end, start]),
+            _ => {}
+        }
+    }
+
+    boundary
+}
+
+/// Link segments into closed cycles
+fn link(segments: Vec<[Point<2>; 2]>) -> Vec<Vec<Point<2>>> {
+    let mut outgoing: BTreeMap<Point<2>, Vec<Point<2>>> = BTreeMap::new();
+    for [start, end] in segments {
+        outgoing.entry(start).or_default().push(end);
+    }
+
+    let mut cycles = Vec::new();
+    while let Some((&first, _)) =
+        outgoing.iter().find(|(_, ends)| !ends.is_empty())
+    {
+        let mut cycle = vec![first];
+        let mut direction: Option<Vector<2>> = None;
+        let mut current = first;
+
+        let is_closed = loop {
+            let candidates = outgoing.entry(current).or_default();
+            if candidates.is_empty() {
+                // The boundary is broken, so this can't be closed.
+                break false;
+            }
+
+            // Where several segments leave the same point, take the one that
+            // turns left the most. That keeps cycles that only touch in a
+            // vertex apart.
+            let index = match direction {
+                Some(direction) => {
+                    let turn = |next: Point<2>| {
+                        let next = next - current;
+                        Scalar::atan2(
+                            direction.u * next.v - direction.v * next.u,
+                            direction.dot(&next),
+                        )
+                    };
+                    (0..candidates.len())
+                        .max_by_key(|&i| turn(candidates[i]))
+                        .unwrap_or(0)
+                }
+                None => 0,
+            };
+            let next = candidates.remove(index);
+
+            direction = Some(next - current);
+            current = next;
+
+            if current == first {
+                break true;
+            }
+            cycle.push(current);
+        };
+
+        if is_closed && cycle.len() >= 3 {
+            cycles.push(cycle);
+        }
+    }
+
+    cycles
+}
+
+/// Sort cycles into faces, each with an exterior and its interiors
+fn faces_from_cycles(
+    cycles: Vec<Vec<Point<2>>>,
+) -> Vec<(Vec<Point<2>>, Vec<Vec<Point<2>>>)> {
+    let (mut exteriors, interiors): (Vec<_>, Vec<_>) = cycles
+        .into_iter()
+        .partition(|cycle| signed_area(cycle) > Scalar::ZERO);
+
+    // An interior belongs to the smallest exterior that contains it.
+    exteriors.sort_by_key(|cycle| signed_area(cycle));
+    let mut faces: Vec<_> = exteriors
+        .into_iter()
+        .map(|exterior| (exterior, Vec::new()))
+        .collect();
+
+    for interior in interiors {
+        // Check a point right next to the interior, inside of the face. That
+        // one's not on any other cycle.
+        let [start, end] = [interior[0], interior[1]];
+        let direction = end - start;
+        let normal = Vector::from([-direction.v, direction.u]).normalize();
+        let clearance = faces
+            .iter()
+            .flat_map(|(exterior, _)| edges(exterior))
+            .map(|edge| distance_to_segment(start + direction / 2., edge))
+            .filter(|distance| *distance > Scalar::ZERO)
+            .fold(direction.magnitude(), Scalar::min);
+        let point = start + direction / 2. + normal * (clearance / 2.);
+
+        let face = faces
+            .iter_mut()
+            .find(|(exterior, _)| winding_number(exterior, point) != 0);
+        if let Some((_, interiors)) = face {
+            interiors.push(interior);
+        }
+    }
+
+    faces
+}
+
+fn edges(cycle: &[Point<2>]) -> impl Iterator<Item = [Point<2>; 2]> + '_ {
+    cycle
+        .iter()
+        .zip(cycle.iter().cycle().skip(1))
+        .map(|(&a, &b)| [a, b])
+}
+
+/// Find the points where two segments touch
+///
+/// Returns the points that are on each of the segments.
+fn split_points(
+    [p, q]: [Point<2>; 2],
+    [r, s]: [Point<2>; 2],
+) -> [Vec<Point<2>>; 2] {
+    let [o1, o2, o3, o4] =
+        [(p, q, r), (p, q, s), (r, s, p), (r, s, q)].map(|(a, b, c)| {
+            robust::orient2d(
+                robust::Coord { x: a.u, y: a.v },
+                robust::Coord { x: b.u, y: b.v },
+                robust::Coord { x: c.u, y: c.v },
+            )
+        });
+
+    // Endpoints that lie on the other segment
+    let on_segment = |[a, b]: [Point<2>; 2],
+                      candidates: [(f64, Point<2>); 2]| {
+        candidates
+            .into_iter()
+            .filter(|&(orientation, point)| {
+                orientation == 0.
+                    && (point - a).dot(&(b - a)) >= Scalar::ZERO
+                    && (point - b).dot(&(a - b)) >= Scalar::ZERO
+            })
+            .map(|(_, point)| point)
+            .collect::<Vec<_>>()
+    };
+    let mut on_pq = on_segment([p, q], [(o1, r), (o2, s)]);
+    let mut on_rs = on_segment([r, s], [(o3, p), (o4, q)]);
+
+    // The segments cross each other
+    if o1 * o2 < 0. && o3 * o4 < 0. {
+        let point = p + (q - p) * (o3 / (o3 - o4));
+        on_pq.push(point);
+        on_rs.push(point);
+    }
+
+    [on_pq, on_rs]
+}
+
+fn distance_to_segment(point: Point<2>, [a, b]: [Point<2>; 2]) -> Scalar {
+    let ab = b - a;
+    let t = ((point - a).dot(&ab) / ab.dot(&ab))
+        .max(Scalar::ZERO)
+        .min(Scalar::ONE);
+    (a + ab * t - point).magnitude()
+}
+
+/// Compute the winding number of a cycle around a point
+fn winding_number(cycle: &[Point<2>], point: Point<2>) -> i32 {
+    let mut winding = 0;
+
+    for [a, b] in edges(cycle) {
+        let orientation = robust::orient2d(
+            robust::Coord { x: a.u, y: a.v },
+            robust::Coord { x: b.u, y: b.v },
+            robust::Coord {
+                x: point.u,
+                y: point.v,
+            },
+        );
+
+        if a.v <= point.v {
+            if b.v > point.v && orientation > 0. {
+                winding += 1;
+            }
+        } else if b.v <= point.v && orientation < 0. {
+            winding -= 1;
+        }
+    }
+
+    winding
+}
+
+fn signed_area(cycle: &[Point<2>]) -> Scalar {
+    edges(cycle).fold(Scalar::ZERO, |area, [a, b]| {
+        area + (a.u * b.v - b.u * a.v) / 2.
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Point, Scalar};
+
+    use crate::{
+        algorithms::Tolerance,
+        objects::{Face, Surface},
+    };
+
+    use super::difference_2d;
+
+    fn square(min: [f64; 2], size: f64) -> Face {
+        let [u, v] = min;
+        Face::build(Surface::xy_plane())
+            .polygon_from_points([
+                [u, v],
+                [u + size, v],
+                [u + size, v + size],
+                [u, v + size],
+            ])
+            .into_face()
+    }
+
+    fn area(faces: &[Face]) -> f64 {
+        faces
+            .iter()
+            .flat_map(|face| face.all_cycles())
+            .map(|cycle| {
+                let points: Vec<Point<2>> = cycle
+                    .edges()
+                    .map(|edge| {
+                        edge.curve().kind().point_from_curve_coords(
+                            edge.vertices().get().unwrap()[0].position(),
+                        )
+                    })
+                    .collect();
+                super::signed_area(&points)
+            })
+            .fold(Scalar::ZERO, |sum, area| sum + area)
+            .into_f64()
+    }
+
+    #[test]
+    fn contained() {
+        let tolerance = Tolerance::from_scalar(0.001).unwrap();
+        let a = [square([0., 0.], 4.)];
+        let b = [square([1., 1.], 1.), square([5., 5.], 1.)];
+
+        let faces = difference_2d(&a, &b, tolerance);
+
+        assert_eq!(faces.len(), 1);
+        assert_eq!(faces[0].interiors().count(), 1);
+        assert_eq!(area(&faces), 15.);
+    }
+
+    #[test]
+    fn overlapping_boundary() {
+        let tolerance = Tolerance::from_scalar(0.001).unwrap();
+        let a = [square([0., 0.], 4.)];
+        let b = [square([3., 1.], 2.), square([-1., -1.], 2.)];
+
+        let faces = difference_2d(&a, &b, tolerance);
+
+        assert_eq!(faces.len(), 1);
+        assert_eq!(faces[0].interiors().count(), 0);
+        assert_eq!(area(&faces), 16. - 2. - 1.);
+    }
+
+    #[test]
+    fn split_in_two() {
+        let tolerance = Tolerance::from_scalar(0.001).unwrap();
+        let a = [square([0., 0.], 3.)];
+        let b = [Face::build(Surface::xy_plane())
+            .polygon_from_points([[1., -1.], [2., -1.], [2., 4.], [1., 4.]])
+            .into_face()];
+
+        let faces = difference_2d(&a, &b, tolerance);
+
+        assert_eq!(faces.len(), 2);
+        assert_eq!(area(&faces), 6.);
+    }
+
+    #[test]
+    fn touching_holes() {
+        let tolerance = Tolerance::from_scalar(0.001).unwrap();
+        let a = [square([0., 0.], 4.)];
+        let b = [square([1., 1.], 1.), square([2., 1.], 1.)];
+
+        let faces = difference_2d(&a, &b, tolerance);
+
+        assert_eq!(faces.len(), 1);
+        assert_eq!(faces[0].interiors().count(), 1);
+        assert_eq!(area(&faces), 14.);
+    }
+
+    #[test]
+    fn everything_removed() {
+        let tolerance = Tolerance::from_scalar(0.001).unwrap();
+        let a = [square([1., 1.], 1.)];
+        let b = [square([0., 0.], 4.)];
+
+        assert_eq!(difference_2d(&a, &b, tolerance), vec![]);
+    }
+}
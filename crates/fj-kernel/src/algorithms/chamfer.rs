@@ -0,0 +1,540 @@
+use std::collections::BTreeSet;
+
+use fj_interop::mesh::Color;
+use fj_math::{Point, Scalar, Vector};
+
+use crate::objects::{CurveKind, Face, Surface, SweptCurve};
+
+/// Bevel sharp edges of a shape
+///
+/// Each edge is identified by the positions of its two vertices. The two faces
+/// that meet at an edge are cut back, as defined by `distance`, and a new flat
+/// face is inserted between them. Distances are measured perpendicular to the
+/// edge within each face. Faces that are not affected by the chamfer are
+/// returned as they are.
+///
+/// If the distances differ between the faces, the order of the edge's vertices
+/// matters: The first face is the one whose boundary runs from the first to the
+/// second vertex.
+///
+/// # Implementation Note
+///
+/// Only planar faces that are bounded by a single polygon without holes can be
+/// chamfered, and each vertex of a chamfered edge must be shared by exactly
+/// three faces. Chamfered edges can't share a vertex, as that would require
+/// a corner patch where the chamfer faces meet.
+pub fn chamfer(
+    faces: &[Face],
+    edges: &[[Point<3>; 2]],
+    distance: impl Into<ChamferDistance>,
+) -> Result<Vec<Face>, ChamferError> {
+    let distance = distance.into();
+
+    let mut vertices = BTreeSet::new();
+    for &[a, b] in edges {
+        for vertex in [a, b] {
+            if !vertices.insert(vertex) {
+                return Err(ChamferError::SharedVertex(vertex));
+            }
+        }
+    }
+
+    // Faces that touch a chamfered vertex are converted into polygons, which
+    // are edited and converted back into faces at the end.
+    let mut polygons = Vec::new();
+    let mut unaffected = Vec::new();
+    for face in faces {
+        let affected = face.triangles().is_none()
+            && face
+                .all_cycles()
+                .flat_map(|cycle| cycle.vertices())
+                .any(|vertex| vertices.contains(&vertex.global().position()));
+
+        if affected {
+            polygons.push(Polygon::from_face(face)?);
+        } else {
+            unaffected.push(face.clone());
+        }
+    }
+
+    let mut chamfer_faces = Vec::new();
+    for &[p, q] in edges {
+        let adjacent: Vec<_> = polygons
+            .iter()
+            .enumerate()
+            .filter(|(_, polygon)| polygon.has_edge(p, q))
+            .map(|(i, _)| i)
+            .collect();
+        let [a, b] = match adjacent[..] {
+            [a, b] => [a, b],
+            _ => return Err(ChamferError::EdgeNotFound([p, q])),
+        };
+
+        // Make sure `a` is the first face.
+        let [a, b] = if polygons[a].runs_from(p, q) {
+            [a, b]
+        } else {
+            [b, a]
+        };
+
+        let [distance_a, distance_b] = match distance {
+            ChamferDistance::Symmetric(distance) => [distance, distance],
+            ChamferDistance::Asymmetric(first, second) => [first, second],
+            ChamferDistance::DistanceAngle(distance, angle) => {
+                // The edge, and the points where the faces are cut back, form
+                // a triangle. Its angle at the edge is the angle between the
+                // faces.
+                let cos =
+                    polygons[a].inward([p, q]).dot(&polygons[b].inward([p, q]));
+                let between =
+                    Scalar::from_f64(cos.into_f64().clamp(-1., 1.)).acos();
+                let opposite = Scalar::PI - between - angle;
+                if angle <= Scalar::ZERO || opposite <= Scalar::ZERO {
+                    return Err(ChamferError::InvalidAngle(angle));
+                }
+
+                [
+                    distance,
+                    distance * angle.sin_cos().0 / opposite.sin_cos().0,
+                ]
+            }
+        };
+
+        // Where `p` and `q` end up in each of the two faces.
+        let [pa, qa] = polygons[a].cut_back([p, q], distance_a)?;
+        let [pb, qb] = polygons[b].cut_back([p, q], distance_b)?;
+
+        for (vertex, [va, vb]) in [(p, [pa, pb]), (q, [qa, qb])] {
+            let others: Vec<_> = polygons
+                .iter()
+                .enumerate()
+                .filter(|&(i, polygon)| {
+                    i != a && i != b && polygon.points.contains(&vertex)
+                })
+                .map(|(i, _)| i)
+                .collect();
+            let other = match others[..] {
+                [other] => other,
+                _ => return Err(ChamferError::UnsupportedVertex(vertex)),
+            };
+
+            polygons[other].split_vertex(vertex, [va, vb])?;
+        }
+
+        polygons[a].replace([p, q], [pa, qa]);
+        polygons[b].replace([p, q], [pb, qb]);
+
+        // The chamfer face points away from the solid, which is towards the
+        // edge it replaces.
+        let points = [pa, qa, qb, pb];
+        let center = points
+            .iter()
+            .fold(Vector::from([0., 0., 0.]), |sum, &point| sum + point.coords)
+            / Scalar::from_u64(4);
+        let outside = p.coords + (q - p) / Scalar::TWO - center;
+
+        let mut surface = Surface::plane_from_points([pa, qa, pb]);
+        let normal = normal(&surface);
+        if normal.dot(&outside) < Scalar::ZERO {
+            surface = surface.reverse();
+        }
+
+        let polygon = Polygon {
+            surface,
+            points: points.into(),
+            color: polygons[a].color,
+        };
+        chamfer_faces.push(polygon.into_face());
+    }
+
+    let mut faces = unaffected;
+    faces.extend(polygons.into_iter().map(Polygon::into_face));
+    faces.extend(chamfer_faces);
+
+    Ok(faces)
+}
+
+/// How far the faces next to a chamfered edge are cut back
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ChamferDistance {
+    /// Both faces are cut back by the same distance
+    Symmetric(Scalar),
+
+    /// The first and the second face are cut back by different distances
+    Asymmetric(Scalar, Scalar),
+
+    /// The first face is cut back by a distance
+    ///
+    /// The second face is cut back, so that the chamfer face meets the first
+    /// face at the given angle, in radians.
+    DistanceAngle(Scalar, Scalar),
+}
+
+impl From<Scalar> for ChamferDistance {
+    fn from(distance: Scalar) -> Self {
+        Self::Symmetric(distance)
+    }
+}
+
+/// An error that can occur when chamfering edges
+#[derive(Debug, thiserror::Error)]
+pub enum ChamferError {
+    /// The edge is not shared by exactly two of the faces
+    #[error("Edge {0:?} is not shared by exactly two faces")]
+    EdgeNotFound([Point<3>; 2]),
+
+    /// A face next to a chamfered edge can't be chamfered
+    ///
+    /// See [`chamfer`] for the kinds of faces that are supported.
+    #[error("Face next to chamfered edge is not a planar polygon")]
+    UnsupportedFace,
+
+    /// A vertex of a chamfered edge is not shared by exactly three faces
+    #[error("Vertex {0:?} is not shared by exactly three faces")]
+    UnsupportedVertex(Point<3>),
+
+    /// Two chamfered edges share a vertex
+    #[error("Chamfered edges share vertex {0:?}")]
+    SharedVertex(Point<3>),
+
+    /// The chamfer distance is larger than one of the adjacent faces
+    #[error("Chamfer distance is larger than face next to edge {0:?}")]
+    DistanceTooLarge([Point<3>; 2]),
+
+    /// The chamfer face can't meet the first face at the given angle
+    ///
+    /// The angle must be positive, and smaller than the angle that is left in
+    /// the triangle between the faces.
+    #[error("Can't chamfer with angle {0:?}")]
+    InvalidAngle(Scalar),
+}
+
+/// A planar face, bounded by a single polygon
+pub(super) struct Polygon {
+    pub(super) surface: Surface,
+    pub(super) points: Vec<Point<3>>,
+    pub(super) color: Color,
+}
+
+impl Polygon {
+    pub(super) fn from_face(face: &Face) -> Result<Self, ChamferError> {
+        let is_planar = matches!(
+            face.surface(),
+            Surface::SweptCurve(SweptCurve {
+                curve: CurveKind::Line(_),
+                ..
+            })
+        );
+
+        let mut exteriors = face.exteriors();
+        let exterior = match (exteriors.next(), exteriors.next()) {
+            (Some(exterior), None) if is_planar => exterior,
+            _ => return Err(ChamferError::UnsupportedFace),
+        };
+        if face.interiors().next().is_some() {
+            return Err(ChamferError::UnsupportedFace);
+        }
+
+        let is_polygon = exterior.edges().all(|edge| {
+            matches!(edge.curve().kind(), CurveKind::Line(_))
+                && edge.vertices().get().is_some()
+        });
+        if !is_polygon {
+            return Err(ChamferError::UnsupportedFace);
+        }
+
+        let points = exterior
+            .vertices()
+            .map(|vertex| vertex.global().position())
+            .collect();
+
+        Ok(Self {
+            surface: face.surface().clone(),
+            points,
+            color: face.color(),
+        })
+    }
+
+    fn neighbors(&self, i: usize) -> [Point<3>; 2] {
+        let n = self.points.len();
+        [self.points[(i + n - 1) % n], self.points[(i + 1) % n]]
+    }
+
+    fn position(&self, vertex: Point<3>) -> usize {
+        self.points
+            .iter()
+            .position(|&point| point == vertex)
+            .expect("Vertex must be part of polygon")
+    }
+
+    /// Indicate whether the boundary runs from `a` to `b`, rather than back
+    fn runs_from(&self, a: Point<3>, b: Point<3>) -> bool {
+        self.neighbors(self.position(a))[1] == b
+    }
+
+    /// The direction from an edge into the polygon, perpendicular to the edge
+    fn inward(&self, [p, q]: [Point<3>; 2]) -> Vector<3> {
+        let [prev, next] = self.neighbors(self.position(p));
+        let neighbor = if prev == q { next } else { prev };
+
+        let direction = (q - p).normalize();
+        let offset = neighbor - p;
+        (offset - direction * offset.dot(&direction)).normalize()
+    }
+
+    fn has_edge(&self, a: Point<3>, b: Point<3>) -> bool {
+        self.points
+            .iter()
+            .position(|&point| point == a)
+            .map(|i| self.neighbors(i).contains(&b))
+            .unwrap_or(false)
+    }
+
+    /// Compute where the vertices of an edge move to, when it is cut back
+    ///
+    /// Each vertex moves along the other edge it is connected to, until it is
+    /// `distance` away from the original edge.
+    fn cut_back(
+        &self,
+        [p, q]: [Point<3>; 2],
+        distance: Scalar,
+    ) -> Result<[Point<3>; 2], ChamferError> {
+        let direction = (q - p).normalize();
+
+        let move_vertex = |vertex: Point<3>| {
+            let i = self
+                .points
+                .iter()
+                .position(|&point| point == vertex)
+                .expect("Vertex of edge must be part of polygon");
+            let [prev, next] = self.neighbors(i);
+            let neighbor = if prev == p || prev == q { next } else { prev };
+
+            let along = neighbor - vertex;
+            // The neighboring edge is at an angle to the chamfered one, so the
+            // vertex needs to move further than `distance`.
+            let sin = along.normalize().cross(&direction).magnitude();
+            if sin == Scalar::ZERO || distance >= along.magnitude() * sin {
+                return Err(ChamferError::DistanceTooLarge([p, q]));
+            }
+            let offset = distance / sin;
+
+            Ok(vertex + along.normalize() * offset)
+        };
+
+        Ok([move_vertex(p)?, move_vertex(q)?])
+    }
+
+    fn replace(&mut self, from: [Point<3>; 2], to: [Point<3>; 2]) {
+        for point in &mut self.points {
+            for (from, to) in from.iter().zip(to) {
+                if point == from {
+                    *point = to;
+                }
+            }
+        }
+    }
+
+    /// Replace a vertex with two, which are on its adjacent edges
+    fn split_vertex(
+        &mut self,
+        vertex: Point<3>,
+        [a, b]: [Point<3>; 2],
+    ) -> Result<(), ChamferError> {
+        let i = self
+            .points
+            .iter()
+            .position(|&point| point == vertex)
+            .expect("Vertex must be part of polygon");
+        let [prev, _] = self.neighbors(i);
+
+        // Keep the winding order, by inserting the point on the edge to the
+        // previous vertex first.
+        let is_on_prev_edge = |point: Point<3>| {
+            let edge = prev - vertex;
+            let offset = point - vertex;
+            edge.cross(&offset).magnitude() <= Scalar::from_f64(1e-9)
+                && edge.dot(&offset) > Scalar::ZERO
+        };
+        let split = if is_on_prev_edge(a) {
+            [a, b]
+        } else if is_on_prev_edge(b) {
+            [b, a]
+        } else {
+            return Err(ChamferError::UnsupportedVertex(vertex));
+        };
+
+        self.points.splice(i..=i, split);
+
+        Ok(())
+    }
+
+    pub(super) fn into_face(self) -> Face {
+        let points = surface_coords(&self.surface, &self.points);
+
+        Face::build(self.surface)
+            .polygon_from_points(points)
+            .into_face()
+            .with_color(self.color)
+    }
+}
+
+/// Convert points on a planar surface into surface coordinates
+pub(super) fn surface_coords(
+    surface: &Surface,
+    points: &[Point<3>],
+) -> Vec<Point<2>> {
+    let origin = surface.point_from_surface_coords([0., 0.]);
+    let u = surface.vector_from_surface_coords([1., 0.]);
+    let v = surface.vector_from_surface_coords([0., 1.]);
+
+    // Solve `origin + u * s + v * t = point` for `s` and `t`, in the least
+    // squares sense, which is exact for points on the surface.
+    let uu = u.dot(&u);
+    let uv = u.dot(&v);
+    let vv = v.dot(&v);
+    let det = uu * vv - uv * uv;
+
+    points
+        .iter()
+        .map(|&point| {
+            let offset = point - origin;
+            let pu = offset.dot(&u);
+            let pv = offset.dot(&v);
+
+            Point::from([(pu * vv - pv * uv) / det, (pv * uu - pu * uv) / det])
+        })
+        .collect()
+}
+
+pub(super) fn normal(surface: &Surface) -> Vector<3> {
+    let u = surface.vector_from_surface_coords([1., 0.]);
+    let v = surface.vector_from_surface_coords([0., 1.]);
+    u.cross(&v)
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Point, Scalar};
+
+    use crate::objects::{Face, Solid};
+
+    use super::{chamfer, ChamferDistance, ChamferError};
+
+    fn cube() -> Vec<Face> {
+        Solid::build()
+            .cube_from_edge_length(2.)
+            .into_faces()
+            .collect()
+    }
+
+    fn points(face: &Face) -> Vec<Point<3>> {
+        face.exteriors()
+            .flat_map(|cycle| cycle.vertices())
+            .map(|vertex| vertex.global().position())
+            .collect()
+    }
+
+    #[test]
+    fn chamfer_cube_edge() -> anyhow::Result<()> {
+        let edge = [[1., 1., -1.], [1., 1., 1.]].map(Point::from);
+        let faces = chamfer(&cube(), &[edge], Scalar::from_f64(0.5))?;
+
+        assert_eq!(faces.len(), 7);
+
+        let chamfer_face = faces.last().expect("Chamfer face must exist");
+        let mut vertices = points(chamfer_face);
+        vertices.sort();
+        assert_eq!(
+            vertices,
+            [[0.5, 1., -1.], [0.5, 1., 1.], [1., 0.5, -1.], [1., 0.5, 1.],]
+                .map(Point::from)
+        );
+
+        let surface = chamfer_face.surface();
+        let u = surface.vector_from_surface_coords([1., 0.]);
+        let v = surface.vector_from_surface_coords([0., 1.]);
+        let normal = u.cross(&v).normalize();
+        let expected = Scalar::from_f64(0.5_f64.sqrt());
+        assert!((normal.x - expected).abs() < Scalar::from_f64(1e-9));
+        assert!((normal.y - expected).abs() < Scalar::from_f64(1e-9));
+        assert_eq!(normal.z, Scalar::ZERO);
+
+        // Top and bottom have gained a vertex; no face contains the chamfered
+        // edge any longer.
+        let mut sizes: Vec<_> =
+            faces.iter().map(|face| points(face).len()).collect();
+        sizes.sort();
+        assert_eq!(sizes, [4, 4, 4, 4, 4, 5, 5]);
+        assert!(faces.iter().all(|face| !points(face).contains(&edge[0])));
+
+        Ok(())
+    }
+
+    #[test]
+    fn chamfer_with_different_distances() -> anyhow::Result<()> {
+        // The faces at x = 1 and y = 1 meet at this edge. The boundary of the
+        // one at x = 1 runs from the first to the second vertex.
+        let edge = [[1., 1., 1.], [1., 1., -1.]].map(Point::from);
+
+        let chamfered = |distance| -> anyhow::Result<Vec<Point<3>>> {
+            let faces = chamfer(&cube(), &[edge], distance)?;
+            let mut vertices = points(faces.last().unwrap());
+            vertices.sort();
+            Ok(vertices)
+        };
+
+        let expected = [
+            [0.5, 1., -1.],
+            [0.5, 1., 1.],
+            [1., 0.75, -1.],
+            [1., 0.75, 1.],
+        ]
+        .map(Point::from);
+
+        let distances = ChamferDistance::Asymmetric(
+            Scalar::from_f64(0.25),
+            Scalar::from_f64(0.5),
+        );
+        assert_eq!(chamfered(distances)?, expected);
+
+        // The same chamfer, defined by the angle at which it meets the first
+        // face.
+        let angle = (0.5_f64 / 0.25).atan();
+        let distances = ChamferDistance::DistanceAngle(
+            Scalar::from_f64(0.25),
+            Scalar::from_f64(angle),
+        );
+        for (vertex, expected) in chamfered(distances)?.iter().zip(expected) {
+            assert!((*vertex - expected).magnitude() < Scalar::from_f64(1e-9));
+        }
+
+        let distances = ChamferDistance::DistanceAngle(
+            Scalar::from_f64(0.25),
+            Scalar::PI / 2.,
+        );
+        assert!(matches!(
+            chamfer(&cube(), &[edge], distances),
+            Err(ChamferError::InvalidAngle(_))
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn chamfer_distance_too_large() {
+        let edge = [[1., 1., -1.], [1., 1., 1.]].map(Point::from);
+        let result = chamfer(&cube(), &[edge], Scalar::from_f64(2.));
+
+        assert!(matches!(result, Err(ChamferError::DistanceTooLarge(_))));
+    }
+
+    #[test]
+    fn chamfer_edges_with_shared_vertex() {
+        let a = [[1., 1., -1.], [1., 1., 1.]].map(Point::from);
+        let b = [[1., 1., 1.], [-1., 1., 1.]].map(Point::from);
+        let result = chamfer(&cube(), &[a, b], Scalar::from_f64(0.5));
+
+        assert!(matches!(result, Err(ChamferError::SharedVertex(_))));
+    }
+}
@@ -0,0 +1,280 @@
+use fj_interop::debug::DebugInfo;
+use fj_math::{Point, Scalar, Triangle, Vector};
+
+use crate::objects::Face;
+
+use super::{triangulate, Tolerance};
+
+/// The location of a point, relative to a solid
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum PointClassification {
+    /// The point is inside of the solid
+    Inside,
+
+    /// The point is outside of the solid
+    Outside,
+
+    /// The point is on the boundary of the solid, within the tolerance
+    Boundary,
+}
+
+/// Classifies points relative to the solid that is bounded by some faces
+///
+/// The faces are triangulated once, on construction, so many points can be
+/// classified cheaply. They must form a closed shell, or the results are
+/// meaningless.
+pub struct PointClassifier {
+    triangles: Vec<Triangle<3>>,
+    tolerance: Tolerance,
+}
+
+impl PointClassifier {
+    /// Create a classifier for the solid bounded by the given faces
+    pub fn new(faces: &[Face], tolerance: Tolerance) -> Self {
+        let mesh =
+            triangulate(faces.to_vec(), tolerance, &mut DebugInfo::new());
+        let triangles =
+            mesh.triangles().map(|triangle| triangle.inner).collect();
+
+        Self {
+            triangles,
+            tolerance,
+        }
+    }
+
+    /// Classify a point
+    pub fn classify(&self, point: impl Into<Point<3>>) -> PointClassification {
+        let point = point.into();
+
+        let on_boundary = self.triangles.iter().any(|triangle| {
+            (closest_point_on_triangle(point, triangle) - point).magnitude()
+                <= self.tolerance.inner()
+        });
+        if on_boundary {
+            return PointClassification::Boundary;
+        }
+
+        // A point is inside, if a ray that starts at it leaves the solid more
+        // often than it enters it. Rays that graze an edge or a vertex might
+        // be counted wrong, so try some other direction for those.
+        let mut num_hits = 0;
+        for direction in RAY_DIRECTIONS {
+            let (hits, grazing) = self.hits(point, Vector::from(direction));
+            num_hits = hits.len();
+
+            if !grazing {
+                break;
+            }
+        }
+
+        if num_hits % 2 == 1 {
+            PointClassification::Inside
+        } else {
+            PointClassification::Outside
+        }
+    }
+
+    /// Cast a ray against the faces
+    ///
+    /// Returns where the ray hits the faces, as multiples of `direction` from
+    /// `origin`, sorted by distance. Hits behind the origin are ignored.
+    pub fn cast_ray(
+        &self,
+        origin: impl Into<Point<3>>,
+        direction: impl Into<Vector<3>>,
+    ) -> Vec<Scalar> {
+        let direction = direction.into();
+        let (mut hits, _) = self.hits(origin.into(), direction);
+
+        // A ray that hits an edge hits both triangles next to it.
+        let min_distance = self.tolerance.inner() / direction.magnitude();
+        hits.dedup_by(|b, a| *b - *a <= min_distance);
+
+        hits
+    }
+
+    /// Find all hits of a ray, and whether any of them grazed an edge
+    fn hits(
+        &self,
+        origin: Point<3>,
+        direction: Vector<3>,
+    ) -> (Vec<Scalar>, bool) {
+        let mut hits = Vec::new();
+        let mut grazing = false;
+
+        for triangle in &self.triangles {
+            if let Some(hit) = cast_ray(origin, direction, triangle) {
+                hits.push(hit.t);
+                grazing |= hit.grazing;
+            }
+        }
+
+        hits.sort();
+        (hits, grazing)
+    }
+}
+
+/// The directions of the rays used for classification
+///
+/// They're chosen to not line up with the axes, or with each other, as faces
+/// and their edges tend to do that.
+const RAY_DIRECTIONS: [[f64; 3]; 3] =
+    [[0.31, 0.83, 0.47], [-0.71, 0.23, 0.67], [0.19, -0.57, 0.8]];
+
+struct RayHit {
+    t: Scalar,
+
+    /// Whether the hit is so close to an edge of the triangle, that it might
+    /// have missed it, or hit a neighboring triangle too
+    grazing: bool,
+}
+
+/// Intersect a ray with a triangle
+///
+/// Uses the Möller–Trumbore algorithm.
+fn cast_ray(
+    origin: Point<3>,
+    direction: Vector<3>,
+    triangle: &Triangle<3>,
+) -> Option<RayHit> {
+    let epsilon = Scalar::from(1e-9);
+
+    let [a, b, c] = triangle.points();
+    let [ab, ac] = [b - a, c - a];
+
+    let p = direction.cross(&ac);
+    let det = ab.dot(&p);
+
+    if det.abs()
+        <= ab.magnitude() * ac.magnitude() * direction.magnitude() * epsilon
+    {
+        // The ray is parallel to the triangle. It only grazes it, if it runs
+        // within the plane of the triangle.
+        let distance = (origin - a).dot(&ab.cross(&ac));
+        if distance.abs() <= ab.cross(&ac).magnitude() * epsilon {
+            return Some(RayHit {
+                t: Scalar::ZERO,
+                grazing: true,
+            });
+        }
+        return None;
+    }
+
+    let s = origin - a;
+    let u = s.dot(&p) / det;
+    let q = s.cross(&ab);
+    let v = direction.dot(&q) / det;
+
+    if u < -epsilon || v < -epsilon || u + v > Scalar::ONE + epsilon {
+        return None;
+    }
+
+    let t = ac.dot(&q) / det;
+    if t <= Scalar::ZERO {
+        return None;
+    }
+
+    Some(RayHit {
+        t,
+        grazing: u < epsilon || v < epsilon || u + v > Scalar::ONE - epsilon,
+    })
+}
+
+/// Find the point on the triangle that is closest to the given point
+///
+/// Algorithm from Real-Time Collision Detection by Christer Ericson. See
+/// section 5.1.5, Closest Point on Triangle to Point.
+fn closest_point_on_triangle(
+    point: Point<3>,
+    triangle: &Triangle<3>,
+) -> Point<3> {
+    let [a, b, c] = triangle.points();
+    let [ab, ac] = [b - a, c - a];
+
+    let ap = point - a;
+    let [d1, d2] = [ab.dot(&ap), ac.dot(&ap)];
+    if d1 <= Scalar::ZERO && d2 <= Scalar::ZERO {
+        return a;
+    }
+
+    let bp = point - b;
+    let [d3, d4] = [ab.dot(&bp), ac.dot(&bp)];
+    if d3 >= Scalar::ZERO && d4 <= d3 {
+        return b;
+    }
+
+    let vc = d1 * d4 - d3 * d2;
+    if vc <= Scalar::ZERO && d1 >= Scalar::ZERO && d3 <= Scalar::ZERO {
+        return a + ab * (d1 / (d1 - d3));
+    }
+
+    let cp = point - c;
+    let [d5, d6] = [ab.dot(&cp), ac.dot(&cp)];
+    if d6 >= Scalar::ZERO && d5 <= d6 {
+        return c;
+    }
+
+    let vb = d5 * d2 - d1 * d6;
+    if vb <= Scalar::ZERO && d2 >= Scalar::ZERO && d6 <= Scalar::ZERO {
+        return a + ac * (d2 / (d2 - d6));
+    }
+
+    let va = d3 * d6 - d5 * d4;
+    if va <= Scalar::ZERO && d4 - d3 >= Scalar::ZERO && d5 - d6 >= Scalar::ZERO
+    {
+        return b + (c - b) * ((d4 - d3) / ((d4 - d3) + (d5 - d6)));
+    }
+
+    let denom = va + vb + vc;
+    a + ab * (vb / denom) + ac * (vc / denom)
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::Scalar;
+
+    use crate::{algorithms::Tolerance, objects::Solid};
+
+    use super::{PointClassification, PointClassifier};
+
+    #[test]
+    fn classify() {
+        let faces: Vec<_> = Solid::build()
+            .cube_from_edge_length(1.)
+            .into_faces()
+            .collect();
+        let tolerance = Tolerance::from_scalar(0.001).unwrap();
+
+        let classifier = PointClassifier::new(&faces, tolerance);
+
+        for (point, expected) in [
+            ([0., 0., 0.], PointClassification::Inside),
+            ([0.4, -0.3, 0.2], PointClassification::Inside),
+            ([2., 0., 0.], PointClassification::Outside),
+            ([0.6, 0.6, 0.6], PointClassification::Outside),
+            ([0.5, 0., 0.], PointClassification::Boundary),
+            ([0.5, 0.5, 0.5], PointClassification::Boundary),
+        ] {
+            assert_eq!(classifier.classify(point), expected, "{point:?}");
+        }
+    }
+
+    #[test]
+    fn cast_ray() {
+        let faces: Vec<_> = Solid::build()
+            .cube_from_edge_length(1.)
+            .into_faces()
+            .collect();
+        let tolerance = Tolerance::from_scalar(0.001).unwrap();
+
+        let classifier = PointClassifier::new(&faces, tolerance);
+
+        // The ray goes through the diagonals of the triangulated faces, but
+        // each face is only hit once.
+        assert_eq!(
+            classifier.cast_ray([-2., 0., 0.], [1., 0., 0.]),
+            vec![Scalar::from(1.5), Scalar::from(2.5)]
+        );
+        assert_eq!(classifier.cast_ray([-2., 0., 0.], [-1., 0., 0.]), vec![]);
+    }
+}
@@ -0,0 +1,490 @@
+use std::{
+    cmp::Reverse,
+    collections::{BTreeSet, BinaryHeap},
+    ops,
+};
+
+use fj_interop::mesh::{Adjacency, Color, Index, Mesh};
+use fj_math::{Point, Scalar, Vector};
+
+/// Reduce the number of triangles in a mesh
+///
+/// See [`decimate_with_max_error`]. This variant doesn't limit the error, and
+/// only stops once the target is reached, or no more edges can be collapsed.
+pub fn decimate(
+    mesh: &Mesh<Point<3>>,
+    target_triangles: usize,
+) -> Mesh<Point<3>> {
+    decimate_with_max_error(
+        mesh,
+        target_triangles,
+        Scalar::from_f64(f64::INFINITY),
+    )
+}
+
+/// Reduce the number of triangles in a mesh, up to a maximum error
+///
+/// Edges are collapsed into a single vertex, one at a time, until the mesh has
+/// no more than `target_triangles` triangles. Collapsing an edge removes the
+/// two triangles that share it.
+///
+/// The edge whose collapse introduces the least error is always collapsed
+/// first. Decimation stops early, if that error is larger than `max_error`.
+/// The error is the sum of squared distances between the new vertex and the
+/// planes of the original triangles around it.
+///
+/// Collapses that would make the mesh non-manifold, or would flip the
+/// orientation of a triangle, are skipped.
+///
+/// # Implementation Note
+///
+/// This is the quadric error metric approach by Garland and Heckbert, without
+/// the collapsing of vertex pairs that don't share an edge. Boundary edges are
+/// not given any special treatment, so the outline of an open mesh might not
+/// be preserved.
+pub fn decimate_with_max_error(
+    mesh: &Mesh<Point<3>>,
+    target_triangles: usize,
+    max_error: Scalar,
+) -> Mesh<Point<3>> {
+    let adjacency = mesh.adjacency();
+    let mut decimation = Decimation::new(mesh, &adjacency);
+
+    let mut queue = BinaryHeap::new();
+    for ([a, b], _) in adjacency.edges() {
+        queue.push(decimation.candidate(a as usize, b as usize));
+    }
+
+    while decimation.num_triangles > target_triangles {
+        let Reverse(candidate) = match queue.pop() {
+            Some(candidate) => candidate,
+            None => break,
+        };
+
+        if !decimation.is_current(&candidate) {
+            continue;
+        }
+        if candidate.error > max_error {
+            break;
+        }
+
+        let (a, b) = (candidate.a, candidate.b);
+        if !decimation.can_collapse(a, b, candidate.point) {
+            continue;
+        }
+
+        decimation.collapse(a, b, candidate.point);
+
+        for neighbor in decimation.neighbors(a) {
+            queue.push(decimation.candidate(a, neighbor));
+        }
+    }
+
+    let mut decimated = Mesh::new();
+    for (triangle, &color) in
+        decimation.triangles.iter().zip(&decimation.colors)
+    {
+        if let Some(triangle) = triangle {
+            let points = triangle.map(|i| decimation.points[i]);
+            decimated.push_triangle(points, color);
+        }
+    }
+
+    decimated
+}
+
+struct Decimation {
+    points: Vec<Point<3>>,
+    quadrics: Vec<Quadric>,
+
+    /// Incremented whenever a vertex changes, to detect outdated candidates
+    versions: Vec<u64>,
+
+    /// The triangles that each vertex is part of
+    triangles_by_vertex: Vec<BTreeSet<usize>>,
+
+    /// The triangles, by vertex index; `None`, if collapsed
+    triangles: Vec<Option<[usize; 3]>>,
+    colors: Vec<Color>,
+    num_triangles: usize,
+}
+
+impl Decimation {
+    fn new(mesh: &Mesh<Point<3>>, adjacency: &Adjacency) -> Self {
+        let points: Vec<_> = mesh.vertices().collect();
+        let indices: Vec<_> = mesh.indices().collect();
+
+        let mut quadrics = vec![Quadric::default(); points.len()];
+        let mut triangles = Vec::new();
+
+        for triangle in indices.chunks(3) {
+            let triangle = [0, 1, 2].map(|j| triangle[j] as usize);
+
+            let quadric = Quadric::from_triangle(triangle.map(|j| points[j]));
+            for j in triangle {
+                quadrics[j] = quadrics[j] + quadric;
+            }
+
+            triangles.push(Some(triangle));
+        }
+
+        let triangles_by_vertex = (0..points.len())
+            .map(|vertex| {
+                adjacency
+                    .incident_triangles(vertex as Index)
+                    .iter()
+                    .copied()
+                    .collect()
+            })
+            .collect();
+
+        Self {
+            versions: vec![0; points.len()],
+            points,
+            quadrics,
+            triangles_by_vertex,
+            num_triangles: triangles.len(),
+            triangles,
+            colors: mesh.triangles().map(|triangle| triangle.color).collect(),
+        }
+    }
+
+    fn neighbors(&self, vertex: usize) -> BTreeSet<usize> {
+        self.triangles_by_vertex[vertex]
+            .iter()
+            .filter_map(|&i| self.triangles[i])
+            .flatten()
+            .filter(|&other| other != vertex)
+            .collect()
+    }
+
+    fn candidate(&self, a: usize, b: usize) -> Reverse<Candidate> {
+        let quadric = self.quadrics[a] + self.quadrics[b];
+
+        let [pa, pb] = [self.points[a], self.points[b]];
+        let midpoint = pa + (pb - pa) / 2.;
+
+        let (error, point) = quadric
+            .minimum()
+            .into_iter()
+            .chain([pa, pb, midpoint])
+            .map(|point| (quadric.error(point), point))
+            .min_by_key(|&(error, _)| error)
+            .expect("Chained non-empty list of points");
+
+        Reverse(Candidate {
+            error,
+            a,
+            b,
+            versions: [self.versions[a], self.versions[b]],
+            point,
+        })
+    }
+
+    fn is_current(&self, candidate: &Candidate) -> bool {
+        candidate.versions
+            == [self.versions[candidate.a], self.versions[candidate.b]]
+            && !self.triangles_by_vertex[candidate.a].is_empty()
+            && !self.triangles_by_vertex[candidate.b].is_empty()
+    }
+
+    fn can_collapse(&self, a: usize, b: usize, point: Point<3>) -> bool {
+        let shared: Vec<_> = self.triangles_by_vertex[a]
+            .intersection(&self.triangles_by_vertex[b])
+            .copied()
+            .collect();
+
+        // If the two vertices have more neighbors in common than the vertices
+        // opposite of their shared edge, collapsing it would pinch the mesh.
+        let common_neighbors =
+            self.neighbors(a).intersection(&self.neighbors(b)).count();
+        if common_neighbors != shared.len() {
+            return false;
+        }
+
+        for &i in
+            self.triangles_by_vertex[a].union(&self.triangles_by_vertex[b])
+        {
+            if shared.contains(&i) {
+                continue;
+            }
+
+            let triangle =
+                self.triangles[i].expect("Vertex refers to collapsed triangle");
+            let before = triangle.map(|j| self.points[j]);
+            let after = triangle.map(|j| {
+                if j == a || j == b {
+                    point
+                } else {
+                    self.points[j]
+                }
+            });
+
+            let [before, after] = [before, after].map(normal);
+            if after.magnitude() == Scalar::ZERO
+                || before.dot(&after) <= Scalar::ZERO
+            {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    /// Collapse the edge between `a` and `b`, moving `a` to `point`
+    fn collapse(&mut self, a: usize, b: usize, point: Point<3>) {
+        let triangles_of_b = std::mem::take(&mut self.triangles_by_vertex[b]);
+
+        for i in triangles_of_b {
+            let triangle = self.triangles[i]
+                .as_mut()
+                .expect("Vertex refers to collapsed triangle");
+
+            if triangle.contains(&a) {
+                for j in *triangle {
+                    self.triangles_by_vertex[j].remove(&i);
+                }
+                self.triangles[i] = None;
+                self.num_triangles -= 1;
+
+                continue;
+            }
+
+            for j in triangle.iter_mut() {
+                if *j == b {
+                    *j = a;
+                }
+            }
+            self.triangles_by_vertex[a].insert(i);
+        }
+
+        self.points[a] = point;
+        self.quadrics[a] = self.quadrics[a] + self.quadrics[b];
+        self.versions[a] += 1;
+        self.versions[b] += 1;
+    }
+}
+
+#[derive(Eq, PartialEq, Ord, PartialOrd)]
+struct Candidate {
+    error: Scalar,
+    a: usize,
+    b: usize,
+    versions: [u64; 2],
+    point: Point<3>,
+}
+
+/// The sum of squared distances to a number of planes
+///
+/// Stores the symmetric matrix `A`, vector `b`, and scalar `c`, so that the
+/// error of a point `x` is `xᵀAx + 2bᵀx + c`.
+#[derive(Clone, Copy, Default)]
+struct Quadric {
+    a: [[f64; 3]; 3],
+    b: [f64; 3],
+    c: f64,
+}
+
+impl Quadric {
+    fn from_triangle(triangle: [Point<3>; 3]) -> Self {
+        let normal = normal(triangle);
+        if normal.magnitude() == Scalar::ZERO {
+            return Self::default();
+        }
+
+        let n = normal.normalize().components.map(Scalar::into_f64);
+        let d = -n
+            .iter()
+            .zip(triangle[0].coords.components)
+            .map(|(n, p)| n * p.into_f64())
+            .sum::<f64>();
+
+        Self {
+            a: n.map(|ni| n.map(|nj| ni * nj)),
+            b: n.map(|ni| ni * d),
+            c: d * d,
+        }
+    }
+
+    fn error(&self, point: Point<3>) -> Scalar {
+        let x = point.coords.components.map(Scalar::into_f64);
+
+        let mut error = self.c;
+        for i in 0..3 {
+            for j in 0..3 {
+                error += x[i] * self.a[i][j] * x[j];
+            }
+            error += 2. * self.b[i] * x[i];
+        }
+
+        // Rounding errors can make this slightly negative.
+        Scalar::from_f64(error.max(0.))
+    }
+
+    /// The point with the smallest error, if there is a unique one
+    fn minimum(&self) -> Option<Point<3>> {
+        // Solving `Ax = -b` using Cramer's rule.
+        let [r0, r1, r2] = self.a.map(Vector::from);
+        let det = r0.dot(&r1.cross(&r2));
+        if det.abs() < Scalar::from_f64(1e-12) {
+            return None;
+        }
+
+        let b = Vector::from(self.b.map(|b| -b));
+        let column = |i: usize| {
+            let replace = |row: [f64; 3], value: Scalar| {
+                let mut row = row;
+                row[i] = value.into_f64();
+                Vector::from(row)
+            };
+            let [r0, r1, r2] =
+                [0, 1, 2].map(|j| replace(self.a[j], b.components[j]));
+            r0.dot(&r1.cross(&r2)) / det
+        };
+
+        Some(Point::from([column(0), column(1), column(2)]))
+    }
+}
+
+impl ops::Add for Quadric {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self {
+            a: [0, 1, 2]
+                .map(|i| [0, 1, 2].map(|j| self.a[i][j] + other.a[i][j])),
+            b: [0, 1, 2].map(|i| self.b[i] + other.b[i]),
+            c: self.c + other.c,
+        }
+    }
+}
+
+fn normal([a, b, c]: [Point<3>; 3]) -> Vector<3> {
+    (b - a).cross(&(c - a))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::{PI, TAU};
+
+    use fj_interop::mesh::{Color, Mesh};
+    use fj_math::{Point, Scalar};
+
+    use super::decimate;
+
+    fn sphere() -> Mesh<Point<3>> {
+        const RINGS: usize = 16;
+        const SEGMENTS: usize = 32;
+
+        let vertex = |ring: usize, segment: usize| {
+            // Computing the poles like the other vertices would result in
+            // slightly different points for each segment.
+            if ring == 0 {
+                return Point::from([0., 0., 1.]);
+            }
+            if ring == RINGS {
+                return Point::from([0., 0., -1.]);
+            }
+
+            let polar = PI * ring as f64 / RINGS as f64;
+            let azimuth = TAU * (segment % SEGMENTS) as f64 / SEGMENTS as f64;
+
+            Point::from([
+                polar.sin() * azimuth.cos(),
+                polar.sin() * azimuth.sin(),
+                polar.cos(),
+            ])
+        };
+
+        let mut mesh = Mesh::new();
+        for ring in 0..RINGS {
+            for segment in 0..SEGMENTS {
+                let a = vertex(ring, segment);
+                let b = vertex(ring + 1, segment);
+                let c = vertex(ring + 1, segment + 1);
+                let d = vertex(ring, segment + 1);
+
+                if ring > 0 {
+                    mesh.push_triangle([a, b, d], Color::default());
+                }
+                if ring < RINGS - 1 {
+                    mesh.push_triangle([b, c, d], Color::default());
+                }
+            }
+        }
+
+        mesh
+    }
+
+    fn distance_to_segment(p: Point<3>, [a, b]: [Point<3>; 2]) -> Scalar {
+        let ab = b - a;
+        let t = ((p - a).dot(&ab) / ab.dot(&ab))
+            .max(Scalar::ZERO)
+            .min(Scalar::ONE);
+
+        (p - (a + ab * t)).magnitude()
+    }
+
+    fn distance_to_triangle(p: Point<3>, [a, b, c]: [Point<3>; 3]) -> Scalar {
+        let normal = (b - a).cross(&(c - a)).normalize();
+        let projected = p - normal * (p - a).dot(&normal);
+
+        let inside = [[a, b], [b, c], [c, a]].iter().all(|&[start, end]| {
+            (end - start).cross(&(projected - start)).dot(&normal)
+                >= Scalar::ZERO
+        });
+        if inside {
+            return (p - projected).magnitude();
+        }
+
+        [[a, b], [b, c], [c, a]]
+            .into_iter()
+            .map(|segment| distance_to_segment(p, segment))
+            .min()
+            .expect("Iterating over non-empty array")
+    }
+
+    /// The largest distance between a vertex of one mesh and the other mesh
+    fn hausdorff_distance(a: &Mesh<Point<3>>, b: &Mesh<Point<3>>) -> Scalar {
+        let one_sided = |a: &Mesh<Point<3>>, b: &Mesh<Point<3>>| {
+            a.vertices()
+                .map(|vertex| {
+                    b.triangles()
+                        .map(|triangle| {
+                            distance_to_triangle(
+                                vertex,
+                                triangle.inner.points(),
+                            )
+                        })
+                        .min()
+                        .expect("Mesh has no triangles")
+                })
+                .max()
+                .expect("Mesh has no vertices")
+        };
+
+        one_sided(a, b).max(one_sided(b, a))
+    }
+
+    #[test]
+    fn decimate_sphere() {
+        let sphere = sphere();
+        let num_triangles = sphere.triangles().count();
+        let target = num_triangles / 4;
+
+        let decimated = decimate(&sphere, target);
+
+        assert_eq!(decimated.triangles().count(), target);
+        assert!(hausdorff_distance(&sphere, &decimated) < Scalar::from(0.05));
+    }
+
+    #[test]
+    fn decimate_to_current_triangle_count() {
+        let sphere = sphere();
+        let num_triangles = sphere.triangles().count();
+
+        let decimated = decimate(&sphere, num_triangles);
+
+        assert_eq!(decimated.triangles().count(), num_triangles);
+    }
+}
@@ -166,8 +166,8 @@ mod tests {
     fn compute() {
         let surface = Surface::xy_plane();
 
-        let curve =
-            Curve::build(surface).line_from_points([[-3., 0.], [-2., 0.]]);
+        let curve = Curve::build(surface.clone())
+            .line_from_points([[-3., 0.], [-2., 0.]]);
 
         #[rustfmt::skip]
         let exterior = [
@@ -184,7 +184,7 @@ mod tests {
             [-1.,  1.],
         ];
 
-        let face = Face::build(surface)
+        let face = Face::build(surface.clone())
             .polygon_from_points(exterior)
             .with_hole(interior);
 
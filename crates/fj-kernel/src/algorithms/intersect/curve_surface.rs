@@ -0,0 +1,311 @@
+use fj_math::{Line, Point, Scalar, Vector};
+
+use crate::objects::{CurveKind, GlobalCurve, Surface, SweptCurve};
+
+/// An intersection between a [`GlobalCurve`] and a [`Surface`]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct CurveSurfaceIntersection {
+    /// The intersection point, in curve coordinates on the curve
+    pub point_on_curve: Point<1>,
+
+    /// The intersection point, in surface coordinates on the surface
+    pub point_on_surface: Point<2>,
+}
+
+impl CurveSurfaceIntersection {
+    /// Compute the intersections between a curve and a surface
+    ///
+    /// Returns the intersections, sorted by their curve coordinates. Curves that
+    /// lie within the surface have no intersections, as far as this method is
+    /// concerned.
+    ///
+    /// Passing a line makes this a ray cast, with the ray going along the
+    /// direction of the line. Intersections with negative curve coordinates
+    /// are behind the origin of the ray.
+    ///
+    /// # Panics
+    ///
+    /// Currently, any curve can be intersected with a plane, but only lines can
+    /// be intersected with cylinders. Panics, if other combinations are
+    /// passed, or if the surface is a NURBS surface.
+    pub fn compute(curve: &GlobalCurve, surface: &Surface) -> Vec<Self> {
+        let surface = match surface {
+            Surface::SweptCurve(surface) => surface,
+            Surface::Nurbs(_) => {
+                todo!("Intersecting NURBS surfaces is not supported yet")
+            }
+        };
+
+        let mut intersections: Vec<_> = match surface.curve {
+            CurveKind::Line(line) => curve_plane(curve.kind(), &line, surface),
+            CurveKind::Circle(_) | CurveKind::Ellipse(_) => {
+                match curve.kind() {
+                    CurveKind::Line(line) => line_cylinder(line, surface),
+                    _ => todo!(
+                        "Only lines can be intersected with cylinders, for now"
+                    ),
+                }
+            }
+            CurveKind::Parabola(_) => {
+                todo!("Intersecting parabolic surfaces is not supported yet")
+            }
+        };
+
+        intersections.sort();
+        intersections
+    }
+}
+
+fn curve_plane(
+    curve: &CurveKind<3>,
+    plane: &Line<3>,
+    surface: &SweptCurve,
+) -> Vec<CurveSurfaceIntersection> {
+    let origin = plane.origin();
+    let normal = plane.direction().cross(&surface.path);
+
+    // Whatever the curve, the distance of its points from the plane can be
+    // written as a simple function of its curve coordinate. Find the roots.
+    let roots = match curve {
+        CurveKind::Line(line) => solve_quadratic(
+            Scalar::ZERO,
+            normal.dot(&line.direction()),
+            normal.dot(&(line.origin() - origin)),
+        ),
+        CurveKind::Circle(circle) => solve_trigonometric(
+            normal.dot(&circle.a()),
+            normal.dot(&circle.b()),
+            normal.dot(&(origin - circle.center())),
+        ),
+        CurveKind::Ellipse(ellipse) => solve_trigonometric(
+            normal.dot(&ellipse.a()),
+            normal.dot(&ellipse.b()),
+            normal.dot(&(origin - ellipse.center())),
+        ),
+        CurveKind::Parabola(parabola) => solve_quadratic(
+            normal.dot(&parabola.b()),
+            normal.dot(&parabola.a()),
+            normal.dot(&(parabola.origin() - origin)),
+        ),
+    };
+
+    roots
+        .into_iter()
+        .map(|t| {
+            let point = curve.point_from_curve_coords([t]) - origin;
+
+            // Solve `point = u * direction + v * path` in the least-squares
+            // sense, which is exact for points on the plane.
+            let [a, b] = [plane.direction(), surface.path];
+            let [aa, ab, bb] = [a.dot(&a), a.dot(&b), b.dot(&b)];
+            let [pa, pb] = [point.dot(&a), point.dot(&b)];
+            let det = aa * bb - ab * ab;
+
+            CurveSurfaceIntersection {
+                point_on_curve: Point::from([t]),
+                point_on_surface: Point::from([
+                    (pa * bb - pb * ab) / det,
+                    (pb * aa - pa * ab) / det,
+                ]),
+            }
+        })
+        .collect()
+}
+
+fn line_cylinder(
+    line: &Line<3>,
+    surface: &SweptCurve,
+) -> Vec<CurveSurfaceIntersection> {
+    let (center, a, b) = match surface.curve {
+        CurveKind::Circle(circle) => (circle.center(), circle.a(), circle.b()),
+        CurveKind::Ellipse(ellipse) => {
+            (ellipse.center(), ellipse.a(), ellipse.b())
+        }
+        _ => unreachable!("Only called for cylinders"),
+    };
+    let path = surface.path;
+
+    // Express the line in the coordinate system spanned by the axes of the
+    // curve and the path. In there, the cylinder is `x² + y² = 1`.
+    let det = a.dot(&b.cross(&path));
+    if det == Scalar::ZERO {
+        // The path lies in the plane of the curve. The surface is degenerate.
+        return Vec::new();
+    }
+    let to_cylinder_coords = |v: Vector<3>| {
+        [b.cross(&path), path.cross(&a), a.cross(&b)].map(|n| n.dot(&v) / det)
+    };
+
+    let [xo, yo, zo] = to_cylinder_coords(line.origin() - center);
+    let [xd, yd, zd] = to_cylinder_coords(line.direction());
+
+    solve_quadratic(
+        xd * xd + yd * yd,
+        (xo * xd + yo * yd) * 2.,
+        xo * xo + yo * yo - Scalar::ONE,
+    )
+    .into_iter()
+    .map(|t| {
+        let [x, y, z] = [xo + xd * t, yo + yd * t, zo + zd * t];
+
+        CurveSurfaceIntersection {
+            point_on_curve: Point::from([t]),
+            point_on_surface: Point::from([normalize_angle(y.atan2(x)), z]),
+        }
+    })
+    .collect()
+}
+
+/// Solve `a * t² + b * t + c = 0`
+///
+/// Also handles the linear case, where `a` is zero. Returns no solutions, if
+/// every `t` is a solution.
+fn solve_quadratic(a: Scalar, b: Scalar, c: Scalar) -> Vec<Scalar> {
+    if a == Scalar::ZERO {
+        if b == Scalar::ZERO {
+            return Vec::new();
+        }
+        return vec![-c / b];
+    }
+
+    let discriminant = b * b - a * c * 4.;
+    if discriminant < Scalar::ZERO {
+        return Vec::new();
+    }
+    if discriminant == Scalar::ZERO {
+        return vec![-b / (a * 2.)];
+    }
+
+    let root = Scalar::from(discriminant.into_f64().sqrt());
+    vec![(-b - root) / (a * 2.), (-b + root) / (a * 2.)]
+}
+
+/// Solve `a * cos(t) + b * sin(t) = c`, for `t` between `0` and `PI * 2`
+fn solve_trigonometric(a: Scalar, b: Scalar, c: Scalar) -> Vec<Scalar> {
+    let amplitude = Scalar::from((a * a + b * b).into_f64().sqrt());
+    if amplitude == Scalar::ZERO {
+        return Vec::new();
+    }
+
+    let ratio = c / amplitude;
+    if ratio.abs() > Scalar::ONE {
+        return Vec::new();
+    }
+
+    let phase = b.atan2(a);
+    let angle = ratio.acos();
+
+    if angle == Scalar::ZERO {
+        return vec![normalize_angle(phase)];
+    }
+    vec![
+        normalize_angle(phase - angle),
+        normalize_angle(phase + angle),
+    ]
+}
+
+fn normalize_angle(angle: Scalar) -> Scalar {
+    let full_turn = Scalar::PI * 2.;
+    let angle = angle % full_turn;
+
+    if angle < Scalar::ZERO {
+        angle + full_turn
+    } else {
+        angle
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Circle, Line, Point, Scalar, Vector};
+    use pretty_assertions::assert_eq;
+
+    use crate::objects::{CurveKind, GlobalCurve, Surface, SweptCurve};
+
+    use super::CurveSurfaceIntersection;
+
+    #[test]
+    fn line_plane() {
+        let surface = Surface::xy_plane();
+
+        let line =
+            GlobalCurve::from_kind(CurveKind::Line(Line::from_points([
+                [1., 2., 3.],
+                [1., 2., 1.],
+            ])));
+        assert_eq!(
+            CurveSurfaceIntersection::compute(&line, &surface),
+            vec![CurveSurfaceIntersection {
+                point_on_curve: Point::from([1.5]),
+                point_on_surface: Point::from([1., 2.]),
+            }]
+        );
+
+        let parallel =
+            GlobalCurve::from_kind(CurveKind::Line(Line::from_points([
+                [1., 2., 3.],
+                [2., 2., 3.],
+            ])));
+        assert_eq!(
+            CurveSurfaceIntersection::compute(&parallel, &surface),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn circle_plane() {
+        let surface = Surface::xz_plane();
+        let circle = GlobalCurve::from_kind(CurveKind::Circle(Circle::new(
+            [0., 0., 0.],
+            [0., 1., 0.],
+            [1., 0., 0.],
+        )));
+
+        let intersections =
+            CurveSurfaceIntersection::compute(&circle, &surface);
+
+        let expected = [(Scalar::PI / 2., 1.), (Scalar::PI * 1.5, -1.)];
+        assert_eq!(intersections.len(), expected.len());
+        for (intersection, (t, u)) in intersections.into_iter().zip(expected) {
+            assert!((intersection.point_on_curve.t - t).abs() < 1e-12.into());
+            assert!(
+                (intersection.point_on_surface - Point::from([u, 0.]))
+                    .magnitude()
+                    < 1e-12.into()
+            );
+        }
+    }
+
+    #[test]
+    fn line_cylinder() {
+        let surface = Surface::SweptCurve(SweptCurve {
+            curve: CurveKind::Circle(Circle::new(
+                [0., 0., 0.],
+                [2., 0., 0.],
+                [0., 2., 0.],
+            )),
+            path: Vector::from([0., 0., 1.]),
+        });
+
+        let line =
+            GlobalCurve::from_kind(CurveKind::Line(Line::from_points([
+                [-3., 0., 1.],
+                [-2., 0., 1.],
+            ])));
+        let intersections = CurveSurfaceIntersection::compute(&line, &surface);
+
+        let expected = [(1., Scalar::PI), (5., Scalar::ZERO)];
+        assert_eq!(intersections.len(), expected.len());
+        for (intersection, (t, u)) in intersections.into_iter().zip(expected) {
+            assert_eq!(intersection.point_on_curve, Point::from([t]));
+            assert!(
+                (intersection.point_on_surface - Point::from([u, 1.0.into()]))
+                    .magnitude()
+                    < 1e-12.into()
+            );
+        }
+
+        let axis = GlobalCurve::from_kind(CurveKind::z_axis());
+        assert_eq!(CurveSurfaceIntersection::compute(&axis, &surface), vec![]);
+    }
+}
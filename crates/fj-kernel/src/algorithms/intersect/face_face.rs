@@ -21,36 +21,48 @@ pub struct FaceFaceIntersection {
 
 impl FaceFaceIntersection {
     /// Compute the intersections between two faces
+    ///
+    /// Returns `None`, if the faces don't intersect, or if the intersection of
+    /// their surfaces can't be computed. See
+    /// [`SurfaceSurfaceIntersection::compute`].
     pub fn compute(faces: [&Face; 2]) -> Option<Self> {
         let surfaces = faces.map(|face| face.surface());
 
         let intersection_curves =
             SurfaceSurfaceIntersection::compute(surfaces)?.intersection_curves;
 
-        // Can be cleaned up, once `zip` is stable:
-        // https://doc.rust-lang.org/std/primitive.array.html#method.zip
-        let curve_face_intersections = {
-            let [curve_a, curve_b] = intersection_curves;
-            let [face_a, face_b] = faces;
-
-            [(curve_a, face_a), (curve_b, face_b)].map(|(curve, face)| {
-                CurveFaceIntersection::compute(&curve, face)
+        // Faces can only intersect along a single curve, for now. Use the first
+        // one that actually runs through both faces.
+        intersection_curves
+            .into_iter()
+            .find_map(|intersection_curves| {
+                // Can be cleaned up, once `zip` is stable:
+                // https://doc.rust-lang.org/std/primitive.array.html#method.zip
+                let curve_face_intersections = {
+                    let [curve_a, curve_b] = &intersection_curves;
+                    let [face_a, face_b] = faces;
+
+                    [(curve_a, face_a), (curve_b, face_b)].map(
+                        |(curve, face)| {
+                            CurveFaceIntersection::compute(curve, face)
+                        },
+                    )
+                };
+
+                let intersection_intervals = {
+                    let [a, b] = curve_face_intersections;
+                    a.merge(&b)
+                };
+
+                if intersection_intervals.is_empty() {
+                    return None;
+                }
+
+                Some(Self {
+                    intersection_curves,
+                    intersection_intervals,
+                })
             })
-        };
-
-        let intersection_intervals = {
-            let [a, b] = curve_face_intersections;
-            a.merge(&b)
-        };
-
-        if intersection_intervals.is_empty() {
-            return None;
-        }
-
-        Some(Self {
-            intersection_curves,
-            intersection_intervals,
-        })
     }
 }
 
@@ -75,7 +87,7 @@ mod tests {
             [1., 2.],
         ];
         let surfaces = [Surface::xy_plane(), Surface::xz_plane()];
-        let [a, b] = surfaces.map(|surface| {
+        let [a, b] = surfaces.clone().map(|surface| {
             Face::build(surface).polygon_from_points(points).into_face()
         });
 
@@ -94,7 +106,7 @@ mod tests {
             [-1.,  1.],
         ];
         let surfaces = [Surface::xy_plane(), Surface::xz_plane()];
-        let [a, b] = surfaces.map(|surface| {
+        let [a, b] = surfaces.clone().map(|surface| {
             Face::build(surface).polygon_from_points(points).into_face()
         });
 
@@ -6,6 +6,7 @@ pub mod ray_segment;
 
 mod curve_edge;
 mod curve_face;
+mod curve_surface;
 mod face_face;
 mod line_segment;
 mod surface_surface;
@@ -15,6 +16,7 @@ use fj_math::Point;
 pub use self::{
     curve_edge::CurveEdgeIntersection,
     curve_face::{CurveFaceIntersection, CurveFaceIntersectionInterval},
+    curve_surface::CurveSurfaceIntersection,
     face_face::FaceFaceIntersection,
     line_segment::LineSegmentIntersection,
     surface_surface::SurfaceSurfaceIntersection,
@@ -17,8 +17,10 @@ impl Intersect for (&HorizontalRayToTheRight<2>, &Edge) {
 
         let line = match edge.curve().kind() {
             CurveKind::Line(line) => line,
-            CurveKind::Circle(_) => {
-                todo!("Casting rays against circles is not supported yet")
+            CurveKind::Circle(_)
+            | CurveKind::Ellipse(_)
+            | CurveKind::Parabola(_) => {
+                todo!("Casting rays against curves is not supported yet")
             }
         };
 
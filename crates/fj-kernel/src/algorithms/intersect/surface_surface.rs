@@ -1,61 +1,237 @@
-use fj_math::{Line, Point, Scalar, Vector};
+use fj_math::{Circle, Line, Point, Scalar, Vector};
 
-use crate::objects::{Curve, CurveKind, GlobalCurve, Surface};
+use crate::objects::{Curve, CurveKind, GlobalCurve, Surface, SweptCurve};
 
 /// The intersection between two surfaces
 #[derive(Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
 pub struct SurfaceSurfaceIntersection {
     /// The intersection curves
-    pub intersection_curves: [Curve; 2],
+    ///
+    /// Each entry is one intersection curve, represented in the local
+    /// coordinates of each of the input surfaces, in the order they were
+    /// passed in.
+    pub intersection_curves: Vec<[Curve; 2]>,
 }
 
 impl SurfaceSurfaceIntersection {
     /// Compute the intersection between two surfaces
+    ///
+    /// Returns `None`, if the surfaces don't intersect, if they coincide, or if
+    /// their intersection can't be computed.
+    ///
+    /// Currently, only the intersections between two planes, and between a
+    /// plane and a cylinder are supported. Other surfaces result in `None`.
+    ///
+    /// Planes that are oblique to the axis of a cylinder, meaning they are
+    /// neither parallel to the axis, nor to the plane of the cylinder's circle,
+    /// result in `None` too. The intersection is an ellipse, but in the
+    /// coordinates of the cylinder, it is a sine wave, which no [`CurveKind`]
+    /// can represent.
     pub fn compute(surfaces: [&Surface; 2]) -> Option<Self> {
-        // Algorithm from Real-Time Collision Detection by Christer Ericson. See
-        // section 5.4.4, Intersection of Two Planes.
-        //
-        // Adaptations were made to get the intersection curves in local
-        // coordinates for each surface.
-
-        let planes_parametric =
-            surfaces.map(PlaneParametric::extract_from_surface);
-        let [a, b] = planes_parametric
-            .map(|plane| PlaneConstantNormal::from_parametric_plane(&plane));
-
-        let direction = a.normal.cross(&b.normal);
-
-        let denom = direction.dot(&direction);
-        if denom == Scalar::ZERO {
-            // Comparing `denom` against zero looks fishy. It's probably better
-            // to compare it against an epsilon value, but I don't know how
-            // large that epsilon should be.
-            //
-            // I'll just leave it like that, until we had the opportunity to
-            // collect some experience with this code.
-            // - @hannobraun
+        let [a, b] = surfaces.map(SurfaceKind::from_surface);
+
+        let intersection_curves = match [a?, b?] {
+            [SurfaceKind::Plane(a), SurfaceKind::Plane(b)] => {
+                vec![plane_plane(&a, &b)?]
+            }
+            [SurfaceKind::Plane(plane), SurfaceKind::Cylinder(cylinder)] => {
+                plane_cylinder(&plane, &cylinder)
+            }
+            [SurfaceKind::Cylinder(cylinder), SurfaceKind::Plane(plane)] => {
+                plane_cylinder(&plane, &cylinder)
+                    .into_iter()
+                    .map(|[a, b]| [b, a])
+                    .collect()
+            }
+            [SurfaceKind::Cylinder(_), SurfaceKind::Cylinder(_)] => {
+                return None;
+            }
+        };
+
+        if intersection_curves.is_empty() {
             return None;
         }
 
-        let origin = (b.normal * a.distance - a.normal * b.distance)
-            .cross(&direction)
-            / denom;
-        let origin = Point { coords: origin };
+        Some(Self {
+            intersection_curves,
+        })
+    }
+}
+
+fn plane_plane(a: &PlaneParametric, b: &PlaneParametric) -> Option<[Curve; 2]> {
+    // Algorithm from Real-Time Collision Detection by Christer Ericson. See
+    // section 5.4.4, Intersection of Two Planes.
+    //
+    // Adaptations were made to get the intersection curves in local
+    // coordinates for each surface.
+
+    let planes_parametric = [a, b];
+    let [a, b] =
+        planes_parametric.map(PlaneConstantNormal::from_parametric_plane);
+
+    let direction = a.normal.cross(&b.normal);
+
+    let denom = direction.dot(&direction);
+    if denom == Scalar::ZERO {
+        // Comparing `denom` against zero looks fishy. It's probably better
+        // to compare it against an epsilon value, but I don't know how
+        // large that epsilon should be.
+        //
+        // I'll just leave it like that, until we had the opportunity to
+        // collect some experience with this code.
+        // - @hannobraun
+        return None;
+    }
+
+    let origin = (b.normal * a.distance - a.normal * b.distance)
+        .cross(&direction)
+        / denom;
+    let origin = Point { coords: origin };
+
+    let line = Line::from_origin_and_direction(origin, direction);
+
+    Some(planes_parametric.map(|plane| {
+        let local = project_line_into_plane(&line, plane);
+        let global = CurveKind::Line(line);
 
-        let line = Line::from_origin_and_direction(origin, direction);
+        Curve::new(local, GlobalCurve::from_kind(global))
+    }))
+}
+
+/// Compute the intersection between a plane and a cylinder
+///
+/// Returns the curves in the order plane, cylinder. Returns no curves, if the
+/// plane is oblique to the axis of the cylinder, as that intersection can't be
+/// represented.
+fn plane_cylinder(
+    plane: &PlaneParametric,
+    cylinder: &Cylinder,
+) -> Vec<[Curve; 2]> {
+    let PlaneConstantNormal { distance, normal } =
+        PlaneConstantNormal::from_parametric_plane(plane);
+    let Cylinder { circle, path } = cylinder;
 
-        let curves = planes_parametric.map(|plane| {
-            let local = project_line_into_plane(&line, &plane);
-            let global = CurveKind::Line(Line::from_origin_and_direction(
-                origin, direction,
+    // A point on the cylinder is `circle(u) + path * v`. It's on the plane, if
+    // `normal * circle(u) + normal * path * v = distance`.
+    let normal_path = normal.dot(path);
+    let [normal_a, normal_b] = [circle.a(), circle.b()].map(|v| normal.dot(&v));
+    let offset = distance - normal.dot(&circle.center().coords);
+
+    // Like in the plane-plane case, these comparisons are exact. Cylinders that
+    // are built from axis-aligned sketches and paths are covered by this.
+    match (
+        normal_path == Scalar::ZERO,
+        [normal_a, normal_b] == [Scalar::ZERO; 2],
+    ) {
+        (true, true) => {
+            // The cylinder is degenerate. Its path lies in the plane of its
+            // circle.
+            Vec::new()
+        }
+        (true, false) => {
+            // The plane is parallel to the axis, and cuts the cylinder along
+            // lines. Solve `normal_a * cos(u) + normal_b * sin(u) = offset`.
+            let amplitude = Scalar::from(
+                (normal_a * normal_a + normal_b * normal_b)
+                    .into_f64()
+                    .sqrt(),
+            );
+            let ratio = offset / amplitude;
+            if ratio.abs() > Scalar::ONE {
+                return Vec::new();
+            }
+
+            let phase = Scalar::atan2(normal_b, normal_a);
+            let angle = ratio.acos();
+
+            // A plane that touches the cylinder has a single intersection.
+            let mut angles = vec![phase + angle];
+            if angle != Scalar::ZERO {
+                angles.push(phase - angle);
+            }
+
+            angles
+                .into_iter()
+                .map(|u| {
+                    let line = Line::from_origin_and_direction(
+                        circle.point_from_circle_coords([u]),
+                        *path,
+                    );
+
+                    let on_plane = project_line_into_plane(&line, plane);
+                    let on_cylinder =
+                        CurveKind::Line(Line::from_origin_and_direction(
+                            Point::from([u, Scalar::ZERO]),
+                            Vector::from([Scalar::ZERO, Scalar::ONE]),
+                        ));
+
+                    let global = GlobalCurve::from_kind(CurveKind::Line(line));
+                    [
+                        Curve::new(on_plane, global),
+                        Curve::new(on_cylinder, global),
+                    ]
+                })
+                .collect()
+        }
+        (false, true) => {
+            // The plane is parallel to the plane of the circle, which makes the
+            // intersection a copy of it.
+            let v = offset / normal_path;
+
+            let center = circle.center() + *path * v;
+            let global = GlobalCurve::from_kind(CurveKind::Circle(
+                Circle::new(center, circle.a(), circle.b()),
             ));
 
-            Curve::new(local, GlobalCurve::from_kind(global))
-        });
+            let on_plane = CurveKind::Circle(Circle::new(
+                plane.point_to_plane_coords(center),
+                plane.vector_to_plane_coords(circle.a()),
+                plane.vector_to_plane_coords(circle.b()),
+            ));
+            let on_cylinder = CurveKind::Line(Line::from_origin_and_direction(
+                Point::from([Scalar::ZERO, v]),
+                Vector::from([Scalar::ONE, Scalar::ZERO]),
+            ));
 
-        Some(Self {
-            intersection_curves: curves,
-        })
+            vec![[
+                Curve::new(on_plane, global),
+                Curve::new(on_cylinder, global),
+            ]]
+        }
+        (false, false) => {
+            // The intersection is an ellipse, but its local form on the
+            // cylinder is `v = (offset - normal_a * cos(u) - normal_b * sin(u))
+            // / normal_path`. That's not a curve we can represent.
+            Vec::new()
+        }
+    }
+}
+
+/// The kinds of surfaces that the intersection algorithm distinguishes
+enum SurfaceKind {
+    Plane(PlaneParametric),
+    Cylinder(Cylinder),
+}
+
+impl SurfaceKind {
+    /// Returns `None`, if the surface is neither a plane nor a cylinder
+    fn from_surface(surface: &Surface) -> Option<Self> {
+        let SweptCurve { curve, path } = match surface {
+            Surface::SweptCurve(surface) => *surface,
+            Surface::Nurbs(_) => return None,
+        };
+
+        match curve {
+            CurveKind::Line(line) => Some(Self::Plane(PlaneParametric {
+                origin: line.origin(),
+                u: line.direction(),
+                v: path,
+            })),
+            CurveKind::Circle(circle) => {
+                Some(Self::Cylinder(Cylinder { circle, path }))
+            }
+            _ => None,
+        }
     }
 }
 
@@ -68,19 +244,35 @@ struct PlaneParametric {
 }
 
 impl PlaneParametric {
-    pub fn extract_from_surface(surface: &Surface) -> Self {
-        let Surface::SweptCurve(surface) = surface;
-        let line = match surface.curve {
-            CurveKind::Line(line) => line,
-            _ => todo!("Only plane-plane intersection is currently supported."),
-        };
-
-        Self {
-            origin: line.origin(),
-            u: line.direction(),
-            v: surface.path,
+    /// Convert a point into plane coordinates
+    ///
+    /// Points that are not on the plane are projected onto it.
+    fn point_to_plane_coords(&self, point: Point<3>) -> Point<2> {
+        Point {
+            coords: self.vector_to_plane_coords(point - self.origin),
         }
     }
+
+    /// Convert a vector into plane coordinates
+    ///
+    /// Vectors that are not parallel to the plane are projected onto it.
+    fn vector_to_plane_coords(&self, vector: Vector<3>) -> Vector<2> {
+        // The axes of the plane don't need to be perpendicular, so it's not
+        // enough to project onto each of them separately.
+        let [uu, uv, vv] =
+            [(self.u, self.u), (self.u, self.v), (self.v, self.v)]
+                .map(|(a, b)| a.dot(&b));
+        let [wu, wv] = [self.u, self.v].map(|axis| vector.dot(&axis));
+
+        let det = uu * vv - uv * uv;
+        Vector::from([(wu * vv - wv * uv) / det, (wv * uu - wu * uv) / det])
+    }
+}
+
+/// A cylinder, made by sweeping a circle along a path
+struct Cylinder {
+    circle: Circle<3>,
+    path: Vector<3>,
 }
 
 /// A plane in constant-normal form
@@ -113,26 +305,9 @@ fn project_line_into_plane(
     line: &Line<3>,
     plane: &PlaneParametric,
 ) -> CurveKind<2> {
-    let line_origin_relative_to_plane = line.origin() - plane.origin;
-    let line_origin_in_plane = Vector::from([
-        plane
-            .u
-            .scalar_projection_onto(&line_origin_relative_to_plane),
-        plane
-            .v
-            .scalar_projection_onto(&line_origin_relative_to_plane),
-    ]);
-
-    let line_direction_in_plane = Vector::from([
-        plane.u.scalar_projection_onto(&line.direction()),
-        plane.v.scalar_projection_onto(&line.direction()),
-    ]);
-
     let line = Line::from_origin_and_direction(
-        Point {
-            coords: line_origin_in_plane,
-        },
-        line_direction_in_plane,
+        plane.point_to_plane_coords(line.origin()),
+        plane.vector_to_plane_coords(line.direction()),
     );
 
     CurveKind::Line(line)
@@ -140,11 +315,12 @@ fn project_line_into_plane(
 
 #[cfg(test)]
 mod tests {
-    use fj_math::Transform;
+    use fj_math::{Circle, Line, Point, Scalar, Transform, Vector};
+    use pretty_assertions::assert_eq;
 
     use crate::{
         algorithms::TransformObject,
-        objects::{Curve, Surface},
+        objects::{Curve, CurveKind, GlobalCurve, Surface, SweptCurve},
     };
 
     use super::SurfaceSurfaceIntersection;
@@ -159,19 +335,160 @@ mod tests {
         assert_eq!(
             SurfaceSurfaceIntersection::compute([
                 &xy,
-                &xy.transform(&Transform::translation([0., 0., 1.]))
+                &xy.clone().transform(&Transform::translation([0., 0., 1.]))
             ]),
             None,
         );
 
-        let expected_xy = Curve::build(xy).u_axis();
-        let expected_xz = Curve::build(xz).u_axis();
+        let expected_xy = Curve::build(xy.clone()).u_axis();
+        let expected_xz = Curve::build(xz.clone()).u_axis();
 
         assert_eq!(
             SurfaceSurfaceIntersection::compute([&xy, &xz]),
             Some(SurfaceSurfaceIntersection {
-                intersection_curves: [expected_xy, expected_xz],
+                intersection_curves: vec![[expected_xy, expected_xz]],
             })
         );
     }
+
+    #[test]
+    fn plane_cylinder() {
+        // A cylinder with radius 1 around the z-axis
+        let circle =
+            Circle::new(Point::origin(), Vector::unit_x(), Vector::unit_y());
+        let cylinder = Surface::SweptCurve(SweptCurve {
+            curve: CurveKind::Circle(circle),
+            path: Vector::unit_z(),
+        });
+
+        let plane_at = |offset: [f64; 3], surface: Surface| {
+            surface.transform(&Transform::translation(offset))
+        };
+
+        // The plane misses the cylinder.
+        let yz = plane_at([2., 0., 0.], Surface::yz_plane());
+        assert_eq!(SurfaceSurfaceIntersection::compute([&yz, &cylinder]), None);
+
+        // The plane touches the cylinder along a single line.
+        let yz = plane_at([1., 0., 0.], Surface::yz_plane());
+        let intersection =
+            SurfaceSurfaceIntersection::compute([&yz, &cylinder]).unwrap();
+        let [[on_plane, on_cylinder]] =
+            <[_; 1]>::try_from(intersection.intersection_curves).unwrap();
+        assert_eq!(
+            on_plane.kind(),
+            &CurveKind::Line(Line::from_origin_and_direction(
+                Point::from([0., 0.]),
+                Vector::from([0., 1.])
+            ))
+        );
+        assert_eq!(
+            on_cylinder.kind(),
+            &CurveKind::Line(Line::from_origin_and_direction(
+                Point::from([0., 0.]),
+                Vector::from([0., 1.])
+            ))
+        );
+
+        // The plane cuts the cylinder along two lines.
+        let xz = Surface::xz_plane();
+        let intersection =
+            SurfaceSurfaceIntersection::compute([&cylinder, &xz]).unwrap();
+        assert_eq!(intersection.intersection_curves.len(), 2);
+        for [on_cylinder, on_plane] in intersection.intersection_curves {
+            let global = on_plane.global().kind();
+            for t in [0., 1.] {
+                let point = global.point_from_curve_coords([t]);
+                assert!(
+                    (point.x.abs() - Scalar::ONE).abs() < Scalar::from(1e-12)
+                );
+                assert!(point.y.abs() < Scalar::from(1e-12));
+
+                let on_cylinder =
+                    on_cylinder.kind().point_from_curve_coords([t]);
+                let on_plane = on_plane.kind().point_from_curve_coords([t]);
+                assert!(
+                    (cylinder.point_from_surface_coords(on_cylinder) - point)
+                        .magnitude()
+                        < Scalar::from(1e-12)
+                );
+                assert!(
+                    (xz.point_from_surface_coords(on_plane) - point)
+                        .magnitude()
+                        < Scalar::from(1e-12)
+                );
+            }
+        }
+
+        // The plane cuts the cylinder along a circle.
+        let xy = plane_at([0., 0., 2.], Surface::xy_plane());
+        let intersection =
+            SurfaceSurfaceIntersection::compute([&xy, &cylinder]).unwrap();
+        assert_eq!(
+            intersection.intersection_curves,
+            vec![[
+                Curve::new(
+                    CurveKind::Circle(Circle::new(
+                        [0., 0.],
+                        [1., 0.],
+                        [0., 1.]
+                    )),
+                    GlobalCurve::from_kind(CurveKind::Circle(Circle::new(
+                        [0., 0., 2.],
+                        Vector::unit_x(),
+                        Vector::unit_y()
+                    )))
+                ),
+                Curve::new(
+                    CurveKind::Line(Line::from_origin_and_direction(
+                        Point::from([0., 2.]),
+                        Vector::from([1., 0.])
+                    )),
+                    GlobalCurve::from_kind(CurveKind::Circle(Circle::new(
+                        [0., 0., 2.],
+                        Vector::unit_x(),
+                        Vector::unit_y()
+                    )))
+                ),
+            ]]
+        );
+    }
+
+    #[test]
+    fn plane_cylinder_oblique() {
+        let circle =
+            Circle::new(Point::origin(), Vector::unit_x(), Vector::unit_y());
+        let cylinder = Surface::SweptCurve(SweptCurve {
+            curve: CurveKind::Circle(circle),
+            path: Vector::unit_z(),
+        });
+        let plane = Surface::plane_from_points([
+            [0., 0., 0.],
+            [1., 0., 1.],
+            [0., 1., 0.],
+        ]);
+
+        assert_eq!(
+            SurfaceSurfaceIntersection::compute([&plane, &cylinder]),
+            None
+        );
+    }
+
+    #[test]
+    fn unsupported_surfaces() {
+        let circle =
+            Circle::new(Point::origin(), Vector::unit_x(), Vector::unit_y());
+        let cylinder = Surface::SweptCurve(SweptCurve {
+            curve: CurveKind::Circle(circle),
+            path: Vector::unit_z(),
+        });
+        let other = cylinder
+            .clone()
+            .transform(&Transform::translation([1., 0., 0.]));
+
+        assert_eq!(
+            SurfaceSurfaceIntersection::compute([&cylinder, &other]),
+            None
+        );
+    }
 }
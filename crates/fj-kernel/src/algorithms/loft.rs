@@ -0,0 +1,321 @@
+use fj_interop::mesh::Color;
+use fj_math::{Point, Scalar, Transform, Triangle, Vector};
+
+use crate::objects::{Face, Solid};
+
+use super::{
+    sweep::{create_bottom_faces, create_top_face},
+    CycleApprox, Tolerance,
+};
+
+/// Create a solid by connecting a series of profiles
+///
+/// Each profile is a face, bounded by a single exterior cycle. The profiles are
+/// approximated as polygons, which are connected in order. If `smooth` is
+/// `false`, consecutive profiles are connected by straight lines. Otherwise,
+/// the connecting surface curves smoothly through all profiles.
+///
+/// Profiles don't need to be approximated by the same number of points. Where
+/// that is the case, edges of the profiles with fewer points are subdivided.
+/// The first and last profile cap off the solid.
+///
+/// All profiles are expected to face the same way, roughly along the direction
+/// of the loft, or roughly against it.
+///
+/// # Panics
+///
+/// Panics, if a profile has interior cycles, or more or less than one exterior
+/// cycle.
+pub fn loft(
+    profiles: &[Face],
+    smooth: bool,
+    tolerance: Tolerance,
+    color: Color,
+) -> Solid {
+    let (first, last) = match profiles {
+        [first, .., last] => (first, last),
+        _ => return Solid::new(),
+    };
+
+    let mut polygons: Vec<_> = profiles
+        .iter()
+        .map(|profile| {
+            assert!(
+                profile.interiors().next().is_none(),
+                "Can't loft profile with interior cycles"
+            );
+
+            let mut exteriors = profile.exteriors();
+            let exterior = match (exteriors.next(), exteriors.next()) {
+                (Some(exterior), None) => exterior,
+                _ => panic!("Can't loft profile without single exterior cycle"),
+            };
+
+            CycleApprox::new(exterior, tolerance)
+                .segments()
+                .into_iter()
+                .map(|segment| segment.points()[0])
+                .collect::<Vec<_>>()
+        })
+        .collect();
+
+    let num_points = polygons.iter().map(Vec::len).max().unwrap_or_default();
+    for polygon in &mut polygons {
+        subdivide(polygon, num_points);
+    }
+    for i in 1..polygons.len() {
+        let (previous, polygon) = polygons.split_at_mut(i);
+        align(&previous[i - 1], &mut polygon[0]);
+    }
+
+    let sections = if smooth {
+        interpolate(&polygons, tolerance)
+    } else {
+        polygons
+    };
+
+    let is_loft_along_negative_direction = {
+        let direction = centroid(&sections[1]) - centroid(&sections[0]);
+        normal(&sections[0]).dot(&direction) < Scalar::ZERO
+    };
+
+    let mut target = Vec::new();
+
+    create_bottom_faces(first, is_loft_along_negative_direction, &mut target);
+    create_top_face(
+        last.clone(),
+        &Transform::identity(),
+        is_loft_along_negative_direction,
+        &mut target,
+    );
+
+    let mut side_face: Vec<(Triangle<3>, _)> = Vec::new();
+    for sections in sections.windows(2) {
+        let [bottom, top] = [&sections[0], &sections[1]];
+
+        for i in 0..num_points {
+            let j = (i + 1) % num_points;
+            let [v0, v1, v2, v3] = [bottom[i], bottom[j], top[j], top[i]];
+
+            let triangles = if is_loft_along_negative_direction {
+                [[v0, v2, v1], [v0, v3, v2]]
+            } else {
+                [[v0, v1, v2], [v0, v2, v3]]
+            };
+
+            for triangle in triangles {
+                if let Some(triangle) = Triangle::from_points(triangle) {
+                    side_face.push((triangle, color));
+                }
+            }
+        }
+    }
+    target.push(Face::from_triangles(side_face));
+
+    Solid::new().with_faces(target)
+}
+
+/// Subdivide the longest edges of a polygon, until it has `num_points` points
+fn subdivide(polygon: &mut Vec<Point<3>>, num_points: usize) {
+    while polygon.len() < num_points {
+        let (i, _) = (0..polygon.len())
+            .map(|i| {
+                let [a, b] = [polygon[i], polygon[(i + 1) % polygon.len()]];
+                (i, (b - a).magnitude())
+            })
+            .fold((0, Scalar::ZERO), |longest, edge| {
+                if edge.1 > longest.1 {
+                    edge
+                } else {
+                    longest
+                }
+            });
+
+        let [a, b] = [polygon[i], polygon[(i + 1) % polygon.len()]];
+        polygon.insert(i + 1, a + (b - a) / 2.);
+    }
+}
+
+/// Rotate the points of a polygon, so they line up with a previous one
+///
+/// Otherwise, the surface between them would be twisted.
+fn align(previous: &[Point<3>], polygon: &mut [Point<3>]) {
+    let offset = centroid(polygon) - centroid(previous);
+
+    let deviation = |start: usize| {
+        previous
+            .iter()
+            .enumerate()
+            .map(|(i, point)| {
+                let other = polygon[(start + i) % polygon.len()] - offset;
+                (other - *point).magnitude()
+            })
+            .fold(Scalar::ZERO, |sum, distance| sum + distance * distance)
+    };
+
+    let start = (0..polygon.len())
+        .map(|start| (start, deviation(start)))
+        .fold((0, Scalar::MAX), |best, candidate| {
+            if candidate.1 < best.1 {
+                candidate
+            } else {
+                best
+            }
+        })
+        .0;
+
+    polygon.rotate_left(start);
+}
+
+/// Insert sections between the polygons, along a smooth curve through them
+///
+/// Each point follows a Catmull-Rom spline through the corresponding points of
+/// the polygons.
+fn interpolate(
+    polygons: &[Vec<Point<3>>],
+    tolerance: Tolerance,
+) -> Vec<Vec<Point<3>>> {
+    let n = polygons.len();
+
+    // Extend the series of polygons at the ends, so the curve passes through
+    // the first and last one too.
+    let control = |i: isize| -> Vec<Point<3>> {
+        match i {
+            -1 => reflect(&polygons[0], &polygons[1]),
+            i if i as usize == n => reflect(&polygons[n - 1], &polygons[n - 2]),
+            i => polygons[i as usize].clone(),
+        }
+    };
+
+    let mut sections = vec![polygons[0].clone()];
+
+    for i in 0..n as isize - 1 {
+        let [p0, p1, p2, p3] = [i - 1, i, i + 1, i + 2].map(control);
+        let point = |j: usize, t: f64| {
+            let [p0, p1, p2, p3] =
+                [p0[j], p1[j], p2[j], p3[j]].map(|p| p.coords);
+            let coords = (p1 * 2.
+                + (p2 - p0) * t
+                + (p0 * 2. - p1 * 5. + p2 * 4. - p3) * (t * t)
+                + (p1 * 3. - p0 - p2 * 3. + p3) * (t * t * t))
+                / 2.;
+            Point::origin() + coords
+        };
+
+        // The deviation of the curve from a straight line shrinks with the
+        // square of the number of steps.
+        let deviation = (0..p1.len())
+            .map(|j| {
+                (point(j, 0.5) - (p1[j] + (p2[j] - p1[j]) / 2.)).magnitude()
+            })
+            .fold(Scalar::ZERO, Scalar::max);
+        let steps = (deviation / tolerance.inner())
+            .into_f64()
+            .sqrt()
+            .ceil()
+            .max(1.) as usize;
+
+        for step in 1..steps {
+            let t = step as f64 / steps as f64;
+            sections.push((0..p1.len()).map(|j| point(j, t)).collect());
+        }
+        sections.push(p2);
+    }
+
+    sections
+}
+
+/// Reflect polygon `b` through polygon `a`
+fn reflect(a: &[Point<3>], b: &[Point<3>]) -> Vec<Point<3>> {
+    a.iter().zip(b).map(|(&a, &b)| a + (a - b)).collect()
+}
+
+fn centroid(polygon: &[Point<3>]) -> Point<3> {
+    let sum = polygon
+        .iter()
+        .fold(Vector::from([0., 0., 0.]), |sum, point| sum + point.coords);
+    Point::origin() + sum / polygon.len().max(1) as f64
+}
+
+/// The normal of a polygon, using Newell's method
+fn normal(polygon: &[Point<3>]) -> Vector<3> {
+    (0..polygon.len())
+        .map(|i| {
+            let [a, b] = [polygon[i], polygon[(i + 1) % polygon.len()]];
+            a.coords.cross(&b.coords)
+        })
+        .fold(Vector::from([0., 0., 0.]), |sum, normal| sum + normal)
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_interop::{debug::DebugInfo, mesh::Color};
+    use fj_math::Scalar;
+
+    use crate::{
+        algorithms::{triangulate, volume, Tolerance, TransformObject},
+        objects::{Cycle, Edge, Face, Surface},
+    };
+
+    fn square(size: f64, z: f64) -> Face {
+        let half = size / 2.;
+        Face::build(Surface::xy_plane())
+            .polygon_from_points([
+                [-half, -half],
+                [half, -half],
+                [half, half],
+                [-half, half],
+            ])
+            .into_face()
+            .translate([0., 0., z])
+    }
+
+    fn volume_of_loft(profiles: &[Face], smooth: bool) -> f64 {
+        let tolerance = Tolerance::from_scalar(0.001).unwrap();
+        let solid =
+            super::loft(profiles, smooth, tolerance, Color([255, 0, 0, 255]));
+
+        let faces = solid.into_faces().collect();
+        let mesh = triangulate(faces, tolerance, &mut DebugInfo::new());
+        volume(&mesh).into_f64()
+    }
+
+    #[test]
+    fn ruled() {
+        // A frustum
+        let volume = volume_of_loft(&[square(2., 0.), square(1., 1.)], false);
+        assert!((volume - 7. / 3.).abs() < 1e-9, "{volume}");
+
+        // Lofting downwards
+        let volume = volume_of_loft(&[square(2., 1.), square(1., 0.)], false);
+        assert!((volume - 7. / 3.).abs() < 1e-9, "{volume}");
+    }
+
+    #[test]
+    fn smooth() {
+        // The profiles line up, so the smooth surface is flat.
+        let profiles = [square(1., 0.), square(1., 1.), square(1., 2.)];
+        let volume = volume_of_loft(&profiles, true);
+        assert!((volume - 2.).abs() < 1e-9, "{volume}");
+
+        // With a narrower profile in the middle, the smooth surface bulges
+        // less than the ruled one.
+        let profiles = [square(2., 0.), square(1., 1.), square(2., 2.)];
+        let ruled = volume_of_loft(&profiles, false);
+        let smooth = volume_of_loft(&profiles, true);
+        assert!(smooth < ruled, "{smooth} >= {ruled}");
+    }
+
+    #[test]
+    fn different_number_of_points() {
+        let surface = Surface::xy_plane();
+        let circle = Face::new(surface.clone())
+            .with_exteriors([Cycle::new(surface.clone())
+                .with_edges([Edge::build().circle_from_radius(Scalar::ONE)])])
+            .translate([0., 0., 1.]);
+
+        // The volume is somewhere between that of the two prisms.
+        let volume = volume_of_loft(&[square(2., 0.), circle], false);
+        assert!(volume > std::f64::consts::PI && volume < 4., "{volume}");
+    }
+}
@@ -0,0 +1,86 @@
+use fj_interop::mesh::Mesh;
+use fj_math::{Point, Scalar, Vector};
+
+/// Compute the volume enclosed by a mesh
+///
+/// The mesh must be a closed manifold, with all triangles facing outward. For
+/// other meshes, the result is meaningless.
+pub fn volume(mesh: &Mesh<Point<3>>) -> Scalar {
+    tetrahedra(mesh).fold(Scalar::ZERO, |sum, (volume, _)| sum + volume)
+}
+
+/// Compute the center of mass of the volume enclosed by a mesh
+///
+/// Assumes uniform density. Like [`volume`], this requires a closed manifold.
+/// If the mesh doesn't enclose any volume, the origin is returned.
+pub fn center_of_mass(mesh: &Mesh<Point<3>>) -> Point<3> {
+    let (volume, moment) = tetrahedra(mesh).fold(
+        (Scalar::ZERO, Vector::from([0., 0., 0.])),
+        |(volume, moment), (v, centroid)| {
+            (volume + v, moment + centroid.coords * v)
+        },
+    );
+
+    if volume == Scalar::ZERO {
+        return Point::origin();
+    }
+
+    Point::origin() + moment / volume
+}
+
+/// The signed volume and centroid of the tetrahedra that make up a mesh
+///
+/// Each triangle forms a tetrahedron with the origin. The volume of the
+/// tetrahedron is negative, if the triangle faces towards the origin, so the
+/// volume outside of the mesh cancels out.
+fn tetrahedra(
+    mesh: &Mesh<Point<3>>,
+) -> impl Iterator<Item = (Scalar, Point<3>)> + '_ {
+    mesh.triangles().map(|triangle| {
+        let [a, b, c] = triangle.inner.points();
+
+        let volume = a.coords.dot(&b.coords.cross(&c.coords)) / 6.;
+        let centroid = Point::origin() + (a.coords + b.coords + c.coords) / 4.;
+
+        (volume, centroid)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_interop::{fixtures::cube, mesh::Mesh};
+    use fj_math::{Point, Scalar};
+
+    use super::{center_of_mass, volume};
+
+    fn assert_near(a: Point<3>, b: Point<3>) {
+        assert!((a - b).magnitude() < Scalar::from_f64(1e-12), "{a:?} {b:?}");
+    }
+
+    #[test]
+    fn centered_cube() {
+        let mesh = cube([-0.5, -0.5, -0.5], 1.);
+
+        assert_eq!(volume(&mesh), Scalar::ONE);
+        assert_near(center_of_mass(&mesh), Point::origin());
+    }
+
+    #[test]
+    fn off_center_cube() {
+        let mesh = cube([1., 2., 3.], 2.);
+
+        assert!(
+            (volume(&mesh) - Scalar::from_f64(8.)).abs()
+                < Scalar::from_f64(1e-12)
+        );
+        assert_near(center_of_mass(&mesh), Point::from([2., 3., 4.]));
+    }
+
+    #[test]
+    fn empty_mesh() {
+        let mesh = Mesh::new();
+
+        assert_eq!(volume(&mesh), Scalar::ZERO);
+        assert_eq!(center_of_mass(&mesh), Point::origin());
+    }
+}
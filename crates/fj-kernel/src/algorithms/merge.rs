@@ -0,0 +1,224 @@
+use std::collections::{BTreeMap, HashMap};
+
+use fj_math::{Line, Point, Scalar};
+
+use crate::objects::{
+    Curve, CurveKind, Cycle, Edge, Face, GlobalCurve, GlobalVertex, Vertex,
+    VerticesOfEdge,
+};
+
+/// Merges points that coincide within a distance
+///
+/// Every point that is passed to [`PointMerger::merge`] is replaced by a point
+/// that was merged before and is within the distance, if there is one. This makes points that are supposed to be the same actually equal, so
+/// code that processes them can compare them exactly.
+pub struct PointMerger<const D: usize> {
+    distance: Scalar,
+    points: Vec<Point<D>>,
+
+    // Points are sorted into a grid with cells the size of the distance, so
+    // only the neighboring cells need to be searched for points to merge with.
+    grid: HashMap<[i64; D], Vec<usize>>,
+}
+
+impl<const D: usize> PointMerger<D> {
+    /// Create a merger that merges points within the given distance
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the distance is not positive.
+    pub fn new(distance: impl Into<Scalar>) -> Self {
+        let distance = distance.into();
+        assert!(
+            distance > Scalar::ZERO,
+            "Distance for merging points must be positive"
+        );
+
+        Self {
+            distance,
+            points: Vec::new(),
+            grid: HashMap::new(),
+        }
+    }
+
+    /// Merge a point with the ones that were merged before
+    ///
+    /// Returns the point it was merged with, or the point itself, if it wasn't
+    /// close to any other.
+    pub fn merge(&mut self, point: impl Into<Point<D>>) -> Point<D> {
+        let point = point.into();
+        let cell = point
+            .coords
+            .components
+            .map(|c| (c / self.distance).into_f64().floor() as i64);
+
+        // Go through the 3^D cells around the point's cell, by counting in
+        // base 3.
+        for i in 0..3_usize.pow(D as u32) {
+            let mut neighbor = cell;
+            let mut digits = i;
+            for c in &mut neighbor {
+                *c += (digits % 3) as i64 - 1;
+                digits /= 3;
+            }
+
+            let close = self.grid.get(&neighbor).and_then(|indices| {
+                indices
+                    .iter()
+                    .map(|&index| self.points[index])
+                    .find(|other| (*other - point).magnitude() <= self.distance)
+            });
+            if let Some(close) = close {
+                return close;
+            }
+        }
+
+        self.grid.entry(cell).or_default().push(self.points.len());
+        self.points.push(point);
+
+        point
+    }
+}
+
+/// Merge the vertices and straight edges of some faces
+///
+/// Global vertices that are within `distance` of each other are replaced by a
+/// single one. Afterwards, straight edges that connect the same vertices share
+/// a single global curve, regardless of their direction.
+///
+/// Straight edges are rebuilt to go exactly through their merged vertices.
+/// Vertices of curved edges are moved to where they were merged, so they might
+/// no longer lie exactly on their curves. `distance` should be small enough for
+/// that not to matter.
+///
+/// Faces that are represented by triangles are returned unchanged.
+pub fn merge_vertices(
+    faces: impl IntoIterator<Item = Face>,
+    distance: impl Into<Scalar>,
+) -> Vec<Face> {
+    let mut vertices = PointMerger::new(distance);
+    let mut curves = BTreeMap::new();
+
+    let mut merge_cycle = |cycle: &Cycle| {
+        let edges = cycle.edges().map(|edge| {
+            let vertices = (*edge.vertices()).map(|vertex| {
+                let position = vertices.merge(vertex.global().position());
+                Vertex::new(
+                    vertex.position(),
+                    GlobalVertex::from_position(position),
+                )
+            });
+
+            match (edge.curve().kind(), vertices.get()) {
+                (CurveKind::Line(_), Some([a, b])) => {
+                    merge_line(edge, [*a, *b], &mut curves)
+                }
+                _ => Edge::new(*edge.curve(), vertices),
+            }
+        });
+
+        Cycle::new(cycle.surface().clone()).with_edges(edges)
+    };
+
+    faces
+        .into_iter()
+        .map(|face| {
+            if face.triangles().is_some() {
+                return face;
+            }
+
+            let exteriors: Vec<_> =
+                face.exteriors().map(&mut merge_cycle).collect();
+            let interiors: Vec<_> =
+                face.interiors().map(&mut merge_cycle).collect();
+
+            Face::new(face.surface().clone())
+                .with_exteriors(exteriors)
+                .with_interiors(interiors)
+                .with_color(face.color())
+        })
+        .collect()
+}
+
+/// Rebuild a straight edge between its merged vertices
+///
+/// The first edge between two vertices defines the global curve. Later ones
+/// reuse it.
+fn merge_line(
+    edge: &Edge,
+    [a, b]: [Vertex; 2],
+    curves: &mut BTreeMap<[GlobalVertex; 2], (GlobalCurve, [Point<1>; 2])>,
+) -> Edge {
+    let [global_a, global_b] = [*a.global(), *b.global()];
+
+    let (global, [t_a, t_b]) = match curves.get(&[global_b, global_a]) {
+        Some(&(global, [t_b, t_a])) => (global, [t_a, t_b]),
+        None => *curves.entry([global_a, global_b]).or_insert_with(|| {
+            let global = GlobalCurve::from_kind(CurveKind::line_from_points(
+                [global_a, global_b].map(|vertex| vertex.position()),
+            ));
+            (global, [Point::from([0.]), Point::from([1.])])
+        }),
+    };
+
+    // The local curve needs to share its parametrization with the global one.
+    let [local_a, local_b] = [a, b].map(|vertex| {
+        edge.curve()
+            .kind()
+            .point_from_curve_coords(vertex.position())
+    });
+    let direction = (local_b - local_a) / (t_b.t - t_a.t);
+    let local = CurveKind::Line(Line::from_origin_and_direction(
+        local_a - direction * t_a.t,
+        direction,
+    ));
+
+    Edge::new(
+        Curve::new(local, global),
+        VerticesOfEdge::from_vertices([
+            Vertex::new(t_a, global_a),
+            Vertex::new(t_b, global_b),
+        ]),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::Point;
+
+    use crate::{
+        iter::ObjectIters,
+        objects::{Face, Surface},
+    };
+
+    use super::{merge_vertices, PointMerger};
+
+    #[test]
+    fn merge_points() {
+        let mut merger = PointMerger::new(0.1);
+
+        assert_eq!(merger.merge([0., 0.]), Point::from([0., 0.]));
+        assert_eq!(merger.merge([0.05, -0.05]), Point::from([0., 0.]));
+        assert_eq!(merger.merge([0.2, 0.]), Point::from([0.2, 0.]));
+        assert_eq!(merger.merge([0.15, 0.]), Point::from([0.2, 0.]));
+        assert_eq!(merger.merge([-0.12, 0.]), Point::from([-0.12, 0.]));
+    }
+
+    #[test]
+    fn merge_faces() {
+        let surface = Surface::xy_plane();
+
+        let a = Face::build(surface.clone())
+            .polygon_from_points([[0., 0.], [1., 0.], [1., 1.]])
+            .into_face();
+        let b = Face::build(surface.clone())
+            .polygon_from_points([[1e-9, 1e-9], [1., 1. + 1e-9], [0., 1.]])
+            .into_face();
+
+        let merged = merge_vertices([a, b], 1e-6);
+        assert_eq!(merged.global_vertex_iter().count(), 4);
+
+        // The diagonal of the square is shared by both faces.
+        assert_eq!(merged.global_curve_iter().count(), 5);
+    }
+}
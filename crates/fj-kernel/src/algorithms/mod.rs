@@ -4,7 +4,20 @@
 //! on their respective purpose.
 
 mod approx;
+mod boolean;
+mod chamfer;
+mod classify;
+mod decimate;
+mod loft;
+mod mass;
+mod merge;
+mod orient;
 mod reverse;
+mod revolve;
+mod self_intersection;
+mod shell;
+mod slice;
+mod smooth;
 mod sweep;
 mod transform;
 mod triangulate;
@@ -12,9 +25,25 @@ mod triangulate;
 pub mod intersect;
 
 pub use self::{
-    approx::{CycleApprox, FaceApprox, InvalidTolerance, Tolerance},
-    reverse::reverse_face,
-    sweep::sweep,
+    approx::{
+        approx_nurbs_surface, CycleApprox, FaceApprox, InvalidTolerance,
+        Quality, Tolerance,
+    },
+    boolean::{difference, difference_2d, intersection, union},
+    chamfer::{chamfer, ChamferDistance, ChamferError},
+    classify::{PointClassification, PointClassifier},
+    decimate::{decimate, decimate_with_max_error},
+    loft::loft,
+    mass::{center_of_mass, volume},
+    merge::{merge_vertices, PointMerger},
+    orient::orient_consistently,
+    reverse::{reverse_cycle, reverse_face},
+    revolve::{revolve, sweep_helix},
+    self_intersection::{sketch_self_intersections, solid_self_intersections},
+    shell::{shell, ShellError},
+    slice::{slice, stitch_segments},
+    smooth::{laplacian_smooth, taubin_smooth},
+    sweep::{sweep, sweep_along_path},
     transform::{transform_faces, TransformObject},
-    triangulate::triangulate,
+    triangulate::{triangulate, TriangulationCache},
 };
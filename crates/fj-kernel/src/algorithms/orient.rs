@@ -0,0 +1,142 @@
+use std::collections::VecDeque;
+
+use fj_interop::mesh::{Index, Mesh};
+use fj_math::Point;
+
+/// Make the winding of the triangles in a mesh consistent
+///
+/// Two triangles that share an edge are consistent, if they traverse that edge
+/// in opposite directions. Starting from a seed triangle, the orientation is
+/// propagated across shared edges, and triangles that disagree with their
+/// neighbor are flipped.
+///
+/// Each connected component of the mesh is processed separately. Within a
+/// component, the orientation that most triangles already have wins, so a few
+/// inverted triangles are flipped, regardless of which one is the seed.
+pub fn orient_consistently(mesh: &mut Mesh<Point<3>>) {
+    let indices: Vec<_> = mesh.indices().collect();
+    let triangles: Vec<_> = indices.chunks(3).collect();
+    let adjacency = mesh.adjacency();
+
+    let mut flip = vec![None; triangles.len()];
+
+    for seed in 0..triangles.len() {
+        if flip[seed].is_some() {
+            continue;
+        }
+
+        flip[seed] = Some(false);
+        let mut component = vec![seed];
+        let mut queue = VecDeque::from([seed]);
+
+        while let Some(t) = queue.pop_front() {
+            let flip_t = flip[t] == Some(true);
+
+            for [a, b] in directed_edges(triangles[t]) {
+                for &neighbor in adjacency.edge_triangles(a, b) {
+                    if flip[neighbor].is_some() {
+                        continue;
+                    }
+
+                    // The neighbor needs to be flipped, if it ends up
+                    // traversing the edge in the same direction as `t`.
+                    let same_direction =
+                        directed_edges(triangles[neighbor]).contains(&[a, b]);
+                    flip[neighbor] = Some(same_direction != flip_t);
+
+                    component.push(neighbor);
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+
+        let flipped =
+            component.iter().filter(|&&t| flip[t] == Some(true)).count();
+        if flipped * 2 > component.len() {
+            for t in component {
+                flip[t] = flip[t].map(|flip| !flip);
+            }
+        }
+    }
+
+    let mut oriented = Mesh::new();
+    for (triangle, flip) in mesh.triangles().zip(flip) {
+        let [a, b, c] = triangle.inner.points();
+        let points = if flip == Some(true) {
+            [a, c, b]
+        } else {
+            [a, b, c]
+        };
+
+        oriented.push_triangle(points, triangle.color);
+    }
+
+    *mesh = oriented;
+}
+
+fn directed_edges(triangle: &[Index]) -> [[Index; 2]; 3] {
+    [0, 1, 2].map(|i| [triangle[i], triangle[(i + 1) % 3]])
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_interop::mesh::{Color, Mesh};
+    use fj_math::Point;
+
+    use super::orient_consistently;
+
+    /// Two tetrahedra that don't touch, with outward-facing triangles
+    fn tetrahedra() -> Vec<[Point<3>; 3]> {
+        let mut triangles = Vec::new();
+
+        for offset in [0., 5.] {
+            let [a, b, c, d] =
+                [[0., 0., 0.], [1., 0., 0.], [0., 1., 0.], [0., 0., 1.]]
+                    .map(|[x, y, z]| Point::from([x + offset, y, z]));
+
+            triangles.extend([[a, c, b], [a, b, d], [b, c, d], [a, d, c]]);
+        }
+
+        triangles
+    }
+
+    fn mesh(triangles: &[[Point<3>; 3]]) -> Mesh<Point<3>> {
+        let mut mesh = Mesh::new();
+        for &triangle in triangles {
+            mesh.push_triangle(triangle, Color::default());
+        }
+        mesh
+    }
+
+    fn points(mesh: &Mesh<Point<3>>) -> Vec<[Point<3>; 3]> {
+        mesh.triangles().map(|t| t.inner.points()).collect()
+    }
+
+    #[test]
+    fn flipped_triangle_is_corrected() {
+        let expected = tetrahedra();
+
+        // Flip one triangle in each component, including the first one, which
+        // would be the seed.
+        for i in [0, 6] {
+            let mut triangles = expected.clone();
+            let [a, b, c] = triangles[i];
+            triangles[i] = [a, c, b];
+
+            let mut mesh = mesh(&triangles);
+            orient_consistently(&mut mesh);
+
+            assert_eq!(points(&mesh), expected);
+        }
+    }
+
+    #[test]
+    fn consistent_mesh_is_unchanged() {
+        let expected = tetrahedra();
+
+        let mut mesh = mesh(&expected);
+        orient_consistently(&mut mesh);
+
+        assert_eq!(points(&mesh), expected);
+    }
+}
@@ -1,6 +1,6 @@
-use fj_math::{Circle, Line, Point, Vector};
+use fj_math::{Circle, Ellipse, Line, Parabola, Point, Vector};
 
-use crate::objects::{Curve, CurveKind, Cycle, Edge, Face};
+use crate::objects::{Curve, CurveKind, Cycle, Edge, Face, GlobalCurve};
 
 /// Reverse the direction of a face
 pub fn reverse_face(face: &Face) -> Face {
@@ -8,7 +8,7 @@ pub fn reverse_face(face: &Face) -> Face {
         panic!("Reversing tri-rep faces is not supported");
     }
 
-    let surface = face.surface().reverse();
+    let surface = face.surface().clone().reverse();
 
     let exteriors = reverse_local_coordinates_in_cycle(face.exteriors());
     let interiors = reverse_local_coordinates_in_cycle(face.interiors());
@@ -19,11 +19,33 @@ pub fn reverse_face(face: &Face) -> Face {
         .with_color(face.color())
 }
 
+/// Reverse the direction of a cycle
+///
+/// The cycle stays on the same surface, but its edges are traversed in the
+/// opposite order and direction. This is what turns the exterior of a face into
+/// a hole in another one.
+pub fn reverse_cycle(cycle: &Cycle) -> Cycle {
+    let mut edges: Vec<_> = cycle
+        .edges()
+        .map(|edge| {
+            let curve = Curve::new(
+                edge.curve().kind().reverse(),
+                GlobalCurve::from_kind(edge.curve().global().kind().reverse()),
+            );
+
+            Edge::new(curve, edge.vertices().reverse())
+        })
+        .collect();
+    edges.reverse();
+
+    Cycle::new(cycle.surface().clone()).with_edges(edges)
+}
+
 fn reverse_local_coordinates_in_cycle<'r>(
     cycles: impl IntoIterator<Item = &'r Cycle> + 'r,
 ) -> impl Iterator<Item = Cycle> + 'r {
     cycles.into_iter().map(|cycle| {
-        let surface = cycle.surface().reverse();
+        let surface = cycle.surface().clone().reverse();
 
         let edges = cycle.edges().map(|edge| {
             let curve = {
@@ -39,6 +61,17 @@ fn reverse_local_coordinates_in_cycle<'r>(
 
                         CurveKind::Circle(Circle::new(center, a, b))
                     }
+                    CurveKind::Ellipse(ellipse) => {
+                        let center = Point::from([
+                            ellipse.center().u,
+                            -ellipse.center().v,
+                        ]);
+
+                        let a = Vector::from([ellipse.a().u, -ellipse.a().v]);
+                        let b = Vector::from([ellipse.b().u, -ellipse.b().v]);
+
+                        CurveKind::Ellipse(Ellipse::new(center, a, b))
+                    }
                     CurveKind::Line(line) => {
                         let origin =
                             Point::from([line.origin().u, -line.origin().v]);
@@ -51,6 +84,17 @@ fn reverse_local_coordinates_in_cycle<'r>(
                             origin, direction,
                         ))
                     }
+                    CurveKind::Parabola(parabola) => {
+                        let origin = Point::from([
+                            parabola.origin().u,
+                            -parabola.origin().v,
+                        ]);
+
+                        let a = Vector::from([parabola.a().u, -parabola.a().v]);
+                        let b = Vector::from([parabola.b().u, -parabola.b().v]);
+
+                        CurveKind::Parabola(Parabola::new(origin, a, b))
+                    }
                 };
 
                 Curve::new(local, *edge.curve().global())
@@ -67,7 +111,9 @@ fn reverse_local_coordinates_in_cycle<'r>(
 mod tests {
     use pretty_assertions::assert_eq;
 
-    use crate::objects::{Face, Surface};
+    use fj_math::Point;
+
+    use crate::objects::{Cycle, Face, Surface};
 
     #[test]
     fn reverse_face() {
@@ -87,4 +133,38 @@ mod tests {
 
         assert_eq!(expected, reversed);
     }
+
+    #[test]
+    fn reverse_cycle() {
+        let surface = Surface::xy_plane();
+        let original = Cycle::build(surface).polygon_from_points([
+            [0., 0.],
+            [1., 0.],
+            [0., 1.],
+        ]);
+
+        let reversed = super::reverse_cycle(&original);
+
+        let vertices: Vec<_> = reversed
+            .vertices()
+            .map(|vertex| vertex.global().position())
+            .collect();
+        assert_eq!(
+            vertices,
+            [[0., 0., 0.], [0., 1., 0.], [1., 0., 0.]].map(Point::from)
+        );
+
+        // The local coordinates of the vertices still match their positions.
+        for edge in reversed.edges() {
+            for vertex in edge.vertices().iter() {
+                assert_eq!(
+                    edge.curve()
+                        .global()
+                        .kind()
+                        .point_from_curve_coords(vertex.position()),
+                    vertex.global().position()
+                );
+            }
+        }
+    }
 }
@@ -0,0 +1,374 @@
+use fj_interop::mesh::Color;
+use fj_math::{Point, Scalar, Transform, Triangle, Vector};
+
+use crate::{
+    iter::ObjectIters,
+    objects::{Cycle, Face, Sketch, Solid, Surface},
+};
+
+use super::{
+    chamfer::normal,
+    sweep::{create_bottom_faces, create_top_face},
+    CycleApprox, Tolerance,
+};
+
+/// Create a solid by revolving a sketch around an axis
+///
+/// The sketch is rotated counterclockwise around the axis (as seen from its
+/// tip) by `angle`, which is given in radians. A negative angle rotates it
+/// clockwise. If the angle is a full revolution or more, the result is closed
+/// around the axis. Otherwise, the sketch and its rotated copy become planar
+/// faces that cap off the ends.
+///
+/// The sketch must not cross the axis, but it may touch it.
+pub fn revolve(
+    source: Sketch,
+    axis_origin: impl Into<Point<3>>,
+    axis: impl Into<Vector<3>>,
+    angle: impl Into<Scalar>,
+    tolerance: Tolerance,
+    color: Color,
+) -> Solid {
+    let angle = angle.into();
+
+    let is_full_revolution = angle.abs() >= Scalar::PI * 2.;
+    let angle = if is_full_revolution {
+        Scalar::PI * 2.
+    } else {
+        angle
+    };
+
+    screw(
+        source,
+        axis_origin.into(),
+        axis.into(),
+        angle,
+        Scalar::ZERO,
+        tolerance,
+        color,
+    )
+}
+
+/// Create a solid by sweeping a sketch along a helix
+///
+/// The sketch is rotated around the axis, like with [`revolve`], while moving
+/// along the axis by `pitch` per full revolution. Threads and springs can be
+/// created like this, by placing the sketch in a plane that contains the axis.
+///
+/// With a positive pitch, the result is a right-handed helix, if the angle is
+/// positive too. The sketch must not cross the axis, and the angle and pitch
+/// must be chosen such that the result doesn't intersect itself.
+pub fn sweep_helix(
+    source: Sketch,
+    axis_origin: impl Into<Point<3>>,
+    axis: impl Into<Vector<3>>,
+    pitch: impl Into<Scalar>,
+    angle: impl Into<Scalar>,
+    tolerance: Tolerance,
+    color: Color,
+) -> Solid {
+    screw(
+        source,
+        axis_origin.into(),
+        axis.into(),
+        angle.into(),
+        pitch.into() / (Scalar::PI * 2.),
+        tolerance,
+        color,
+    )
+}
+
+/// Rotate a sketch around an axis while moving it along that axis
+///
+/// `advance` is the distance moved per radian.
+fn screw(
+    source: Sketch,
+    origin: Point<3>,
+    axis: Vector<3>,
+    angle: Scalar,
+    advance: Scalar,
+    tolerance: Tolerance,
+    color: Color,
+) -> Solid {
+    let axis = axis.normalize();
+
+    // Only a revolution in place can close up on itself.
+    let is_full_revolution =
+        angle.abs() >= Scalar::PI * 2. && advance == Scalar::ZERO;
+
+    if angle == Scalar::ZERO {
+        return Solid::new();
+    }
+
+    let approximations: Vec<Vec<_>> = source
+        .face_iter()
+        .map(|face| {
+            face.all_cycles()
+                .flat_map(|cycle| cycle.edges())
+                .map(|edge| {
+                    // The surface doesn't matter, as only the global form of
+                    // the approximation is used.
+                    let cycle =
+                        Cycle::new(Surface::xy_plane()).with_edges([*edge]);
+                    CycleApprox::new(&cycle, tolerance).segments()
+                })
+                .collect()
+        })
+        .collect();
+
+    let radius = approximations
+        .iter()
+        .flatten()
+        .flatten()
+        .flat_map(|segment| segment.points())
+        .map(|point| distance_to_axis(point, origin, axis))
+        .fold(Scalar::ZERO, Scalar::max);
+    let steps = number_of_steps(angle, radius, tolerance, is_full_revolution);
+
+    let rotations: Vec<_> = (0..=steps)
+        .map(|i| {
+            let angle = angle * i as f64 / steps as f64;
+            Transform::translation(origin.coords + axis * advance * angle)
+                * Transform::rotation(axis * angle)
+                * Transform::translation(-origin.coords)
+        })
+        .collect();
+
+    let mut target = Vec::new();
+
+    for (face, edges) in source.face_iter().zip(approximations) {
+        // Whether the face moves in the direction it faces, which decides the
+        // orientation of all faces created from it.
+        let center = {
+            let points: Vec<_> = edges
+                .iter()
+                .flatten()
+                .map(|segment| segment.points()[0])
+                .collect();
+            let sum = points
+                .iter()
+                .fold(Vector::from([0., 0., 0.]), |sum, point| {
+                    sum + point.coords
+                });
+            Point::origin() + sum / points.len().max(1) as f64
+        };
+        let motion = (axis.cross(&(center - origin)) + axis * advance) * angle;
+        let is_revolve_along_negative_direction =
+            motion.dot(&normal(face.surface())) < Scalar::ZERO;
+
+        if !is_full_revolution {
+            create_bottom_faces(
+                face,
+                is_revolve_along_negative_direction,
+                &mut target,
+            );
+            create_top_face(
+                face.clone(),
+                &rotations[steps],
+                is_revolve_along_negative_direction,
+                &mut target,
+            );
+        }
+
+        for segments in edges {
+            let mut side_face: Vec<(Triangle<3>, _)> = Vec::new();
+
+            for segment in segments {
+                let [a, b] = segment.points().map(|point| {
+                    // Points on the axis only move along it. Rotating them
+                    // anyway would introduce rounding errors.
+                    if distance_to_axis(point, origin, axis) == Scalar::ZERO {
+                        return (0..=steps)
+                            .map(|i| {
+                                let angle = angle * i as f64 / steps as f64;
+                                point + axis * advance * angle
+                            })
+                            .collect();
+                    }
+
+                    let mut ring: Vec<_> = rotations
+                        .iter()
+                        .map(|rotation| rotation.transform_point(&point))
+                        .collect();
+                    if is_full_revolution {
+                        ring[steps] = point;
+                    }
+
+                    ring
+                });
+
+                for i in 0..steps {
+                    let [v0, v1, v2, v3] = [a[i], b[i], b[i + 1], a[i + 1]];
+
+                    let triangles = if is_revolve_along_negative_direction {
+                        [[v0, v2, v1], [v0, v3, v2]]
+                    } else {
+                        [[v0, v1, v2], [v0, v2, v3]]
+                    };
+
+                    // Where the sketch touches the axis, some triangles
+                    // collapse.
+                    for triangle in triangles {
+                        if let Some(triangle) = Triangle::from_points(triangle)
+                        {
+                            side_face.push((triangle, color));
+                        }
+                    }
+                }
+            }
+
+            target.push(Face::from_triangles(side_face));
+        }
+    }
+
+    Solid::new().with_faces(target)
+}
+
+fn distance_to_axis(
+    point: Point<3>,
+    origin: Point<3>,
+    axis: Vector<3>,
+) -> Scalar {
+    (point - origin).cross(&axis).magnitude()
+}
+
+/// The number of steps, so that no point deviates from its circular path by
+/// more than the tolerance
+fn number_of_steps(
+    angle: Scalar,
+    radius: Scalar,
+    tolerance: Tolerance,
+    is_full_revolution: bool,
+) -> usize {
+    let min = if is_full_revolution { 3 } else { 1 };
+
+    let tolerance = tolerance.for_radius(radius);
+    if radius <= tolerance {
+        return min;
+    }
+
+    let max_step = (Scalar::ONE - tolerance / radius).acos() * 2.;
+    let steps = (angle.abs() / max_step).ceil().into_u64() as usize;
+
+    steps.max(min)
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_interop::{debug::DebugInfo, mesh::Color};
+    use fj_math::Scalar;
+
+    use crate::{
+        algorithms::{triangulate, volume, Tolerance},
+        objects::{Face, Sketch, Solid, Surface},
+    };
+
+    fn revolve(points: [[f64; 2]; 4], angle: f64) -> (Solid, f64) {
+        let tolerance = Tolerance::from_scalar(0.0001).unwrap();
+
+        let face = Face::build(Surface::xy_plane()).polygon_from_points(points);
+        let sketch = Sketch::new().with_faces([face]);
+
+        let solid = super::revolve(
+            sketch,
+            [0., 0., 0.],
+            [0., 1., 0.],
+            angle,
+            tolerance,
+            Color([255, 0, 0, 255]),
+        );
+
+        let faces = solid.clone().into_faces().collect();
+        let mesh = triangulate(faces, tolerance, &mut DebugInfo::new());
+
+        (solid, volume(&mesh).into_f64())
+    }
+
+    #[test]
+    fn full_revolution() {
+        let pi = std::f64::consts::PI;
+
+        // A ring with a rectangular cross-section
+        let (solid, volume) =
+            revolve([[1., 0.], [2., 0.], [2., 1.], [1., 1.]], pi * 2.);
+        assert_eq!(solid.faces().count(), 4);
+        assert!((volume - pi * 3.).abs() < 0.001, "{volume}");
+
+        // A cylinder, from a sketch that touches the axis
+        let (_, volume) =
+            revolve([[0., 0.], [1., 0.], [1., 1.], [0., 1.]], pi * 2.);
+        assert!((volume - pi).abs() < 0.001, "{volume}");
+    }
+
+    #[test]
+    fn partial_revolution() {
+        let pi = std::f64::consts::PI;
+
+        for angle in [pi / 2., -pi / 2.] {
+            let (solid, volume) =
+                revolve([[1., 0.], [2., 0.], [2., 1.], [1., 1.]], angle);
+
+            // The side faces, plus one cap on either end
+            assert_eq!(solid.faces().count(), 6);
+            assert!((volume - pi * 3. / 4.).abs() < 0.001, "{volume}");
+
+            // The end cap is perpendicular to the sketch.
+            let end_cap = solid.faces().find(|face| {
+                let vertices: Vec<_> = face
+                    .all_cycles()
+                    .flat_map(|cycle| cycle.vertices())
+                    .map(|vertex| vertex.global().position())
+                    .collect();
+
+                !vertices.is_empty()
+                    && vertices.iter().all(|position| {
+                        position.x.abs() < Scalar::from_f64(1e-12)
+                            && position.z * -angle > Scalar::ZERO
+                    })
+            });
+            assert!(end_cap.is_some());
+        }
+    }
+
+    #[test]
+    fn helix() {
+        let pi = std::f64::consts::PI;
+        let tolerance = Tolerance::from_scalar(0.0001).unwrap();
+
+        // A square cross-section in the xz-plane, going around the z-axis
+        let face = Face::build(Surface::xz_plane()).polygon_from_points([
+            [1.75, -0.25],
+            [2.25, -0.25],
+            [2.25, 0.25],
+            [1.75, 0.25],
+        ]);
+        let sketch = Sketch::new().with_faces([face]);
+
+        let solid = super::sweep_helix(
+            sketch,
+            [0., 0., 0.],
+            [0., 0., 1.],
+            1.,
+            pi * 4.,
+            tolerance,
+            Color([255, 0, 0, 255]),
+        );
+
+        // The end cap has moved up by two turns.
+        assert!(solid.faces().any(|face| face
+            .all_cycles()
+            .flat_map(|cycle| cycle.vertices())
+            .all(|vertex| (vertex.global().position().z.into_f64() - 2.)
+                .abs()
+                <= 0.25 + 1e-9)
+            && face.all_cycles().next().is_some()));
+
+        // The volume is the same as that of the revolution, as moving along
+        // the axis doesn't change it.
+        let faces = solid.into_faces().collect();
+        let mesh = triangulate(faces, tolerance, &mut DebugInfo::new());
+        let volume = volume(&mesh).into_f64();
+        let expected = 0.25 * 2. * pi * 2. * 2.;
+        assert!((volume - expected).abs() < 0.01, "{volume}");
+    }
+}
@@ -0,0 +1,237 @@
+use std::collections::BTreeSet;
+
+use fj_interop::debug::DebugInfo;
+use fj_math::{
+    Aabb, Point, Scalar, Segment, SegmentIntersection, Triangle, Vector,
+};
+
+use crate::objects::{Face, Sketch, Solid};
+
+use super::{triangulate, CycleApprox, Tolerance};
+
+/// Find the places where the profile of a sketch intersects itself
+///
+/// Approximates the cycles of all faces, and returns the pairs of segments that
+/// intersect, in the surface coordinates of the sketch. Non-neighboring
+/// segments of the same cycle may not even touch, while segments of different
+/// cycles only count, if they cross each other.
+pub fn sketch_self_intersections(
+    sketch: &Sketch,
+    tolerance: Tolerance,
+) -> Vec<[Segment<2>; 2]> {
+    let cycles: Vec<_> = sketch
+        .faces()
+        .filter(|face| face.triangles().is_none())
+        .flat_map(|face| face.all_cycles())
+        .map(|cycle| {
+            let mut points: Vec<_> = CycleApprox::new(cycle, tolerance)
+                .points
+                .into_iter()
+                .map(|point| *point.local_form())
+                .collect();
+            if points.len() > 1 && points.first() == points.last() {
+                points.pop();
+            }
+
+            (0..points.len())
+                .map(|i| [points[i], points[(i + 1) % points.len()]])
+                // A cycle that consists of a single point has no segments.
+                .filter(|[a, b]| a != b)
+                .map(Segment::from_points)
+                .collect::<Vec<_>>()
+        })
+        .collect();
+
+    let mut intersections = Vec::new();
+
+    for (i, a) in cycles.iter().enumerate() {
+        for (k, &s) in a.iter().enumerate() {
+            for (l, &t) in a.iter().enumerate().skip(k + 2) {
+                // The first and last segments are neighbors too.
+                if k == 0 && l == a.len() - 1 {
+                    continue;
+                }
+
+                if s.intersect(&t).is_some() {
+                    intersections.push([s, t]);
+                }
+            }
+        }
+
+        for b in &cycles[i + 1..] {
+            for &s in a {
+                for &t in b {
+                    if segments_cross(&s, &t) {
+                        intersections.push([s, t]);
+                    }
+                }
+            }
+        }
+    }
+
+    intersections
+}
+
+/// Find the faces of a solid that intersect each other
+///
+/// Triangulates each face, and returns the pairs of faces whose triangles
+/// pierce each other. Faces that just touch, as neighboring faces do, don't
+/// count. Neither do faces that overlap within the same plane.
+pub fn solid_self_intersections(
+    solid: &Solid,
+    tolerance: Tolerance,
+) -> Vec<[Face; 2]> {
+    let faces: Vec<_> = solid.faces().cloned().collect();
+
+    let mut triangles = Vec::new();
+    for (i, face) in faces.iter().enumerate() {
+        let mesh =
+            triangulate(vec![face.clone()], tolerance, &mut DebugInfo::new());
+
+        for triangle in mesh.triangles() {
+            let aabb = Aabb::<3>::from_points(triangle.inner.points());
+            triangles.push((i, triangle.inner, aabb));
+        }
+    }
+
+    // Sort by the start of the bounding boxes along one axis, so only triangles
+    // whose boxes overlap along that axis need to be compared.
+    triangles.sort_by_key(|(_, _, aabb)| aabb.min.x);
+
+    let mut pairs = BTreeSet::new();
+    for (k, (i, a, a_aabb)) in triangles.iter().enumerate() {
+        for (j, b, b_aabb) in &triangles[k + 1..] {
+            if b_aabb.min.x > a_aabb.max.x {
+                break;
+            }
+            if i == j || pairs.contains(&[*i.min(j), *i.max(j)]) {
+                continue;
+            }
+            if a_aabb.intersection(b_aabb).is_none() {
+                continue;
+            }
+
+            if triangles_pierce(a, b, tolerance.inner()) {
+                pairs.insert([*i.min(j), *i.max(j)]);
+            }
+        }
+    }
+
+    pairs
+        .into_iter()
+        .map(|pair| pair.map(|i| faces[i].clone()))
+        .collect()
+}
+
+/// Determine whether two segments cross, with both ends of each on opposite
+/// sides of the other
+fn segments_cross(a: &Segment<2>, b: &Segment<2>) -> bool {
+    // `Segment::intersect` returns end points exactly, so any other point
+    // means that the segments cross.
+    match a.intersect(b) {
+        Some(SegmentIntersection::Point(point)) => !a
+            .points()
+            .into_iter()
+            .chain(b.points())
+            .any(|end| end == point),
+        _ => false,
+    }
+}
+
+/// Determine whether an edge of either triangle goes through the other one
+///
+/// The edge must start and end farther than `margin` from the plane of the
+/// other triangle, and go through it farther than `margin` from its edges.
+fn triangles_pierce(a: &Triangle<3>, b: &Triangle<3>, margin: Scalar) -> bool {
+    edge_pierces(a, b, margin) || edge_pierces(b, a, margin)
+}
+
+fn edge_pierces(a: &Triangle<3>, b: &Triangle<3>, margin: Scalar) -> bool {
+    let normal = match b.normal() {
+        Some(normal) => normal,
+        None => return false,
+    };
+    let points = b.points();
+    let distance = |point: Point<3>| (point - points[0]).dot(&normal);
+
+    let [p0, p1, p2] = a.points();
+    [[p0, p1], [p1, p2], [p2, p0]].into_iter().any(|[p, q]| {
+        let [dp, dq] = [distance(p), distance(q)];
+        let crosses_plane =
+            (dp > margin && dq < -margin) || (dp < -margin && dq > margin);
+        if !crosses_plane {
+            return false;
+        }
+
+        let point = p + (q - p) * (dp / (dp - dq));
+        is_well_inside(point, points, normal, margin)
+    })
+}
+
+fn is_well_inside(
+    point: Point<3>,
+    [a, b, c]: [Point<3>; 3],
+    normal: Vector<3>,
+    margin: Scalar,
+) -> bool {
+    [[a, b], [b, c], [c, a]].into_iter().all(|[start, end]| {
+        let edge = end - start;
+        let distance =
+            edge.cross(&(point - start)).dot(&normal) / edge.magnitude();
+        distance > margin
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        algorithms::{sweep, Tolerance},
+        objects::{Face, Sketch, Solid, Surface},
+    };
+
+    use super::{sketch_self_intersections, solid_self_intersections};
+
+    #[test]
+    fn sketch() {
+        let tolerance = Tolerance::from_scalar(0.001).unwrap();
+        let surface = Surface::xy_plane();
+
+        let square = |[u, v]: [f64; 2]| {
+            Face::build(surface.clone())
+                .polygon_from_points([
+                    [u, v],
+                    [u + 1., v],
+                    [u + 1., v + 1.],
+                    [u, v + 1.],
+                ])
+                .into_face()
+        };
+
+        let apart =
+            Sketch::new().with_faces([square([0., 0.]), square([2., 0.])]);
+        assert!(sketch_self_intersections(&apart, tolerance).is_empty());
+
+        let overlapping =
+            Sketch::new().with_faces([square([0., 0.]), square([0.5, 0.5])]);
+        assert_eq!(sketch_self_intersections(&overlapping, tolerance).len(), 2);
+    }
+
+    #[test]
+    fn solid() {
+        let tolerance = Tolerance::from_scalar(0.001).unwrap();
+
+        let cube = Solid::build().cube_from_edge_length(1.);
+        assert!(solid_self_intersections(&cube, tolerance).is_empty());
+
+        // A face that goes right through the middle of the cube.
+        let wall = Face::build(Surface::xz_plane())
+            .polygon_from_points([[-1., -1.], [1., -1.], [1., 1.], [-1., 1.]])
+            .into_face();
+        let sketch = Sketch::new().with_faces([wall]);
+        let slab = sweep(sketch, [0., 0.01, 0.], tolerance, Default::default());
+        let solid =
+            Solid::new().with_faces(cube.into_faces().chain(slab.into_faces()));
+
+        assert!(!solid_self_intersections(&solid, tolerance).is_empty());
+    }
+}
@@ -0,0 +1,362 @@
+use std::collections::BTreeMap;
+
+use fj_math::{Point, Scalar, Vector};
+
+use crate::objects::Face;
+
+use super::{
+    chamfer::{normal, surface_coords, Polygon},
+    TransformObject,
+};
+
+/// Hollow out a solid, leaving walls of the given thickness
+///
+/// Every face is offset towards the inside of the solid by `thickness`, and
+/// the offset faces bound a cavity. The faces in `openings` are removed, and
+/// a rim between the outer and the inner wall is left in their place, opening
+/// up the cavity.
+///
+/// The faces must form a closed solid. They don't need to be oriented
+/// consistently, as the outside is determined from how they are connected. The
+/// returned faces all face away from the wall.
+///
+/// # Implementation Note
+///
+/// Only planar faces that are bounded by a single polygon without holes are
+/// supported. Where faces meet at a vertex, their offset planes must intersect
+/// in a single point, which requires at least three faces that are not
+/// parallel to each other.
+pub fn shell(
+    faces: &[Face],
+    thickness: Scalar,
+    openings: &[Face],
+) -> Result<Vec<Face>, ShellError> {
+    if thickness <= Scalar::ZERO {
+        return Err(ShellError::InvalidThickness(thickness));
+    }
+    for opening in openings {
+        if !faces.contains(opening) {
+            return Err(ShellError::OpeningNotFound);
+        }
+    }
+
+    let mut polygons = faces
+        .iter()
+        .map(|face| {
+            let polygon = Polygon::from_face(face)
+                .map_err(|_| ShellError::UnsupportedFace)?;
+            let is_opening = openings.contains(face);
+
+            Ok((polygon, is_opening))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    orient_outward(&mut polygons)?;
+
+    // The planes that the inner vertices must be on. The inner vertices of an
+    // opening stay in its plane, so the rim is flat.
+    let mut planes: BTreeMap<Point<3>, Vec<(Vector<3>, Scalar)>> =
+        BTreeMap::new();
+    for (polygon, is_opening) in &polygons {
+        let normal = normal(&polygon.surface).normalize();
+        let offset = if *is_opening { Scalar::ZERO } else { thickness };
+
+        let origin = polygon.surface.point_from_surface_coords([0., 0.]);
+        let distance = normal.dot(&origin.coords) - offset;
+
+        for &point in &polygon.points {
+            planes.entry(point).or_default().push((normal, distance));
+        }
+    }
+
+    let inner = planes
+        .into_iter()
+        .map(|(point, planes)| {
+            let inner = intersect_planes(&planes)
+                .ok_or(ShellError::UnsupportedVertex(point))?;
+            Ok((point, inner))
+        })
+        .collect::<Result<BTreeMap<_, _>, _>>()?;
+
+    let mut shell = Vec::new();
+    for (polygon, is_opening) in polygons {
+        let inner_points: Vec<_> =
+            polygon.points.iter().map(|point| inner[point]).collect();
+
+        // If the wall is thicker than the space available, the edges of the
+        // inner face end up pointing the other way.
+        let is_inverted = directed_edges(&polygon.points)
+            .zip(directed_edges(&inner_points))
+            .any(|([a, b], [c, d])| (b - a).dot(&(d - c)) <= Scalar::ZERO);
+        if is_inverted {
+            return Err(ShellError::ThicknessTooLarge);
+        }
+
+        if is_opening {
+            let outer = surface_coords(&polygon.surface, &polygon.points);
+            let inner = surface_coords(&polygon.surface, &inner_points);
+
+            let rim = Face::build(polygon.surface)
+                .polygon_from_points(outer)
+                .with_hole(inner)
+                .into_face()
+                .with_color(polygon.color);
+            shell.push(rim);
+
+            continue;
+        }
+
+        let normal = normal(&polygon.surface).normalize();
+
+        // The inner face faces the cavity, away from the outer one.
+        let mut inner_points = inner_points;
+        inner_points.reverse();
+        let inner = Polygon {
+            surface: polygon
+                .surface
+                .clone()
+                .reverse()
+                .translate(-normal * thickness),
+            points: inner_points,
+            color: polygon.color,
+        };
+
+        shell.push(polygon.into_face());
+        shell.push(inner.into_face());
+    }
+
+    Ok(shell)
+}
+
+/// Turn all polygons, so they face outward
+///
+/// The points of each polygon are put into counterclockwise order, as seen
+/// from the front of its surface.
+fn orient_outward(polygons: &mut [(Polygon, bool)]) -> Result<(), ShellError> {
+    for (polygon, _) in polygons.iter_mut() {
+        if newell_normal(&polygon.points).dot(&normal(&polygon.surface))
+            < Scalar::ZERO
+        {
+            polygon.points.reverse();
+        }
+    }
+
+    let mut edges: BTreeMap<_, Vec<_>> = BTreeMap::new();
+    for (i, (polygon, _)) in polygons.iter().enumerate() {
+        for edge in directed_edges(&polygon.points) {
+            edges.entry(edge).or_default().push(i);
+        }
+    }
+
+    // Neighboring polygons are consistent, if they traverse their shared edge
+    // in opposite directions.
+    let mut flip = vec![None; polygons.len()];
+    for seed in 0..polygons.len() {
+        if flip[seed].is_some() {
+            continue;
+        }
+        flip[seed] = Some(false);
+
+        let mut queue = vec![seed];
+        while let Some(i) = queue.pop() {
+            for [a, b] in directed_edges(&polygons[i].0.points) {
+                let consistent = edges.get(&[b, a]).into_iter().flatten();
+                let inconsistent = edges.get(&[a, b]).into_iter().flatten();
+                let neighbors: Vec<_> = consistent
+                    .map(|&j| (j, true))
+                    .chain(
+                        inconsistent.filter(|&&j| j != i).map(|&j| (j, false)),
+                    )
+                    .collect();
+                let (neighbor, is_consistent) = match neighbors[..] {
+                    [neighbor] => neighbor,
+                    _ => return Err(ShellError::NotClosed),
+                };
+
+                let is_flipped = flip[i] == Some(true);
+                let expected = is_flipped == is_consistent;
+                match flip[neighbor] {
+                    Some(flip) if flip != expected => {
+                        return Err(ShellError::NotClosed)
+                    }
+                    Some(_) => {}
+                    None => {
+                        flip[neighbor] = Some(expected);
+                        queue.push(neighbor);
+                    }
+                }
+            }
+        }
+    }
+
+    // Now all polygons face the same way, which is either inward or outward.
+    // The enclosed volume tells which one it is.
+    let volume = polygons
+        .iter()
+        .zip(&flip)
+        .map(|((polygon, _), flip)| {
+            let sign = if *flip == Some(true) { -1. } else { 1. };
+            newell_normal(&polygon.points).dot(&polygon.points[0].coords) * sign
+        })
+        .fold(Scalar::ZERO, |sum, volume| sum + volume);
+    let is_inward = volume < Scalar::ZERO;
+
+    for ((polygon, _), flip) in polygons.iter_mut().zip(flip) {
+        if (flip == Some(true)) != is_inward {
+            polygon.surface = polygon.surface.clone().reverse();
+            polygon.points.reverse();
+        }
+    }
+
+    Ok(())
+}
+
+fn directed_edges(
+    points: &[Point<3>],
+) -> impl Iterator<Item = [Point<3>; 2]> + '_ {
+    (0..points.len()).map(|i| [points[i], points[(i + 1) % points.len()]])
+}
+
+/// The normal of a polygon, scaled by twice its area
+fn newell_normal(points: &[Point<3>]) -> Vector<3> {
+    directed_edges(points)
+        .map(|[a, b]| a.coords.cross(&b.coords))
+        .fold(Vector::from([0., 0., 0.]), |sum, normal| sum + normal)
+}
+
+/// Find the point that is on all planes, in the least squares sense
+///
+/// Each plane is given as a normal, and the distance from the origin along that
+/// normal. Returns `None`, if the planes don't intersect in a single point.
+fn intersect_planes(planes: &[(Vector<3>, Scalar)]) -> Option<Point<3>> {
+    // Solve `A x = b` with `A = sum(n n^T)` and `b = sum(n d)`.
+    let mut a = [[0.; 3]; 3];
+    let mut b = [0.; 3];
+    for (normal, distance) in planes {
+        let n = normal.components.map(Scalar::into_f64);
+        for i in 0..3 {
+            for j in 0..3 {
+                a[i][j] += n[i] * n[j];
+            }
+            b[i] += n[i] * distance.into_f64();
+        }
+    }
+
+    let det = |m: [[f64; 3]; 3]| {
+        m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+            - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+            + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+    };
+
+    let determinant = det(a);
+    if determinant.abs() < 1e-12 {
+        return None;
+    }
+
+    // Cramer's rule
+    let mut x = [0.; 3];
+    for (i, x) in x.iter_mut().enumerate() {
+        let mut m = a;
+        for (row, b) in m.iter_mut().zip(b) {
+            row[i] = b;
+        }
+        *x = det(m) / determinant;
+    }
+
+    Some(Point::from(x))
+}
+
+/// An error that can occur when shelling a solid
+#[derive(Debug, thiserror::Error)]
+pub enum ShellError {
+    /// The wall thickness is not positive
+    #[error("Wall thickness must be positive, not {0:?}")]
+    InvalidThickness(Scalar),
+
+    /// A face that was selected as an opening is not one of the faces
+    #[error("Opening is not one of the faces being shelled")]
+    OpeningNotFound,
+
+    /// The faces don't form a closed solid
+    #[error("Faces don't form a closed solid")]
+    NotClosed,
+
+    /// A face can't be shelled
+    ///
+    /// See [`shell`] for the kinds of faces that are supported.
+    #[error("Face is not a planar polygon")]
+    UnsupportedFace,
+
+    /// The offset faces at a vertex don't intersect in a single point
+    #[error("Offset faces at vertex {0:?} don't intersect in a single point")]
+    UnsupportedVertex(Point<3>),
+
+    /// The wall thickness is larger than the solid allows
+    #[error("Wall thickness is too large for the solid")]
+    ThicknessTooLarge,
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_interop::debug::DebugInfo;
+    use fj_math::Scalar;
+
+    use crate::{
+        algorithms::{triangulate, volume, Tolerance},
+        objects::{Face, Solid},
+    };
+
+    use super::{shell, ShellError};
+
+    fn cube() -> Vec<Face> {
+        Solid::build()
+            .cube_from_edge_length(2.)
+            .into_faces()
+            .collect()
+    }
+
+    fn volume_of(faces: Vec<Face>) -> f64 {
+        let tolerance = Tolerance::from_scalar(0.001).unwrap();
+        let mesh = triangulate(faces, tolerance, &mut DebugInfo::new());
+        volume(&mesh).into_f64()
+    }
+
+    #[test]
+    fn closed() -> anyhow::Result<()> {
+        let faces = shell(&cube(), Scalar::from_f64(0.25), &[])?;
+        assert_eq!(faces.len(), 12);
+
+        let volume = volume_of(faces);
+        assert!((volume - (8. - 1.5 * 1.5 * 1.5)).abs() < 1e-9, "{volume}");
+
+        Ok(())
+    }
+
+    #[test]
+    fn with_opening() -> anyhow::Result<()> {
+        let cube = cube();
+        let top = cube
+            .iter()
+            .find(|face| {
+                face.all_cycles()
+                    .flat_map(|cycle| cycle.vertices())
+                    .all(|vertex| vertex.global().position().z == Scalar::ONE)
+            })
+            .unwrap()
+            .clone();
+
+        let faces = shell(&cube, Scalar::from_f64(0.25), &[top])?;
+        assert_eq!(faces.len(), 11);
+
+        // The cavity reaches up to the top.
+        let volume = volume_of(faces);
+        assert!((volume - (8. - 1.5 * 1.5 * 1.75)).abs() < 1e-9, "{volume}");
+
+        Ok(())
+    }
+
+    #[test]
+    fn thickness_too_large() {
+        let result = shell(&cube(), Scalar::from_f64(1.5), &[]);
+        assert!(matches!(result, Err(ShellError::ThicknessTooLarge)));
+    }
+}
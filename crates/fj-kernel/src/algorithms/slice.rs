@@ -0,0 +1,205 @@
+use std::collections::BTreeMap;
+
+use fj_interop::mesh::Mesh;
+use fj_math::{Plane, Point, Scalar, Segment};
+
+/// Compute the cross-section of a mesh with a plane
+///
+/// Returns one segment for each triangle that crosses the plane. The segments
+/// are in the plane, but not in any particular order. Use [`stitch_segments`]
+/// to connect them into loops.
+///
+/// # Implementation Note
+///
+/// Vertices that are exactly on the plane are treated as being in front of
+/// it. This means triangles that lie in the plane don't produce any segments,
+/// while an edge in the plane is produced by the triangle behind it. A vertex
+/// in the plane doesn't produce a segment of its own.
+pub fn slice(mesh: &Mesh<Point<3>>, plane: &Plane) -> Vec<Segment<3>> {
+    let mut segments = Vec::new();
+
+    for triangle in mesh.triangles() {
+        let points = triangle.inner.points();
+        let distances = points.map(|point| plane.distance_to_point(&point));
+
+        let mut intersections = Vec::new();
+        for i in 0..3 {
+            let j = (i + 1) % 3;
+
+            let is_behind = |d: Scalar| d < Scalar::ZERO;
+            if is_behind(distances[i]) != is_behind(distances[j]) {
+                intersections.push(intersect_edge(
+                    [points[i], points[j]],
+                    [distances[i], distances[j]],
+                ));
+            }
+        }
+
+        // A triangle that only touches the plane with one vertex results in
+        // two identical intersections.
+        if let [a, b] = intersections[..] {
+            if a != b {
+                segments.push(Segment::from_points([a, b]));
+            }
+        }
+    }
+
+    segments
+}
+
+/// Connect segments that share end points into chains
+///
+/// Each chain is returned as a list of its points. Closed chains don't repeat
+/// their first point at the end. Segments that can't be connected into a loop
+/// are returned as open chains.
+pub fn stitch_segments(segments: &[Segment<3>]) -> Vec<Vec<Point<3>>> {
+    let mut segments_by_point: BTreeMap<_, Vec<_>> = BTreeMap::new();
+    for (i, segment) in segments.iter().enumerate() {
+        for point in segment.points() {
+            segments_by_point.entry(point).or_default().push(i);
+        }
+    }
+
+    let mut used = vec![false; segments.len()];
+    let mut chains = Vec::new();
+
+    // Follow unused segments from a point, until the chain closes or ends.
+    // Returns the points after `from`, and whether the chain closed.
+    let follow = |used: &mut Vec<bool>, from: Point<3>, stop: Point<3>| {
+        let mut points = Vec::new();
+        let mut current = from;
+
+        while let Some(&next) = segments_by_point[&current]
+            .iter()
+            .find(|&&next| !used[next])
+        {
+            used[next] = true;
+
+            let [a, b] = segments[next].points();
+            current = if a == current { b } else { a };
+
+            if current == stop {
+                return (points, true);
+            }
+            points.push(current);
+        }
+
+        (points, false)
+    };
+
+    for (i, segment) in segments.iter().enumerate() {
+        if used[i] {
+            continue;
+        }
+        used[i] = true;
+
+        let [start, end] = segment.points();
+        let mut chain = vec![start, end];
+
+        let (forward, is_closed) = follow(&mut used, end, start);
+        chain.extend(forward);
+
+        if !is_closed {
+            // We might have started in the middle of an open chain.
+            let (backward, _) = follow(&mut used, start, end);
+            chain.splice(0..0, backward.into_iter().rev());
+        }
+
+        chains.push(chain);
+    }
+
+    chains
+}
+
+/// Compute where an edge crosses the plane, given the distances of its points
+fn intersect_edge(points: [Point<3>; 2], distances: [Scalar; 2]) -> Point<3> {
+    // Neighboring triangles share edges, so make sure to compute the same
+    // point, regardless of the direction of the edge.
+    let ([a, b], [da, db]) = if points[0] <= points[1] {
+        (points, distances)
+    } else {
+        ([points[1], points[0]], [distances[1], distances[0]])
+    };
+
+    if da == Scalar::ZERO {
+        return a;
+    }
+    if db == Scalar::ZERO {
+        return b;
+    }
+
+    let t = da / (da - db);
+    a + (b - a) * t
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_interop::{fixtures, mesh::Mesh};
+    use fj_math::{Plane, Point, Scalar, Segment};
+
+    use super::{slice, stitch_segments};
+
+    /// A cube with an edge length of one, centered at the origin
+    fn cube() -> Mesh<Point<3>> {
+        fixtures::cube([-0.5, -0.5, -0.5], 1.)
+    }
+
+    fn length(segments: &[Segment<3>]) -> Scalar {
+        segments.iter().fold(Scalar::ZERO, |sum, segment| {
+            let [a, b] = segment.points();
+            sum + (b - a).magnitude()
+        })
+    }
+
+    #[test]
+    fn slice_cube_through_middle() {
+        let plane = Plane::from_origin_and_normal([0., 0., 0.], [0., 0., 1.]);
+        let segments = slice(&cube(), &plane);
+
+        // Each side consists of two triangles, which are both cut.
+        assert_eq!(segments.len(), 8);
+        assert_eq!(length(&segments), Scalar::from_f64(4.));
+
+        let chains = stitch_segments(&segments);
+        assert_eq!(chains.len(), 1);
+
+        let chain = &chains[0];
+        assert_eq!(chain.len(), 8);
+        for point in chain {
+            assert_eq!(point.z, Scalar::ZERO);
+            assert_eq!(point.x.abs().max(point.y.abs()), Scalar::from_f64(0.5));
+        }
+        for corner in [[-0.5, -0.5], [0.5, -0.5], [0.5, 0.5], [-0.5, 0.5]] {
+            let [x, y] = corner;
+            assert!(chain.contains(&Point::from([x, y, 0.])));
+        }
+    }
+
+    #[test]
+    fn slice_cube_through_face() {
+        let plane = Plane::from_origin_and_normal([0., 0., 0.5], [0., 0., 1.]);
+        let segments = slice(&cube(), &plane);
+
+        // The triangles of the top face are in the plane, and the vertices of
+        // the side triangles touch it. None of them must result in duplicate
+        // or degenerate segments.
+        assert_eq!(segments.len(), 4);
+        assert_eq!(length(&segments), Scalar::from_f64(4.));
+
+        let chains = stitch_segments(&segments);
+        assert_eq!(chains.len(), 1);
+        assert_eq!(chains[0].len(), 4);
+    }
+
+    #[test]
+    fn stitch_open_chain() {
+        let [a, b, c, d] =
+            [[0., 0., 0.], [1., 0., 0.], [1., 1., 0.], [0., 1., 0.]]
+                .map(Point::from);
+
+        // Start in the middle, to make sure both directions are followed.
+        let segments = [[b, c], [a, b], [c, d]].map(Segment::from_points);
+
+        assert_eq!(stitch_segments(&segments), [vec![a, b, c, d]]);
+    }
+}
@@ -0,0 +1,197 @@
+use std::collections::BTreeSet;
+
+use fj_interop::mesh::{Index, Mesh};
+use fj_math::{Point, Scalar, Vector};
+
+/// Smooth a mesh, by moving each vertex towards the average of its neighbors
+///
+/// In each of the `iterations`, every vertex is moved towards the average of
+/// the vertices it shares an edge with. `lambda` controls how far it moves:
+/// `0.0` doesn't move it at all, `1.0` moves it all the way to the average.
+///
+/// Vertices on the boundary of the mesh (those on an edge that is only part of
+/// one triangle) are pinned, so open meshes keep their outline.
+///
+/// Repeated smoothing shrinks the mesh. If that is a problem, use
+/// [`taubin_smooth`] instead.
+pub fn laplacian_smooth(
+    mesh: &mut Mesh<Point<3>>,
+    iterations: usize,
+    lambda: f32,
+) {
+    smooth(mesh, iterations, &[lambda]);
+}
+
+/// Smooth a mesh, without shrinking it
+///
+/// Like [`laplacian_smooth`], except that each iteration consists of two steps:
+/// A smoothing step with `lambda`, followed by an inflating step with `mu`,
+/// which must be negative and of a larger magnitude than `lambda`. This is
+/// Taubin's λ/μ scheme. Typical values are `lambda = 0.5` and `mu = -0.53`.
+pub fn taubin_smooth(
+    mesh: &mut Mesh<Point<3>>,
+    iterations: usize,
+    lambda: f32,
+    mu: f32,
+) {
+    smooth(mesh, iterations, &[lambda, mu]);
+}
+
+fn smooth(mesh: &mut Mesh<Point<3>>, iterations: usize, factors: &[f32]) {
+    let mut vertices: Vec<_> = mesh.vertices().collect();
+    let indices: Vec<_> = mesh.indices().collect();
+
+    let adjacency = mesh.adjacency();
+
+    let neighbors: Vec<BTreeSet<Index>> = (0..vertices.len())
+        .map(|vertex| {
+            let vertex = vertex as Index;
+
+            adjacency
+                .incident_triangles(vertex)
+                .iter()
+                .flat_map(|&t| &indices[t * 3..t * 3 + 3])
+                .copied()
+                .filter(|&other| other != vertex)
+                .collect()
+        })
+        .collect();
+
+    let mut pinned = vec![false; vertices.len()];
+    for ([a, b], triangles) in adjacency.edges() {
+        if triangles.len() == 1 {
+            pinned[a as usize] = true;
+            pinned[b as usize] = true;
+        }
+    }
+
+    for _ in 0..iterations {
+        for &factor in factors {
+            let factor = Scalar::from_f64(factor.into());
+
+            vertices = vertices
+                .iter()
+                .enumerate()
+                .map(|(i, &vertex)| {
+                    if pinned[i] || neighbors[i].is_empty() {
+                        return vertex;
+                    }
+
+                    let sum = neighbors[i]
+                        .iter()
+                        .map(|&j: &Index| vertices[j as usize] - vertex)
+                        .fold(Vector::from([0., 0., 0.]), |sum, v| sum + v);
+                    let offset =
+                        sum / Scalar::from_u64(neighbors[i].len() as u64);
+
+                    vertex + offset * factor
+                })
+                .collect();
+        }
+    }
+
+    let mut smoothed = Mesh::new();
+    for (triangle, indices) in mesh.triangles().zip(indices.chunks(3)) {
+        let points = [0, 1, 2].map(|i| vertices[indices[i] as usize]);
+        smoothed.push_triangle(points, triangle.color);
+    }
+
+    *mesh = smoothed;
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::{FRAC_PI_2, TAU};
+
+    use fj_interop::mesh::{Color, Mesh};
+    use fj_math::{Point, Scalar};
+
+    use super::{laplacian_smooth, taubin_smooth};
+
+    /// A hemisphere around the origin, open at the equator
+    ///
+    /// The radius of each vertex is disturbed by a deterministic amount, so the
+    /// surface is bumpy.
+    fn noisy_hemisphere() -> Mesh<Point<3>> {
+        const RINGS: usize = 16;
+        const SEGMENTS: usize = 32;
+
+        let vertex = |ring: usize, segment: usize| {
+            let segment = segment % SEGMENTS;
+
+            let noise = if ring == 0 {
+                0.
+            } else {
+                ((ring * 7 + segment * 13) % 5) as f64 * 0.02 - 0.04
+            };
+            let radius = 1. + noise;
+
+            let polar = FRAC_PI_2 * ring as f64 / RINGS as f64;
+            let azimuth = TAU * segment as f64 / SEGMENTS as f64;
+
+            Point::from([
+                radius * polar.sin() * azimuth.cos(),
+                radius * polar.sin() * azimuth.sin(),
+                radius * polar.cos(),
+            ])
+        };
+
+        let mut mesh = Mesh::new();
+        for ring in 0..RINGS {
+            for segment in 0..SEGMENTS {
+                let a = vertex(ring, segment);
+                let b = vertex(ring + 1, segment);
+                let c = vertex(ring + 1, segment + 1);
+                let d = vertex(ring, segment + 1);
+
+                if ring > 0 {
+                    mesh.push_triangle([a, b, d], Color::default());
+                }
+                mesh.push_triangle([b, c, d], Color::default());
+            }
+        }
+
+        mesh
+    }
+
+    fn boundary(mesh: &Mesh<Point<3>>) -> Vec<Point<3>> {
+        mesh.vertices()
+            .filter(|vertex| vertex.z.abs() < Scalar::from_f64(1e-9))
+            .collect()
+    }
+
+    fn radius_variance(mesh: &Mesh<Point<3>>) -> f64 {
+        let radii: Vec<_> = mesh
+            .vertices()
+            .map(|vertex| vertex.coords.magnitude().into_f64())
+            .collect();
+
+        let mean = radii.iter().sum::<f64>() / radii.len() as f64;
+        radii.iter().map(|r| (r - mean).powi(2)).sum::<f64>()
+            / radii.len() as f64
+    }
+
+    #[test]
+    fn laplacian_smooth_reduces_noise() {
+        let mut mesh = noisy_hemisphere();
+        let variance_before = radius_variance(&mesh);
+        let boundary_before = boundary(&mesh);
+
+        laplacian_smooth(&mut mesh, 3, 0.5);
+
+        assert!(radius_variance(&mesh) < variance_before);
+        assert_eq!(boundary(&mesh), boundary_before);
+    }
+
+    #[test]
+    fn taubin_smooth_reduces_noise() {
+        let mut mesh = noisy_hemisphere();
+        let variance_before = radius_variance(&mesh);
+        let boundary_before = boundary(&mesh);
+
+        taubin_smooth(&mut mesh, 3, 0.5, -0.53);
+
+        assert!(radius_variance(&mesh) < variance_before);
+        assert_eq!(boundary(&mesh), boundary_before);
+    }
+}
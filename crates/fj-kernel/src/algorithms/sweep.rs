@@ -33,14 +33,23 @@ pub fn sweep(
         );
         create_top_face(
             face.clone(),
-            path,
+            &Transform::translation(path),
             is_sweep_along_negative_direction,
             &mut target,
         );
 
         for cycle in face.all_cycles() {
             for edge in cycle.edges() {
-                if let Some(vertices) = edge.vertices().get() {
+                // Only straight edges result in planar side faces. Curved
+                // edges are approximated, like full circles are.
+                let vertices = match edge.curve().kind() {
+                    CurveKind::Line(_) => edge.vertices().get(),
+                    CurveKind::Circle(_)
+                    | CurveKind::Ellipse(_)
+                    | CurveKind::Parabola(_) => None,
+                };
+
+                if let Some(vertices) = vertices {
                     create_non_continuous_side_face(
                         path,
                         is_sweep_along_negative_direction,
@@ -54,6 +63,7 @@ pub fn sweep(
                 create_continuous_side_face(
                     *edge,
                     path,
+                    is_sweep_along_negative_direction,
                     tolerance,
                     color,
                     &mut target,
@@ -65,7 +75,181 @@ pub fn sweep(
     Solid::new().with_faces(target)
 }
 
-fn create_bottom_faces(
+/// Create a solid by sweeping a sketch along a path
+///
+/// The path is a polyline, given in the same coordinate system as the sketch.
+/// The sketch is moved along the path, starting at its first point, and turned
+/// with it at every corner, using the smallest rotation that aligns it with the
+/// next segment. Corners are mitered, so the cross-section stays the same along
+/// each segment.
+///
+/// A path with only two points results in the same shape as [`sweep`], except
+/// that the side faces are always approximated.
+///
+/// # Panics
+///
+/// Panics, if the path reverses its direction at any point.
+pub fn sweep_along_path(
+    source: Sketch,
+    path: impl IntoIterator<Item = impl Into<Point<3>>>,
+    tolerance: Tolerance,
+    color: Color,
+) -> Solid {
+    let mut path: Vec<Point<3>> = path.into_iter().map(Into::into).collect();
+    path.dedup();
+
+    let path = match SweepPath::new(&path) {
+        Some(path) => path,
+        None => return Solid::new(),
+    };
+
+    let is_sweep_along_negative_direction =
+        path.directions[0].dot(&Vector::from([0., 0., 1.])) < Scalar::ZERO;
+
+    let mut target = Vec::new();
+
+    for face in source.face_iter() {
+        create_bottom_faces(
+            face,
+            is_sweep_along_negative_direction,
+            &mut target,
+        );
+        create_top_face(
+            face.clone(),
+            &path.end(),
+            is_sweep_along_negative_direction,
+            &mut target,
+        );
+
+        for cycle in face.all_cycles() {
+            for edge in cycle.edges() {
+                let placeholder = Surface::xy_plane();
+                let cycle = Cycle::new(placeholder).with_edges([*edge]);
+                let approx = CycleApprox::new(&cycle, tolerance);
+
+                let mut side_face: Vec<(Triangle<3>, _)> = Vec::new();
+                for segment in approx.segments() {
+                    let [a, b] =
+                        segment.points().map(|point| path.rings(point));
+
+                    for i in 0..a.len() - 1 {
+                        let [v0, v1, v2, v3] = [a[i], b[i], b[i + 1], a[i + 1]];
+
+                        if is_sweep_along_negative_direction {
+                            side_face.push(([v0, v2, v1].into(), color));
+                            side_face.push(([v0, v3, v2].into(), color));
+                        } else {
+                            side_face.push(([v0, v1, v2].into(), color));
+                            side_face.push(([v0, v2, v3].into(), color));
+                        }
+                    }
+                }
+
+                target.push(Face::from_triangles(side_face));
+            }
+        }
+    }
+
+    Solid::new().with_faces(target)
+}
+
+/// A path that a sketch is swept along
+struct SweepPath<'r> {
+    points: &'r [Point<3>],
+
+    /// The normalized direction of each segment
+    directions: Vec<Vector<3>>,
+
+    /// The transform that moves the sketch to the start of each segment
+    placements: Vec<Transform>,
+}
+
+impl<'r> SweepPath<'r> {
+    fn new(points: &'r [Point<3>]) -> Option<Self> {
+        let first = *points.first()?;
+
+        let directions: Vec<_> = points
+            .windows(2)
+            .map(|segment| (segment[1] - segment[0]).normalize())
+            .collect();
+        if directions.is_empty() {
+            return None;
+        }
+
+        // The sketch is carried from segment to segment, rotating it as little
+        // as possible. This keeps it from twisting around the path.
+        let mut rotation = Transform::identity();
+        let mut placements = Vec::new();
+        for (i, point) in points[..directions.len()].iter().enumerate() {
+            if i > 0 {
+                rotation = rotation_between(directions[i - 1], directions[i])
+                    * rotation;
+            }
+
+            placements.push(
+                Transform::translation(point.coords)
+                    * rotation
+                    * Transform::translation(-first.coords),
+            );
+        }
+
+        Some(Self {
+            points,
+            directions,
+            placements,
+        })
+    }
+
+    /// The transform that moves the sketch to the end of the path
+    fn end(&self) -> Transform {
+        let last = self.directions.len() - 1;
+        let offset = self.points[last + 1] - self.points[last];
+
+        Transform::translation(offset) * self.placements[last]
+    }
+
+    /// Compute where a point of the sketch ends up at each point of the path
+    fn rings(&self, point: Point<3>) -> Vec<Point<3>> {
+        let mut rings = vec![point];
+
+        for i in 1..self.directions.len() {
+            let incoming = self.directions[i - 1];
+            let miter = (incoming + self.directions[i]).normalize();
+
+            // Move the point along the incoming segment, until it reaches the
+            // plane that bisects the corner.
+            let start = self.placements[i - 1].transform_point(&point);
+            let distance =
+                (self.points[i] - start).dot(&miter) / incoming.dot(&miter);
+
+            rings.push(start + incoming * distance);
+        }
+
+        rings.push(self.end().transform_point(&point));
+
+        rings
+    }
+}
+
+/// The smallest rotation that turns direction `a` into direction `b`
+fn rotation_between(a: Vector<3>, b: Vector<3>) -> Transform {
+    let axis = a.cross(&b);
+    let cos = a.dot(&b);
+
+    assert!(
+        axis.magnitude() > Scalar::ZERO || cos > Scalar::ZERO,
+        "Sweep path reverses its direction"
+    );
+
+    if axis.magnitude() == Scalar::ZERO {
+        return Transform::identity();
+    }
+
+    let angle = axis.magnitude().atan2(cos);
+    Transform::rotation(axis.normalize() * angle)
+}
+
+pub(super) fn create_bottom_faces(
     face: &Face,
     is_sweep_along_negative_direction: bool,
     target: &mut Vec<Face>,
@@ -79,13 +263,13 @@ fn create_bottom_faces(
     target.push(face);
 }
 
-fn create_top_face(
+pub(super) fn create_top_face(
     face: Face,
-    path: Vector<3>,
+    transform: &Transform,
     is_sweep_along_negative_direction: bool,
     target: &mut Vec<Face>,
 ) {
-    let mut face = face.translate(path);
+    let mut face = face.transform(transform);
 
     if is_sweep_along_negative_direction {
         face = reverse_face(&face);
@@ -158,7 +342,7 @@ fn create_non_continuous_side_face(
             edges.push(edge);
         }
 
-        Cycle::new(surface).with_edges(edges)
+        Cycle::new(surface.clone()).with_edges(edges)
     };
 
     let face = Face::new(surface).with_exteriors([cycle]).with_color(color);
@@ -168,6 +352,7 @@ fn create_non_continuous_side_face(
 fn create_continuous_side_face(
     edge: Edge,
     path: Vector<3>,
+    is_sweep_along_negative_direction: bool,
     tolerance: Tolerance,
     color: Color,
     target: &mut Vec<Face>,
@@ -196,8 +381,13 @@ fn create_continuous_side_face(
 
     let mut side_face: Vec<(Triangle<3>, _)> = Vec::new();
     for [v0, v1, v2, v3] in quads {
-        side_face.push(([v0, v1, v2].into(), color));
-        side_face.push(([v0, v2, v3].into(), color));
+        if is_sweep_along_negative_direction {
+            side_face.push(([v0, v2, v1].into(), color));
+            side_face.push(([v0, v3, v2].into(), color));
+        } else {
+            side_face.push(([v0, v1, v2].into(), color));
+            side_face.push(([v0, v2, v3].into(), color));
+        }
     }
 
     target.push(Face::from_triangles(side_face));
@@ -205,13 +395,15 @@ fn create_continuous_side_face(
 
 #[cfg(test)]
 mod tests {
-    use fj_interop::mesh::Color;
+    use std::f64::consts::PI;
+
+    use fj_interop::{debug::DebugInfo, mesh::Color};
     use fj_math::{Point, Scalar, Vector};
 
     use crate::{
-        algorithms::Tolerance,
+        algorithms::{triangulate, volume, Tolerance},
         iter::ObjectIters,
-        objects::{Face, Sketch, Surface},
+        objects::{Cycle, Edge, Face, Sketch, Surface},
     };
 
     #[test]
@@ -274,6 +466,125 @@ mod tests {
         )
     }
 
+    #[test]
+    fn along_path() -> anyhow::Result<()> {
+        let tolerance = Tolerance::from_scalar(Scalar::ONE)?;
+
+        let face = Face::build(Surface::xy_plane()).polygon_from_points([
+            [-0.5, -0.5],
+            [0.5, -0.5],
+            [0.5, 0.5],
+            [-0.5, 0.5],
+        ]);
+        let sketch = Sketch::new().with_faces([face]);
+
+        let solid = super::sweep_along_path(
+            sketch,
+            [[0., 0., 0.], [0., 0., 2.], [2., 0., 2.]],
+            tolerance,
+            Color([255, 0, 0, 255]),
+        );
+        let faces: Vec<_> = solid.into_faces().collect();
+
+        // The sketch is turned with the path, so it ends up perpendicular to
+        // the last segment.
+        let top = faces.iter().find(|face| {
+            let vertices: Vec<_> = face
+                .all_cycles()
+                .flat_map(|cycle| cycle.vertices())
+                .map(|vertex| vertex.global().position())
+                .collect();
+
+            !vertices.is_empty()
+                && vertices
+                    .iter()
+                    .all(|position| position.x == Scalar::from_f64(2.))
+        });
+        assert!(top.is_some());
+
+        // The cross-section stays the same along the mitered path.
+        let mesh = triangulate(faces, tolerance, &mut DebugInfo::new());
+        let volume = volume(&mesh).into_f64();
+        assert!((volume - 4.).abs() < 1e-9, "{volume}");
+
+        Ok(())
+    }
+
+    #[test]
+    fn arcs() -> anyhow::Result<()> {
+        let tolerance = Tolerance::from_scalar(0.001)?;
+
+        // A half-disc, bounded by a line and an arc
+        let surface = Surface::xy_plane();
+        let edges = [
+            Edge::build()
+                .line_segment_from_points(&surface, [[-1., 0.], [1., 0.]]),
+            Edge::build()
+                .arc_through_points(&surface, [[1., 0.], [0., 1.], [-1., 0.]]),
+        ];
+        let face = Face::new(surface.clone())
+            .with_exteriors([Cycle::new(surface.clone()).with_edges(edges)]);
+
+        for z in [1., -1.] {
+            let sketch = Sketch::new().with_faces([face.clone()]);
+            let solid = super::sweep(
+                sketch,
+                [0., 0., z],
+                tolerance,
+                Color([255, 0, 0, 255]),
+            );
+
+            let faces: Vec<_> = solid.into_faces().collect();
+            let mesh = triangulate(faces, tolerance, &mut DebugInfo::new());
+            let volume = volume(&mesh).into_f64();
+            assert!((volume - PI / 2.).abs() < 0.01, "{volume}");
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn conics() -> anyhow::Result<()> {
+        let tolerance = Tolerance::from_scalar(0.001)?;
+
+        // A parabolic segment with an elliptical hole
+        let surface = Surface::xy_plane();
+        let exterior = [
+            Edge::build()
+                .line_segment_from_points(&surface, [[-1., 0.], [1., 0.]]),
+            Edge::build().parabola_from_points(
+                &surface,
+                [[1., 0.], [0., 2.], [-1., 0.]],
+            ),
+        ];
+        let interior = [Edge::build().ellipse_from_axes(
+            &surface,
+            [0., 0.4],
+            [[0.3, 0.], [0., -0.2]],
+        )];
+        let face = Face::new(surface.clone())
+            .with_exteriors([Cycle::new(surface.clone()).with_edges(exterior)])
+            .with_interiors([Cycle::new(surface.clone()).with_edges(interior)]);
+
+        for z in [1., -1.] {
+            let sketch = Sketch::new().with_faces([face.clone()]);
+            let solid = super::sweep(
+                sketch,
+                [0., 0., z],
+                tolerance,
+                Color([255, 0, 0, 255]),
+            );
+
+            let faces: Vec<_> = solid.into_faces().collect();
+            let mesh = triangulate(faces, tolerance, &mut DebugInfo::new());
+            let volume = volume(&mesh).into_f64();
+            let expected = 4. / 3. - PI * 0.06;
+            assert!((volume - expected).abs() < 0.01, "{volume}");
+        }
+
+        Ok(())
+    }
+
     fn test_side(
         direction: impl Into<Vector<3>>,
         expected_surfaces: [[impl Into<Point<3>>; 3]; 3],
@@ -301,7 +612,7 @@ mod tests {
         let tolerance = Tolerance::from_scalar(Scalar::ONE)?;
 
         let surface = Surface::xy_plane();
-        let face = Face::build(surface).polygon_from_points([
+        let face = Face::build(surface.clone()).polygon_from_points([
             [0., 0.],
             [1., 0.],
             [0., 1.],
@@ -319,7 +630,7 @@ mod tests {
         let faces = expected_surfaces.into_iter().map(|surface| {
             let surface = Surface::plane_from_points(surface);
 
-            Face::build(surface)
+            Face::build(surface.clone())
                 .polygon_from_points(expected_vertices.clone())
                 .into_face()
         });
@@ -1,10 +1,12 @@
-use fj_math::{Transform, Vector};
+use fj_math::{Transform, Triangle, Vector};
 
 use crate::objects::{
     Curve, Cycle, Edge, Face, GlobalCurve, GlobalVertex, Sketch, Solid,
     Surface, Vertex,
 };
 
+use super::reverse_face;
+
 /// Transform an object
 ///
 /// # Implementation Note
@@ -43,7 +45,7 @@ impl TransformObject for Curve {
 
 impl TransformObject for Cycle {
     fn transform(self, transform: &Transform) -> Self {
-        Self::new(self.surface().transform(transform))
+        Self::new(self.surface().clone().transform(transform))
             .with_edges(self.into_edges().map(|edge| edge.transform(transform)))
     }
 }
@@ -69,23 +71,40 @@ impl TransformObject for Face {
 
             for (triangle, color) in triangles.clone() {
                 let triangle = transform.transform_triangle(&triangle);
+
+                // A reflection would turn the triangle inside-out otherwise.
+                let triangle = if transform.is_mirroring() {
+                    let [a, b, c] = triangle.points();
+                    Triangle::from([a, c, b])
+                } else {
+                    triangle
+                };
+
                 target.push((triangle, color));
             }
 
             return Self::from_triangles(target);
         }
 
-        let surface = self.surface().transform(transform);
+        let surface = self.surface().clone().transform(transform);
 
         let exteriors = transform_cycles(self.exteriors(), transform);
         let interiors = transform_cycles(self.interiors(), transform);
 
         let color = self.color();
 
-        Face::new(surface)
+        let face = Face::new(surface)
             .with_exteriors(exteriors)
             .with_interiors(interiors)
-            .with_color(color)
+            .with_color(color);
+
+        // A reflection flips the surface normal relative to the geometry, so
+        // the face would point into the solid it bounds.
+        if transform.is_mirroring() {
+            return reverse_face(&face);
+        }
+
+        face
     }
 }
 
@@ -123,6 +142,7 @@ impl TransformObject for Surface {
             Self::SweptCurve(surface) => {
                 Self::SweptCurve(surface.transform(transform))
             }
+            Self::Nurbs(surface) => Self::nurbs(surface.transform(transform)),
         }
     }
 }
@@ -148,3 +168,45 @@ fn transform_cycles<'a>(
         .into_iter()
         .map(|cycle| cycle.clone().transform(transform))
 }
+
+#[cfg(test)]
+mod tests {
+    use fj_interop::{debug::DebugInfo, mesh::Color};
+    use fj_math::{Scalar, Transform};
+
+    use crate::{
+        algorithms::{sweep, triangulate, volume, Tolerance},
+        objects::{Face, Sketch, Surface},
+    };
+
+    use super::TransformObject;
+
+    #[test]
+    fn mirror() {
+        let tolerance = Tolerance::from_scalar(0.001).unwrap();
+
+        let face = Face::build(Surface::xy_plane()).polygon_from_points([
+            [1., 0.],
+            [2., 0.],
+            [2., 1.],
+            [1., 1.],
+        ]);
+        let solid = sweep(
+            Sketch::new().with_faces([face]),
+            [0., 0., 2.],
+            tolerance,
+            Color([255, 0, 0, 255]),
+        );
+
+        let mirrored = solid.transform(&Transform::mirror([1., 0., 0.], 0.));
+        let faces = mirrored.into_faces().collect::<Vec<_>>();
+        assert!(faces.iter().all(|face| face
+            .all_cycles()
+            .flat_map(|cycle| cycle.vertices())
+            .all(|vertex| vertex.global().position().x < Scalar::ZERO)));
+
+        // The faces still point outwards, or the volume would be negative.
+        let mesh = triangulate(faces, tolerance, &mut DebugInfo::new());
+        assert!((volume(&mesh).into_f64() - 2.).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,130 @@
+use std::{collections::HashMap, mem};
+
+use fj_interop::{debug::DebugInfo, mesh::Mesh};
+use fj_math::Point;
+
+use crate::{algorithms::Tolerance, objects::Face};
+
+use super::{push_face, triangulate_faces, FaceTriangulation};
+
+/// Caches the triangulations of faces
+///
+/// Faces are identified by their content. When a model changes, most of its
+/// faces usually stay the same, and only the ones that actually changed need to
+/// be triangulated again.
+///
+/// Triangulations that weren't needed by the most recent call to
+/// [`TriangulationCache::triangulate`] are discarded, as are all of them, if
+/// the tolerance changes.
+#[derive(Default)]
+pub struct TriangulationCache {
+    tolerance: Option<Tolerance>,
+    faces: HashMap<Face, FaceTriangulation>,
+    misses: usize,
+}
+
+impl TriangulationCache {
+    /// Create an empty cache
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Triangulate a shape, only triangulating faces that aren't cached
+    ///
+    /// The resulting mesh is the same as the one from [`triangulate`].
+    ///
+    /// [`triangulate`]: super::triangulate
+    pub fn triangulate(
+        &mut self,
+        faces: Vec<Face>,
+        tolerance: Tolerance,
+        debug_info: &mut DebugInfo,
+    ) -> Mesh<Point<3>> {
+        let mut previous = mem::take(&mut self.faces);
+        if self.tolerance != Some(tolerance) {
+            previous.clear();
+            self.tolerance = Some(tolerance);
+        }
+
+        let mut missing = Vec::new();
+        for face in &faces {
+            if self.faces.contains_key(face) {
+                continue;
+            }
+
+            match previous.remove_entry(face) {
+                Some((face, triangulation)) => {
+                    self.faces.insert(face, triangulation);
+                }
+                None => missing.push(face.clone()),
+            }
+        }
+        missing.sort();
+        missing.dedup();
+
+        self.misses += missing.len();
+        let triangulations = triangulate_faces(&missing, tolerance);
+        self.faces.extend(missing.into_iter().zip(triangulations));
+
+        let mut mesh = Mesh::new();
+        for face in &faces {
+            let triangulation = self.faces[face].clone();
+            push_face(&mut mesh, debug_info, triangulation);
+        }
+
+        mesh
+    }
+
+    /// The number of faces that had to be triangulated
+    pub fn misses(&self) -> usize {
+        self.misses
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_interop::debug::DebugInfo;
+
+    use crate::{
+        algorithms::Tolerance,
+        objects::{Face, Surface},
+    };
+
+    use super::TriangulationCache;
+
+    #[test]
+    fn only_changed_faces_are_triangulated() -> anyhow::Result<()> {
+        let surface = Surface::xy_plane();
+        let square = |size: f64| {
+            Face::build(surface.clone())
+                .polygon_from_points([
+                    [0., 0.],
+                    [size, 0.],
+                    [size, size],
+                    [0., size],
+                ])
+                .into_face()
+        };
+
+        let tolerance = Tolerance::from_scalar(0.1)?;
+        let mut cache = TriangulationCache::new();
+        let mut triangulate = |faces: Vec<Face>, tolerance| {
+            cache
+                .triangulate(faces, tolerance, &mut DebugInfo::new())
+                .triangles()
+                .count()
+        };
+
+        assert_eq!(triangulate(vec![square(1.), square(2.)], tolerance), 4);
+        assert_eq!(triangulate(vec![square(1.), square(3.)], tolerance), 4);
+        assert_eq!(triangulate(vec![square(1.), square(3.)], tolerance), 4);
+
+        // Changing the tolerance invalidates everything.
+        let tolerance = Tolerance::from_scalar(0.2)?;
+        assert_eq!(triangulate(vec![square(1.), square(3.)], tolerance), 4);
+
+        assert_eq!(cache.misses(), 5);
+
+        Ok(())
+    }
+}
@@ -1,29 +1,62 @@
-use fj_math::{Point, Scalar, Triangle, Winding};
+use fj_math::{Point, Scalar};
 use spade::HasPosition;
 
 use crate::algorithms::approx::Local;
 
-/// Create a Delaunay triangulation of all points
-pub fn triangulate(points: Vec<Local<Point<2>>>) -> Vec<[Local<Point<2>>; 3]> {
+/// Create a constrained Delaunay triangulation of all points
+///
+/// Each of the constraints becomes an edge of the triangulation. Constraints
+/// that would cross previous ones can't be honored, and are skipped.
+///
+/// The triangles are oriented counter-clockwise. Triangles without any area,
+/// which can result from collinear points, are left out.
+pub fn triangulate(
+    points: Vec<Local<Point<2>>>,
+    constraints: Vec<[Local<Point<2>>; 2]>,
+) -> Vec<[Local<Point<2>>; 3]> {
     use spade::Triangulation as _;
 
-    let triangulation = spade::DelaunayTriangulation::<_>::bulk_load(points)
-        .expect("Inserted invalid values into triangulation");
+    // Bulk loading would be faster, but can result in a triangulation that
+    // adding constraints fails on, if many of the points are (almost) on the
+    // same line. The faces that boolean operations result in have lots of
+    // those.
+    let mut triangulation = spade::ConstrainedDelaunayTriangulation::<_>::new();
+    for point in points {
+        triangulation
+            .insert(point)
+            .expect("Inserted invalid values into triangulation");
+    }
+
+    for [a, b] in constraints {
+        if a.position() == b.position()
+            || triangulation.intersects_constraint(a.position(), b.position())
+        {
+            continue;
+        }
+
+        triangulation
+            .add_constraint_edge(a, b)
+            .expect("Inserted invalid values into triangulation");
+    }
 
     let mut triangles = Vec::new();
     for triangle in triangulation.inner_faces() {
         let [v0, v1, v2] = triangle.vertices().map(|vertex| *vertex.data());
-        let orientation = Triangle::<2>::from_points([
-            *v0.local_form(),
-            *v1.local_form(),
-            *v2.local_form(),
-        ])
-        .expect("invalid triangle")
-        .winding_direction();
-
-        let triangle = match orientation {
-            Winding::Ccw => [v0, v1, v2],
-            Winding::Cw => [v0, v2, v1],
+
+        // Computing the orientation exactly makes sure that every triangle
+        // that is kept has an orientation.
+        let [a, b, c] = [v0, v1, v2].map(|vertex| robust::Coord {
+            x: vertex.local_form().u,
+            y: vertex.local_form().v,
+        });
+        let orientation = robust::orient2d(a, b, c);
+
+        let triangle = if orientation > 0. {
+            [v0, v1, v2]
+        } else if orientation < 0. {
+            [v0, v2, v1]
+        } else {
+            continue;
         };
 
         triangles.push(triangle);
@@ -43,3 +76,61 @@ impl HasPosition for Local<Point<2>> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{
+        convex_hull_2d, sample::Sampler, Aabb, Point, Triangle, Winding,
+    };
+
+    use crate::algorithms::approx::Local;
+
+    #[test]
+    fn random_points() {
+        let aabb = Aabb {
+            min: Point::from([0., 0.]),
+            max: Point::from([10., 10.]),
+        };
+
+        for seed in 0..8 {
+            let points = Sampler::new(seed).points(&aabb, 50);
+            let hull = convex_hull_2d(&points);
+
+            let triangles = super::triangulate(
+                points
+                    .iter()
+                    .map(|&point| Local::new(point, point.to_xyz()))
+                    .collect(),
+                Vec::new(),
+            );
+
+            // This holds for any triangulation of points in general position.
+            assert_eq!(triangles.len(), 2 * points.len() - 2 - hull.len());
+
+            for triangle in triangles {
+                let triangle =
+                    Triangle::from_points(triangle.map(|p| *p.local_form()))
+                        .unwrap();
+                assert!(matches!(triangle.winding_direction(), Winding::Ccw));
+            }
+        }
+    }
+
+    #[test]
+    fn collinear_constraints() {
+        // `c` is a tiny distance to the left of the line from `a` to `b`. It's
+        // so close, that computing the area of the triangle naively results
+        // in zero.
+        let a = Point::from([0.7359699890685233, 0.6697304014402209]);
+        let b = Point::from([13.081364575891442, 16.059441656784625]);
+        let c = Point::from([7.699773009091724, 9.350774734494042]);
+        let d = Point::from([10., 0.]);
+
+        let [a, b, c, d] =
+            [a, b, c, d].map(|point| Local::new(point, point.to_xyz()));
+        let triangles =
+            super::triangulate(vec![a, b, c, d], vec![[a, b], [a, c], [c, b]]);
+
+        assert_eq!(triangles.len(), 2);
+    }
+}
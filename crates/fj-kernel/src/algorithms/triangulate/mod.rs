@@ -1,8 +1,16 @@
+mod cache;
 mod delaunay;
 mod polygon;
 
-use fj_interop::{debug::DebugInfo, mesh::Mesh};
-use fj_math::Point;
+pub use self::cache::TriangulationCache;
+
+use std::iter;
+
+use fj_interop::{
+    debug::{Approximation, DebugInfo},
+    mesh::{Color, Mesh},
+};
+use fj_math::{Point, Scalar, Triangle};
 
 use crate::objects::Face;
 
@@ -11,6 +19,10 @@ use self::polygon::Polygon;
 use super::{FaceApprox, Tolerance};
 
 /// Triangulate a shape
+///
+/// Triangles that are too thin to be useful are left out of the mesh. They are
+/// recorded in [`DebugInfo::degenerate_triangles`] instead, in case the caller
+/// is interested.
 pub fn triangulate(
     faces: Vec<Face>,
     tolerance: Tolerance,
@@ -18,45 +30,171 @@ pub fn triangulate(
 ) -> Mesh<Point<3>> {
     let mut mesh = Mesh::new();
 
-    for face in faces {
-        if let Some(triangles) = face.triangles() {
-            for &(triangle, color) in triangles {
-                mesh.push_triangle(triangle, color);
-            }
-            continue;
-        }
+    for triangulation in triangulate_faces(&faces, tolerance) {
+        push_face(&mut mesh, debug_info, triangulation);
+    }
+
+    mesh
+}
+
+/// The triangles of a face, and the debug info from triangulating it
+type FaceTriangulation = (Vec<(Triangle<3>, Color)>, DebugInfo);
+
+fn push_face(
+    mesh: &mut Mesh<Point<3>>,
+    debug_info: &mut DebugInfo,
+    (triangles, mut face_debug_info): FaceTriangulation,
+) {
+    for (triangle, color) in triangles {
+        mesh.push_triangle(triangle, color);
+    }
+
+    debug_info
+        .triangle_edge_checks
+        .append(&mut face_debug_info.triangle_edge_checks);
+    debug_info
+        .approximations
+        .append(&mut face_debug_info.approximations);
+    debug_info
+        .degenerate_triangles
+        .append(&mut face_debug_info.degenerate_triangles);
+}
+
+/// Triangulate faces one after the other
+#[cfg(not(feature = "parallel"))]
+fn triangulate_faces(
+    faces: &[Face],
+    tolerance: Tolerance,
+) -> Vec<FaceTriangulation> {
+    faces
+        .iter()
+        .map(|face| triangulate_face(face, tolerance))
+        .collect()
+}
+
+/// Triangulate faces in parallel
+///
+/// The results are returned in the order of the faces, so the resulting mesh
+/// is the same as with the sequential version.
+#[cfg(feature = "parallel")]
+fn triangulate_faces(
+    faces: &[Face],
+    tolerance: Tolerance,
+) -> Vec<FaceTriangulation> {
+    use rayon::prelude::*;
 
-        let surface = face.surface();
-        let approx = FaceApprox::new(&face, tolerance);
-
-        let points: Vec<_> = approx.points.into_iter().collect();
-        let face_as_polygon = Polygon::new(*surface)
-            .with_exterior(
-                approx
-                    .exterior
-                    .points
-                    .into_iter()
-                    .map(|point| *point.local_form()),
-            )
-            .with_interiors(approx.interiors.into_iter().map(|interior| {
-                interior.points.into_iter().map(|point| *point.local_form())
-            }));
-
-        let mut triangles = delaunay::triangulate(points);
-        triangles.retain(|triangle| {
-            face_as_polygon.contains_triangle(
-                triangle.map(|point| *point.local_form()),
-                debug_info,
-            )
+    faces
+        .par_iter()
+        .map(|face| triangulate_face(face, tolerance))
+        .collect()
+}
+
+fn triangulate_face(face: &Face, tolerance: Tolerance) -> FaceTriangulation {
+    let mut debug_info = DebugInfo::new();
+
+    if let Some(triangles) = face.triangles() {
+        return (triangles.clone(), debug_info);
+    }
+
+    let surface = face.surface();
+    let approx = FaceApprox::new(face, tolerance);
+
+    for cycle in iter::once(&approx.exterior).chain(&approx.interiors) {
+        debug_info.approximations.push(Approximation {
+            points: cycle
+                .points
+                .iter()
+                .map(|point| *point.global_form())
+                .collect(),
         });
+    }
+
+    let points: Vec<_> = approx.points.into_iter().collect();
+
+    // The edges of the cycles are constraints of the triangulation. This
+    // way, every triangle is either completely inside of the face, or
+    // completely outside of it, no matter its shape.
+    let constraints: Vec<_> = iter::once(&approx.exterior)
+        .chain(&approx.interiors)
+        .flat_map(|cycle| {
+            let points = &cycle.points;
+            let closing = match (points.first(), points.last()) {
+                (Some(first), Some(last)) if first != last => {
+                    Some([*last, *first])
+                }
+                _ => None,
+            };
+
+            points
+                .windows(2)
+                .map(|pair| [pair[0], pair[1]])
+                .chain(closing)
+                .collect::<Vec<_>>()
+        })
+        .collect();
+
+    let face_as_polygon = Polygon::new(surface.clone())
+        .with_exterior(
+            approx
+                .exterior
+                .points
+                .into_iter()
+                .map(|point| *point.local_form()),
+        )
+        .with_interiors(approx.interiors.into_iter().map(|interior| {
+            interior.points.into_iter().map(|point| *point.local_form())
+        }));
+
+    let mut triangles = delaunay::triangulate(points, constraints);
+    triangles.retain(|triangle| {
+        face_as_polygon.contains_triangle(
+            triangle.map(|point| *point.local_form()),
+            &mut debug_info,
+        )
+    });
+
+    let mut face_triangles = Vec::new();
+    for triangle in triangles {
+        let points = triangle.map(|point| *point.global_form());
 
-        for triangle in triangles {
-            let points = triangle.map(|point| *point.global_form());
-            mesh.push_triangle(points, face.color());
+        if is_degenerate(points) {
+            debug_info.degenerate_triangles.push(points);
+            continue;
         }
+
+        face_triangles.push((points.into(), face.color()));
     }
 
-    mesh
+    (face_triangles, debug_info)
+}
+
+/// Determine whether a triangle is a sliver
+///
+/// A triangle is considered degenerate, if its smallest height is tiny
+/// compared to its longest edge. Such triangles don't cover any meaningful
+/// area, but their normals are dominated by floating point error.
+fn is_degenerate(points: [Point<3>; 3]) -> bool {
+    // Relative to the longest edge, this is far below any tolerance that makes
+    // sense, but far above the precision of `f64`.
+    const MIN_HEIGHT_RATIO: f64 = 1e-9;
+
+    let [a, b, c] = points;
+    let longest_edge = [b - a, c - b, a - c]
+        .into_iter()
+        .map(|edge| edge.magnitude())
+        .reduce(Scalar::max)
+        .unwrap_or(Scalar::ZERO);
+
+    if longest_edge == Scalar::ZERO {
+        return true;
+    }
+
+    // Compute the area directly, instead of using `Triangle`, which can't
+    // represent a triangle without area.
+    let area = (b - a).cross(&(c - a)).magnitude() / 2.;
+    let min_height = area * 2. / longest_edge;
+
+    min_height < longest_edge * MIN_HEIGHT_RATIO
 }
 
 #[cfg(test)]
@@ -77,7 +215,8 @@ mod tests {
         let d = [0., 1.];
 
         let surface = Surface::xy_plane();
-        let face = Face::build(surface).polygon_from_points([a, b, c, d]);
+        let face =
+            Face::build(surface.clone()).polygon_from_points([a, b, c, d]);
 
         let a = Point::from(a).to_xyz();
         let b = Point::from(b).to_xyz();
@@ -107,7 +246,7 @@ mod tests {
         let h = [1., 2.];
 
         let surface = Surface::xy_plane();
-        let face = Face::build(surface)
+        let face = Face::build(surface.clone())
             .polygon_from_points([a, b, c, d])
             .with_hole([e, f, g, h]);
 
@@ -134,7 +273,6 @@ mod tests {
         Ok(())
     }
 
-    #[ignore]
     #[test]
     fn sharp_concave_shape() -> anyhow::Result<()> {
         //
@@ -157,7 +295,8 @@ mod tests {
         let e = Point::from([0., 0.8]);
 
         let surface = Surface::xy_plane();
-        let face = Face::build(surface).polygon_from_points([a, b, c, d, e]);
+        let face =
+            Face::build(surface.clone()).polygon_from_points([a, b, c, d, e]);
 
         let triangles = triangulate(face)?;
 
@@ -176,6 +315,127 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn concave_shape_with_hole() -> anyhow::Result<()> {
+        //
+        //   d ---------- c
+        //   |          /
+        //   |  h-g   /
+        //   |  | | n
+        //   |  e-f   \
+        //   |          \
+        //   a ---------- b
+        //
+        // Without the boundary being respected, triangles that connect the
+        // notch to the other side would cross it.
+
+        let [a, b, c, d] = [[0., 0.], [4., 0.], [4., 4.], [0., 4.]];
+        let n = [1.5, 2.];
+        let [e, f, g, h] = [[0.5, 1.5], [1., 1.5], [1., 2.5], [0.5, 2.5]];
+
+        let surface = Surface::xy_plane();
+        let face = Face::build(surface.clone())
+            .polygon_from_points([a, b, n, c, d])
+            .with_hole([e, h, g, f]);
+
+        let triangles = triangulate(face)?;
+
+        let area = triangles
+            .triangles()
+            .map(|triangle| triangle.inner.area())
+            .fold(Scalar::ZERO, |sum, area| sum + area);
+        let expected = Scalar::from(16. - 2.5 * 4. / 2. - 0.5);
+        assert!((area - expected).abs() < Scalar::from(1e-12));
+
+        Ok(())
+    }
+
+    #[test]
+    fn multiple_faces() -> anyhow::Result<()> {
+        let surface = Surface::xy_plane();
+        let faces: Vec<Face> = (0..8_u32)
+            .map(|i| {
+                let u = f64::from(i) * 2.;
+                Face::build(surface.clone())
+                    .polygon_from_points([
+                        [u, 0.],
+                        [u + 1., 0.],
+                        [u + 1., 1.],
+                        [u + 0.5, 0.5],
+                        [u, 1.],
+                    ])
+                    .into_face()
+            })
+            .collect();
+
+        let tolerance = Tolerance::from_scalar(Scalar::ONE)?;
+        let mesh =
+            super::triangulate(faces.clone(), tolerance, &mut DebugInfo::new());
+
+        // No matter how the faces are triangulated, their triangles end up in
+        // the mesh in the order of the faces.
+        let mut triangles = mesh.triangles();
+        for (i, face) in faces.into_iter().enumerate() {
+            let num_triangles = triangulate(face)?.triangles().count();
+            let u = f64::from(i as u32) * 2.;
+
+            for triangle in triangles.by_ref().take(num_triangles) {
+                assert!(triangle.inner.points().iter().all(|point| {
+                    point.x >= Scalar::from(u)
+                        && point.x <= Scalar::from(u + 1.)
+                }));
+            }
+        }
+        assert!(triangles.next().is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn degenerate_triangles() -> anyhow::Result<()> {
+        //
+        //   g ---------- f
+        //   |   i --- h  |
+        //   |    \   /   |
+        //   |     \ /    |
+        //   a ---- b --- c
+        //
+        // `b` is barely above the edge from `a` to `c`. Since that edge is a
+        // constraint, the triangulation has to connect `b` to both of its
+        // ends, creating a sliver.
+        //
+        // The offset is a power of two, so `b` survives the round trip through
+        // the curve coordinates of its edges exactly.
+        let [a, c, f, g] = [[0., 0.], [2., 0.], [2., 1.], [0., 1.]];
+        let [b, h, i] = [[1., 2f64.powi(-30)], [1.5, 0.5], [0.5, 0.5]];
+
+        let surface = Surface::xy_plane();
+        let face = Face::build(surface.clone())
+            .polygon_from_points([a, c, f, g])
+            .with_hole([b, h, i]);
+
+        let tolerance = Tolerance::from_scalar(Scalar::ONE)?;
+        let mut debug_info = DebugInfo::new();
+        let mesh =
+            super::triangulate(vec![face.into()], tolerance, &mut debug_info);
+
+        let [a, b, c] = [a, b, c].map(|point| Point::from(point).to_xyz());
+        assert_eq!(debug_info.degenerate_triangles.len(), 1);
+        for point in [a, b, c] {
+            assert!(debug_info.degenerate_triangles[0].contains(&point));
+        }
+
+        // The valid triangles remain, and still cover the whole face.
+        let area = mesh
+            .triangles()
+            .map(|triangle| triangle.inner.area())
+            .fold(Scalar::ZERO, |sum, area| sum + area);
+        let expected = Scalar::from(2. - 0.25);
+        assert!((area - expected).abs() < Scalar::from(1e-9));
+
+        Ok(())
+    }
+
     fn triangulate(face: impl Into<Face>) -> anyhow::Result<Mesh<Point<3>>> {
         let tolerance = Tolerance::from_scalar(Scalar::ONE)?;
 
@@ -1,13 +1,11 @@
 use fj_interop::debug::{DebugInfo, TriangleEdgeCheck};
-use fj_math::{Point, PolyChain, Segment};
-
-use crate::{
-    algorithms::intersect::{
-        ray_segment::RaySegmentIntersection, HorizontalRayToTheRight, Intersect,
-    },
-    objects::Surface,
+use fj_math::{
+    point_in_polygon, point_on_polygon_boundary, ray_crosses_edge, Point,
+    PolyChain, Segment,
 };
 
+use crate::objects::Surface;
+
 pub struct Polygon {
     surface: Surface,
     exterior: PolyChain<2>,
@@ -62,187 +60,67 @@ impl Polygon {
         self
     }
 
+    /// Check whether the polygon contains a triangle
+    ///
+    /// The triangles passed to this method come from a triangulation that is
+    /// constrained by the polygon's edges, which means they are either
+    /// completely inside of the polygon, or completely outside of it. Checking
+    /// the centroid is enough to tell which.
     pub fn contains_triangle(
         &self,
         triangle: [impl Into<Point<2>>; 3],
         debug_info: &mut DebugInfo,
     ) -> bool {
         let [a, b, c] = triangle.map(Into::into);
+        let centroid = a + ((b - a) + (c - a)) / 3.;
 
-        let mut might_be_hole = true;
-
-        for edge in [a, b, c, a].windows(2) {
-            // This can't panic, as we passed `2` to `windows`. It can be
-            // cleaned up a bit, once `array_windows` is stable.
-            let edge = Segment::from([edge[0], edge[1]]);
-
-            let is_exterior_edge = self.contains_exterior_edge(edge);
-            let is_interior_edge = self.contains_interior_edge(edge);
-
-            // If the triangle edge is not an interior edge of the polygon, we
-            // can rule out that the triangle is identical with a hole in the
-            // polygon.
-            if !is_interior_edge {
-                might_be_hole = false;
-            }
-
-            // If the triangle edge is an edge of the face, we don't need to
-            // take a closer look.
-            if is_exterior_edge || is_interior_edge {
-                continue;
-            }
-
-            // To determine if the edge is within the polygon, we determine if
-            // its center point is in the polygon.
-            //
-            // Since we already checked above, whether the triangle edge is a
-            // polygon edge (and if we reached this point, it isn't), we don't
-            // need to care about the distinction between "inside the polygon"
-            // and "on the polygon boundary".
-            if !self.contains_point(edge.center(), debug_info) {
-                // The segment is outside of the face. This means we can throw
-                // away the whole triangle.
-                return false;
-            }
-        }
-
-        // We haven't ruled out that the triangle is a polygon hole. Since we
-        // checked all its edges, this means we now know for certain that is is.
-        if might_be_hole {
-            return false;
-        }
-
-        // If we didn't throw away the triangle up till now, this means all its
-        // edges are within the face.
-        true
-    }
-
-    pub fn contains_exterior_edge(&self, edge: Segment<2>) -> bool {
-        self.exterior.segments().contains(&edge)
-            || self.exterior.segments().contains(&edge.reverse())
-    }
-
-    pub fn contains_interior_edge(&self, edge: Segment<2>) -> bool {
-        let mut contains = false;
-
-        for chain in &self.interiors {
-            contains |= chain.segments().contains(&edge);
-            contains |= chain.segments().contains(&edge.reverse());
-        }
-
-        contains
+        self.contains_point(centroid, debug_info)
     }
 
     /// Check whether the polygon contains a point
     ///
-    /// # Implementation Note
-    ///
-    /// This code is being duplicated by the `Contains<Point<2>>` implementation
-    /// for `Face`. It would be nice to be able to consolidate the duplication,
-    /// but this has turned out to be difficult.
+    /// Points on the boundary of the polygon, including the boundaries of its
+    /// holes, are considered to be contained.
     pub fn contains_point(
         &self,
         point: impl Into<Point<2>>,
         debug_info: &mut DebugInfo,
     ) -> bool {
-        let ray = HorizontalRayToTheRight {
-            origin: point.into(),
-        };
+        let point = point.into();
+
+        let chains = Some(&self.exterior).into_iter().chain(&self.interiors);
 
         let mut check = TriangleEdgeCheck::new(
-            self.surface.point_from_surface_coords(ray.origin),
+            self.surface.point_from_surface_coords(point),
         );
-
-        let mut num_hits = 0;
-
-        for chain in Some(&self.exterior).into_iter().chain(&self.interiors) {
-            let edges = chain.segments();
-
-            // We need to properly detect the ray passing the boundary at the
-            // "seam" of the polygon, i.e. the vertex between the last and the
-            // first segment. The logic in the loop properly takes care of that,
-            // as long as we initialize the `previous_hit` variable with the
-            // result of the last segment.
-            let mut previous_hit = edges
-                .last()
-                .copied()
-                .and_then(|edge| (&ray, &edge).intersect());
-
-            for edge in edges {
-                let hit = (&ray, &edge).intersect();
-
-                let count_hit = match (hit, previous_hit) {
-                    (
-                        Some(
-                            RaySegmentIntersection::RayStartsOnSegment
-                            | RaySegmentIntersection::RayStartsOnOnFirstVertex
-                            | RaySegmentIntersection::RayStartsOnSecondVertex,
-                        ),
-                        _,
-                    ) => {
-                        // If the ray starts on the boundary of the polygon,
-                        // there's nothing else to check. By the definition of
-                        // this intersection test, the polygon contains the
-                        // point.
-                        return true;
-                    }
-                    (Some(RaySegmentIntersection::RayHitsSegment), _) => {
-                        // We're hitting a segment right-on. Clear case.
-                        true
-                    }
-                    (
-                        Some(RaySegmentIntersection::RayHitsUpperVertex),
-                        Some(RaySegmentIntersection::RayHitsLowerVertex),
-                    )
-                    | (
-                        Some(RaySegmentIntersection::RayHitsLowerVertex),
-                        Some(RaySegmentIntersection::RayHitsUpperVertex),
-                    ) => {
-                        // If we're hitting a vertex, only count it if we've hit
-                        // the other kind of vertex right before.
-                        //
-                        // That means, we're passing through the polygon
-                        // boundary at where two edges touch. Depending on the
-                        // order in which edges are checked, we're seeing this
-                        // as a hit to one edge's lower/upper vertex, then the
-                        // other edge's opposite vertex.
-                        //
-                        // If we're seeing two of the same vertices in a row,
-                        // we're not actually passing through the polygon
-                        // boundary. Then we're just touching a vertex without
-                        // passing through anything.
-                        true
-                    }
-                    (Some(RaySegmentIntersection::RayHitsSegmentAndAreParallel), _) => {
-                        // A parallel edge must be completely ignored. Its
-                        // presence won't change anything, so we can treat it as
-                        // if it wasn't there, and its neighbors were connected
-                        // to each other.
-                        continue;
-                    }
-                    _ => {
-                        // Any other case is not a valid hit.
-                        false
-                    }
-                };
-
-                if count_hit {
-                    num_hits += 1;
-
-                    let edge =
-                        Segment::from_points(edge.points().map(|point| {
+        for chain in chains.clone() {
+            // Record the edges that `point_in_polygon` counts as crossed, so
+            // the check can be visualized.
+            for segment in chain.segments() {
+                if ray_crosses_edge(&point, segment.points()) {
+                    check.hits.push(Segment::from_points(
+                        segment.points().map(|point| {
                             self.surface.point_from_surface_coords(point)
-                        }));
-                    check.hits.push(edge);
+                        }),
+                    ));
                 }
-
-                previous_hit = hit;
             }
         }
-
         debug_info.triangle_edge_checks.push(check);
 
-        num_hits % 2 == 1
+        let mut contains = false;
+        for chain in chains {
+            if point_on_polygon_boundary(&point, chain.points()) {
+                return true;
+            }
+
+            // The point is inside of the polygon, if it's within the exterior,
+            // but not within any of the holes. Since holes don't overlap each
+            // other or the exterior, this can be computed like this.
+            contains ^= point_in_polygon(&point, chain.points());
+        }
+
+        contains
     }
 }
 
@@ -1,4 +1,4 @@
-use fj_math::{Circle, Line, Point, Scalar, Vector};
+use fj_math::{Circle, Ellipse, Line, Parabola, Point, Scalar, Vector};
 
 use crate::objects::{
     Curve, CurveKind, Edge, GlobalCurve, GlobalVertex, Surface, Vertex,
@@ -29,6 +29,166 @@ impl EdgeBuilder {
         )
     }
 
+    /// Create a circular arc from its end points and its center
+    ///
+    /// The arc goes around the center counterclockwise, or clockwise, as seen
+    /// from the direction the surface normal points to. If both end points are
+    /// the same, the arc is a full circle.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the first point coincides with the center.
+    pub fn arc_from_center(
+        &self,
+        surface: &Surface,
+        points: [impl Into<Point<2>>; 2],
+        center: impl Into<Point<2>>,
+        counterclockwise: bool,
+    ) -> Edge {
+        let [start, end] = points.map(Into::into);
+        let center = center.into();
+
+        let a = start - center;
+        let b = if counterclockwise {
+            Vector::from([-a.v, a.u])
+        } else {
+            Vector::from([a.v, -a.u])
+        };
+
+        // The angle from the start to the end point, in the direction of the
+        // arc.
+        let angle = {
+            let end = end - center;
+            let angle = Scalar::atan2(end.dot(&b), end.dot(&a));
+            if angle <= Scalar::ZERO {
+                angle + Scalar::PI * 2.
+            } else {
+                angle
+            }
+        };
+
+        let curve_local = CurveKind::Circle(Circle::new(center, a, b));
+        let curve_global =
+            GlobalCurve::from_kind(CurveKind::Circle(Circle::new(
+                surface.point_from_surface_coords(center),
+                surface.vector_from_surface_coords(a),
+                surface.vector_from_surface_coords(b),
+            )));
+
+        let vertices =
+            [(Scalar::ZERO, start), (angle, end)].map(|(position, point)| {
+                let global = GlobalVertex::from_position(
+                    surface.point_from_surface_coords(point),
+                );
+                Vertex::new(Point::from([position]), global)
+            });
+
+        Edge::new(
+            Curve::new(curve_local, curve_global),
+            VerticesOfEdge::from_vertices(vertices),
+        )
+    }
+
+    /// Create a circular arc that passes through three points
+    ///
+    /// The arc starts at the first point, passes through the second, and ends
+    /// at the third.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the points lie on a straight line.
+    pub fn arc_through_points(
+        &self,
+        surface: &Surface,
+        points: [impl Into<Point<2>>; 3],
+    ) -> Edge {
+        let [a, b, c] = points.map(Into::into);
+
+        let ab = b - a;
+        let ac = c - a;
+        let cross = ab.u * ac.v - ab.v * ac.u;
+        assert_ne!(cross, Scalar::ZERO, "points must not lie on a line");
+
+        // The circumcenter of the triangle, relative to `a`
+        let center = {
+            let [ab2, ac2] = [ab.dot(&ab), ac.dot(&ac)];
+            let u = (ac.v * ab2 - ab.v * ac2) / (cross * 2.);
+            let v = (ab.u * ac2 - ac.u * ab2) / (cross * 2.);
+            a + Vector::from([u, v])
+        };
+
+        self.arc_from_center(surface, [a, c], center, cross > Scalar::ZERO)
+    }
+
+    /// Create an ellipse from its center and its two axes
+    ///
+    /// The ellipse starts at the end of the first axis, and goes towards the
+    /// end of the second one.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the axes are not perpendicular, or if either of them has zero
+    /// length.
+    pub fn ellipse_from_axes(
+        &self,
+        surface: &Surface,
+        center: impl Into<Point<2>>,
+        axes: [impl Into<Vector<2>>; 2],
+    ) -> Edge {
+        let center = center.into();
+        let [a, b] = axes.map(Into::into);
+
+        let curve_local = CurveKind::Ellipse(Ellipse::new(center, a, b));
+        let curve_global =
+            GlobalCurve::from_kind(CurveKind::Ellipse(Ellipse::new(
+                surface.point_from_surface_coords(center),
+                surface.vector_from_surface_coords(a),
+                surface.vector_from_surface_coords(b),
+            )));
+
+        Edge::new(
+            Curve::new(curve_local, curve_global),
+            VerticesOfEdge::none(),
+        )
+    }
+
+    /// Create a parabolic segment from the control points of a quadratic Bézier
+    ///
+    /// The segment starts at the first point, ends at the last, and is pulled
+    /// towards the middle one.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the points lie on a straight line.
+    pub fn parabola_from_points(
+        &self,
+        surface: &Surface,
+        points: [impl Into<Point<2>>; 3],
+    ) -> Edge {
+        let points = points.map(Into::into);
+
+        let curve_local =
+            CurveKind::Parabola(Parabola::from_bezier_points(points));
+        let curve_global = GlobalCurve::from_kind(CurveKind::Parabola(
+            Parabola::from_bezier_points(
+                points.map(|point| surface.point_from_surface_coords(point)),
+            ),
+        ));
+
+        let [start, _, end] = points;
+        let vertices = [(0., start), (1., end)].map(|(position, point)| {
+            let global = GlobalVertex::from_position(
+                surface.point_from_surface_coords(point),
+            );
+            Vertex::new(Point::from([position]), global)
+        });
+
+        Edge::new(
+            Curve::new(curve_local, curve_global),
+            VerticesOfEdge::from_vertices(vertices),
+        )
+    }
+
     /// Create a line segment from two points
     pub fn line_segment_from_points(
         &self,
@@ -0,0 +1,11 @@
+//! Geometry that isn't part of any object yet
+//!
+//! The types in this module describe geometry that the kernel can evaluate and
+//! approximate, but that can't be referenced by [`Surface`] yet. Faces that are
+//! based on them use triangle representation for now.
+//!
+//! [`Surface`]: crate::objects::Surface
+
+mod nurbs;
+
+pub use self::nurbs::NurbsSurface;
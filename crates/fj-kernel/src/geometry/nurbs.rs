@@ -0,0 +1,495 @@
+use fj_math::{Point, Scalar, Transform, Vector};
+
+/// A non-uniform rational B-spline (NURBS) surface
+///
+/// The surface is defined by a rectangular grid of weighted control points. The
+/// outer dimension of the grid runs along the surface's `u` coordinate, the
+/// inner dimension along its `v` coordinate.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct NurbsSurface {
+    control_points: Vec<Vec<Point<3>>>,
+    weights: Vec<Vec<Scalar>>,
+    degree: [usize; 2],
+    knots: [Vec<Scalar>; 2],
+}
+
+impl NurbsSurface {
+    /// Construct a surface from its control points, weights, degrees and knots
+    ///
+    /// `degree` and `knots` contain the values for the `u` and `v` directions,
+    /// in that order.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the control points don't form a rectangular grid of at least
+    /// 2x2 points, if `weights` doesn't have the same shape, or if any weight
+    /// is not positive.
+    ///
+    /// Panics, if a degree is zero or too high for the number of control
+    /// points, if a knot vector doesn't have the length that the number of
+    /// control points and the degree require, or if it is decreasing anywhere.
+    pub fn new(
+        control_points: Vec<Vec<Point<3>>>,
+        weights: Vec<Vec<Scalar>>,
+        degree: [usize; 2],
+        knots: [Vec<Scalar>; 2],
+    ) -> Self {
+        let num_u = control_points.len();
+        let num_v = control_points.first().map(Vec::len).unwrap_or(0);
+
+        assert!(
+            num_u >= 2 && num_v >= 2,
+            "NURBS surface needs at least 2x2 control points"
+        );
+        assert!(
+            control_points.iter().all(|row| row.len() == num_v),
+            "NURBS control points must form a rectangular grid"
+        );
+        assert!(
+            weights.len() == num_u
+                && weights.iter().all(|row| row.len() == num_v),
+            "NURBS weights must match control points"
+        );
+        assert!(
+            weights
+                .iter()
+                .flatten()
+                .all(|&weight| weight > Scalar::ZERO),
+            "NURBS weights must be positive"
+        );
+
+        for ((degree, knots), num_points) in
+            degree.iter().zip(&knots).zip([num_u, num_v])
+        {
+            assert!(
+                *degree > 0 && *degree < num_points,
+                "NURBS degree must be between 1 and number of points minus 1"
+            );
+            assert_eq!(
+                knots.len(),
+                num_points + degree + 1,
+                "Unexpected number of knots for NURBS surface"
+            );
+            assert!(
+                knots.windows(2).all(|pair| pair[0] <= pair[1]),
+                "NURBS knots must not decrease"
+            );
+            assert!(
+                knots[*degree] < knots[num_points],
+                "NURBS surface domain must not be empty"
+            );
+        }
+
+        Self {
+            control_points,
+            weights,
+            degree,
+            knots,
+        }
+    }
+
+    /// Construct a non-rational surface with clamped, uniform knot vectors
+    ///
+    /// All weights are one. The surface covers the range from `0.` to `1.` in
+    /// both surface coordinates, and passes through the corner control points.
+    ///
+    /// If there aren't enough control points for a requested degree, the
+    /// highest degree that the control points allow for is used instead.
+    ///
+    /// # Panics
+    ///
+    /// Panics, under the same conditions as [`NurbsSurface::new`].
+    pub fn from_control_points(
+        control_points: impl IntoIterator<
+            Item = impl IntoIterator<Item = impl Into<Point<3>>>,
+        >,
+        degree: [usize; 2],
+    ) -> Self {
+        let control_points: Vec<Vec<_>> = control_points
+            .into_iter()
+            .map(|row| row.into_iter().map(Into::into).collect())
+            .collect();
+
+        let num_u = control_points.len();
+        let num_v = control_points.first().map(Vec::len).unwrap_or(0);
+
+        let weights = control_points
+            .iter()
+            .map(|row| vec![Scalar::ONE; row.len()])
+            .collect();
+
+        let degree = [
+            degree[0].min(num_u.saturating_sub(1)),
+            degree[1].min(num_v.saturating_sub(1)),
+        ];
+        let knots = [
+            clamped_uniform_knots(num_u, degree[0]),
+            clamped_uniform_knots(num_v, degree[1]),
+        ];
+
+        Self::new(control_points, weights, degree, knots)
+    }
+
+    /// Access the control points of the surface
+    pub fn control_points(&self) -> &[Vec<Point<3>>] {
+        &self.control_points
+    }
+
+    /// Access the weights of the control points
+    pub fn weights(&self) -> &[Vec<Scalar>] {
+        &self.weights
+    }
+
+    /// Access the degrees of the surface in the `u` and `v` directions
+    pub fn degree(&self) -> [usize; 2] {
+        self.degree
+    }
+
+    /// Access the knot vectors of the surface in the `u` and `v` directions
+    pub fn knots(&self) -> &[Vec<Scalar>; 2] {
+        &self.knots
+    }
+
+    /// Access the range of surface coordinates that the surface is defined in
+    ///
+    /// Returns the start and end of the range, for the `u` and `v` directions.
+    pub fn domain(&self) -> [[Scalar; 2]; 2] {
+        [0, 1].map(|i| {
+            let knots = &self.knots[i];
+            let degree = self.degree[i];
+            [knots[degree], knots[knots.len() - degree - 1]]
+        })
+    }
+
+    /// Access the number of polynomial pieces in the `u` and `v` directions
+    ///
+    /// Only pieces that cover a non-empty range are counted.
+    pub fn num_spans(&self) -> [usize; 2] {
+        [0, 1].map(|i| {
+            let knots = &self.knots[i];
+            let degree = self.degree[i];
+            knots[degree..knots.len() - degree]
+                .windows(2)
+                .filter(|pair| pair[0] < pair[1])
+                .count()
+        })
+    }
+
+    /// Convert a point in surface coordinates to model coordinates
+    ///
+    /// Coordinates outside of the surface's domain are clamped to it.
+    pub fn point_from_surface_coords(
+        &self,
+        point: impl Into<Point<2>>,
+    ) -> Point<3> {
+        self.evaluate(point.into()).0
+    }
+
+    /// Compute the partial derivatives at the given surface coordinates
+    ///
+    /// Returns the derivatives with respect to `u` and `v`, in that order.
+    pub fn partial_derivatives(
+        &self,
+        point: impl Into<Point<2>>,
+    ) -> [Vector<3>; 2] {
+        self.evaluate(point.into()).1
+    }
+
+    /// Compute the normal at the given surface coordinates
+    ///
+    /// The normal points into the direction of the cross product of the `u`
+    /// and `v` derivatives. Returns `None`, if the surface is degenerate at
+    /// this point, as it would be at the pole of a sphere.
+    pub fn normal(&self, point: impl Into<Point<2>>) -> Option<Vector<3>> {
+        let [du, dv] = self.partial_derivatives(point);
+        let normal = du.cross(&dv);
+
+        // The cross product of two derivatives can be tiny for perfectly
+        // regular surfaces, if they're small enough. Compare against their
+        // magnitudes, to only catch actual degeneracies.
+        let scale = du.magnitude() * dv.magnitude();
+        if scale == Scalar::ZERO
+            || normal.magnitude() <= scale * Scalar::from(1e-12)
+        {
+            return None;
+        }
+
+        Some(normal.normalize())
+    }
+
+    /// Create a new instance that is reversed
+    ///
+    /// The `u` direction of the surface is reversed, which flips its normal.
+    /// The domain stays the same.
+    #[must_use]
+    pub fn reverse(mut self) -> Self {
+        self.control_points.reverse();
+        self.weights.reverse();
+
+        let knots = &mut self.knots[0];
+        let sum = knots[0] + knots[knots.len() - 1];
+        knots.reverse();
+        for knot in knots {
+            *knot = sum - *knot;
+        }
+
+        self
+    }
+
+    /// Transform the surface
+    #[must_use]
+    pub fn transform(mut self, transform: &Transform) -> Self {
+        // NURBS are invariant under affine transformations, so transforming the
+        // control points is enough.
+        for point in self.control_points.iter_mut().flatten() {
+            *point = transform.transform_point(point);
+        }
+
+        self
+    }
+
+    fn evaluate(&self, point: Point<2>) -> (Point<3>, [Vector<3>; 2]) {
+        let [u, v] = [point.u, point.v];
+        let (span_u, basis_u, deriv_u) =
+            basis_functions(&self.knots[0], self.degree[0], u);
+        let (span_v, basis_v, deriv_v) =
+            basis_functions(&self.knots[1], self.degree[1], v);
+
+        // Evaluate in homogeneous coordinates. Index 0 is the value, 1 and 2
+        // are the derivatives with respect to `u` and `v`.
+        let mut points = [Vector::from([0., 0., 0.]); 3];
+        let mut weights = [Scalar::ZERO; 3];
+
+        for (i, (&nu, &du)) in basis_u.iter().zip(&deriv_u).enumerate() {
+            let row = span_u - self.degree[0] + i;
+
+            for (j, (&nv, &dv)) in basis_v.iter().zip(&deriv_v).enumerate() {
+                let column = span_v - self.degree[1] + j;
+
+                let weight = self.weights[row][column];
+                let point = self.control_points[row][column].coords * weight;
+
+                for (k, factor) in
+                    [nu * nv, du * nv, nu * dv].into_iter().enumerate()
+                {
+                    points[k] = points[k] + point * factor;
+                    weights[k] += weight * factor;
+                }
+            }
+        }
+
+        let position = points[0] / weights[0];
+        let derivatives =
+            [1, 2].map(|k| (points[k] - position * weights[k]) / weights[0]);
+
+        (Point { coords: position }, derivatives)
+    }
+}
+
+/// Compute the non-zero basis functions and their derivatives at `t`
+///
+/// Returns the index of the knot span that `t` falls into, followed by the
+/// `degree + 1` basis functions that are non-zero in that span, and their first
+/// derivatives.
+fn basis_functions(
+    knots: &[Scalar],
+    degree: usize,
+    t: Scalar,
+) -> (usize, Vec<Scalar>, Vec<Scalar>) {
+    let num_points = knots.len() - degree - 1;
+    let t = t.max(knots[degree]).min(knots[num_points]);
+
+    // The end of the domain belongs to the last non-empty span.
+    let mut span = degree;
+    while span < num_points - 1 && knots[span + 1] <= t {
+        span += 1;
+    }
+
+    // Build up the basis functions degree by degree, by distributing each
+    // function of the previous degree to the two functions it contributes to.
+    let mut basis = vec![Scalar::ONE];
+    let mut derivatives = vec![Scalar::ZERO];
+
+    for d in 1..=degree {
+        let mut next = vec![Scalar::ZERO; d + 1];
+        let mut next_derivatives = vec![Scalar::ZERO; d + 1];
+
+        for (r, &value) in basis.iter().enumerate() {
+            let i = span + 1 + r - d;
+            let denominator = knots[i + d] - knots[i];
+            if denominator == Scalar::ZERO {
+                continue;
+            }
+
+            let a = value / denominator;
+            next[r] += a * (knots[i + d] - t);
+            next[r + 1] += a * (t - knots[i]);
+
+            if d == degree {
+                let a = a * d as f64;
+                next_derivatives[r] = next_derivatives[r] - a;
+                next_derivatives[r + 1] += a;
+            }
+        }
+
+        basis = next;
+        derivatives = next_derivatives;
+    }
+
+    (span, basis, derivatives)
+}
+
+fn clamped_uniform_knots(num_points: usize, degree: usize) -> Vec<Scalar> {
+    let num_spans = num_points.saturating_sub(degree).max(1);
+
+    (0..num_points + degree + 1)
+        .map(|i| {
+            let i = i.max(degree).min(num_points) - degree;
+            Scalar::from(i as f64 / num_spans as f64)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_abs_diff_eq;
+    use fj_math::{Point, Scalar, Transform, Vector};
+
+    use super::NurbsSurface;
+
+    /// A quarter of a cylinder with radius 1, around the z-axis
+    fn quarter_cylinder() -> NurbsSurface {
+        let weight = Scalar::from(0.5_f64.sqrt());
+
+        NurbsSurface::new(
+            vec![
+                vec![Point::from([1., 0., 0.]), Point::from([1., 0., 1.])],
+                vec![Point::from([1., 1., 0.]), Point::from([1., 1., 1.])],
+                vec![Point::from([0., 1., 0.]), Point::from([0., 1., 1.])],
+            ],
+            vec![
+                vec![Scalar::ONE, Scalar::ONE],
+                vec![weight, weight],
+                vec![Scalar::ONE, Scalar::ONE],
+            ],
+            [2, 1],
+            [
+                [0., 0., 0., 1., 1., 1.].map(Scalar::from).to_vec(),
+                [0., 0., 1., 1.].map(Scalar::from).to_vec(),
+            ],
+        )
+    }
+
+    #[test]
+    fn bilinear() {
+        let surface = NurbsSurface::from_control_points(
+            [[[0., 0., 0.], [0., 2., 0.]], [[1., 0., 0.], [1., 2., 1.]]],
+            [3, 3],
+        );
+
+        assert_eq!(surface.degree(), [1, 1]);
+        assert_eq!(
+            surface.point_from_surface_coords([0.5, 0.5]),
+            Point::from([0.5, 1., 0.25])
+        );
+        assert_eq!(
+            surface.partial_derivatives([0., 0.]),
+            [Vector::from([1., 0., 0.]), Vector::from([0., 2., 0.])]
+        );
+        assert_eq!(surface.normal([0., 0.]), Some(Vector::from([0., 0., 1.])));
+    }
+
+    #[test]
+    fn rational() {
+        let surface = quarter_cylinder();
+
+        for u in [0., 0.2, 0.5, 0.9, 1.] {
+            for v in [0., 0.5, 1.] {
+                let point = surface.point_from_surface_coords([u, v]);
+                let radius = Vector::from([point.x, point.y, Scalar::ZERO]);
+
+                assert_abs_diff_eq!(
+                    radius.magnitude(),
+                    Scalar::ONE,
+                    epsilon = Scalar::from(1e-12),
+                );
+                assert_abs_diff_eq!(
+                    point.z,
+                    Scalar::from(v),
+                    epsilon = Scalar::from(1e-12),
+                );
+
+                // The normal of a cylinder points away from its axis.
+                assert_abs_diff_eq!(
+                    surface.normal([u, v]).unwrap(),
+                    radius,
+                    epsilon = Scalar::from(1e-12),
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn partial_derivatives() {
+        let surface = NurbsSurface::from_control_points(
+            [
+                [[0., 0., 0.], [0., 1., 1.], [0., 2., 0.], [0., 3., 2.]],
+                [[1., 0., 1.], [1., 1., -1.], [1., 2., 2.], [1., 3., 0.]],
+                [[2., 0., 0.], [2., 1., 3.], [2., 2., 1.], [2., 3., -1.]],
+            ],
+            [2, 2],
+        );
+        let surface = NurbsSurface::new(
+            surface.control_points().to_vec(),
+            vec![
+                [1., 2., 1., 1.].map(Scalar::from).to_vec(),
+                [0.5, 1., 3., 1.].map(Scalar::from).to_vec(),
+                [1., 1., 1., 2.].map(Scalar::from).to_vec(),
+            ],
+            surface.degree(),
+            surface.knots().clone(),
+        );
+
+        let h = 1e-6;
+        for [u, v] in [[0.1, 0.2], [0.5, 0.5], [0.7, 0.8]] {
+            let [du, dv] = surface.partial_derivatives([u, v]);
+
+            let expected_du = (surface.point_from_surface_coords([u + h, v])
+                - surface.point_from_surface_coords([u - h, v]))
+                / (2. * h);
+            let expected_dv = (surface.point_from_surface_coords([u, v + h])
+                - surface.point_from_surface_coords([u, v - h]))
+                / (2. * h);
+
+            assert_abs_diff_eq!(du, expected_du, epsilon = Scalar::from(1e-4));
+            assert_abs_diff_eq!(dv, expected_dv, epsilon = Scalar::from(1e-4));
+        }
+    }
+
+    #[test]
+    fn reverse_and_transform() {
+        let surface = quarter_cylinder();
+
+        let reversed = surface.clone().reverse();
+        assert_eq!(reversed.domain(), surface.domain());
+        assert_abs_diff_eq!(
+            reversed.point_from_surface_coords([0.25, 0.5]),
+            surface.point_from_surface_coords([0.75, 0.5]),
+            epsilon = Scalar::from(1e-12),
+        );
+        assert_abs_diff_eq!(
+            reversed.normal([0.25, 0.5]).unwrap(),
+            -surface.normal([0.75, 0.5]).unwrap(),
+            epsilon = Scalar::from(1e-12),
+        );
+
+        let transform = Transform::translation([0., 0., 2.]);
+        let transformed = surface.clone().transform(&transform);
+        assert_abs_diff_eq!(
+            transformed.point_from_surface_coords([0.5, 0.5]),
+            surface.point_from_surface_coords([0.5, 0.5])
+                + Vector::from([0., 0., 2.]),
+            epsilon = Scalar::from(1e-12),
+        );
+    }
+}
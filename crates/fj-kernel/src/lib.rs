@@ -89,6 +89,7 @@
 
 pub mod algorithms;
 pub mod builder;
+pub mod geometry;
 pub mod iter;
 pub mod objects;
 pub mod validation;
@@ -1,4 +1,6 @@
-use fj_math::{Circle, Line, Point, Transform, Vector};
+use fj_math::{
+    Circle, Ellipse, Line, Parabola, Point, Scalar, Transform, Vector,
+};
 
 use crate::builder::{CurveBuilder, GlobalCurveBuilder};
 
@@ -74,8 +76,14 @@ pub enum CurveKind<const D: usize> {
     /// A circle
     Circle(Circle<D>),
 
+    /// An ellipse
+    Ellipse(Ellipse<D>),
+
     /// A line
     Line(Line<D>),
+
+    /// A parabola
+    Parabola(Parabola<D>),
 }
 
 impl<const D: usize> CurveKind<D> {
@@ -88,7 +96,9 @@ impl<const D: usize> CurveKind<D> {
     pub fn origin(&self) -> Point<D> {
         match self {
             Self::Circle(curve) => curve.center(),
+            Self::Ellipse(curve) => curve.center(),
             Self::Line(curve) => curve.origin(),
+            Self::Parabola(curve) => curve.origin(),
         }
     }
 
@@ -97,7 +107,9 @@ impl<const D: usize> CurveKind<D> {
     pub fn reverse(self) -> Self {
         match self {
             Self::Circle(curve) => Self::Circle(curve.reverse()),
+            Self::Ellipse(curve) => Self::Ellipse(curve.reverse()),
             Self::Line(curve) => Self::Line(curve.reverse()),
+            Self::Parabola(curve) => Self::Parabola(curve.reverse()),
         }
     }
 
@@ -108,7 +120,9 @@ impl<const D: usize> CurveKind<D> {
     ) -> Point<D> {
         match self {
             Self::Circle(curve) => curve.point_from_circle_coords(point),
+            Self::Ellipse(curve) => curve.point_from_ellipse_coords(point),
             Self::Line(curve) => curve.point_from_line_coords(point),
+            Self::Parabola(curve) => curve.point_from_parabola_coords(point),
         }
     }
 
@@ -119,7 +133,31 @@ impl<const D: usize> CurveKind<D> {
     ) -> Vector<D> {
         match self {
             Self::Circle(curve) => curve.vector_from_circle_coords(point),
+            Self::Ellipse(curve) => curve.vector_from_ellipse_coords(point),
             Self::Line(curve) => curve.vector_from_line_coords(point),
+            Self::Parabola(curve) => curve.vector_from_parabola_coords(point),
+        }
+    }
+
+    /// Compute the tangent of the curve at the given point
+    ///
+    /// The tangent is the derivative of the curve with respect to its curve
+    /// coordinate. Its length is only constant for lines and circles.
+    pub fn tangent_from_curve_coords(
+        &self,
+        point: impl Into<Point<1>>,
+    ) -> Vector<D> {
+        let point = point.into();
+
+        match self {
+            Self::Circle(curve) => {
+                // The derivative of a circle is the circle, rotated by a
+                // quarter turn.
+                curve.vector_from_circle_coords([point.t + Scalar::PI / 2.])
+            }
+            Self::Ellipse(curve) => curve.tangent_from_ellipse_coords(point),
+            Self::Line(curve) => curve.direction(),
+            Self::Parabola(curve) => curve.tangent_from_parabola_coords(point),
         }
     }
 }
@@ -156,9 +194,15 @@ impl CurveKind<3> {
             CurveKind::Circle(curve) => {
                 CurveKind::Circle(transform.transform_circle(&curve))
             }
+            CurveKind::Ellipse(curve) => {
+                CurveKind::Ellipse(transform.transform_ellipse(&curve))
+            }
             CurveKind::Line(curve) => {
                 CurveKind::Line(transform.transform_line(&curve))
             }
+            CurveKind::Parabola(curve) => {
+                CurveKind::Parabola(transform.transform_parabola(&curve))
+            }
         }
     }
 }
@@ -1,6 +1,6 @@
 use crate::builder::CycleBuilder;
 
-use super::{Edge, Surface};
+use super::{Edge, Surface, Vertex};
 
 /// A cycle of connected edges
 ///
@@ -52,8 +52,54 @@ impl Cycle {
         self.edges.iter()
     }
 
+    /// Access the vertices of the cycle, in order
+    ///
+    /// Yields the start vertex of each edge. Since the cycle is closed, this
+    /// covers every vertex exactly once. Edges without vertices (like a circle
+    /// that closes on itself) don't contribute any vertices.
+    pub fn vertices(&self) -> impl Iterator<Item = Vertex> + '_ {
+        self.edges
+            .iter()
+            .filter_map(|edge| edge.vertices().get().map(|[&a, _]| a))
+    }
+
     /// Consume the cycle and return its edges
     pub fn into_edges(self) -> impl Iterator<Item = Edge> {
         self.edges.into_iter()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Point, Scalar};
+
+    use crate::objects::{Edge, Surface};
+
+    use super::Cycle;
+
+    #[test]
+    fn vertices() {
+        let cycle = Cycle::build(Surface::xy_plane()).polygon_from_points([
+            [0., 0.],
+            [1., 0.],
+            [0., 1.],
+        ]);
+
+        let vertices: Vec<_> = cycle
+            .vertices()
+            .map(|vertex| vertex.global().position())
+            .collect();
+        assert_eq!(
+            vertices,
+            [[0., 0., 0.], [1., 0., 0.], [0., 1., 0.]].map(Point::from),
+        );
+    }
+
+    #[test]
+    fn vertices_of_continuous_edge() {
+        let cycle = Cycle::new(Surface::xy_plane())
+            .with_edges([Edge::build().circle_from_radius(Scalar::ONE)]);
+
+        assert_eq!(cycle.vertices().count(), 0);
+    }
+}
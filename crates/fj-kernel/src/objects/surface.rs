@@ -1,12 +1,22 @@
 use fj_math::{Line, Point, Transform, Vector};
 
+use crate::geometry::NurbsSurface;
+
 use super::CurveKind;
 
 /// A two-dimensional shape
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
 pub enum Surface {
     /// A swept curve
     SweptCurve(SweptCurve),
+
+    /// A NURBS surface
+    ///
+    /// Faces on a NURBS surface are approximated from the surface itself, not
+    /// just from their edges. See [`FaceApprox`].
+    ///
+    /// [`FaceApprox`]: crate::algorithms::FaceApprox
+    Nurbs(Box<NurbsSurface>),
 }
 
 impl Surface {
@@ -44,11 +54,17 @@ impl Surface {
         Self::SweptCurve(SweptCurve { curve, path })
     }
 
+    /// Construct a `Surface` from a NURBS surface
+    pub fn nurbs(surface: NurbsSurface) -> Self {
+        Self::Nurbs(Box::new(surface))
+    }
+
     /// Create a new instance that is reversed
     #[must_use]
     pub fn reverse(self) -> Self {
         match self {
             Self::SweptCurve(surface) => Self::SweptCurve(surface.reverse()),
+            Self::Nurbs(surface) => Self::nurbs(surface.reverse()),
         }
     }
 
@@ -61,10 +77,15 @@ impl Surface {
             Self::SweptCurve(surface) => {
                 surface.point_from_surface_coords(point)
             }
+            Self::Nurbs(surface) => surface.point_from_surface_coords(point),
         }
     }
 
     /// Convert a vector in surface coordinates to model coordinates
+    ///
+    /// A NURBS surface is curved, so the same vector maps to different vectors
+    /// at different points. Its partial derivatives at the start of its domain
+    /// are used, as if the surface were a plane there.
     pub fn vector_from_surface_coords(
         &self,
         vector: impl Into<Vector<2>>,
@@ -73,6 +94,13 @@ impl Surface {
             Self::SweptCurve(surface) => {
                 surface.vector_from_surface_coords(vector)
             }
+            Self::Nurbs(surface) => {
+                let vector = vector.into();
+                let [[u, _], [v, _]] = surface.domain();
+                let [du, dv] = surface.partial_derivatives([u, v]);
+
+                du * vector.u + dv * vector.v
+            }
         }
     }
 }
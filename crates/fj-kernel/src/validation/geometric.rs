@@ -0,0 +1,268 @@
+use std::{collections::HashSet, fmt};
+
+use fj_math::{Aabb, Point, Scalar, Segment};
+
+use crate::{
+    algorithms::{
+        sketch_self_intersections, solid_self_intersections, CycleApprox,
+        Quality, Tolerance,
+    },
+    objects::{CurveKind, Cycle, Face, GlobalVertex, Sketch, Solid},
+};
+
+pub fn validate_cycle(cycle: &Cycle) -> Result<(), GeometricIssues> {
+    let mut issues = GeometricIssues::default();
+
+    let points = approx_cycle(cycle);
+    let segments: Vec<_> = (0..points.len())
+        .map(|i| [points[i], points[(i + 1) % points.len()]])
+        // A cycle that consists of a single point has no segments.
+        .filter(|[a, b]| a != b)
+        .map(Segment::from_points)
+        .collect();
+
+    for (i, &a) in segments.iter().enumerate() {
+        for (j, &b) in segments.iter().enumerate().skip(i + 2) {
+            // The first and last segments are neighbors too.
+            if i == 0 && j == segments.len() - 1 {
+                continue;
+            }
+
+            if a.intersect(&b).is_some() {
+                issues.self_intersections.push([a, b]);
+            }
+        }
+    }
+
+    issues.into_result()
+}
+
+pub fn validate_face(face: &Face) -> Result<(), GeometricIssues> {
+    let mut issues = GeometricIssues::default();
+
+    // All exteriors must wind around the face in the same direction, and all
+    // interiors in the other one.
+    if let Some(exterior) = face.exteriors().next() {
+        let exteriors_ccw = is_ccw(exterior);
+
+        let misoriented = face
+            .exteriors()
+            .filter(|cycle| is_ccw(cycle) != exteriors_ccw)
+            .chain(
+                face.interiors()
+                    .filter(|cycle| is_ccw(cycle) == exteriors_ccw),
+            );
+        issues.misoriented_cycles.extend(misoriented.cloned());
+    }
+
+    issues.into_result()
+}
+
+pub fn validate_sketch(sketch: &Sketch) -> Result<(), GeometricIssues> {
+    let mut issues = GeometricIssues::default();
+
+    let points = sketch
+        .faces()
+        .flat_map(|face| face.all_cycles())
+        .flat_map(points_on_cycle);
+    if let Some(tolerance) = tolerance_for(points) {
+        issues
+            .self_intersections
+            .extend(sketch_self_intersections(sketch, tolerance));
+    }
+
+    issues.into_result()
+}
+
+pub fn validate_solid(solid: &Solid) -> Result<(), GeometricIssues> {
+    let mut issues = GeometricIssues::default();
+
+    let points = solid.faces().flat_map(|face| match face.triangles() {
+        Some(triangles) => triangles
+            .iter()
+            .flat_map(|(triangle, _)| triangle.points())
+            .collect::<Vec<_>>(),
+        None => face.all_cycles().flat_map(points_on_cycle).collect(),
+    });
+    if let Some(tolerance) = tolerance_for(points) {
+        issues
+            .intersecting_faces
+            .extend(solid_self_intersections(solid, tolerance));
+    }
+
+    // A face is oriented by its surface, and by the direction its exteriors
+    // wind around it in. Taking that into account, neighboring faces of a
+    // consistently oriented solid go along their shared edge in opposite
+    // directions. If they go along it in the same one, one of them faces the
+    // wrong way.
+    let mut directed_edges = HashSet::new();
+    let mut inconsistent = HashSet::new();
+    for face in solid.faces().filter(|face| face.triangles().is_none()) {
+        let is_ccw = match face.exteriors().next() {
+            Some(exterior) => is_ccw(exterior),
+            None => continue,
+        };
+
+        for edge in face.all_cycles().flat_map(|cycle| cycle.edges()) {
+            // Curved edges can share their vertices with other edges, so only
+            // straight ones are identified by them.
+            if !matches!(edge.curve().global().kind(), CurveKind::Line(_)) {
+                continue;
+            }
+
+            if let Some([a, b]) = edge.vertices().get() {
+                let [a, b] = [a, b].map(|vertex| *vertex.global());
+                let directed_edge = if is_ccw { [a, b] } else { [b, a] };
+
+                if !directed_edges.insert(directed_edge) {
+                    inconsistent.insert(directed_edge);
+                }
+            }
+        }
+    }
+    issues.inconsistently_oriented_edges.extend(inconsistent);
+
+    issues.into_result()
+}
+
+/// Geometric issues found during validation
+///
+/// Used by [`ValidationError`].
+///
+/// [`ValidationError`]: super::ValidationError
+#[derive(Debug, Default, thiserror::Error)]
+pub struct GeometricIssues {
+    /// Pairs of segments of approximated cycles, that intersect each other
+    ///
+    /// The segments are in the coordinates of the surface the cycles are in.
+    pub self_intersections: Vec<[Segment<2>; 2]>,
+
+    /// Pairs of faces of a solid that intersect each other
+    pub intersecting_faces: Vec<[Face; 2]>,
+
+    /// Cycles that wind around their face in the wrong direction
+    pub misoriented_cycles: Vec<Cycle>,
+
+    /// Edges where inconsistently oriented faces meet
+    pub inconsistently_oriented_edges: Vec<[GlobalVertex; 2]>,
+}
+
+impl GeometricIssues {
+    fn into_result(self) -> Result<(), Self> {
+        if self.self_intersections.is_empty()
+            && self.intersecting_faces.is_empty()
+            && self.misoriented_cycles.is_empty()
+            && self.inconsistently_oriented_edges.is_empty()
+        {
+            return Ok(());
+        }
+
+        Err(self)
+    }
+}
+
+impl fmt::Display for GeometricIssues {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Geometric issues found:")?;
+
+        if !self.self_intersections.is_empty() {
+            writeln!(f, "- Self-intersecting cycles:")?;
+
+            for [a, b] in &self.self_intersections {
+                writeln!(f, "  - {:?} intersects {:?}", a, b)?;
+            }
+        }
+        if !self.intersecting_faces.is_empty() {
+            writeln!(f, "- Intersecting faces:")?;
+
+            for [a, b] in &self.intersecting_faces {
+                writeln!(
+                    f,
+                    "  - face in {:?} intersects face in {:?}",
+                    a.surface(),
+                    b.surface()
+                )?;
+            }
+        }
+        if !self.misoriented_cycles.is_empty() {
+            writeln!(
+                f,
+                "- {} cycles wind around their face in the wrong direction",
+                self.misoriented_cycles.len()
+            )?;
+        }
+        if !self.inconsistently_oriented_edges.is_empty() {
+            writeln!(f, "- Inconsistently oriented faces meet at edges:")?;
+
+            for [a, b] in &self.inconsistently_oriented_edges {
+                writeln!(
+                    f,
+                    "  - from {:?} to {:?}",
+                    a.position(),
+                    b.position()
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Approximate a cycle in surface coordinates, without repeating the first
+/// point at the end
+fn approx_cycle(cycle: &Cycle) -> Vec<Point<2>> {
+    let tolerance = match tolerance_for(points_on_cycle(cycle)) {
+        Some(tolerance) => tolerance,
+        None => return Vec::new(),
+    };
+
+    let mut points: Vec<_> = CycleApprox::new(cycle, tolerance)
+        .points
+        .into_iter()
+        .map(|point| *point.local_form())
+        .collect();
+    if points.len() > 1 && points.first() == points.last() {
+        points.pop();
+    }
+
+    points
+}
+
+/// Pick a tolerance that is good enough to tell the shape of an object, based
+/// on the extent of some points on it
+fn tolerance_for(
+    points: impl IntoIterator<Item = Point<3>>,
+) -> Option<Tolerance> {
+    let extent = Aabb::<3>::from_points(points);
+    Tolerance::from_quality(Quality::default(), &extent).ok()
+}
+
+fn points_on_cycle(cycle: &Cycle) -> Vec<Point<3>> {
+    cycle
+        .edges()
+        .flat_map(|edge| {
+            let curve = edge.curve().global().kind();
+            let coords = match edge.vertices().get() {
+                Some(vertices) => {
+                    vertices.map(|vertex| vertex.position().t).to_vec()
+                }
+                None => [0., 0.5, 1., 1.5].map(|t| Scalar::PI * t).to_vec(),
+            };
+            coords
+                .into_iter()
+                .map(|t| curve.point_from_curve_coords([t]))
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+fn is_ccw(cycle: &Cycle) -> bool {
+    signed_area(&approx_cycle(cycle)) > Scalar::ZERO
+}
+
+fn signed_area(points: &[Point<2>]) -> Scalar {
+    (0..points.len()).fold(Scalar::ZERO, |area, i| {
+        let [a, b] = [points[i], points[(i + 1) % points.len()]];
+        area + (a.u * b.v - b.u * a.v) / 2.
+    })
+}
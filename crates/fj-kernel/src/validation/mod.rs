@@ -7,18 +7,23 @@
 //!   canonical forms.
 //! - **Geometric:** Comprises various object-specific constraints, for example
 //!   edges or faces might not be allowed to intersect.
-//! - **Structural:** All other objects that an object references must be part
-//!   of the same shape.
+//! - **Structural:** Objects must be connected in the way their type requires,
+//!   for example the edges of a cycle must connect to each other, and must be
+//!   in the same surface as the objects that reference them.
 //! - **Uniqueness:** Objects within a shape must be unique.
 //!
 //! Please note that not all of these validation categories are fully
 //! implemented, as of this writing.
 
 mod coherence;
+mod geometric;
+mod structural;
 mod uniqueness;
 
 pub use self::{
     coherence::{CoherenceIssues, CoherenceMismatch},
+    geometric::GeometricIssues,
+    structural::StructuralIssues,
     uniqueness::UniquenessIssues,
 };
 
@@ -52,6 +57,28 @@ where
         coherence::validate_edge(edge, config.identical_max_distance)?;
     }
 
+    for cycle in object.cycle_iter() {
+        structural::validate_cycle(cycle, config.distinct_min_distance)?;
+        uniqueness::validate_cycle(cycle)?;
+        geometric::validate_cycle(cycle)?;
+    }
+
+    // Faces that are represented by triangles don't have cycles to check.
+    for face in object.face_iter().filter(|face| face.triangles().is_none()) {
+        structural::validate_face(face)?;
+        uniqueness::validate_face(face)?;
+        geometric::validate_face(face)?;
+    }
+
+    for sketch in object.sketch_iter() {
+        structural::validate_sketch(sketch)?;
+        geometric::validate_sketch(sketch)?;
+    }
+
+    for solid in object.solid_iter() {
+        geometric::validate_solid(solid)?;
+    }
+
     Ok(Validated(object))
 }
 
@@ -118,7 +145,11 @@ pub enum ValidationError {
 
     /// Geometric validation failed
     #[error("Geometric validation failed")]
-    Geometric,
+    Geometric(#[from] GeometricIssues),
+
+    /// Structural validation failed
+    #[error("Structural validation failed")]
+    Structural(#[from] StructuralIssues),
 
     /// Uniqueness validation failed
     #[error("Uniqueness validation failed")]
@@ -131,8 +162,8 @@ mod tests {
 
     use crate::{
         objects::{
-            Curve, CurveKind, Edge, GlobalCurve, GlobalVertex, Vertex,
-            VerticesOfEdge,
+            Curve, CurveKind, Cycle, Edge, Face, GlobalCurve, GlobalVertex,
+            Surface, Vertex, VerticesOfEdge,
         },
         validation::{validate, ValidationConfig, ValidationError},
     };
@@ -206,4 +237,29 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn structural_cycle_gap() {
+        let surface = Surface::xy_plane();
+        let edges = [
+            [[0., 0.], [1., 0.]],
+            [[1., 0.], [0., 1.]],
+            [[0., 2.], [0., 0.]],
+        ]
+        .map(|points| Edge::build().line_segment_from_points(&surface, points));
+        let cycle = Cycle::new(surface).with_edges(edges);
+
+        let result = validate(cycle, &ValidationConfig::default());
+        assert!(matches!(result, Err(ValidationError::Structural(_))));
+    }
+
+    #[test]
+    fn geometric_self_intersection() {
+        let face = Face::build(Surface::xy_plane())
+            .polygon_from_points([[0., 0.], [1., 0.], [0., 1.], [1., 1.]])
+            .into_face();
+
+        let result = validate(face, &ValidationConfig::default());
+        assert!(matches!(result, Err(ValidationError::Geometric(_))));
+    }
 }
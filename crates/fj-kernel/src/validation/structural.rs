@@ -0,0 +1,134 @@
+use std::fmt;
+
+use fj_math::{Point, Scalar};
+
+use crate::objects::{Cycle, Face, Sketch, Surface};
+
+pub fn validate_cycle(
+    cycle: &Cycle,
+    max_distance: impl Into<Scalar>,
+) -> Result<(), StructuralIssues> {
+    let max_distance = max_distance.into();
+
+    let mut issues = StructuralIssues::default();
+
+    let edges: Vec<_> = cycle.edges().collect();
+    for (i, edge) in edges.iter().enumerate() {
+        let next = edges[(i + 1) % edges.len()];
+
+        // An edge without vertices connects to itself, so it only makes sense
+        // as the only edge of its cycle.
+        let (end, start) = match (edge.vertices().get(), next.vertices().get())
+        {
+            (Some([_, end]), Some([start, _])) => (end, start),
+            (None, None) if edges.len() == 1 => continue,
+            _ => {
+                issues.unbounded_edges_in_cycles += 1;
+                continue;
+            }
+        };
+
+        let [end, start] =
+            [end, start].map(|vertex| vertex.global().position());
+        if (end - start).magnitude() > max_distance {
+            issues.cycle_gaps.push([end, start]);
+        }
+    }
+
+    issues.into_result()
+}
+
+pub fn validate_face(face: &Face) -> Result<(), StructuralIssues> {
+    let mut issues = StructuralIssues::default();
+
+    for cycle in face.all_cycles() {
+        if cycle.surface() != face.surface() {
+            issues
+                .surface_mismatches
+                .push([face.surface().clone(), cycle.surface().clone()]);
+        }
+    }
+
+    issues.into_result()
+}
+
+pub fn validate_sketch(sketch: &Sketch) -> Result<(), StructuralIssues> {
+    let mut issues = StructuralIssues::default();
+
+    let mut faces = sketch.faces();
+    if let Some(first) = faces.next() {
+        for face in faces {
+            if face.surface() != first.surface() {
+                issues
+                    .surface_mismatches
+                    .push([first.surface().clone(), face.surface().clone()]);
+            }
+        }
+    }
+
+    issues.into_result()
+}
+
+/// Structural issues found during validation
+///
+/// Used by [`ValidationError`].
+///
+/// [`ValidationError`]: super::ValidationError
+#[derive(Debug, Default, thiserror::Error)]
+pub struct StructuralIssues {
+    /// Gaps between the edges of cycles
+    ///
+    /// Each gap goes from the end of one edge, to the start of the next.
+    pub cycle_gaps: Vec<[Point<3>; 2]>,
+
+    /// The number of edges without vertices that share a cycle with others
+    pub unbounded_edges_in_cycles: usize,
+
+    /// Objects that are in a different surface than the object they belong to
+    ///
+    /// Each entry holds the expected surface, then the actual one.
+    pub surface_mismatches: Vec<[Surface; 2]>,
+}
+
+impl StructuralIssues {
+    fn into_result(self) -> Result<(), Self> {
+        if self.cycle_gaps.is_empty()
+            && self.unbounded_edges_in_cycles == 0
+            && self.surface_mismatches.is_empty()
+        {
+            return Ok(());
+        }
+
+        Err(self)
+    }
+}
+
+impl fmt::Display for StructuralIssues {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Structural issues found:")?;
+
+        if !self.cycle_gaps.is_empty() {
+            writeln!(f, "- Gaps between the edges of cycles:")?;
+
+            for [end, start] in &self.cycle_gaps {
+                writeln!(f, "  - from {:?} to {:?}", end, start)?;
+            }
+        }
+        if self.unbounded_edges_in_cycles > 0 {
+            writeln!(
+                f,
+                "- {} edges without vertices share a cycle with other edges",
+                self.unbounded_edges_in_cycles
+            )?;
+        }
+        if !self.surface_mismatches.is_empty() {
+            writeln!(f, "- Objects in the wrong surface:")?;
+
+            for [expected, actual] in &self.surface_mismatches {
+                writeln!(f, "  - expected {:?}, got {:?}", expected, actual)?;
+            }
+        }
+
+        Ok(())
+    }
+}
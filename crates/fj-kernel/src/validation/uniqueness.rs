@@ -2,7 +2,7 @@ use std::{collections::HashSet, fmt};
 
 use fj_math::Scalar;
 
-use crate::objects::GlobalVertex;
+use crate::objects::{Cycle, Edge, Face, GlobalVertex};
 
 pub fn validate_vertex(
     vertex: &GlobalVertex,
@@ -14,6 +14,7 @@ pub fn validate_vertex(
         {
             return Err(UniquenessIssues {
                 duplicate_vertex: Some(*existing),
+                ..UniquenessIssues::default()
             });
         }
     }
@@ -21,6 +22,44 @@ pub fn validate_vertex(
     Ok(())
 }
 
+pub fn validate_cycle(cycle: &Cycle) -> Result<(), UniquenessIssues> {
+    let edges: Vec<_> = cycle.edges().collect();
+
+    let duplicate_edges: Vec<_> = edges
+        .iter()
+        .enumerate()
+        .filter(|(i, edge)| edges[i + 1..].contains(edge))
+        .map(|(_, edge)| **edge)
+        .collect();
+    if !duplicate_edges.is_empty() {
+        return Err(UniquenessIssues {
+            duplicate_edges,
+            ..UniquenessIssues::default()
+        });
+    }
+
+    Ok(())
+}
+
+pub fn validate_face(face: &Face) -> Result<(), UniquenessIssues> {
+    let cycles: Vec<_> = face.all_cycles().collect();
+
+    let duplicate_cycles: Vec<_> = cycles
+        .iter()
+        .enumerate()
+        .filter(|(i, cycle)| cycles[i + 1..].contains(cycle))
+        .map(|(_, cycle)| (*cycle).clone())
+        .collect();
+    if !duplicate_cycles.is_empty() {
+        return Err(UniquenessIssues {
+            duplicate_cycles,
+            ..UniquenessIssues::default()
+        });
+    }
+
+    Ok(())
+}
+
 /// Uniqueness issues found during validation
 ///
 /// Used by [`ValidationError`].
@@ -36,6 +75,12 @@ pub fn validate_vertex(
 pub struct UniquenessIssues {
     /// Duplicate vertex found
     pub duplicate_vertex: Option<GlobalVertex>,
+
+    /// Edges found more than once in the same cycle
+    pub duplicate_edges: Vec<Edge>,
+
+    /// Cycles found more than once in the same face
+    pub duplicate_cycles: Vec<Cycle>,
 }
 
 impl fmt::Display for UniquenessIssues {
@@ -45,6 +90,12 @@ impl fmt::Display for UniquenessIssues {
         if let Some(duplicate_vertex) = &self.duplicate_vertex {
             writeln!(f, "- Duplicate vertex ({:?}", duplicate_vertex)?;
         }
+        for duplicate_edge in &self.duplicate_edges {
+            writeln!(f, "- Duplicate edge ({:?})", duplicate_edge)?;
+        }
+        for duplicate_cycle in &self.duplicate_cycles {
+            writeln!(f, "- Duplicate cycle ({:?})", duplicate_cycle)?;
+        }
 
         Ok(())
     }
@@ -67,6 +67,14 @@ impl Aabb<2> {
             max: aabb.maxs.into(),
         }
     }
+
+    /// Convert the AABB to a Parry AABB
+    pub fn to_parry(self) -> parry2d_f64::bounding_volume::AABB {
+        parry2d_f64::bounding_volume::AABB {
+            mins: self.min.to_na(),
+            maxs: self.max.to_na(),
+        }
+    }
 }
 
 impl Aabb<3> {
@@ -126,6 +134,15 @@ impl Aabb<3> {
     pub fn merged(&self, other: &Self) -> Self {
         self.to_parry().merged(&other.to_parry()).into()
     }
+
+    /// Compute the AABB that is covered by both this AABB and another
+    ///
+    /// Returns `None`, if they don't overlap.
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        self.to_parry()
+            .intersection(&other.to_parry())
+            .map(Into::into)
+    }
 }
 
 impl From<parry2d_f64::bounding_volume::AABB> for Aabb<2> {
@@ -140,6 +157,18 @@ impl From<parry3d_f64::bounding_volume::AABB> for Aabb<3> {
     }
 }
 
+impl From<Aabb<2>> for parry2d_f64::bounding_volume::AABB {
+    fn from(aabb: Aabb<2>) -> Self {
+        aabb.to_parry()
+    }
+}
+
+impl From<Aabb<3>> for parry3d_f64::bounding_volume::AABB {
+    fn from(aabb: Aabb<3>) -> Self {
+        aabb.to_parry()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::Aabb;
@@ -160,4 +189,28 @@ mod tests {
         assert!(!aabb.contains([0., 2.]));
         assert!(!aabb.contains([4., 2.]));
     }
+
+    #[test]
+    fn parry_round_trip() {
+        let aabb_2d = Aabb::<2>::from_points([[-1., 2.], [3., -4.]]);
+        assert_eq!(Aabb::<2>::from_parry(aabb_2d.to_parry()), aabb_2d);
+
+        let aabb_3d = Aabb::<3>::from_points([[-1., 2., 0.5], [3., -4., 5.]]);
+        let parry: parry3d_f64::bounding_volume::AABB = aabb_3d.into();
+        assert_eq!(Aabb::<3>::from(parry), aabb_3d);
+    }
+
+    #[test]
+    fn intersection() {
+        let a = Aabb::<3>::from_points([[0., 0., 0.], [2., 2., 2.]]);
+        let b = Aabb::<3>::from_points([[1., -1., 1.], [3., 1., 3.]]);
+
+        assert_eq!(
+            a.intersection(&b),
+            Some(Aabb::<3>::from_points([[1., 0., 1.], [2., 1., 2.]]))
+        );
+
+        let c = Aabb::<3>::from_points([[3., 3., 3.], [4., 4., 4.]]);
+        assert_eq!(a.intersection(&c), None);
+    }
 }
@@ -0,0 +1,158 @@
+use crate::{Point, Scalar};
+
+/// An n-dimensional, clamped, uniform B-spline curve
+///
+/// The dimensionality of the curve is defined by the const generic `D`
+/// parameter.
+///
+/// The curve starts at the first control point and ends at the last one. The
+/// control points in between pull the curve towards them, but are generally not
+/// on it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BSpline<const D: usize> {
+    control_points: Vec<Point<D>>,
+    degree: usize,
+}
+
+impl<const D: usize> BSpline<D> {
+    /// Construct a curve from its control points and degree
+    ///
+    /// If there aren't enough control points for the requested degree, the
+    /// highest degree that the control points allow for is used instead.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if fewer than two control points are provided, or if `degree`
+    /// is zero.
+    pub fn from_control_points(
+        control_points: impl IntoIterator<Item = impl Into<Point<D>>>,
+        degree: usize,
+    ) -> Self {
+        let control_points: Vec<_> =
+            control_points.into_iter().map(Into::into).collect();
+
+        assert!(
+            control_points.len() >= 2,
+            "B-spline needs at least two control points"
+        );
+        assert_ne!(degree, 0, "B-spline degree must not be zero");
+
+        let degree = degree.min(control_points.len() - 1);
+
+        Self {
+            control_points,
+            degree,
+        }
+    }
+
+    /// Access the control points of the curve
+    pub fn control_points(&self) -> &[Point<D>] {
+        &self.control_points
+    }
+
+    /// Access the degree of the curve
+    pub fn degree(&self) -> usize {
+        self.degree
+    }
+
+    /// Access the number of polynomial pieces that make up the curve
+    pub fn num_spans(&self) -> usize {
+        self.control_points.len() - self.degree
+    }
+
+    /// Convert a point in curve coordinates into a `D`-dimensional point
+    ///
+    /// The curve starts at the coordinate `0.` and ends at `1.`. Each span
+    /// covers an equal part of that range.
+    pub fn point_from_curve_coords(
+        &self,
+        point: impl Into<Point<1>>,
+    ) -> Point<D> {
+        let n = self.control_points.len();
+        let p = self.degree;
+
+        // Work in knot space, where the internal knots are at the integers.
+        let u = point.into().t.max(Scalar::ZERO).min(Scalar::ONE)
+            * self.num_spans() as f64;
+        let knot = |i: usize| Scalar::from((i.max(p).min(n) - p) as f64);
+
+        let mut k = p;
+        while k < n - 1 && knot(k + 1) <= u {
+            k += 1;
+        }
+
+        // De Boor's algorithm
+        let mut points = self.control_points[k - p..=k].to_vec();
+        for r in 1..=p {
+            for j in (r..=p).rev() {
+                let [from, to] = [knot(j + k - p), knot(j + 1 + k - r)];
+                let alpha = (u - from) / (to - from);
+                points[j] = points[j - 1] + (points[j] - points[j - 1]) * alpha;
+            }
+        }
+
+        points[p]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_abs_diff_eq;
+
+    use crate::{CubicBezier, Point, Scalar};
+
+    use super::BSpline;
+
+    #[test]
+    fn single_span_equals_bezier() {
+        let points = [[0., 0.], [0., 1.], [1., 1.], [1., 0.]];
+
+        let spline = BSpline::<2>::from_control_points(points, 3);
+        let bezier = CubicBezier::from_points(points);
+
+        for t in [0., 0.25, 0.5, 0.75, 1.] {
+            assert_abs_diff_eq!(
+                spline.point_from_curve_coords([t]),
+                bezier.point_from_curve_coords([t]),
+                epsilon = Scalar::from(1e-12),
+            );
+        }
+    }
+
+    #[test]
+    fn degree_one_is_polyline() {
+        let spline = BSpline::<2>::from_control_points(
+            [[0., 0.], [1., 0.], [1., 1.]],
+            1,
+        );
+
+        assert_eq!(
+            spline.point_from_curve_coords([0.25]),
+            Point::from([0.5, 0.])
+        );
+        assert_eq!(
+            spline.point_from_curve_coords([0.75]),
+            Point::from([1., 0.5])
+        );
+        assert_eq!(spline.point_from_curve_coords([1.]), Point::from([1., 1.]));
+    }
+
+    #[test]
+    fn multiple_spans() {
+        let spline = BSpline::<2>::from_control_points(
+            [[0., 0.], [1., 2.], [2., -2.], [3., 2.], [4., 0.]],
+            3,
+        );
+        assert_eq!(spline.num_spans(), 2);
+
+        // The ends are interpolated, and the curve is continuous between the
+        // spans.
+        assert_eq!(spline.point_from_curve_coords([0.]), Point::from([0., 0.]));
+        assert_eq!(spline.point_from_curve_coords([1.]), Point::from([4., 0.]));
+        assert_abs_diff_eq!(
+            spline.point_from_curve_coords([0.5 - 1e-9]),
+            spline.point_from_curve_coords([0.5]),
+            epsilon = Scalar::from(1e-6),
+        );
+    }
+}
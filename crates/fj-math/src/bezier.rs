@@ -0,0 +1,68 @@
+use crate::Point;
+
+/// An n-dimensional cubic Bézier curve
+///
+/// The dimensionality of the curve is defined by the const generic `D`
+/// parameter.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CubicBezier<const D: usize> {
+    points: [Point<D>; 4],
+}
+
+impl<const D: usize> CubicBezier<D> {
+    /// Construct a curve from its control points
+    ///
+    /// The curve starts at the first point and ends at the last one. The two
+    /// points in between pull the curve towards them, but are generally not on
+    /// it.
+    pub fn from_points(points: [impl Into<Point<D>>; 4]) -> Self {
+        Self {
+            points: points.map(Into::into),
+        }
+    }
+
+    /// Access the control points of the curve
+    pub fn points(&self) -> [Point<D>; 4] {
+        self.points
+    }
+
+    /// Convert a point in curve coordinates into a `D`-dimensional point
+    ///
+    /// The curve starts at the coordinate `0.` and ends at `1.`.
+    pub fn point_from_curve_coords(
+        &self,
+        point: impl Into<Point<1>>,
+    ) -> Point<D> {
+        let t = point.into().t;
+
+        // De Casteljau's algorithm
+        let mut points = self.points;
+        for n in (1..points.len()).rev() {
+            for i in 0..n {
+                points[i] = points[i] + (points[i + 1] - points[i]) * t;
+            }
+        }
+
+        points[0]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Point;
+
+    use super::CubicBezier;
+
+    #[test]
+    fn point_from_curve_coords() {
+        let curve =
+            CubicBezier::from_points([[0., 0.], [0., 1.], [1., 1.], [1., 0.]]);
+
+        assert_eq!(curve.point_from_curve_coords([0.]), Point::from([0., 0.]));
+        assert_eq!(
+            curve.point_from_curve_coords([0.5]),
+            Point::from([0.5, 0.75])
+        );
+        assert_eq!(curve.point_from_curve_coords([1.]), Point::from([1., 0.]));
+    }
+}
@@ -22,7 +22,7 @@ impl<const D: usize> Circle<D> {
     ///
     /// - The circle radius (defined by the length of `a` and `b`) must not be
     ///   zero.
-    /// - `a` and `b` must be of equal length.
+    /// - `a` and `b` must be of equal length, up to rounding errors.
     /// - `a` and `b` must be perpendicular to each other.
     pub fn new(
         center: impl Into<Point<D>>,
@@ -33,9 +33,11 @@ impl<const D: usize> Circle<D> {
         let a = a.into();
         let b = b.into();
 
-        assert_eq!(
-            a.magnitude(),
-            b.magnitude(),
+        // Vectors that have been rotated or projected pick up rounding errors,
+        // so their lengths can't be expected to be *precisely* equal either.
+        assert!(
+            (a.magnitude() - b.magnitude()).abs()
+                <= a.magnitude() * Scalar::default_epsilon() * 4.,
             "`a` and `b` must be of equal length"
         );
         assert_ne!(
@@ -0,0 +1,196 @@
+use crate::{Point, Scalar, Vector};
+
+/// An n-dimensional ellipse
+///
+/// The dimensionality of the ellipse is defined by the const generic `D`
+/// parameter.
+///
+/// Works like [`Circle`], except that its two axes can have different lengths.
+///
+/// [`Circle`]: crate::Circle
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct Ellipse<const D: usize> {
+    center: Point<D>,
+    a: Vector<D>,
+    b: Vector<D>,
+}
+
+impl<const D: usize> Ellipse<D> {
+    /// Construct an ellipse
+    ///
+    /// # Panics
+    ///
+    /// Panics, if `a` or `b` has zero length, or if they are not perpendicular
+    /// to each other.
+    pub fn new(
+        center: impl Into<Point<D>>,
+        a: impl Into<Vector<D>>,
+        b: impl Into<Vector<D>>,
+    ) -> Self {
+        let center = center.into();
+        let a = a.into();
+        let b = b.into();
+
+        assert!(
+            a.magnitude() != Scalar::ZERO && b.magnitude() != Scalar::ZERO,
+            "ellipse axes must not have zero length"
+        );
+        // Same as for circles, vectors that have been transformed can't be
+        // expected to be *precisely* perpendicular.
+        assert!(
+            a.dot(&b).abs()
+                <= a.magnitude() * b.magnitude() * Scalar::from(1e-9),
+            "`a` and `b` must be perpendicular to each other"
+        );
+
+        Self { center, a, b }
+    }
+
+    /// Access the center point of the ellipse
+    pub fn center(&self) -> Point<D> {
+        self.center
+    }
+
+    /// Access the first axis of the ellipse
+    ///
+    /// The point where this vector points from the center is the zero
+    /// coordinate of the ellipse's coordinate system. Its length is the radius
+    /// of the ellipse in that direction.
+    pub fn a(&self) -> Vector<D> {
+        self.a
+    }
+
+    /// Access the second axis of the ellipse
+    ///
+    /// Defines the direction of the ellipse's coordinate system. Its length is
+    /// the radius of the ellipse in that direction.
+    pub fn b(&self) -> Vector<D> {
+        self.b
+    }
+
+    /// Create a new instance that is reversed
+    #[must_use]
+    pub fn reverse(mut self) -> Self {
+        self.b = -self.b;
+        self
+    }
+
+    /// Convert a `D`-dimensional point to ellipse coordinates
+    ///
+    /// Converts the provided point into ellipse coordinates between `0.`
+    /// (inclusive) and `PI * 2.` (exclusive). Points that are not on the
+    /// ellipse are projected onto it along a line through its center.
+    pub fn point_to_ellipse_coords(
+        &self,
+        point: impl Into<Point<D>>,
+    ) -> Point<1> {
+        let vector = point.into() - self.center;
+        let u = vector.dot(&self.a) / self.a.dot(&self.a);
+        let v = vector.dot(&self.b) / self.b.dot(&self.b);
+
+        let atan = Scalar::atan2(v, u);
+        let coord = if atan >= Scalar::ZERO {
+            atan
+        } else {
+            atan + Scalar::PI * 2.
+        };
+        Point::from([coord])
+    }
+
+    /// Convert a point in ellipse coordinates into a `D`-dimensional point
+    pub fn point_from_ellipse_coords(
+        &self,
+        point: impl Into<Point<1>>,
+    ) -> Point<D> {
+        self.center + self.vector_from_ellipse_coords(point.into().coords)
+    }
+
+    /// Convert a vector in ellipse coordinates into a `D`-dimensional vector
+    pub fn vector_from_ellipse_coords(
+        &self,
+        vector: impl Into<Vector<1>>,
+    ) -> Vector<D> {
+        let angle = vector.into().t;
+        let (sin, cos) = angle.sin_cos();
+
+        self.a * cos + self.b * sin
+    }
+
+    /// Compute the tangent at a point in ellipse coordinates
+    ///
+    /// The tangent is the derivative of the ellipse with respect to its
+    /// coordinate, so its length varies along the ellipse.
+    pub fn tangent_from_ellipse_coords(
+        &self,
+        point: impl Into<Point<1>>,
+    ) -> Vector<D> {
+        let angle = point.into().t;
+        let (sin, cos) = angle.sin_cos();
+
+        self.b * cos - self.a * sin
+    }
+}
+
+impl<const D: usize> approx::AbsDiffEq for Ellipse<D> {
+    type Epsilon = <Scalar as approx::AbsDiffEq>::Epsilon;
+
+    fn default_epsilon() -> Self::Epsilon {
+        Scalar::default_epsilon()
+    }
+
+    fn abs_diff_eq(&self, other: &Self, epsilon: Self::Epsilon) -> bool {
+        self.center.abs_diff_eq(&other.center, epsilon)
+            && self.a.abs_diff_eq(&other.a, epsilon)
+            && self.b.abs_diff_eq(&other.b, epsilon)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::{FRAC_PI_2, PI};
+
+    use approx::assert_abs_diff_eq;
+
+    use crate::{Point, Scalar, Vector};
+
+    use super::Ellipse;
+
+    #[test]
+    fn ellipse_coords() {
+        let ellipse = Ellipse::new([1., 2.], [2., 0.], [0., 1.]);
+
+        for (coord, point) in [
+            (0., [3., 2.]),
+            (FRAC_PI_2, [1., 3.]),
+            (PI, [-1., 2.]),
+            (FRAC_PI_2 * 3., [1., 1.]),
+        ] {
+            assert_abs_diff_eq!(
+                ellipse.point_from_ellipse_coords([coord]),
+                Point::from(point),
+                epsilon = Scalar::from(1e-12),
+            );
+            assert_abs_diff_eq!(
+                ellipse.point_to_ellipse_coords(point),
+                Point::from([coord]),
+                epsilon = Scalar::from(1e-12),
+            );
+        }
+    }
+
+    #[test]
+    fn tangent() {
+        let ellipse = Ellipse::new([0., 0.], [2., 0.], [0., 1.]);
+
+        assert_abs_diff_eq!(
+            ellipse.tangent_from_ellipse_coords([0.]),
+            Vector::from([0., 1.]),
+            epsilon = Scalar::from(1e-12),
+        );
+        assert_abs_diff_eq!(
+            ellipse.tangent_from_ellipse_coords([FRAC_PI_2]),
+            Vector::from([-2., 0.]),
+            epsilon = Scalar::from(1e-12),
+        );
+    }
+}
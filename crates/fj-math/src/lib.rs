@@ -36,11 +36,19 @@
 #![warn(missing_docs)]
 
 mod aabb;
+mod b_spline;
+mod bezier;
 mod circle;
 mod coordinates;
+mod ellipse;
 mod line;
+mod parabola;
+mod plane;
 mod point;
 mod poly_chain;
+mod polygon;
+#[cfg(any(test, feature = "sample"))]
+pub mod sample;
 mod scalar;
 mod segment;
 mod transform;
@@ -49,13 +57,22 @@ mod vector;
 
 pub use self::{
     aabb::Aabb,
+    b_spline::BSpline,
+    bezier::CubicBezier,
     circle::Circle,
     coordinates::{Uv, Xyz, T},
+    ellipse::Ellipse,
     line::Line,
+    parabola::Parabola,
+    plane::Plane,
     point::Point,
     poly_chain::PolyChain,
+    polygon::{
+        convex_hull_2d, convex_hull_2d_with_collinear_points, point_in_polygon,
+        point_on_polygon_boundary, ray_crosses_edge,
+    },
     scalar::Scalar,
-    segment::Segment,
+    segment::{Segment, SegmentIntersection},
     transform::Transform,
     triangle::{Triangle, Winding},
     vector::Vector,
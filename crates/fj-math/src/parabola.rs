@@ -0,0 +1,201 @@
+use crate::{Point, Scalar, Vector};
+
+/// An n-dimensional parabola
+///
+/// The dimensionality of the parabola is defined by the const generic `D`
+/// parameter.
+///
+/// The parabola is defined as `origin + a * t + b * t²`, where `t` is the
+/// parabola coordinate. This is the same curve as a quadratic Bézier curve,
+/// but unbounded.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct Parabola<const D: usize> {
+    origin: Point<D>,
+    a: Vector<D>,
+    b: Vector<D>,
+}
+
+impl<const D: usize> Parabola<D> {
+    /// Construct a parabola
+    ///
+    /// # Panics
+    ///
+    /// Panics, if `a` and `b` are parallel, or if either has zero length. The
+    /// result would be a straight line.
+    pub fn new(
+        origin: impl Into<Point<D>>,
+        a: impl Into<Vector<D>>,
+        b: impl Into<Vector<D>>,
+    ) -> Self {
+        let origin = origin.into();
+        let a = a.into();
+        let b = b.into();
+
+        let [a_mag, b_mag] = [a, b].map(|vector| vector.magnitude());
+        assert!(
+            a.dot(&b).abs()
+                < a_mag * b_mag * (Scalar::ONE - Scalar::from(1e-9)),
+            "`a` and `b` must not be parallel"
+        );
+
+        Self { origin, a, b }
+    }
+
+    /// Construct a parabola from the control points of a quadratic Bézier curve
+    ///
+    /// The first and last point are at the parabola coordinates `0.` and `1.`,
+    /// respectively.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the points lie on a straight line.
+    pub fn from_bezier_points(points: [impl Into<Point<D>>; 3]) -> Self {
+        let [p0, p1, p2] = points.map(Into::into);
+
+        let a = (p1 - p0) * 2.;
+        let b = (p2 - p1) - (p1 - p0);
+
+        Self::new(p0, a, b)
+    }
+
+    /// Access the origin of the parabola
+    ///
+    /// This is the point at the parabola coordinate zero.
+    pub fn origin(&self) -> Point<D> {
+        self.origin
+    }
+
+    /// Access the tangent of the parabola at its origin
+    pub fn a(&self) -> Vector<D> {
+        self.a
+    }
+
+    /// Access the vector that defines how the parabola bends
+    ///
+    /// This vector is parallel to the axis of the parabola.
+    pub fn b(&self) -> Vector<D> {
+        self.b
+    }
+
+    /// Access the vertex of the parabola
+    ///
+    /// This is the point where the curvature is highest, and the parabola is
+    /// symmetric around.
+    pub fn vertex(&self) -> Point<D> {
+        let t = -self.a.dot(&self.b) / (self.b.dot(&self.b) * 2.);
+        self.point_from_parabola_coords([t])
+    }
+
+    /// Create a new instance that is reversed
+    #[must_use]
+    pub fn reverse(mut self) -> Self {
+        self.a = -self.a;
+        self
+    }
+
+    /// Convert a `D`-dimensional point to parabola coordinates
+    ///
+    /// Finds the parabola coordinate of the closest point on the tangent at the
+    /// origin, which is only exact for points on the parabola. Callers need to
+    /// make sure that the points they pass are.
+    pub fn point_to_parabola_coords(
+        &self,
+        point: impl Into<Point<D>>,
+    ) -> Point<1> {
+        // Get rid of the part along `b`, to be left with `a * t`.
+        let vector = point.into() - self.origin;
+        let b = self.b.normalize();
+        let a = self.a - b * self.a.dot(&b);
+        let vector = vector - b * vector.dot(&b);
+
+        Point::from([vector.dot(&a) / a.dot(&a)])
+    }
+
+    /// Convert a point in parabola coordinates into a `D`-dimensional point
+    pub fn point_from_parabola_coords(
+        &self,
+        point: impl Into<Point<1>>,
+    ) -> Point<D> {
+        self.origin + self.vector_from_parabola_coords(point.into().coords)
+    }
+
+    /// Convert a vector in parabola coordinates into a `D`-dimensional vector
+    pub fn vector_from_parabola_coords(
+        &self,
+        vector: impl Into<Vector<1>>,
+    ) -> Vector<D> {
+        let t = vector.into().t;
+        self.a * t + self.b * (t * t)
+    }
+
+    /// Compute the tangent at a point in parabola coordinates
+    ///
+    /// The tangent is the derivative of the parabola with respect to its
+    /// coordinate, so its length varies along the parabola.
+    pub fn tangent_from_parabola_coords(
+        &self,
+        point: impl Into<Point<1>>,
+    ) -> Vector<D> {
+        let t = point.into().t;
+        self.a + self.b * (t * 2.)
+    }
+}
+
+impl<const D: usize> approx::AbsDiffEq for Parabola<D> {
+    type Epsilon = <Scalar as approx::AbsDiffEq>::Epsilon;
+
+    fn default_epsilon() -> Self::Epsilon {
+        Scalar::default_epsilon()
+    }
+
+    fn abs_diff_eq(&self, other: &Self, epsilon: Self::Epsilon) -> bool {
+        self.origin.abs_diff_eq(&other.origin, epsilon)
+            && self.a.abs_diff_eq(&other.a, epsilon)
+            && self.b.abs_diff_eq(&other.b, epsilon)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_abs_diff_eq;
+
+    use crate::{CubicBezier, Point, Scalar, Vector};
+
+    use super::Parabola;
+
+    #[test]
+    fn from_bezier_points() {
+        let points = [[0., 0.], [1., 2.], [2., 0.]];
+        let parabola = Parabola::from_bezier_points(points);
+
+        // A quadratic Bézier curve is a cubic one, with its control points
+        // two thirds of the way towards the middle one.
+        let [p0, p1, p2] = points.map(Point::from);
+        let bezier = CubicBezier::from_points([
+            p0,
+            p0 + (p1 - p0) * (2. / 3.),
+            p2 + (p1 - p2) * (2. / 3.),
+            p2,
+        ]);
+
+        for t in [0., 0.25, 0.5, 1.] {
+            let point = parabola.point_from_parabola_coords([t]);
+            assert_abs_diff_eq!(
+                point,
+                bezier.point_from_curve_coords([t]),
+                epsilon = Scalar::from(1e-12),
+            );
+            assert_abs_diff_eq!(
+                parabola.point_to_parabola_coords(point),
+                Point::from([t]),
+                epsilon = Scalar::from(1e-12),
+            );
+        }
+
+        assert_eq!(parabola.vertex(), Point::from([1., 1.]));
+        assert_eq!(
+            parabola.tangent_from_parabola_coords([0.5]),
+            Vector::from([2., 0.])
+        );
+    }
+}
@@ -0,0 +1,74 @@
+use crate::{Point, Scalar, Vector};
+
+/// A plane in 3-dimensional space, defined by a point on it and its normal
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+#[repr(C)]
+pub struct Plane {
+    origin: Point<3>,
+    normal: Vector<3>,
+}
+
+impl Plane {
+    /// Create a plane from a point on it and its normal
+    ///
+    /// The normal is normalized, so it can have any length.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if `normal` has a length of zero.
+    pub fn from_origin_and_normal(
+        origin: impl Into<Point<3>>,
+        normal: impl Into<Vector<3>>,
+    ) -> Self {
+        let normal = normal.into();
+
+        if normal.magnitude() == Scalar::ZERO {
+            panic!("Can't construct `Plane`. Normal is zero: {:?}", normal);
+        }
+
+        Self {
+            origin: origin.into(),
+            normal: normal.normalize(),
+        }
+    }
+
+    /// Access the origin of the plane
+    pub fn origin(&self) -> Point<3> {
+        self.origin
+    }
+
+    /// Access the normal of the plane
+    ///
+    /// The normal always has a length of one.
+    pub fn normal(&self) -> Vector<3> {
+        self.normal
+    }
+
+    /// Compute the signed distance of a point from the plane
+    ///
+    /// The distance is positive on the side the normal points to.
+    pub fn distance_to_point(&self, point: &Point<3>) -> Scalar {
+        (point - self.origin).dot(&self.normal)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Point, Scalar};
+
+    use super::Plane;
+
+    #[test]
+    fn distance_to_point() {
+        let plane = Plane::from_origin_and_normal([0., 0., 1.], [0., 0., 2.]);
+
+        assert_eq!(
+            plane.distance_to_point(&Point::from([3., 4., 3.])),
+            Scalar::TWO
+        );
+        assert_eq!(
+            plane.distance_to_point(&Point::from([3., 4., 0.])),
+            -Scalar::ONE
+        );
+    }
+}
@@ -63,6 +63,30 @@ impl<const D: usize> Point<D> {
     pub fn distance(p1: &Point<D>, p2: &Point<D>) -> Scalar {
         (p1.coords - p2.coords).magnitude()
     }
+
+    /// Round each coordinate to the nearest multiple of `to`
+    ///
+    /// This can be used to make points that should be identical, but differ
+    /// due to floating-point error, compare equal. Points that are close to
+    /// each other, but on different sides of a grid line, still snap to
+    /// different points.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if `to` is not positive.
+    pub fn snapped(&self, to: f64) -> Self {
+        assert!(to > 0., "Can't snap to grid of size {to}");
+
+        let coords = self.coords.components.map(|c| {
+            // Adding zero turns a negative zero into a positive one, which
+            // keeps the bit pattern of snapped points consistent.
+            Scalar::from_f64((c.into_f64() / to).round() * to + 0.)
+        });
+
+        Self {
+            coords: coords.into(),
+        }
+    }
 }
 
 impl ops::Deref for Point<1> {
@@ -216,3 +240,40 @@ impl<const D: usize> approx::AbsDiffEq for Point<D> {
         self.coords.abs_diff_eq(&other.coords, epsilon)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use approx::{assert_abs_diff_eq, assert_abs_diff_ne};
+
+    use crate::Scalar;
+
+    use super::Point;
+
+    #[test]
+    fn abs_diff_eq() {
+        let a = Point::from([1., 2., 3.]);
+
+        assert_abs_diff_eq!(
+            a,
+            Point::from([1., 2. + 1e-9, 3.]),
+            epsilon = Scalar::from(1e-6),
+        );
+        assert_abs_diff_ne!(
+            a,
+            Point::from([1., 2. + 1e-3, 3.]),
+            epsilon = Scalar::from(1e-6),
+        );
+    }
+
+    #[test]
+    fn snapped() {
+        let a = Point::from([0.1, -0.000_000_000_2]);
+        let b = Point::from([0.1 + 1e-12, 0.]);
+        assert_ne!(a, b);
+        assert_eq!(a.snapped(1e-9), b.snapped(1e-9));
+        assert_eq!(a.snapped(1e-9).v.into_f64().to_bits(), 0.0_f64.to_bits());
+
+        let c = Point::from([0.1 + 1e-6, 0.]);
+        assert_ne!(a.snapped(1e-9), c.snapped(1e-9));
+    }
+}
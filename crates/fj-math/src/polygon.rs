@@ -0,0 +1,267 @@
+use crate::{segment::orient, Point};
+
+/// Determine whether a polygon contains a point
+///
+/// The polygon is given as its vertices, in either winding order. It is closed
+/// implicitly, so the first vertex must not be repeated at the end (if it is,
+/// the extra zero-length edge is harmless).
+///
+/// Points on the boundary of the polygon are considered to be contained.
+///
+/// # Implementation Note
+///
+/// This is a crossing number test, casting a ray from the point in the positive
+/// u-direction. An edge only counts as crossed, if one of its vertices is above
+/// the ray and the other one is not. This takes care of the ray passing through
+/// vertices, as well as of edges that are parallel to the ray.
+pub fn point_in_polygon(point: &Point<2>, polygon: &[Point<2>]) -> bool {
+    if point_on_polygon_boundary(point, polygon) {
+        return true;
+    }
+
+    let mut inside = false;
+
+    for edge in edges(polygon) {
+        if ray_crosses_edge(point, edge) {
+            inside = !inside;
+        }
+    }
+
+    inside
+}
+
+/// Determine whether the ray that [`point_in_polygon`] casts crosses an edge
+///
+/// The ray starts at `point` and goes into the positive u-direction.
+pub fn ray_crosses_edge(point: &Point<2>, [a, b]: [Point<2>; 2]) -> bool {
+    if (a.v > point.v) == (b.v > point.v) {
+        return false;
+    }
+
+    // Where the edge crosses the line the ray is on. Can't divide by zero, as
+    // we checked above that `a` and `b` are on opposite sides of it.
+    let u = a.u + (point.v - a.v) * (b.u - a.u) / (b.v - a.v);
+
+    point.u < u
+}
+
+/// Determine whether a point is on the boundary of a polygon
+///
+/// See [`point_in_polygon`] for how the polygon is defined.
+pub fn point_on_polygon_boundary(
+    point: &Point<2>,
+    polygon: &[Point<2>],
+) -> bool {
+    edges(polygon).any(|[a, b]| {
+        let is_collinear = orient([a, b], *point) == 0.;
+        let is_within_edge = point.u >= a.u.min(b.u)
+            && point.u <= a.u.max(b.u)
+            && point.v >= a.v.min(b.v)
+            && point.v <= a.v.max(b.v);
+
+        is_collinear && is_within_edge
+    })
+}
+
+/// Compute the convex hull of a set of points
+///
+/// Returns the vertices of the hull in counter-clockwise order, starting with
+/// the point that has the smallest coordinates. Duplicate points are ignored,
+/// as are points that are on an edge of the hull, but aren't a corner of it.
+/// See [`convex_hull_2d_with_collinear_points`], to keep those.
+///
+/// If all points are collinear, the two extreme points are returned.
+pub fn convex_hull_2d(points: &[Point<2>]) -> Vec<Point<2>> {
+    convex_hull(points, false)
+}
+
+/// Compute the convex hull of a set of points, including collinear points
+///
+/// Like [`convex_hull_2d`], except that points on the edges of the hull are
+/// part of the result. If all points are collinear, they are all returned,
+/// ordered from one extreme point to the other.
+pub fn convex_hull_2d_with_collinear_points(
+    points: &[Point<2>],
+) -> Vec<Point<2>> {
+    convex_hull(points, true)
+}
+
+fn convex_hull(points: &[Point<2>], keep_collinear: bool) -> Vec<Point<2>> {
+    // This is Andrew's monotone chain algorithm.
+
+    let mut points = points.to_vec();
+    points.sort();
+    points.dedup();
+
+    if points.len() <= 2 {
+        return points;
+    }
+
+    // Determines whether `c` is a valid next point of a chain that ends with
+    // `a` and `b`. That is the case, if the chain turns left at `b`.
+    let turns_left = |a: Point<2>, b: Point<2>, c: Point<2>| {
+        let turn = orient([a, b], c);
+
+        if keep_collinear {
+            turn >= 0.
+        } else {
+            turn > 0.
+        }
+    };
+
+    let mut lower: Vec<Point<2>> = Vec::new();
+    for &point in &points {
+        while let [.., a, b] = lower[..] {
+            if turns_left(a, b, point) {
+                break;
+            }
+            lower.pop();
+        }
+        lower.push(point);
+    }
+
+    let all_collinear = lower.len() == points.len()
+        && points.windows(3).all(|p| orient([p[0], p[1]], p[2]) == 0.);
+    if all_collinear {
+        // Only possible, if we're keeping collinear points. The upper chain
+        // would just be the lower one in reverse.
+        return lower;
+    }
+
+    let mut upper: Vec<Point<2>> = Vec::new();
+    for &point in points.iter().rev() {
+        while let [.., a, b] = upper[..] {
+            if turns_left(a, b, point) {
+                break;
+            }
+            upper.pop();
+        }
+        upper.push(point);
+    }
+
+    // The last point of each chain is the first point of the other one.
+    lower.pop();
+    upper.pop();
+
+    lower.extend(upper);
+    lower
+}
+
+fn edges(polygon: &[Point<2>]) -> impl Iterator<Item = [Point<2>; 2]> + '_ {
+    let next = polygon.iter().cycle().skip(1);
+    polygon.iter().zip(next).map(|(&a, &b)| [a, b])
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Point;
+
+    use super::{
+        convex_hull_2d, convex_hull_2d_with_collinear_points, point_in_polygon,
+        point_on_polygon_boundary,
+    };
+
+    fn polygon() -> Vec<Point<2>> {
+        //  d-----c
+        //  |    /
+        //  |   b
+        //  |    \
+        //  a-----e
+        let a = [0., 0.];
+        let e = [2., 0.];
+        let b = [1., 1.];
+        let c = [2., 2.];
+        let d = [0., 2.];
+
+        [a, e, b, c, d].map(Point::from).into()
+    }
+
+    #[test]
+    fn inside() {
+        let polygon = polygon();
+
+        assert!(point_in_polygon(&Point::from([0.5, 1.]), &polygon));
+        assert!(point_in_polygon(&Point::from([1.5, 0.25]), &polygon));
+        assert!(point_in_polygon(&Point::from([1.5, 1.75]), &polygon));
+    }
+
+    #[test]
+    fn outside() {
+        let polygon = polygon();
+
+        // In the notch of the concave polygon.
+        assert!(!point_in_polygon(&Point::from([1.5, 1.]), &polygon));
+
+        assert!(!point_in_polygon(&Point::from([3., 1.]), &polygon));
+        assert!(!point_in_polygon(&Point::from([1., 3.]), &polygon));
+
+        // The ray passes through the vertex at the tip of the notch.
+        assert!(!point_in_polygon(&Point::from([-1., 1.]), &polygon));
+    }
+
+    #[test]
+    fn on_boundary() {
+        let polygon = polygon();
+
+        for point in [[0., 1.], [1., 0.], [1.5, 0.5], [1., 1.], [0., 0.]] {
+            let point = Point::from(point);
+
+            assert!(point_on_polygon_boundary(&point, &polygon));
+            assert!(point_in_polygon(&point, &polygon));
+        }
+
+        assert!(!point_on_polygon_boundary(
+            &Point::from([0.5, 1.]),
+            &polygon
+        ));
+    }
+
+    #[test]
+    fn winding_order_does_not_matter() {
+        let mut polygon = polygon();
+        polygon.reverse();
+
+        assert!(point_in_polygon(&Point::from([0.5, 1.]), &polygon));
+        assert!(!point_in_polygon(&Point::from([1.5, 1.]), &polygon));
+    }
+
+    #[test]
+    fn convex_hull_of_square_with_interior_points() {
+        let points = [
+            [0.5, 0.5],
+            [1., 1.],
+            [0., 0.],
+            [0.25, 0.75],
+            [1., 0.],
+            [0., 1.],
+            [0.5, 0.5],
+            [1., 0.],
+            [0.5, 0.],
+        ]
+        .map(Point::from);
+
+        assert_eq!(
+            convex_hull_2d(&points),
+            [[0., 0.], [1., 0.], [1., 1.], [0., 1.]].map(Point::from),
+        );
+        assert_eq!(
+            convex_hull_2d_with_collinear_points(&points),
+            [[0., 0.], [0.5, 0.], [1., 0.], [1., 1.], [0., 1.]]
+                .map(Point::from),
+        );
+    }
+
+    #[test]
+    fn convex_hull_of_collinear_points() {
+        let points = [[1., 1.], [3., 3.], [0., 0.], [2., 2.]].map(Point::from);
+
+        assert_eq!(
+            convex_hull_2d(&points),
+            [[0., 0.], [3., 3.]].map(Point::from),
+        );
+        assert_eq!(
+            convex_hull_2d_with_collinear_points(&points),
+            [[0., 0.], [1., 1.], [2., 2.], [3., 3.]].map(Point::from),
+        );
+    }
+}
@@ -0,0 +1,122 @@
+//! Reproducible random geometry, for tests
+//!
+//! Available in this crate's tests and, for the tests of other crates, with
+//! the `sample` feature.
+
+use rand::{rngs::SmallRng, Rng, SeedableRng};
+
+use crate::{Aabb, Point, Scalar, Transform, Triangle, Vector};
+
+/// Generates random geometry within an AABB
+///
+/// Uses a fast, non-cryptographic RNG. The same seed always yields the same
+/// sequence of values, so tests that use this remain repeatable.
+#[derive(Clone, Debug)]
+pub struct Sampler {
+    rng: SmallRng,
+}
+
+impl Sampler {
+    /// Construct a sampler from a seed
+    pub fn new(seed: u64) -> Self {
+        Self {
+            rng: SmallRng::seed_from_u64(seed),
+        }
+    }
+
+    /// Generate a point within the AABB
+    pub fn point<const D: usize>(&mut self, aabb: &Aabb<D>) -> Point<D> {
+        let mut components = [Scalar::ZERO; D];
+        for (i, component) in components.iter_mut().enumerate() {
+            let [min, max] =
+                [aabb.min, aabb.max].map(|p| p.coords.components[i]);
+            *component = min + (max - min) * self.rng.gen_range(0. ..=1.);
+        }
+
+        Point::from(components)
+    }
+
+    /// Generate a number of points within the AABB
+    pub fn points<const D: usize>(
+        &mut self,
+        aabb: &Aabb<D>,
+        n: usize,
+    ) -> Vec<Point<D>> {
+        (0..n).map(|_| self.point(aabb)).collect()
+    }
+
+    /// Generate a non-degenerate triangle within the AABB
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the AABB is flat in more than one dimension, as no valid
+    /// triangle fits in it then.
+    pub fn triangle(&mut self, aabb: &Aabb<3>) -> Triangle<3> {
+        for _ in 0..MAX_ATTEMPTS {
+            let points = [(); 3].map(|_| self.point(aabb));
+            if let Some(triangle) = Triangle::from_points(points) {
+                return triangle;
+            }
+        }
+
+        panic!("Unable to generate a triangle within {aabb:?}");
+    }
+
+    /// Generate a transform that moves the origin into the AABB
+    ///
+    /// The transform rotates by an arbitrary angle, around an arbitrary axis,
+    /// before translating.
+    pub fn transform(&mut self, aabb: &Aabb<3>) -> Transform {
+        let axis_angle = Vector::from(
+            [(); 3].map(|_| Scalar::PI * self.rng.gen_range(-1. ..=1.)),
+        );
+        let translation = self.point(aabb).coords;
+
+        Transform::translation(translation) * Transform::rotation(axis_angle)
+    }
+}
+
+const MAX_ATTEMPTS: usize = 100;
+
+#[cfg(test)]
+mod tests {
+    use crate::{Aabb, Point};
+
+    use super::Sampler;
+
+    fn aabb() -> Aabb<3> {
+        Aabb {
+            min: Point::from([-1., 0., 2.]),
+            max: Point::from([1., 5., 3.]),
+        }
+    }
+
+    #[test]
+    fn same_seed_same_sequence() {
+        let mut a = Sampler::new(7);
+        let mut b = Sampler::new(7);
+
+        assert_eq!(a.points(&aabb(), 16), b.points(&aabb(), 16));
+        assert_eq!(a.triangle(&aabb()), b.triangle(&aabb()));
+        assert_eq!(a.transform(&aabb()).data(), b.transform(&aabb()).data());
+
+        let mut c = Sampler::new(8);
+        assert_ne!(Sampler::new(7).points(&aabb(), 16), c.points(&aabb(), 16));
+    }
+
+    #[test]
+    fn within_aabb() {
+        let mut sampler = Sampler::new(0);
+
+        for point in sampler.points(&aabb(), 100) {
+            assert!(aabb().contains(point));
+        }
+        for point in sampler.triangle(&aabb()).points() {
+            assert!(aabb().contains(point));
+        }
+
+        let origin =
+            sampler.transform(&aabb()).transform_point(&Point::origin());
+        assert!(aabb().contains(origin));
+    }
+}
@@ -52,6 +52,103 @@ impl Segment<2> {
     pub fn to_parry(self) -> parry2d_f64::shape::Segment {
         self.points.map(|point| point.to_na()).into()
     }
+
+    /// Compute the intersection with another segment
+    ///
+    /// Returns `None`, if the segments don't intersect. If they are collinear
+    /// and overlap, the overlap has the same direction as `self`.
+    ///
+    /// End points that are involved in an intersection are returned exactly as
+    /// they are, without being subject to any numerical error.
+    pub fn intersect(&self, other: &Self) -> Option<SegmentIntersection> {
+        let [a, b] = self.points;
+        let [c, d] = other.points;
+
+        // Which side of each segment the end points of the other one are on.
+        // These are exact, so whether the segments intersect, and whether an
+        // end point is involved, is decided without numerical error.
+        let [orient_c, orient_d] = [c, d].map(|point| orient([a, b], point));
+        let [orient_a, orient_b] = [a, b].map(|point| orient([c, d], point));
+
+        if orient_c == 0. && orient_d == 0. {
+            return intersect_collinear([a, b], [c, d]);
+        }
+
+        if orient_c * orient_d > 0. || orient_a * orient_b > 0. {
+            // Both end points of one segment are on the same side of the
+            // other.
+            return None;
+        }
+
+        let point = if orient_a == 0. {
+            a
+        } else if orient_b == 0. {
+            b
+        } else if orient_c == 0. {
+            c
+        } else if orient_d == 0. {
+            d
+        } else {
+            a + (b - a) * (orient_a / (orient_a - orient_b))
+        };
+
+        Some(SegmentIntersection::Point(point))
+    }
+}
+
+/// Intersect two segments that are on the same line
+fn intersect_collinear(
+    [a, b]: [Point<2>; 2],
+    [c, d]: [Point<2>; 2],
+) -> Option<SegmentIntersection> {
+    // Express the other segment's points as parameters along the first one,
+    // then intersect the intervals.
+    let r = b - a;
+    let r_dot_r = r.dot(&r);
+    let t_c = ((c - a).dot(&r) / r_dot_r, c);
+    let t_d = ((d - a).dot(&r) / r_dot_r, d);
+
+    let (other_start, other_end) = if t_c.0 <= t_d.0 {
+        (t_c, t_d)
+    } else {
+        (t_d, t_c)
+    };
+
+    let start = if other_start.0 > Scalar::ZERO {
+        other_start
+    } else {
+        (Scalar::ZERO, a)
+    };
+    let end = if other_end.0 < Scalar::ONE {
+        other_end
+    } else {
+        (Scalar::ONE, b)
+    };
+
+    if start.0 > end.0 {
+        return None;
+    }
+    if start.1 == end.1 {
+        // The segments touch at their end points.
+        return Some(SegmentIntersection::Point(start.1));
+    }
+
+    Some(SegmentIntersection::Overlap(Segment::from_points([
+        start.1, end.1,
+    ])))
+}
+
+/// Compute on which side of the line through `a` and `b` the point `c` is
+///
+/// The result is positive, if `c` is to the left of the line, negative, if it
+/// is to the right, and zero, if it is on the line. Unlike a naively computed
+/// cross product, it is exact.
+pub(crate) fn orient([a, b]: [Point<2>; 2], c: Point<2>) -> f64 {
+    let [a, b, c] = [a, b, c].map(|point| robust::Coord {
+        x: point.u,
+        y: point.v,
+    });
+    robust::orient2d(a, b, c)
 }
 
 impl Segment<3> {
@@ -61,6 +158,19 @@ impl Segment<3> {
     }
 }
 
+/// An intersection between two [`Segment`]s
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SegmentIntersection {
+    /// The segments intersect at a single point
+    ///
+    /// This includes segments that cross each other, as well as segments that
+    /// touch at an end point.
+    Point(Point<2>),
+
+    /// The segments are collinear and overlap
+    Overlap(Segment<2>),
+}
+
 impl<P, const D: usize> From<[P; 2]> for Segment<D>
 where
     P: Into<Point<D>>,
@@ -75,3 +185,107 @@ impl<const D: usize> fmt::Debug for Segment<D> {
         write!(f, "[{:?} -> {:?}]", self.points[0], self.points[1])
     }
 }
+
+impl<const D: usize> approx::AbsDiffEq for Segment<D> {
+    type Epsilon = <Scalar as approx::AbsDiffEq>::Epsilon;
+
+    fn default_epsilon() -> Self::Epsilon {
+        Scalar::default_epsilon()
+    }
+
+    fn abs_diff_eq(&self, other: &Self, epsilon: Self::Epsilon) -> bool {
+        self.points.abs_diff_eq(&other.points, epsilon)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Point, Segment};
+
+    use super::SegmentIntersection;
+
+    #[test]
+    fn intersect_crossing() {
+        let a = Segment::from([[0., 0.], [2., 2.]]);
+        let b = Segment::from([[0., 2.], [2., 0.]]);
+
+        let expected = Some(SegmentIntersection::Point(Point::from([1., 1.])));
+        assert_eq!(a.intersect(&b), expected);
+        assert_eq!(b.intersect(&a), expected);
+    }
+
+    #[test]
+    fn intersect_t_junction() {
+        let a = Segment::from([[0., 0.], [2., 0.]]);
+        let b = Segment::from([[1., 0.], [1., 1.]]);
+
+        let expected = Some(SegmentIntersection::Point(Point::from([1., 0.])));
+        assert_eq!(a.intersect(&b), expected);
+        assert_eq!(b.intersect(&a), expected);
+    }
+
+    #[test]
+    fn intersect_shared_end_point() {
+        let a = Segment::from([[0., 0.], [1., 0.]]);
+        let b = Segment::from([[1., 0.], [1., 1.]]);
+        let c = Segment::from([[1., 0.], [2., 0.]]);
+
+        let expected = Some(SegmentIntersection::Point(Point::from([1., 0.])));
+        assert_eq!(a.intersect(&b), expected);
+        assert_eq!(a.intersect(&c), expected);
+    }
+
+    #[test]
+    fn intersect_near_miss() {
+        // `c` is a tiny distance to the left of `a`. Computed naively, the
+        // cross product that tells which side it's on rounds to zero.
+        let a = Segment::from([
+            [0.7359699890685233, 0.6697304014402209],
+            [13.081364575891442, 16.059441656784625],
+        ]);
+        let b =
+            Segment::from([[7.699773009091724, 9.350774734494042], [0., 10.]]);
+
+        assert_eq!(a.intersect(&b), None);
+        assert_eq!(b.intersect(&a), None);
+    }
+
+    #[test]
+    fn intersect_parallel() {
+        let a = Segment::from([[0., 0.], [2., 0.]]);
+        let b = Segment::from([[0., 1.], [2., 1.]]);
+
+        assert_eq!(a.intersect(&b), None);
+    }
+
+    #[test]
+    fn intersect_collinear() {
+        let a = Segment::from([[0., 0.], [2., 0.]]);
+        let b = Segment::from([[3., 0.], [1., 0.]]);
+        let c = Segment::from([[3., 0.], [4., 0.]]);
+
+        assert_eq!(
+            a.intersect(&b),
+            Some(SegmentIntersection::Overlap(Segment::from([
+                [1., 0.],
+                [2., 0.]
+            ])))
+        );
+        assert_eq!(
+            b.intersect(&a),
+            Some(SegmentIntersection::Overlap(Segment::from([
+                [2., 0.],
+                [1., 0.]
+            ])))
+        );
+        assert_eq!(a.intersect(&c), None);
+    }
+
+    #[test]
+    fn intersect_disjoint() {
+        let a = Segment::from([[0., 0.], [1., 1.]]);
+        let b = Segment::from([[0., 3.], [3., 0.]]);
+
+        assert_eq!(a.intersect(&b), None);
+    }
+}
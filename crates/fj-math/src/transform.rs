@@ -2,7 +2,7 @@ use std::ops;
 
 use nalgebra::Perspective3;
 
-use crate::{Circle, Line, Scalar};
+use crate::{Circle, Ellipse, Line, Parabola, Scalar};
 
 use super::{Aabb, Point, Segment, Triangle, Vector};
 
@@ -40,6 +40,60 @@ impl Transform {
         ))
     }
 
+    /// Construct a uniform scaling
+    pub fn scaling(factor: impl Into<Scalar>) -> Self {
+        let factor = factor.into();
+
+        Self(nalgebra::Transform::from_matrix_unchecked(
+            nalgebra::OMatrix::new_scaling(factor.into_f64()),
+        ))
+    }
+
+    /// Construct a reflection across a plane
+    ///
+    /// The plane is defined by its normal and its distance from the origin,
+    /// along that normal. The normal doesn't need to be normalized.
+    pub fn mirror(
+        normal: impl Into<Vector<3>>,
+        offset: impl Into<Scalar>,
+    ) -> Self {
+        let normal = normal.into().normalize().to_na();
+        let offset = offset.into().into_f64();
+
+        let linear =
+            nalgebra::Matrix3::identity() - normal * normal.transpose() * 2.;
+        let mut matrix = linear.to_homogeneous();
+        matrix
+            .fixed_slice_mut::<3, 1>(0, 3)
+            .copy_from(&(normal * offset * 2.));
+
+        Self(nalgebra::Transform::from_matrix_unchecked(matrix))
+    }
+
+    /// Construct the view transform of a camera
+    ///
+    /// The returned transform maps points into a space, in which `eye` is the
+    /// origin, `target` is on the negative z-axis, and `up` is in the yz-plane,
+    /// pointing towards positive y.
+    pub fn look_at(eye: &Point<3>, target: &Point<3>, up: &Vector<3>) -> Self {
+        Self(nalgebra::Transform::from_matrix_unchecked(
+            nalgebra::Isometry3::look_at_rh(
+                &eye.to_na(),
+                &target.to_na(),
+                &up.to_na(),
+            )
+            .to_homogeneous(),
+        ))
+    }
+
+    /// Indicate whether this transform changes handedness
+    ///
+    /// This is the case for reflections. Objects that are transformed like
+    /// that end up inside-out, unless their orientation is fixed up.
+    pub fn is_mirroring(&self) -> bool {
+        self.0.matrix().fixed_resize::<3, 3>(0.).determinant() < 0.
+    }
+
     /// Transform the given point
     pub fn transform_point(&self, point: &Point<3>) -> Point<3> {
         Point::from(self.0.transform_point(&point.to_na()))
@@ -88,6 +142,24 @@ impl Transform {
         )
     }
 
+    /// Transform the given ellipse
+    pub fn transform_ellipse(&self, ellipse: &Ellipse<3>) -> Ellipse<3> {
+        Ellipse::new(
+            self.transform_point(&ellipse.center()),
+            self.transform_vector(&ellipse.a()),
+            self.transform_vector(&ellipse.b()),
+        )
+    }
+
+    /// Transform the given parabola
+    pub fn transform_parabola(&self, parabola: &Parabola<3>) -> Parabola<3> {
+        Parabola::new(
+            self.transform_point(&parabola.origin()),
+            self.transform_vector(&parabola.a()),
+            self.transform_vector(&parabola.b()),
+        )
+    }
+
     /// Inverse transform
     pub fn inverse(&self) -> Transform {
         Self(self.0.inverse())
@@ -120,11 +192,15 @@ impl Transform {
     }
 
     /// Transform the given axis-aligned bounding box
+    ///
+    /// The result bounds all corners of the transformed box, which is larger
+    /// than necessary, if the transform contains a rotation.
     pub fn transform_aabb(&self, aabb: &Aabb<3>) -> Aabb<3> {
-        Aabb {
-            min: self.transform_point(&aabb.min),
-            max: self.transform_point(&aabb.max),
-        }
+        Aabb::<3>::from_points(
+            aabb.vertices()
+                .iter()
+                .map(|vertex| self.transform_point(vertex)),
+        )
     }
 
     /// Exposes the data of this Transform as a slice of f64.
@@ -157,7 +233,7 @@ impl ops::Mul<Self> for Transform {
 mod tests {
     use approx::assert_abs_diff_eq;
 
-    use crate::{Line, Point, Scalar, Vector};
+    use crate::{Aabb, Line, Point, Scalar, Vector};
 
     use super::Transform;
 
@@ -182,6 +258,68 @@ mod tests {
         );
     }
 
+    #[test]
+    fn look_at() {
+        let transform = Transform::look_at(
+            &Point::from([1., 0., 0.]),
+            &Point::from([1., 5., 0.]),
+            &Vector::unit_z(),
+        );
+
+        assert_abs_diff_eq!(
+            transform.transform_point(&Point::from([1., 2., 0.])),
+            Point::from([0., 0., -2.]),
+            epsilon = Scalar::from(1e-8),
+        );
+        assert_abs_diff_eq!(
+            transform.transform_vector(&Vector::unit_z()),
+            Vector::unit_y(),
+            epsilon = Scalar::from(1e-8),
+        );
+    }
+
+    #[test]
+    fn transform_aabb() {
+        let aabb = Aabb {
+            min: Point::from([0., 0., 0.]),
+            max: Point::from([1., 2., 3.]),
+        };
+
+        let transform = Transform::rotation(Vector::unit_z() * Scalar::PI)
+            * Transform::scaling(2.);
+        let aabb = transform.transform_aabb(&aabb);
+
+        assert_abs_diff_eq!(
+            aabb.min,
+            Point::from([-2., -4., 0.]),
+            epsilon = Scalar::from(1e-8),
+        );
+        assert_abs_diff_eq!(
+            aabb.max,
+            Point::from([0., 0., 6.]),
+            epsilon = Scalar::from(1e-8),
+        );
+    }
+
+    #[test]
+    fn mirror() {
+        let transform = Transform::mirror([0., 0., 2.], 1.);
+
+        assert_abs_diff_eq!(
+            transform.transform_point(&Point::from([1., 2., 3.])),
+            Point::from([1., 2., -1.]),
+            epsilon = Scalar::from(1e-8),
+        );
+        assert_abs_diff_eq!(
+            transform.transform_vector(&Vector::unit_x()),
+            Vector::unit_x(),
+            epsilon = Scalar::from(1e-8),
+        );
+        assert!(transform.is_mirroring());
+        assert!(!Transform::rotation(Vector::unit_z()).is_mirroring());
+        assert!(!(transform * transform).is_mirroring());
+    }
+
     #[test]
     fn extract_rotation_translation() {
         let rotation =
@@ -42,6 +42,12 @@ impl<const D: usize> Triangle<D> {
         self.points
     }
 
+    /// Compute the triangle's centroid
+    pub fn centroid(&self) -> Point<D> {
+        let [a, b, c] = self.points;
+        a + ((b - a) + (c - a)) / 3.
+    }
+
     /// Normalize the triangle
     ///
     /// Returns a new `Triangle` instance with the same points, but the points
@@ -89,12 +95,28 @@ impl Triangle<3> {
     }
 
     /// Compute the triangle's normal
-    pub fn normal(&self) -> Vector<3> {
-        self.to_parry()
-            .normal()
-            .expect("triangle is valid (validated on construction)")
-            .into_inner()
-            .into()
+    ///
+    /// The normal points towards the side from which the points of the
+    /// triangle appear in counter-clockwise order.
+    ///
+    /// Returns `None`, if the triangle is degenerate. Triangles constructed via
+    /// [`Triangle::from_points`] are not, unless their points are so close to
+    /// being collinear, that the normal can't be computed precisely enough.
+    pub fn normal(&self) -> Option<Vector<3>> {
+        let [a, b, c] = self.points;
+        let normal = (b - a).cross(&(c - a));
+
+        if normal.magnitude() == Scalar::ZERO {
+            return None;
+        }
+
+        Some(normal.normalize())
+    }
+
+    /// Compute the triangle's area
+    pub fn area(&self) -> Scalar {
+        let [a, b, c] = self.points;
+        (b - a).cross(&(c - a)).magnitude() / 2.
     }
 }
 
@@ -107,6 +129,18 @@ where
     }
 }
 
+impl<const D: usize> approx::AbsDiffEq for Triangle<D> {
+    type Epsilon = <Scalar as approx::AbsDiffEq>::Epsilon;
+
+    fn default_epsilon() -> Self::Epsilon {
+        Scalar::default_epsilon()
+    }
+
+    fn abs_diff_eq(&self, other: &Self, epsilon: Self::Epsilon) -> bool {
+        self.points.abs_diff_eq(&other.points, epsilon)
+    }
+}
+
 /// Winding direction of a triangle.
 pub enum Winding {
     /// Counter-clockwise
@@ -127,7 +161,7 @@ impl From<Orientation> for Winding {
 
 #[cfg(test)]
 mod tests {
-    use crate::{Point, Vector};
+    use crate::{Point, Scalar, Vector};
 
     use super::Triangle;
 
@@ -169,6 +203,29 @@ mod tests {
     fn normal() {
         let triangle =
             Triangle::from([[0.0, 0.0, 0.0], [2.0, 1.0, 0.0], [2.0, 0.0, 0.0]]);
-        assert_eq!(triangle.normal(), Vector::from([0.0, 0.0, -1.0]));
+        assert_eq!(triangle.normal(), Some(Vector::from([0.0, 0.0, -1.0])));
+    }
+
+    #[test]
+    fn normal_of_degenerate_triangle() {
+        // Can't construct a degenerate triangle the normal way.
+        let triangle = Triangle {
+            points: [[0.0, 0.0, 0.0], [1.0, 1.0, 1.0], [2.0, 2.0, 2.0]]
+                .map(Point::from),
+        };
+        assert_eq!(triangle.normal(), None);
+    }
+
+    #[test]
+    fn area() {
+        let triangle =
+            Triangle::from([[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]]);
+        assert_eq!(triangle.area(), Scalar::from(0.5));
+    }
+
+    #[test]
+    fn centroid() {
+        let triangle = Triangle::from([[0.0, 0.0], [3.0, 0.0], [0.0, 3.0]]);
+        assert_eq!(triangle.centroid(), Point::from([1.0, 1.0]));
     }
 }
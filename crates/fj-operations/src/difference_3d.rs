@@ -0,0 +1,35 @@
+use fj_interop::debug::DebugInfo;
+use fj_kernel::{
+    algorithms::{difference, Tolerance},
+    objects::Face,
+    validation::{validate, Validated, ValidationConfig, ValidationError},
+};
+use fj_math::Aabb;
+
+use super::Shape;
+
+impl Shape for fj::Difference3d {
+    type Brep = Vec<Face>;
+
+    fn compute_brep(
+        &self,
+        config: &ValidationConfig,
+        tolerance: Tolerance,
+        debug_info: &mut DebugInfo,
+    ) -> Result<Validated<Self::Brep>, ValidationError> {
+        let [a, b] = self.shapes();
+        let a = a.compute_brep(config, tolerance, debug_info)?;
+        let b = b.compute_brep(config, tolerance, debug_info)?;
+
+        let faces = difference(&a.into_inner(), &b.into_inner(), tolerance);
+
+        validate(faces, config)
+    }
+
+    fn bounding_volume(&self) -> Aabb<3> {
+        // This is a conservative estimate of the bounding box: It's never going
+        // to be bigger than the bounding box of the original shape that another
+        // is being subtracted from.
+        self.shapes()[0].bounding_volume()
+    }
+}
@@ -32,6 +32,15 @@ impl Shape for fj::Group {
         let a = self.a.bounding_volume();
         let b = self.b.bounding_volume();
 
+        // An empty shape's AABB is at an unspecified point, which must not
+        // affect the result.
+        if a.min == a.max {
+            return b;
+        }
+        if b.min == b.max {
+            return a;
+        }
+
         a.merged(&b)
     }
 }
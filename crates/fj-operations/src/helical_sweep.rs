@@ -0,0 +1,79 @@
+use std::f64::consts::{FRAC_PI_2, TAU};
+
+use fj_interop::{debug::DebugInfo, mesh::Color};
+use fj_kernel::{
+    algorithms::{sweep_helix, Tolerance, TransformObject},
+    objects::Solid,
+    validation::{validate, Validated, ValidationConfig, ValidationError},
+};
+use fj_math::{Aabb, Point, Scalar, Transform};
+
+use super::Shape;
+
+impl Shape for fj::HelicalSweep {
+    type Brep = Solid;
+
+    fn compute_brep(
+        &self,
+        config: &ValidationConfig,
+        tolerance: Tolerance,
+        debug_info: &mut DebugInfo,
+    ) -> Result<Validated<Self::Brep>, ValidationError> {
+        let helix = self.helix();
+        let sketch = self
+            .shape()
+            .compute_brep(config, tolerance, debug_info)?
+            .into_inner()
+            .transform(&placement(&helix));
+        let color = self.shape().color();
+
+        let solid = sweep_helix(
+            sketch,
+            [0., 0., 0.],
+            [0., 0., 1.],
+            helix.pitch,
+            helix.turns * TAU,
+            tolerance,
+            Color(color),
+        );
+        validate(solid, config)
+    }
+
+    fn bounding_volume(&self) -> Aabb<3> {
+        let aabb = self.shape().bounding_volume();
+
+        // Sweeping an empty shape results in an empty shape.
+        if aabb.min == aabb.max {
+            return aabb;
+        }
+
+        let helix = self.helix();
+        let aabb = placement(&helix).transform_aabb(&aabb);
+
+        // The shape is rotated around the z-axis, so it can reach as far from
+        // it as its farthest point, in any direction.
+        let radius = aabb
+            .vertices()
+            .into_iter()
+            .map(|vertex| vertex.coords.xy().magnitude())
+            .fold(Scalar::ZERO, Scalar::max);
+
+        let height = Scalar::from_f64(helix.height());
+        let (min, max) = if height < Scalar::ZERO {
+            (aabb.min.z + height, aabb.max.z)
+        } else {
+            (aabb.min.z, aabb.max.z + height)
+        };
+
+        Aabb {
+            min: Point::from([-radius, -radius, min]),
+            max: Point::from([radius, radius, max]),
+        }
+    }
+}
+
+/// Move the shape from the xy-plane to the start of the helix
+fn placement(helix: &fj::Helix) -> Transform {
+    Transform::translation([helix.radius, 0., 0.])
+        * Transform::rotation([FRAC_PI_2, 0., 0.])
+}
@@ -0,0 +1,197 @@
+//! Detection of repeated shapes, for instanced rendering
+
+use fj_math::Transform;
+
+use crate::{
+    pattern::{circular_transforms, linear_transforms},
+    transform::make_transform,
+};
+
+/// A shape that appears in a model one or more times
+///
+/// Each appearance is described by a transform, that moves the shape from its
+/// own coordinate system into the model's.
+#[derive(Clone, Debug)]
+pub struct Instances {
+    /// The shape that is repeated
+    pub shape: fj::Shape,
+
+    /// One transform for each appearance of the shape
+    pub transforms: Vec<Transform>,
+}
+
+/// Find the shapes that are repeated within groups
+///
+/// Groups, patterns, and transforms are flattened. Every other shape is a leaf,
+/// and leaves that are equal are collected into the same [`Instances`], in the
+/// order they first appear in.
+///
+/// Triangulating each returned shape once, and drawing it with all of its
+/// transforms, produces the same result as triangulating the whole shape.
+pub fn find_instances(shape: &fj::Shape) -> Vec<Instances> {
+    let mut instances = Vec::new();
+    collect(shape, Transform::identity(), &mut instances);
+    instances
+}
+
+fn collect(
+    shape: &fj::Shape,
+    transform: Transform,
+    instances: &mut Vec<Instances>,
+) {
+    match shape {
+        fj::Shape::Group(group) => {
+            collect(&group.a, transform, instances);
+            collect(&group.b, transform, instances);
+        }
+        fj::Shape::LinearPattern(pattern) => {
+            for instance in linear_transforms(pattern) {
+                collect(&pattern.shape, transform * instance, instances);
+            }
+        }
+        fj::Shape::CircularPattern(pattern) => {
+            for instance in circular_transforms(pattern) {
+                collect(&pattern.shape, transform * instance, instances);
+            }
+        }
+        // Scaling an instance would scale its approximation error too. Scaled
+        // shapes are triangulated on their own, at the right tolerance.
+        fj::Shape::Transform(inner) if inner.scale == 1. => {
+            collect(&inner.shape, transform * make_transform(inner), instances);
+        }
+        fj::Shape::Difference3d(_)
+        | fj::Shape::HelicalSweep(_)
+        | fj::Shape::Intersection(_)
+        | fj::Shape::Loft(_)
+        | fj::Shape::Mirror(_)
+        | fj::Shape::PathSweep(_)
+        | fj::Shape::Revolve(_)
+        | fj::Shape::Shape2d(_)
+        | fj::Shape::Sweep(_)
+        | fj::Shape::Transform(_)
+        | fj::Shape::Union(_) => {
+            match instances.iter_mut().find(|i| i.shape == *shape) {
+                Some(existing) => existing.transforms.push(transform),
+                None => instances.push(Instances {
+                    shape: shape.clone(),
+                    transforms: vec![transform],
+                }),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Point, Transform};
+
+    use super::find_instances;
+
+    fn tooth(x: f64) -> fj::Shape {
+        let sketch =
+            fj::Sketch::from_points(vec![[0., 0.], [1., 0.], [0., 1.]]);
+        fj::Sweep::from_path(sketch.into(), [0., 0., x]).into()
+    }
+
+    fn moved(shape: fj::Shape, offset: [f64; 3]) -> fj::Shape {
+        fj::Transform {
+            shape,
+            axis: [0., 0., 1.],
+            angle: fj::Angle::from_rad(0.),
+            offset,
+            scale: 1.,
+        }
+        .into()
+    }
+
+    fn group(shapes: impl IntoIterator<Item = fj::Shape>) -> fj::Shape {
+        shapes
+            .into_iter()
+            .reduce(|a, b| fj::Group { a, b }.into())
+            .unwrap()
+    }
+
+    #[test]
+    fn identical_children() {
+        let n = 5;
+        let shape =
+            group((0..n).map(|i| moved(tooth(1.), [i as f64 * 2., 0., 0.])));
+
+        let instances = find_instances(&shape);
+
+        assert_eq!(instances.len(), 1);
+        assert_eq!(instances[0].shape, tooth(1.));
+        assert_eq!(instances[0].transforms.len(), n);
+
+        let origin = Point::origin();
+        for (i, transform) in instances[0].transforms.iter().enumerate() {
+            assert_eq!(
+                transform.transform_point(&origin),
+                Point::from([i as f64 * 2., 0., 0.])
+            );
+        }
+    }
+
+    #[test]
+    fn different_children() {
+        let shape = group([
+            tooth(1.),
+            moved(tooth(2.), [3., 0., 0.]),
+            moved(moved(tooth(1.), [0., 1., 0.]), [1., 0., 0.]),
+        ]);
+
+        let instances = find_instances(&shape);
+
+        assert_eq!(instances.len(), 2);
+        assert_eq!(instances[0].transforms.len(), 2);
+        assert_eq!(instances[1].transforms.len(), 1);
+
+        // Nested transforms are combined.
+        assert_eq!(
+            instances[0].transforms[1].transform_point(&Point::origin()),
+            Point::from([1., 1., 0.])
+        );
+        assert_eq!(
+            instances[0].transforms[0].data(),
+            Transform::identity().data()
+        );
+    }
+
+    #[test]
+    fn scaled_children() {
+        let scaled: fj::Shape = fj::Transform {
+            shape: tooth(1.),
+            axis: [0., 0., 1.],
+            angle: fj::Angle::from_rad(0.),
+            offset: [1., 0., 0.],
+            scale: 2.,
+        }
+        .into();
+        let shape = group([tooth(1.), scaled.clone()]);
+
+        let instances = find_instances(&shape);
+
+        // Scaled shapes can't be drawn as instances of the unscaled one.
+        assert_eq!(instances.len(), 2);
+        assert_eq!(instances[1].shape, scaled);
+    }
+
+    #[test]
+    fn pattern_children() {
+        let shape: fj::Shape = fj::LinearPattern {
+            shape: tooth(1.),
+            count: 3,
+            spacing: [2., 0., 0.],
+        }
+        .into();
+
+        let instances = find_instances(&shape);
+
+        assert_eq!(instances.len(), 1);
+        assert_eq!(instances[0].transforms.len(), 3);
+        assert_eq!(
+            instances[0].transforms[2].transform_point(&Point::origin()),
+            Point::from([4., 0., 0.])
+        );
+    }
+}
@@ -0,0 +1,39 @@
+use fj_interop::debug::DebugInfo;
+use fj_kernel::{
+    algorithms::{intersection, Tolerance},
+    objects::Face,
+    validation::{validate, Validated, ValidationConfig, ValidationError},
+};
+use fj_math::Aabb;
+
+use super::Shape;
+
+impl Shape for fj::Intersection {
+    type Brep = Vec<Face>;
+
+    fn compute_brep(
+        &self,
+        config: &ValidationConfig,
+        tolerance: Tolerance,
+        debug_info: &mut DebugInfo,
+    ) -> Result<Validated<Self::Brep>, ValidationError> {
+        let a = self.a.compute_brep(config, tolerance, debug_info)?;
+        let b = self.b.compute_brep(config, tolerance, debug_info)?;
+
+        let faces = intersection(&a.into_inner(), &b.into_inner(), tolerance);
+
+        validate(faces, config)
+    }
+
+    fn bounding_volume(&self) -> Aabb<3> {
+        let a = self.a.bounding_volume();
+        let b = self.b.bounding_volume();
+
+        // The intersection can't extend beyond either shape. If the boxes
+        // don't overlap, the intersection is empty.
+        a.intersection(&b).unwrap_or(Aabb {
+            min: a.min,
+            max: a.min,
+        })
+    }
+}
@@ -16,13 +16,24 @@
 
 #![warn(missing_docs)]
 
+pub mod instances;
 pub mod shape_processor;
 
 mod difference_2d;
+mod difference_3d;
 mod group;
+mod helical_sweep;
+mod intersection;
+mod loft;
+mod mirror;
+mod path_sweep;
+mod pattern;
+mod revolve;
 mod sketch;
 mod sweep;
+mod text;
 mod transform;
+mod union;
 
 use fj_interop::debug::DebugInfo;
 use fj_kernel::{
@@ -30,7 +41,7 @@ use fj_kernel::{
     objects::{Face, Sketch},
     validation::{validate, Validated, ValidationConfig, ValidationError},
 };
-use fj_math::Aabb;
+use fj_math::{Aabb, Point};
 
 /// Implemented for all operations from the [`fj`] crate
 pub trait Shape {
@@ -50,6 +61,20 @@ pub trait Shape {
     /// If a shape is empty, its [`Aabb`]'s `min` and `max` points must be equal
     /// (but are otherwise not specified).
     fn bounding_volume(&self) -> Aabb<3>;
+
+    /// Access a bounding sphere of a shape
+    ///
+    /// Returns the center and radius of the sphere around the shape's
+    /// [`Aabb`], which is cheap to compute, but not tight. For a tighter sphere,
+    /// use [`Mesh::bounding_sphere`] on the triangulated shape.
+    ///
+    /// [`Mesh::bounding_sphere`]: fj_interop::mesh::Mesh::bounding_sphere
+    fn bounding_sphere(&self) -> (Point<3>, f64) {
+        let aabb = self.bounding_volume();
+        let radius = (aabb.max - aabb.min).magnitude().into_f64() / 2.;
+
+        (aabb.center(), radius)
+    }
 }
 
 impl Shape for fj::Shape {
@@ -62,6 +87,42 @@ impl Shape for fj::Shape {
         debug_info: &mut DebugInfo,
     ) -> Result<Validated<Self::Brep>, ValidationError> {
         match self {
+            Self::CircularPattern(shape) => {
+                shape.compute_brep(config, tolerance, debug_info)
+            }
+            Self::Difference3d(shape) => {
+                shape.compute_brep(config, tolerance, debug_info)
+            }
+            Self::LinearPattern(shape) => {
+                shape.compute_brep(config, tolerance, debug_info)
+            }
+            Self::Loft(shape) => validate(
+                shape
+                    .compute_brep(config, tolerance, debug_info)?
+                    .into_inner()
+                    .into_faces()
+                    .collect(),
+                config,
+            ),
+            Self::Mirror(shape) => {
+                shape.compute_brep(config, tolerance, debug_info)
+            }
+            Self::PathSweep(shape) => validate(
+                shape
+                    .compute_brep(config, tolerance, debug_info)?
+                    .into_inner()
+                    .into_faces()
+                    .collect(),
+                config,
+            ),
+            Self::Revolve(shape) => validate(
+                shape
+                    .compute_brep(config, tolerance, debug_info)?
+                    .into_inner()
+                    .into_faces()
+                    .collect(),
+                config,
+            ),
             Self::Shape2d(shape) => validate(
                 shape
                     .compute_brep(config, tolerance, debug_info)?
@@ -73,6 +134,17 @@ impl Shape for fj::Shape {
             Self::Group(shape) => {
                 shape.compute_brep(config, tolerance, debug_info)
             }
+            Self::HelicalSweep(shape) => validate(
+                shape
+                    .compute_brep(config, tolerance, debug_info)?
+                    .into_inner()
+                    .into_faces()
+                    .collect(),
+                config,
+            ),
+            Self::Intersection(shape) => {
+                shape.compute_brep(config, tolerance, debug_info)
+            }
             Self::Sweep(shape) => validate(
                 shape
                     .compute_brep(config, tolerance, debug_info)?
@@ -84,15 +156,28 @@ impl Shape for fj::Shape {
             Self::Transform(shape) => {
                 shape.compute_brep(config, tolerance, debug_info)
             }
+            Self::Union(shape) => {
+                shape.compute_brep(config, tolerance, debug_info)
+            }
         }
     }
 
     fn bounding_volume(&self) -> Aabb<3> {
         match self {
+            Self::CircularPattern(shape) => shape.bounding_volume(),
+            Self::Difference3d(shape) => shape.bounding_volume(),
+            Self::LinearPattern(shape) => shape.bounding_volume(),
+            Self::Loft(shape) => shape.bounding_volume(),
+            Self::Mirror(shape) => shape.bounding_volume(),
+            Self::PathSweep(shape) => shape.bounding_volume(),
+            Self::Revolve(shape) => shape.bounding_volume(),
             Self::Shape2d(shape) => shape.bounding_volume(),
             Self::Group(shape) => shape.bounding_volume(),
+            Self::HelicalSweep(shape) => shape.bounding_volume(),
+            Self::Intersection(shape) => shape.bounding_volume(),
             Self::Sweep(shape) => shape.bounding_volume(),
             Self::Transform(shape) => shape.bounding_volume(),
+            Self::Union(shape) => shape.bounding_volume(),
         }
     }
 }
@@ -113,6 +198,9 @@ impl Shape for fj::Shape2d {
             Self::Sketch(shape) => {
                 shape.compute_brep(config, tolerance, debug_info)
             }
+            Self::Text(shape) => {
+                shape.compute_brep(config, tolerance, debug_info)
+            }
         }
     }
 
@@ -120,6 +208,7 @@ impl Shape for fj::Shape2d {
         match self {
             Self::Difference(shape) => shape.bounding_volume(),
             Self::Sketch(shape) => shape.bounding_volume(),
+            Self::Text(shape) => shape.bounding_volume(),
         }
     }
 }
@@ -0,0 +1,72 @@
+use fj_interop::{debug::DebugInfo, mesh::Color};
+use fj_kernel::{
+    algorithms::{loft, Tolerance, TransformObject},
+    objects::Solid,
+    validation::{validate, Validated, ValidationConfig, ValidationError},
+};
+use fj_math::{Aabb, Transform, Vector};
+
+use super::Shape;
+
+impl Shape for fj::Loft {
+    type Brep = Solid;
+
+    fn compute_brep(
+        &self,
+        config: &ValidationConfig,
+        tolerance: Tolerance,
+        debug_info: &mut DebugInfo,
+    ) -> Result<Validated<Self::Brep>, ValidationError> {
+        let mut profiles = Vec::new();
+        for profile in self.profiles() {
+            let sketch = profile
+                .sketch
+                .compute_brep(config, tolerance, debug_info)?
+                .into_inner();
+            let transform = make_transform(profile);
+
+            profiles.extend(
+                sketch.into_faces().map(|face| face.transform(&transform)),
+            );
+        }
+
+        let color = self
+            .profiles()
+            .first()
+            .map(|profile| profile.sketch.color())
+            .unwrap_or_default();
+
+        let solid = loft(&profiles, self.is_smooth(), tolerance, Color(color));
+        validate(solid, config)
+    }
+
+    fn bounding_volume(&self) -> Aabb<3> {
+        let mut aabb: Option<Aabb<3>> = None;
+        for profile in self.profiles() {
+            let profile = make_transform(profile)
+                .transform_aabb(&profile.sketch.bounding_volume());
+            aabb = Some(aabb.map_or(profile, |aabb| aabb.merged(&profile)));
+        }
+
+        // A smooth surface can bulge out between the profiles, but not by
+        // much. Leave some room for that.
+        aabb.map(|aabb| {
+            if self.is_smooth() {
+                let margin = aabb.size() / 4.;
+                Aabb {
+                    min: aabb.min - margin,
+                    max: aabb.max + margin,
+                }
+            } else {
+                aabb
+            }
+        })
+        .unwrap_or_else(|| Aabb::<3>::from_points([[0., 0., 0.]]))
+    }
+}
+
+fn make_transform(profile: &fj::LoftProfile) -> Transform {
+    let axis = Vector::from(profile.axis).normalize();
+    Transform::translation(profile.offset)
+        * Transform::rotation(axis * profile.angle.rad())
+}
@@ -0,0 +1,37 @@
+use fj_interop::debug::DebugInfo;
+use fj_kernel::{
+    algorithms::{transform_faces, Tolerance},
+    objects::Face,
+    validation::{validate, Validated, ValidationConfig, ValidationError},
+};
+use fj_math::{Aabb, Transform};
+
+use super::Shape;
+
+impl Shape for fj::Mirror {
+    type Brep = Vec<Face>;
+
+    fn compute_brep(
+        &self,
+        config: &ValidationConfig,
+        tolerance: Tolerance,
+        debug_info: &mut DebugInfo,
+    ) -> Result<Validated<Self::Brep>, ValidationError> {
+        let mut faces = self
+            .shape
+            .compute_brep(config, tolerance, debug_info)?
+            .into_inner();
+
+        transform_faces(&mut faces, &make_transform(self));
+
+        validate(faces, config)
+    }
+
+    fn bounding_volume(&self) -> Aabb<3> {
+        make_transform(self).transform_aabb(&self.shape.bounding_volume())
+    }
+}
+
+fn make_transform(mirror: &fj::Mirror) -> Transform {
+    Transform::mirror(mirror.normal, mirror.offset)
+}
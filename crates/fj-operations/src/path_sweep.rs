@@ -0,0 +1,73 @@
+use fj_interop::{debug::DebugInfo, mesh::Color};
+use fj_kernel::{
+    algorithms::{sweep_along_path, Tolerance},
+    objects::Solid,
+    validation::{validate, Validated, ValidationConfig, ValidationError},
+};
+use fj_math::{Aabb, Point, Scalar, Vector};
+
+use super::Shape;
+
+impl Shape for fj::PathSweep {
+    type Brep = Solid;
+
+    fn compute_brep(
+        &self,
+        config: &ValidationConfig,
+        tolerance: Tolerance,
+        debug_info: &mut DebugInfo,
+    ) -> Result<Validated<Self::Brep>, ValidationError> {
+        let sketch =
+            self.shape().compute_brep(config, tolerance, debug_info)?;
+        let path = self.path().iter().copied().map(Point::from);
+        let color = self.shape().color();
+
+        let solid = sweep_along_path(
+            sketch.into_inner(),
+            path,
+            tolerance,
+            Color(color),
+        );
+        validate(solid, config)
+    }
+
+    fn bounding_volume(&self) -> Aabb<3> {
+        let aabb = self.shape().bounding_volume();
+
+        let mut path: Vec<_> =
+            self.path().iter().copied().map(Point::from).collect();
+        path.dedup();
+
+        // Sweeping an empty shape, or along an empty path, results in an empty
+        // shape.
+        if aabb.min == aabb.max || path.len() < 2 {
+            return Aabb::<3>::from_points([aabb.min]);
+        }
+
+        // However the shape is turned, it stays within this distance of the
+        // path. Only at the corners is it stretched.
+        let radius = aabb
+            .vertices()
+            .into_iter()
+            .map(|vertex| (vertex - path[0]).magnitude())
+            .fold(Scalar::ZERO, Scalar::max);
+
+        let mut points = Vec::new();
+        for (i, &point) in path.iter().enumerate() {
+            let stretch = match (path.get(i.wrapping_sub(1)), path.get(i + 1)) {
+                (Some(&prev), Some(&next)) => {
+                    let incoming = (point - prev).normalize();
+                    let miter =
+                        (incoming + (next - point).normalize()).normalize();
+                    Scalar::ONE / incoming.dot(&miter)
+                }
+                _ => Scalar::ONE,
+            };
+
+            let offset = Vector::from([radius * stretch; 3]);
+            points.extend([point - offset, point + offset]);
+        }
+
+        Aabb::<3>::from_points(points)
+    }
+}
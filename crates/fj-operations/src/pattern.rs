@@ -0,0 +1,187 @@
+use fj_interop::debug::DebugInfo;
+use fj_kernel::{
+    algorithms::{transform_faces, Tolerance},
+    objects::Face,
+    validation::{validate, Validated, ValidationConfig, ValidationError},
+};
+use fj_math::{Aabb, Point, Transform, Vector};
+
+use super::Shape;
+
+impl Shape for fj::LinearPattern {
+    type Brep = Vec<Face>;
+
+    fn compute_brep(
+        &self,
+        config: &ValidationConfig,
+        tolerance: Tolerance,
+        debug_info: &mut DebugInfo,
+    ) -> Result<Validated<Self::Brep>, ValidationError> {
+        compute_brep(
+            &self.shape,
+            &linear_transforms(self),
+            config,
+            tolerance,
+            debug_info,
+        )
+    }
+
+    fn bounding_volume(&self) -> Aabb<3> {
+        bounding_volume(&self.shape, &linear_transforms(self))
+    }
+}
+
+impl Shape for fj::CircularPattern {
+    type Brep = Vec<Face>;
+
+    fn compute_brep(
+        &self,
+        config: &ValidationConfig,
+        tolerance: Tolerance,
+        debug_info: &mut DebugInfo,
+    ) -> Result<Validated<Self::Brep>, ValidationError> {
+        compute_brep(
+            &self.shape,
+            &circular_transforms(self),
+            config,
+            tolerance,
+            debug_info,
+        )
+    }
+
+    fn bounding_volume(&self) -> Aabb<3> {
+        bounding_volume(&self.shape, &circular_transforms(self))
+    }
+}
+
+/// The transforms that move the shape to each instance of a linear pattern
+pub(crate) fn linear_transforms(pattern: &fj::LinearPattern) -> Vec<Transform> {
+    let spacing = Vector::from(pattern.spacing);
+
+    (0..pattern.count)
+        .map(|i| Transform::translation(spacing * f64::from(i)))
+        .collect()
+}
+
+/// The transforms that move the shape to each instance of a circular pattern
+pub(crate) fn circular_transforms(
+    pattern: &fj::CircularPattern,
+) -> Vec<Transform> {
+    let origin = Vector::from(pattern.origin);
+    let axis = Vector::from(pattern.axis).normalize();
+
+    (0..pattern.count)
+        .map(|i| {
+            let angle = pattern.angle.rad() * f64::from(i);
+
+            Transform::translation(origin)
+                * Transform::rotation(axis * angle)
+                * Transform::translation(-origin)
+        })
+        .collect()
+}
+
+fn compute_brep(
+    shape: &fj::Shape,
+    transforms: &[Transform],
+    config: &ValidationConfig,
+    tolerance: Tolerance,
+    debug_info: &mut DebugInfo,
+) -> Result<Validated<Vec<Face>>, ValidationError> {
+    // The shape is the same for every instance, so only compute it once.
+    let original = shape
+        .compute_brep(config, tolerance, debug_info)?
+        .into_inner();
+
+    let mut faces = Vec::new();
+
+    for transform in transforms {
+        let mut instance = original.clone();
+        transform_faces(&mut instance, transform);
+        faces.extend(instance);
+    }
+
+    validate(faces, config)
+}
+
+fn bounding_volume(shape: &fj::Shape, transforms: &[Transform]) -> Aabb<3> {
+    let aabb = shape.bounding_volume();
+
+    // An empty shape's AABB is at an unspecified point, so there's no point in
+    // transforming it. If there are no instances, the pattern is empty too.
+    if aabb.min == aabb.max || transforms.is_empty() {
+        return Aabb {
+            min: Point::origin(),
+            max: Point::origin(),
+        };
+    }
+
+    // Rotating an AABB's minimum and maximum doesn't result in a valid AABB, so
+    // transform all of its vertices.
+    Aabb::<3>::from_points(transforms.iter().flat_map(|transform| {
+        aabb.vertices()
+            .map(|vertex| transform.transform_point(&vertex))
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use fj::syntax::*;
+    use fj_interop::debug::DebugInfo;
+    use fj_kernel::{algorithms::Tolerance, validation::ValidationConfig};
+    use fj_math::{Point, Scalar};
+
+    use crate::Shape as _;
+
+    fn cube() -> fj::Shape {
+        let sketch = fj::Sketch::from_points(vec![
+            [0., 0.],
+            [1., 0.],
+            [1., 1.],
+            [0., 1.],
+        ]);
+        sketch.sweep([0., 0., 1.]).into()
+    }
+
+    #[test]
+    fn linear_pattern() {
+        let pattern: fj::Shape = cube().linear_pattern(3, [2., 0., 0.]).into();
+
+        let tolerance = Tolerance::from_scalar(0.1).unwrap();
+        let faces = pattern
+            .compute_brep(
+                &ValidationConfig::default(),
+                tolerance,
+                &mut DebugInfo::new(),
+            )
+            .unwrap()
+            .into_inner();
+        assert_eq!(faces.len(), 3 * 6);
+
+        let aabb = pattern.bounding_volume();
+        assert_eq!(aabb.min, Point::from([0., 0., 0.]));
+        assert_eq!(aabb.max, Point::from([5., 1., 1.]));
+    }
+
+    #[test]
+    fn circular_pattern() {
+        let pattern: fj::Shape = cube()
+            .circular_pattern(
+                4,
+                [0., 0., 0.],
+                [0., 0., 1.],
+                fj::Angle::from_deg(90.),
+            )
+            .into();
+
+        let aabb = pattern.bounding_volume();
+        assert!(
+            (aabb.min - Point::from([-1., -1., 0.])).magnitude()
+                < Scalar::from(1e-9)
+        );
+        assert!(
+            (aabb.max - Point::from([1., 1., 1.])).magnitude()
+                < Scalar::from(1e-9)
+        );
+    }
+}
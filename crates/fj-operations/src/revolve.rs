@@ -0,0 +1,71 @@
+use std::f64::consts::TAU;
+
+use fj_interop::{debug::DebugInfo, mesh::Color};
+use fj_kernel::{
+    algorithms::{revolve, Tolerance},
+    objects::Solid,
+    validation::{validate, Validated, ValidationConfig, ValidationError},
+};
+use fj_math::{Aabb, Point, Scalar, Vector};
+
+use super::Shape;
+
+impl Shape for fj::Revolve {
+    type Brep = Solid;
+
+    fn compute_brep(
+        &self,
+        config: &ValidationConfig,
+        tolerance: Tolerance,
+        debug_info: &mut DebugInfo,
+    ) -> Result<Validated<Self::Brep>, ValidationError> {
+        let sketch =
+            self.shape().compute_brep(config, tolerance, debug_info)?;
+        let color = self.shape().color();
+
+        // `fj::Angle` wraps around, so a full revolution ends up as zero.
+        let angle = match self.angle().rad() {
+            angle if angle == 0. => TAU,
+            angle => angle,
+        };
+
+        let solid = revolve(
+            sketch.into_inner(),
+            self.axis_origin(),
+            self.axis(),
+            angle,
+            tolerance,
+            Color(color),
+        );
+        validate(solid, config)
+    }
+
+    fn bounding_volume(&self) -> Aabb<3> {
+        let aabb = self.shape().bounding_volume();
+
+        // Revolving an empty shape results in an empty shape.
+        if aabb.min == aabb.max {
+            return aabb;
+        }
+
+        // The shape stays within a cylinder around the axis, that is as wide
+        // as the shape's farthest point from the axis.
+        let origin = Point::from(self.axis_origin());
+        let axis = Vector::from(self.axis()).normalize();
+
+        let mut radius = Scalar::ZERO;
+        let mut points = Vec::new();
+        for vertex in aabb.vertices() {
+            let along_axis = axis * (vertex - origin).dot(&axis);
+            radius = radius.max((vertex - origin - along_axis).magnitude());
+            points.push(origin + along_axis);
+        }
+
+        let offset = Vector::from([radius; 3]);
+        Aabb::<3>::from_points(
+            points
+                .into_iter()
+                .flat_map(|point| [point - offset, point + offset]),
+        )
+    }
+}
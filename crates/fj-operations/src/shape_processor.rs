@@ -1,56 +1,334 @@
 //! API for processing shapes
 
-use fj_interop::{debug::DebugInfo, processed_shape::ProcessedShape};
+use std::collections::VecDeque;
+
+use fj_interop::{
+    debug::DebugInfo, mesh::Mesh, processed_shape::ProcessedShape,
+};
 use fj_kernel::{
-    algorithms::{triangulate, InvalidTolerance, Tolerance},
+    algorithms::{
+        triangulate, InvalidTolerance, Quality, Tolerance, TriangulationCache,
+    },
+    objects::Face,
     validation::{ValidationConfig, ValidationError},
 };
-use fj_math::Scalar;
+use fj_math::{Aabb, Point, Transform};
 
-use crate::Shape as _;
+use crate::{
+    instances::{find_instances, Instances},
+    Shape as _,
+};
 
 /// Processes an [`fj::Shape`] into a [`ProcessedShape`]
+#[derive(Clone)]
 pub struct ShapeProcessor {
     /// The tolerance value used for creating the triangle mesh
+    ///
+    /// Overrides `quality`, if set.
     pub tolerance: Option<Tolerance>,
+
+    /// The quality preset used, if no tolerance is set
+    pub quality: Quality,
+
+    /// Whether curves are approximated relative to their curvature
+    ///
+    /// See [`Tolerance::adaptive`]. The relative tolerance for curves comes from
+    /// `quality`, even if `tolerance` is set.
+    pub adaptive: bool,
 }
 
 impl ShapeProcessor {
     /// Process an [`fj::Shape`] into [`ProcessedShape`]
     pub fn process(&self, shape: &fj::Shape) -> Result<ProcessedShape, Error> {
         let aabb = shape.bounding_volume();
+        let tolerance = self.tolerance(&aabb)?;
+
+        process_with_tolerance(shape, aabb, tolerance, None)
+    }
+
+    /// Compute the boundary representation of an [`fj::Shape`]
+    ///
+    /// This is the first half of [`ShapeProcessor::process`], for callers that
+    /// need the faces, instead of a triangle mesh.
+    pub fn compute_faces(&self, shape: &fj::Shape) -> Result<Vec<Face>, Error> {
+        let tolerance = self.tolerance(&shape.bounding_volume())?;
 
+        let config = ValidationConfig::default();
+        let faces =
+            shape.compute_brep(&config, tolerance, &mut DebugInfo::new())?;
+
+        Ok(faces.into_inner())
+    }
+
+    /// Process an [`fj::Shape`] into meshes that can be drawn with instancing
+    ///
+    /// Parts of the shape that appear multiple times, as found by
+    /// [`find_instances`], are triangulated only once. All parts are
+    /// triangulated with the tolerance of the whole shape, so together they
+    /// match the mesh returned by [`ShapeProcessor::process`].
+    ///
+    /// Returns `None`, if no part of the shape repeats. The mesh returned by
+    /// [`ShapeProcessor::process`] is all there is to draw, in that case.
+    pub fn process_instances(
+        &self,
+        shape: &fj::Shape,
+    ) -> Result<Option<InstancedMeshes>, Error> {
+        let tolerance = self.tolerance(&shape.bounding_volume())?;
+        process_instances_with_tolerance(shape, tolerance, None)
+    }
+
+    fn tolerance(&self, aabb: &Aabb<3>) -> Result<Tolerance, InvalidTolerance> {
         let tolerance = match self.tolerance {
+            None => Tolerance::from_quality(self.quality, aabb)?,
+            Some(user_defined_tolerance) => user_defined_tolerance,
+        };
+
+        if self.adaptive {
+            return Ok(tolerance.adaptive(self.quality));
+        }
+
+        Ok(tolerance)
+    }
+}
+
+fn process_with_tolerance(
+    shape: &fj::Shape,
+    aabb: Aabb<3>,
+    tolerance: Tolerance,
+    triangulations: Option<&mut TriangulationCache>,
+) -> Result<ProcessedShape, Error> {
+    let config = ValidationConfig::default();
+    let mut debug_info = DebugInfo::new();
+    let shape = shape.compute_brep(&config, tolerance, &mut debug_info)?;
+    let mesh = match triangulations {
+        Some(triangulations) => triangulations.triangulate(
+            shape.into_inner(),
+            tolerance,
+            &mut debug_info,
+        ),
+        None => triangulate(shape.into_inner(), tolerance, &mut debug_info),
+    };
+
+    Ok(ProcessedShape {
+        aabb,
+        mesh,
+        debug_info,
+    })
+}
+
+fn process_instances_with_tolerance(
+    shape: &fj::Shape,
+    tolerance: Tolerance,
+    mut triangulations: Option<&mut TriangulationCache>,
+) -> Result<Option<InstancedMeshes>, Error> {
+    let instances = find_instances(shape);
+    if instances
+        .iter()
+        .all(|instance| instance.transforms.len() == 1)
+    {
+        return Ok(None);
+    }
+
+    let mut meshes = InstancedMeshes {
+        unique: Mesh::new(),
+        repeated: Vec::new(),
+    };
+    for Instances { shape, transforms } in instances {
+        let aabb = shape.bounding_volume();
+        let mesh = process_with_tolerance(
+            &shape,
+            aabb,
+            tolerance,
+            triangulations.as_deref_mut(),
+        )?
+        .mesh;
+
+        match transforms.as_slice() {
+            [transform] => {
+                for triangle in mesh.triangles() {
+                    meshes.unique.push_triangle(
+                        transform.transform_triangle(&triangle.inner),
+                        triangle.color,
+                    );
+                }
+            }
+            _ => meshes.repeated.push((mesh, transforms)),
+        }
+    }
+
+    Ok(Some(meshes))
+}
+
+/// A shape, triangulated for instanced rendering
+///
+/// Returned by [`ShapeProcessor::process_instances`].
+#[derive(Clone)]
+pub struct InstancedMeshes {
+    /// The parts of the shape that appear once, in model coordinates
+    pub unique: Mesh<Point<3>>,
+
+    /// The parts of the shape that repeat, each with one transform for each
+    /// time it appears
+    pub repeated: Vec<(Mesh<Point<3>>, Vec<Transform>)>,
+}
+
+/// Caches the results of [`ShapeProcessor`]
+///
+/// Results are keyed by a caller-provided shape ID and the tolerance they were
+/// processed with, so going back to a previously used tolerance doesn't require
+/// re-running the kernel. Processing a shape that differs from the one that was
+/// cached under the same ID discards all results for that ID.
+///
+/// Once the cache is full, the least recently used result is evicted.
+///
+/// When a shape has to be processed again, faces that it shares with the
+/// previously processed shape aren't triangulated again.
+pub struct ProcessedShapeCache {
+    capacity: usize,
+    entries: VecDeque<CacheEntry>,
+    triangulations: TriangulationCache,
+    misses: usize,
+}
+
+impl ProcessedShapeCache {
+    /// Create an empty cache that holds up to `capacity` results
+    ///
+    /// # Panics
+    ///
+    /// Panics, if `capacity` is zero.
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "Cache must be able to hold a result");
+
+        Self {
+            capacity,
+            entries: VecDeque::new(),
+            triangulations: TriangulationCache::new(),
+            misses: 0,
+        }
+    }
+
+    /// Process a shape, or return the cached result
+    pub fn process(
+        &mut self,
+        processor: &ShapeProcessor,
+        shape_id: &str,
+        shape: &fj::Shape,
+    ) -> Result<&ProcessedShape, Error> {
+        let entry = self.entry(processor, shape_id, shape)?;
+        Ok(&entry.processed)
+    }
+
+    /// Process a shape for instanced rendering, or return the cached result
+    ///
+    /// Like [`ShapeProcessor::process_instances`], but the result is cached
+    /// together with the one of [`ProcessedShapeCache::process`] for the same
+    /// shape and tolerance.
+    pub fn process_instances(
+        &mut self,
+        processor: &ShapeProcessor,
+        shape_id: &str,
+        shape: &fj::Shape,
+    ) -> Result<Option<&InstancedMeshes>, Error> {
+        self.entry(processor, shape_id, shape)?;
+        let entry = self.entries.back_mut().expect("Just used an entry");
+
+        if entry.instanced.is_none() {
+            self.misses += 1;
+
+            let instanced = process_instances_with_tolerance(
+                shape,
+                entry.tolerance,
+                Some(&mut self.triangulations),
+            )?;
+            entry.instanced = Some(instanced);
+        }
+
+        Ok(entry.instanced.as_ref().and_then(Option::as_ref))
+    }
+
+    /// Look up the entry for a shape, processing it, if it's not cached
+    ///
+    /// The entry ends up at the back of the queue.
+    fn entry(
+        &mut self,
+        processor: &ShapeProcessor,
+        shape_id: &str,
+        shape: &fj::Shape,
+    ) -> Result<&CacheEntry, Error> {
+        let aabb = shape.bounding_volume();
+        let tolerance = processor.tolerance(&aabb)?;
+
+        self.entries.retain(|entry| {
+            entry.shape_id != shape_id || entry.shape == *shape
+        });
+
+        let cached = self.entries.iter().position(|entry| {
+            entry.shape_id == shape_id && entry.tolerance == tolerance
+        });
+
+        let entry = match cached {
+            Some(index) => self
+                .entries
+                .remove(index)
+                .expect("Index returned by `position` must be valid"),
             None => {
-                // Compute a reasonable default for the tolerance value. To do
-                // this, we just look at the smallest non-zero extent of the
-                // bounding box and divide that by some value.
-                let mut min_extent = Scalar::MAX;
-                for extent in aabb.size().components {
-                    if extent > Scalar::ZERO && extent < min_extent {
-                        min_extent = extent;
-                    }
+                self.misses += 1;
+
+                let processed = process_with_tolerance(
+                    shape,
+                    aabb,
+                    tolerance,
+                    Some(&mut self.triangulations),
+                )?;
+
+                if self.entries.len() >= self.capacity {
+                    self.entries.pop_front();
                 }
 
-                let tolerance = min_extent / Scalar::from_f64(1000.);
-                Tolerance::from_scalar(tolerance)?
+                CacheEntry {
+                    shape_id: shape_id.to_owned(),
+                    shape: shape.clone(),
+                    tolerance,
+                    processed,
+                    instanced: None,
+                }
             }
-            Some(user_defined_tolerance) => user_defined_tolerance,
         };
 
-        let config = ValidationConfig::default();
-        let mut debug_info = DebugInfo::new();
-        let shape = shape.compute_brep(&config, tolerance, &mut debug_info)?;
-        let mesh = triangulate(shape.into_inner(), tolerance, &mut debug_info);
+        // The back of the queue is the most recently used entry.
+        self.entries.push_back(entry);
+        Ok(self.entries.back().expect("Just pushed an entry"))
+    }
 
-        Ok(ProcessedShape {
-            aabb,
-            mesh,
-            debug_info,
-        })
+    /// The number of times the shape processing pipeline had to run
+    ///
+    /// Processing a shape for instanced rendering counts separately.
+    pub fn misses(&self) -> usize {
+        self.misses
+    }
+
+    /// The number of faces that had to be triangulated
+    pub fn triangulated_faces(&self) -> usize {
+        self.triangulations.misses()
+    }
+
+    /// Discard all cached results
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.triangulations = TriangulationCache::new();
     }
 }
 
+struct CacheEntry {
+    shape_id: String,
+    shape: fj::Shape,
+    tolerance: Tolerance,
+    processed: ProcessedShape,
+
+    /// Computed on demand, as most callers don't need it
+    instanced: Option<Option<InstancedMeshes>>,
+}
+
 /// A shape processing error
 #[allow(clippy::large_enum_variant)]
 #[derive(Debug, thiserror::Error)]
@@ -63,3 +341,172 @@ pub enum Error {
     #[error("Model has zero size")]
     Extent(#[from] InvalidTolerance),
 }
+
+#[cfg(test)]
+mod tests {
+    use fj_kernel::algorithms::{Quality, Tolerance};
+
+    use super::{Error, ProcessedShapeCache, ShapeProcessor};
+
+    fn processor(tolerance: f64) -> ShapeProcessor {
+        ShapeProcessor {
+            tolerance: Some(Tolerance::from_scalar(tolerance).unwrap()),
+            quality: Quality::default(),
+            adaptive: false,
+        }
+    }
+
+    fn circle(radius: f64) -> fj::Shape {
+        fj::Sketch::from_circle(fj::Circle::from_radius(radius)).into()
+    }
+
+    #[test]
+    fn same_tolerance_hits_cache() -> Result<(), Error> {
+        let mut cache = ProcessedShapeCache::new(4);
+        let shape = circle(1.);
+
+        cache.process(&processor(0.1), "circle", &shape)?;
+        cache.process(&processor(0.01), "circle", &shape)?;
+        cache.process(&processor(0.1), "circle", &shape)?;
+
+        assert_eq!(cache.misses(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn switching_quality_hits_cache() -> Result<(), Error> {
+        let mut cache = ProcessedShapeCache::new(4);
+        let shape = circle(1.);
+
+        let mut processor = ShapeProcessor {
+            tolerance: None,
+            quality: Quality::Draft,
+            adaptive: false,
+        };
+        let draft = cache.process(&processor, "circle", &shape)?.mesh.clone();
+
+        processor.quality = Quality::Ultra;
+        let ultra = cache.process(&processor, "circle", &shape)?.mesh.clone();
+        assert!(ultra.triangles().count() > draft.triangles().count());
+
+        processor.quality = Quality::Draft;
+        cache.process(&processor, "circle", &shape)?;
+
+        assert_eq!(cache.misses(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn changed_shape_misses_cache() -> Result<(), Error> {
+        let mut cache = ProcessedShapeCache::new(4);
+
+        cache.process(&processor(0.1), "circle", &circle(1.))?;
+        cache.process(&processor(0.1), "circle", &circle(2.))?;
+        cache.process(&processor(0.1), "circle", &circle(1.))?;
+
+        assert_eq!(cache.misses(), 3);
+        Ok(())
+    }
+
+    #[test]
+    fn changed_shape_reuses_unchanged_faces() -> Result<(), Error> {
+        let mut cache = ProcessedShapeCache::new(4);
+        let group = |radius| -> fj::Shape {
+            fj::Group {
+                a: circle(1.),
+                b: circle(radius),
+            }
+            .into()
+        };
+
+        cache.process(&processor(0.1), "group", &group(2.))?;
+        assert_eq!(cache.triangulated_faces(), 2);
+
+        cache.process(&processor(0.1), "group", &group(3.))?;
+        assert_eq!(cache.triangulated_faces(), 3);
+
+        Ok(())
+    }
+
+    #[test]
+    fn least_recently_used_is_evicted() -> Result<(), Error> {
+        let mut cache = ProcessedShapeCache::new(2);
+        let shape = circle(1.);
+
+        cache.process(&processor(0.1), "circle", &shape)?;
+        cache.process(&processor(0.2), "circle", &shape)?;
+        cache.process(&processor(0.1), "circle", &shape)?;
+        cache.process(&processor(0.3), "circle", &shape)?;
+        assert_eq!(cache.misses(), 3);
+
+        // `0.1` was used more recently than `0.2`, so it must still be there.
+        cache.process(&processor(0.1), "circle", &shape)?;
+        assert_eq!(cache.misses(), 3);
+
+        cache.process(&processor(0.2), "circle", &shape)?;
+        assert_eq!(cache.misses(), 4);
+
+        Ok(())
+    }
+
+    fn repeated_circles() -> fj::Shape {
+        let moved: fj::Shape = fj::Transform {
+            shape: circle(1.),
+            axis: [0., 0., 1.],
+            angle: fj::Angle::from_rad(0.),
+            offset: [3., 0., 0.],
+            scale: 1.,
+        }
+        .into();
+        fj::Group {
+            a: fj::Group {
+                a: circle(1.),
+                b: moved,
+            }
+            .into(),
+            b: circle(2.),
+        }
+        .into()
+    }
+
+    #[test]
+    fn repeated_parts_are_triangulated_once() -> Result<(), Error> {
+        let shape = repeated_circles();
+
+        let processor = processor(0.1);
+        let meshes = processor.process_instances(&shape)?.unwrap();
+
+        assert_eq!(meshes.repeated.len(), 1);
+        let (repeated, transforms) = &meshes.repeated[0];
+        assert_eq!(transforms.len(), 2);
+
+        // Together, the parts make up the same mesh as the whole shape.
+        let whole = processor.process(&shape)?.mesh.triangles().count();
+        let parts = meshes.unique.triangles().count()
+            + repeated.triangles().count() * transforms.len();
+        assert_eq!(parts, whole);
+
+        assert!(processor.process_instances(&circle(1.))?.is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn instanced_meshes_are_cached() -> Result<(), Error> {
+        let mut cache = ProcessedShapeCache::new(4);
+        let shape = repeated_circles();
+
+        for _ in 0..2 {
+            cache.process(&processor(0.1), "circles", &shape)?;
+            assert!(cache
+                .process_instances(&processor(0.1), "circles", &shape)?
+                .is_some());
+        }
+        assert_eq!(cache.misses(), 2);
+
+        cache.process_instances(&processor(0.2), "circles", &shape)?;
+        assert_eq!(cache.misses(), 4);
+
+        Ok(())
+    }
+}
@@ -1,10 +1,10 @@
 use fj_interop::{debug::DebugInfo, mesh::Color};
 use fj_kernel::{
-    algorithms::Tolerance,
-    objects::{Cycle, Edge, Face, Sketch, Surface},
+    algorithms::{reverse_cycle, CycleApprox, Tolerance},
+    objects::{CurveKind, Cycle, Edge, Face, Sketch, Surface},
     validation::{validate, Validated, ValidationConfig, ValidationError},
 };
-use fj_math::{Aabb, Point, Scalar};
+use fj_math::{Aabb, BSpline, CubicBezier, Point, Scalar};
 
 use super::Shape;
 
@@ -14,34 +14,32 @@ impl Shape for fj::Sketch {
     fn compute_brep(
         &self,
         config: &ValidationConfig,
-        _: Tolerance,
+        tolerance: Tolerance,
         _: &mut DebugInfo,
     ) -> Result<Validated<Self::Brep>, ValidationError> {
         let surface = Surface::xy_plane();
 
-        let face = match self.chain() {
-            fj::Chain::Circle(circle) => {
-                // Circles have just a single round edge with no vertices. So
-                // none need to be added here.
+        let exterior = chain_cycle(self.chain(), &surface, tolerance);
 
-                let edge = Edge::build()
-                    .circle_from_radius(Scalar::from_f64(circle.radius()));
-                let cycle = Cycle::new(surface).with_edges([edge]);
-
-                Face::new(surface)
-                    .with_exteriors([cycle])
-                    .with_color(Color(self.color()))
+        // Holes need to be oriented opposite to the exterior, or they'd be
+        // swept into faces that point into the material.
+        let is_exterior_counterclockwise =
+            is_counterclockwise(&exterior, tolerance);
+        let interiors = self.holes().iter().map(|hole| {
+            let cycle = chain_cycle(hole, &surface, tolerance);
+            if is_counterclockwise(&cycle, tolerance)
+                == is_exterior_counterclockwise
+            {
+                reverse_cycle(&cycle)
+            } else {
+                cycle
             }
-            fj::Chain::PolyChain(poly_chain) => {
-                let points =
-                    poly_chain.to_points().into_iter().map(Point::from);
+        });
 
-                Face::build(surface)
-                    .polygon_from_points(points)
-                    .into_face()
-                    .with_color(Color(self.color()))
-            }
-        };
+        let face = Face::new(surface.clone())
+            .with_exteriors([exterior])
+            .with_interiors(interiors)
+            .with_color(Color(self.color()));
 
         let sketch = Sketch::new().with_faces([face]);
         validate(sketch, config)
@@ -53,13 +51,409 @@ impl Shape for fj::Sketch {
                 min: Point::from([-circle.radius(), -circle.radius(), 0.0]),
                 max: Point::from([circle.radius(), circle.radius(), 0.0]),
             },
-            fj::Chain::PolyChain(poly_chain) => Aabb::<3>::from_points(
-                poly_chain
-                    .to_points()
+            fj::Chain::Ellipse(ellipse) => {
+                let [x, y] = ellipse.center();
+                let [rx, ry] = ellipse.radii().map(f64::abs);
+                Aabb {
+                    min: Point::from([x - rx, y - ry, 0.0]),
+                    max: Point::from([x + rx, y + ry, 0.0]),
+                }
+            }
+            fj::Chain::PolyChain(poly_chain) => {
+                let points = poly_chain.to_points();
+
+                // Parry can't compute the AABB of an empty point cloud.
+                if points.is_empty() {
+                    let origin = Point::origin();
+                    return Aabb {
+                        min: origin,
+                        max: origin,
+                    };
+                }
+
+                Aabb::<3>::from_points(
+                    points.into_iter().map(Point::from).map(Point::to_xyz),
+                )
+            }
+            fj::Chain::Segments(chain) => {
+                let surface = Surface::xy_plane();
+
+                // The control points of curves bound them. Bounding the full
+                // circle of each arc is more than necessary, but much simpler
+                // than finding the extremes of the arc.
+                let points = segments_with_start(chain).into_iter().flat_map(
+                    |(start, segment)| match &segment {
+                        fj::SketchSegment::Line { end } => vec![*end],
+                        fj::SketchSegment::Arc { .. }
+                        | fj::SketchSegment::ArcThrough { .. } => {
+                            let edge = arc_edge(&surface, start, &segment);
+                            let circle = match edge.curve().kind() {
+                                CurveKind::Circle(circle) => *circle,
+                                _ => unreachable!(),
+                            };
+
+                            let radius = circle.a().magnitude().into_f64();
+                            let [u, v] = [circle.center().u, circle.center().v]
+                                .map(Scalar::into_f64);
+                            vec![
+                                [u - radius, v - radius],
+                                [u + radius, v + radius],
+                            ]
+                        }
+                        fj::SketchSegment::Parabola { end, control } => {
+                            vec![*control, *end]
+                        }
+                        fj::SketchSegment::Bezier { end, controls } => {
+                            vec![controls[0], controls[1], *end]
+                        }
+                        fj::SketchSegment::BSpline { end, controls } => {
+                            let mut points = controls.to_vec();
+                            points.push(*end);
+                            points
+                        }
+                    },
+                );
+
+                Aabb::<3>::from_points(
+                    points
+                        .chain([chain.start()])
+                        .map(Point::from)
+                        .map(Point::to_xyz),
+                )
+            }
+        }
+    }
+}
+
+pub(crate) fn chain_cycle(
+    chain: &fj::Chain,
+    surface: &Surface,
+    tolerance: Tolerance,
+) -> Cycle {
+    match chain {
+        fj::Chain::Circle(circle) => {
+            // Circles have just a single round edge with no vertices. So none
+            // need to be added here.
+
+            let edge = Edge::build()
+                .circle_from_radius(Scalar::from_f64(circle.radius()));
+            Cycle::new(surface.clone()).with_edges([edge])
+        }
+        fj::Chain::Ellipse(ellipse) => {
+            let [rx, ry] = ellipse.radii();
+            let edge = Edge::build().ellipse_from_axes(
+                surface,
+                ellipse.center(),
+                [[rx, 0.], [0., ry]],
+            );
+            Cycle::new(surface.clone()).with_edges([edge])
+        }
+        fj::Chain::PolyChain(poly_chain) => {
+            let points = poly_chain.to_points().into_iter().map(Point::from);
+            Cycle::build(surface.clone()).polygon_from_points(points)
+        }
+        fj::Chain::Segments(chain) => Cycle::new(surface.clone())
+            .with_edges(segment_edges(chain, tolerance)),
+    }
+}
+
+pub(crate) fn is_counterclockwise(cycle: &Cycle, tolerance: Tolerance) -> bool {
+    let points = CycleApprox::new(cycle, tolerance).points;
+
+    // The shoelace formula, which results in twice the signed area
+    let area = points.iter().zip(points.iter().cycle().skip(1)).fold(
+        Scalar::ZERO,
+        |area, (a, b)| {
+            let [a, b] = [a, b].map(|point| point.local_form());
+            area + a.u * b.v - b.u * a.v
+        },
+    );
+
+    area > Scalar::ZERO
+}
+
+/// Pair each segment of the chain with the point it starts at
+///
+/// Includes the line that closes the chain, unless the last segment already
+/// does that.
+fn segments_with_start(
+    chain: &fj::SegmentChain,
+) -> Vec<([f64; 2], fj::SketchSegment)> {
+    let mut segments = chain.segments().to_vec();
+    if segments.last().map(|segment| segment.end()) != Some(chain.start()) {
+        segments.push(fj::SketchSegment::Line { end: chain.start() });
+    }
+
+    let mut start = chain.start();
+    segments
+        .into_iter()
+        .map(|segment| {
+            let segment_start = start;
+            start = segment.end();
+            (segment_start, segment)
+        })
+        .collect()
+}
+
+/// Create the edges of a chain of segments in the xy-plane
+fn segment_edges(chain: &fj::SegmentChain, tolerance: Tolerance) -> Vec<Edge> {
+    let surface = Surface::xy_plane();
+    let mut edges = Vec::new();
+
+    for (start, segment) in segments_with_start(chain) {
+        let end = segment.end();
+
+        let points = match &segment {
+            fj::SketchSegment::Line { .. } => vec![start, end],
+            fj::SketchSegment::Arc { .. }
+            | fj::SketchSegment::ArcThrough { .. } => {
+                edges.push(arc_edge(&surface, start, &segment));
+                continue;
+            }
+            fj::SketchSegment::Parabola { control, .. } => {
+                edges.push(
+                    Edge::build()
+                        .parabola_from_points(&surface, [start, *control, end]),
+                );
+                continue;
+            }
+            fj::SketchSegment::Bezier { controls, .. } => {
+                let curve = CubicBezier::<2>::from_points([
+                    start,
+                    controls[0],
+                    controls[1],
+                    end,
+                ]);
+                approx_smooth(
+                    |t| curve.point_from_curve_coords([t]),
+                    1,
+                    tolerance,
+                )
+            }
+            fj::SketchSegment::BSpline { controls, .. } => {
+                let control_points = [start]
                     .into_iter()
-                    .map(Point::from)
-                    .map(Point::to_xyz),
-            ),
+                    .chain(controls.iter().copied())
+                    .chain([end]);
+                let curve =
+                    BSpline::<2>::from_control_points(control_points, 3);
+                approx_smooth(
+                    |t| curve.point_from_curve_coords([t]),
+                    curve.num_spans(),
+                    tolerance,
+                )
+            }
+        };
+
+        let mut points: Vec<_> = points.into_iter().map(Point::from).collect();
+
+        // The end points must match those of the neighboring edges exactly,
+        // or the vertices wouldn't be shared.
+        let n = points.len();
+        points[0] = Point::from(start);
+        points[n - 1] = Point::from(end);
+        points.dedup();
+
+        for pair in points.windows(2) {
+            edges.push(
+                Edge::build()
+                    .line_segment_from_points(&surface, [pair[0], pair[1]]),
+            );
+        }
+    }
+
+    edges
+}
+
+fn arc_edge(
+    surface: &Surface,
+    start: [f64; 2],
+    segment: &fj::SketchSegment,
+) -> Edge {
+    match *segment {
+        fj::SketchSegment::Arc {
+            end,
+            center,
+            counterclockwise,
+        } => Edge::build().arc_from_center(
+            surface,
+            [start, end],
+            center,
+            counterclockwise,
+        ),
+        fj::SketchSegment::ArcThrough { end, via } => {
+            Edge::build().arc_through_points(surface, [start, via, end])
+        }
+        _ => unreachable!("Expected arc, got {segment:?}"),
+    }
+}
+
+/// Approximate a curve that is defined between the curve coordinates `0.` and
+/// `1.`
+///
+/// The curve is split into a few parts per span, each of which is subdivided,
+/// until the approximation doesn't deviate from the curve by more than the
+/// tolerance.
+fn approx_smooth(
+    curve: impl Fn(f64) -> Point<2>,
+    spans: usize,
+    tolerance: Tolerance,
+) -> Vec<[f64; 2]> {
+    let n = spans * 4;
+
+    let mut points = vec![curve(0.)];
+    for i in 0..n {
+        let range = [i, i + 1].map(|i| i as f64 / n as f64);
+        subdivide(&curve, range, tolerance.inner(), 0, &mut points);
+    }
+
+    points
+        .into_iter()
+        .map(|point| [point.u, point.v].map(Scalar::into_f64))
+        .collect()
+}
+
+/// Add the points that approximate the curve after `range[0]`, up to and
+/// including `range[1]`
+fn subdivide(
+    curve: &impl Fn(f64) -> Point<2>,
+    range: [f64; 2],
+    tolerance: Scalar,
+    depth: u32,
+    points: &mut Vec<Point<2>>,
+) {
+    const MAX_DEPTH: u32 = 16;
+
+    let [t0, t1] = range;
+    let [a, b] = range.map(curve);
+
+    // Checking more than the midpoint catches S-shaped parts, whose midpoint
+    // can happen to be on the chord.
+    let is_flat = [0.25, 0.5, 0.75].into_iter().all(|f| {
+        let point = curve(t0 + (t1 - t0) * f);
+        distance_to_segment(point, [a, b]) <= tolerance
+    });
+
+    if is_flat || depth >= MAX_DEPTH {
+        points.push(b);
+        return;
+    }
+
+    let t = (t0 + t1) / 2.;
+    subdivide(curve, [t0, t], tolerance, depth + 1, points);
+    subdivide(curve, [t, t1], tolerance, depth + 1, points);
+}
+
+fn distance_to_segment(point: Point<2>, segment: [Point<2>; 2]) -> Scalar {
+    let [a, b] = segment;
+    let direction = b - a;
+    let length_squared = direction.dot(&direction);
+
+    if length_squared == Scalar::ZERO {
+        return (point - a).magnitude();
+    }
+
+    let t = ((point - a).dot(&direction) / length_squared)
+        .max(Scalar::ZERO)
+        .min(Scalar::ONE);
+    (point - (a + direction * t)).magnitude()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use fj_interop::debug::DebugInfo;
+    use fj_kernel::{
+        algorithms::{triangulate, volume, Tolerance},
+        validation::ValidationConfig,
+    };
+    use fj_math::{CubicBezier, Point, Scalar};
+
+    use crate::Shape;
+
+    #[test]
+    fn holes() {
+        let tolerance = Tolerance::from_scalar(0.001).unwrap();
+
+        let washer = fj::Sketch::from_circle(fj::Circle::from_radius(2.))
+            .with_hole(fj::Circle::from_radius(1.));
+        let frame = fj::Sketch::from_points(vec![
+            [-2., -2.],
+            [2., -2.],
+            [2., 2.],
+            [-2., 2.],
+        ])
+        .with_hole(fj::PolyChain::from_points(vec![
+            [-1., -1.],
+            [1., -1.],
+            [1., 1.],
+            [-1., 1.],
+        ]));
+
+        let cam = fj::Sketch::from_segments(
+            [-1., 0.],
+            vec![
+                fj::SketchSegment::Line { end: [1., 0.] },
+                fj::SketchSegment::Parabola {
+                    end: [-1., 0.],
+                    control: [0., 2.],
+                },
+            ],
+        )
+        .with_hole(fj::Ellipse::from_radii([0.3, 0.2]).with_center([0., 0.4]));
+
+        for (sketch, expected) in
+            [(washer, PI * 3.), (frame, 12.), (cam, 4. / 3. - PI * 0.06)]
+        {
+            let sweep = fj::Sweep::from_path(sketch.into(), [0., 0., 1.]);
+            let faces = sweep
+                .compute_brep(
+                    &ValidationConfig::default(),
+                    tolerance,
+                    &mut DebugInfo::new(),
+                )
+                .unwrap()
+                .into_inner()
+                .into_faces()
+                .collect();
+
+            let mesh = triangulate(faces, tolerance, &mut DebugInfo::new());
+            let volume = volume(&mesh).into_f64();
+            assert!((volume - expected).abs() < 0.01, "{volume}");
+        }
+    }
+
+    #[test]
+    fn approx_smooth() {
+        let curve = CubicBezier::<2>::from_points([
+            [0., 0.],
+            [0., 2.],
+            [1., -2.],
+            [1., 0.],
+        ]);
+        let tolerance = Tolerance::from_scalar(0.001).unwrap();
+
+        let points: Vec<_> = super::approx_smooth(
+            |t| curve.point_from_curve_coords([t]),
+            1,
+            tolerance,
+        )
+        .into_iter()
+        .map(Point::from)
+        .collect();
+
+        for i in 0..=100 {
+            let point = curve.point_from_curve_coords([i as f64 / 100.]);
+            let distance = points
+                .windows(2)
+                .map(|pair| {
+                    super::distance_to_segment(point, [pair[0], pair[1]])
+                })
+                .min()
+                .unwrap();
+
+            assert!(distance <= Scalar::from(0.001), "{distance}");
         }
     }
 }
@@ -27,13 +27,15 @@ impl Shape for fj::Sweep {
     }
 
     fn bounding_volume(&self) -> Aabb<3> {
-        self.shape()
-            .bounding_volume()
-            .merged(&Aabb::<3>::from_points(
-                self.shape()
-                    .bounding_volume()
-                    .vertices()
-                    .map(|v| v + self.path()),
-            ))
+        let aabb = self.shape().bounding_volume();
+
+        // Sweeping an empty shape results in an empty shape.
+        if aabb.min == aabb.max {
+            return aabb;
+        }
+
+        aabb.merged(&Aabb::<3>::from_points(
+            aabb.vertices().map(|v| v + self.path()),
+        ))
     }
 }
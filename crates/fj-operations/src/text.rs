@@ -0,0 +1,334 @@
+use std::mem;
+
+use fj_interop::{debug::DebugInfo, mesh::Color};
+use fj_kernel::{
+    algorithms::{reverse_cycle, CycleApprox, Tolerance},
+    objects::{Cycle, Face, Sketch, Surface},
+    validation::{validate, Validated, ValidationConfig, ValidationError},
+};
+use fj_math::{point_in_polygon, Aabb, Point};
+use ttf_parser::{GlyphId, OutlineBuilder};
+
+use crate::sketch::{chain_cycle, is_counterclockwise};
+
+use super::Shape;
+
+impl Shape for fj::Text {
+    type Brep = Sketch;
+
+    fn compute_brep(
+        &self,
+        config: &ValidationConfig,
+        tolerance: Tolerance,
+        _: &mut DebugInfo,
+    ) -> Result<Validated<Self::Brep>, ValidationError> {
+        let font = parse_font(self);
+
+        let mut outlines = Outlines::default();
+        for (glyph, offset) in layout(self, &font) {
+            outlines.offset = offset;
+            outlines.scale = scale(self, &font);
+            font.outline_glyph(glyph, &mut outlines);
+        }
+
+        let faces = outlines
+            .into_faces(tolerance)
+            .into_iter()
+            .map(|face| face.with_color(Color(self.color())));
+
+        let sketch = Sketch::new().with_faces(faces);
+        validate(sketch, config)
+    }
+
+    fn bounding_volume(&self) -> Aabb<3> {
+        let font = parse_font(self);
+        let scale = scale(self, &font);
+
+        let points: Vec<_> = layout(self, &font)
+            .into_iter()
+            .filter_map(|(glyph, [x, y])| {
+                let rect = font.glyph_bounding_box(glyph)?;
+                Some([
+                    [
+                        x + f64::from(rect.x_min) * scale,
+                        y + f64::from(rect.y_min) * scale,
+                    ],
+                    [
+                        x + f64::from(rect.x_max) * scale,
+                        y + f64::from(rect.y_max) * scale,
+                    ],
+                ])
+            })
+            .flatten()
+            .collect();
+
+        // Parry can't compute the AABB of an empty point cloud.
+        if points.is_empty() {
+            let origin = Point::origin();
+            return Aabb {
+                min: origin,
+                max: origin,
+            };
+        }
+
+        Aabb::<3>::from_points(points.into_iter().map(|[x, y]| [x, y, 0.]))
+    }
+}
+
+fn parse_font(text: &fj::Text) -> ttf_parser::Face {
+    ttf_parser::Face::from_slice(text.font(), 0)
+        .unwrap_or_else(|err| panic!("Failed to parse font: {err}"))
+}
+
+/// The factor that converts font units into model units
+fn scale(text: &fj::Text, font: &ttf_parser::Face) -> f64 {
+    text.size() / f64::from(font.units_per_em())
+}
+
+/// Determine which glyph goes where
+///
+/// Glyphs are positioned by their advance width. Kerning and other features of
+/// more advanced text layout are not supported.
+fn layout(
+    text: &fj::Text,
+    font: &ttf_parser::Face,
+) -> Vec<(GlyphId, [f64; 2])> {
+    let scale = scale(text, font);
+    let line_height = f64::from(
+        i32::from(font.ascender()) - i32::from(font.descender())
+            + i32::from(font.line_gap()),
+    ) * scale;
+
+    let mut glyphs = Vec::new();
+    let mut pen = [0., 0.];
+
+    for c in text.text().chars() {
+        if c == '\n' {
+            pen = [0., pen[1] - line_height];
+            continue;
+        }
+
+        // Characters that the font doesn't have are shown as its "missing
+        // glyph", which is always the first one.
+        let glyph = font.glyph_index(c).unwrap_or(GlyphId(0));
+        glyphs.push((glyph, pen));
+
+        let advance = font.glyph_hor_advance(glyph).unwrap_or(0);
+        pen[0] += f64::from(advance) * scale;
+    }
+
+    glyphs
+}
+
+/// Collects the contours of glyph outlines
+#[derive(Default)]
+struct Outlines {
+    /// The position of the current glyph, in model units
+    offset: [f64; 2],
+
+    /// The factor that converts font units into model units
+    scale: f64,
+
+    start: [f64; 2],
+    current: [f64; 2],
+    segments: Vec<fj::SketchSegment>,
+
+    contours: Vec<fj::SegmentChain>,
+}
+
+impl Outlines {
+    fn point(&self, x: f32, y: f32) -> [f64; 2] {
+        let [u, v] = self.offset;
+        [u + f64::from(x) * self.scale, v + f64::from(y) * self.scale]
+    }
+
+    fn push(&mut self, segment: fj::SketchSegment) {
+        self.current = segment.end();
+        self.segments.push(segment);
+    }
+
+    /// Convert the contours into faces in the xy-plane
+    ///
+    /// Fonts differ in how they orient their contours, so holes are detected by
+    /// checking which contours contain each other. Every contour that isn't a
+    /// hole results in a face.
+    fn into_faces(self, tolerance: Tolerance) -> Vec<Face> {
+        let surface = Surface::xy_plane();
+
+        let cycles: Vec<_> = self
+            .contours
+            .into_iter()
+            .map(|chain| {
+                chain_cycle(&fj::Chain::Segments(chain), &surface, tolerance)
+            })
+            .collect();
+        let polygons: Vec<Vec<_>> = cycles
+            .iter()
+            .map(|cycle| {
+                CycleApprox::new(cycle, tolerance)
+                    .points
+                    .into_iter()
+                    .map(|point| *point.local_form())
+                    .collect()
+            })
+            .collect();
+
+        // The contours that contain each contour
+        let containers: Vec<Vec<_>> = polygons
+            .iter()
+            .enumerate()
+            .map(|(i, polygon)| {
+                (0..polygons.len())
+                    .filter(|&j| {
+                        j != i
+                            && polygon.first().map_or(false, |point| {
+                                point_in_polygon(point, &polygons[j])
+                            })
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let orient = |cycle: &Cycle, counterclockwise: bool| {
+            if is_counterclockwise(cycle, tolerance) == counterclockwise {
+                cycle.clone()
+            } else {
+                reverse_cycle(cycle)
+            }
+        };
+
+        // Contours within an odd number of others are holes. They belong to
+        // the one among those that is nested the deepest.
+        let is_hole = |i: usize| containers[i].len() % 2 == 1;
+        let mut faces = Vec::new();
+        for (i, cycle) in cycles.iter().enumerate() {
+            if is_hole(i) {
+                continue;
+            }
+
+            let interiors = (0..cycles.len())
+                .filter(|&j| {
+                    is_hole(j)
+                        && containers[j].contains(&i)
+                        && containers[j].len() == containers[i].len() + 1
+                })
+                .map(|j| orient(&cycles[j], false));
+
+            faces.push(
+                Face::new(surface.clone())
+                    .with_exteriors([orient(cycle, true)])
+                    .with_interiors(interiors),
+            );
+        }
+
+        faces
+    }
+}
+
+impl OutlineBuilder for Outlines {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.start = self.point(x, y);
+        self.current = self.start;
+        self.segments.clear();
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        let end = self.point(x, y);
+        if end != self.current {
+            self.push(fj::SketchSegment::Line { end });
+        }
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        // Every quadratic Bézier curve can be expressed as a cubic one.
+        let [c, q, end] = [self.current, self.point(x1, y1), self.point(x, y)];
+        let control = |from: [f64; 2]| {
+            [0, 1].map(|i| from[i] + (q[i] - from[i]) * 2. / 3.)
+        };
+
+        self.push(fj::SketchSegment::Bezier {
+            end,
+            controls: [control(c), control(end)],
+        });
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        self.push(fj::SketchSegment::Bezier {
+            end: self.point(x, y),
+            controls: [self.point(x1, y1), self.point(x2, y2)],
+        });
+    }
+
+    fn close(&mut self) {
+        if self.segments.is_empty() {
+            return;
+        }
+
+        let segments = mem::take(&mut self.segments);
+        self.contours
+            .push(fj::SegmentChain::from_segments(self.start, segments));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_interop::debug::DebugInfo;
+    use fj_kernel::algorithms::{triangulate, Tolerance};
+    use fj_math::Triangle;
+    use ttf_parser::OutlineBuilder;
+
+    use super::Outlines;
+
+    #[test]
+    fn holes() {
+        let tolerance = Tolerance::from_scalar(0.001).unwrap();
+
+        let mut outlines = Outlines {
+            scale: 0.1,
+            ..Outlines::default()
+        };
+
+        // Something like an "O", with the orientation TrueType fonts use
+        let square = |outlines: &mut Outlines, min: f32, max: f32, cw: bool| {
+            let mut points = [(min, min), (max, min), (max, max), (min, max)];
+            if cw {
+                points.reverse();
+            }
+
+            outlines.move_to(points[0].0, points[0].1);
+            for &(x, y) in &points[1..] {
+                outlines.line_to(x, y);
+            }
+            outlines.close();
+        };
+        square(&mut outlines, 0., 10., true);
+        square(&mut outlines, 3., 7., false);
+
+        // Something like the dot of an "i", next to it
+        outlines.offset = [2., 0.];
+        square(&mut outlines, 0., 10., true);
+
+        let faces = outlines.into_faces(tolerance);
+        assert_eq!(faces.len(), 2);
+        assert_eq!(
+            faces
+                .iter()
+                .map(|face| face.interiors().count())
+                .sum::<usize>(),
+            1
+        );
+
+        let mesh = triangulate(faces, tolerance, &mut DebugInfo::new());
+        let area: f64 = mesh
+            .triangles()
+            .map(|triangle| Triangle::from(triangle.inner.points()))
+            .map(|triangle| {
+                let [a, b, c] = triangle.points();
+                (b - a).cross(&(c - a)).z.into_f64() / 2.
+            })
+            .sum();
+
+        // Both faces point up, with the hole cut out of the first one.
+        assert!((area - (1. - 0.16 + 1.)).abs() < 1e-9, "{area}");
+    }
+}
@@ -32,8 +32,9 @@ impl Shape for fj::Transform {
     }
 }
 
-fn make_transform(transform: &fj::Transform) -> Transform {
+pub(crate) fn make_transform(transform: &fj::Transform) -> Transform {
     let axis = Vector::from(transform.axis).normalize();
     Transform::translation(transform.offset)
         * Transform::rotation(axis * transform.angle.rad())
+        * Transform::scaling(transform.scale)
 }
@@ -0,0 +1,43 @@
+use fj_interop::debug::DebugInfo;
+use fj_kernel::{
+    algorithms::{union, Tolerance},
+    objects::Face,
+    validation::{validate, Validated, ValidationConfig, ValidationError},
+};
+use fj_math::Aabb;
+
+use super::Shape;
+
+impl Shape for fj::Union {
+    type Brep = Vec<Face>;
+
+    fn compute_brep(
+        &self,
+        config: &ValidationConfig,
+        tolerance: Tolerance,
+        debug_info: &mut DebugInfo,
+    ) -> Result<Validated<Self::Brep>, ValidationError> {
+        let a = self.a.compute_brep(config, tolerance, debug_info)?;
+        let b = self.b.compute_brep(config, tolerance, debug_info)?;
+
+        let faces = union(&a.into_inner(), &b.into_inner(), tolerance);
+
+        validate(faces, config)
+    }
+
+    fn bounding_volume(&self) -> Aabb<3> {
+        let a = self.a.bounding_volume();
+        let b = self.b.bounding_volume();
+
+        // An empty shape's AABB is at an unspecified point, which must not
+        // affect the result.
+        if a.min == a.max {
+            return b;
+        }
+        if b.min == b.max {
+            return a;
+        }
+
+        a.merged(&b)
+    }
+}
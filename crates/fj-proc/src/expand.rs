@@ -2,8 +2,8 @@ use proc_macro2::TokenStream;
 use quote::{quote, ToTokens};
 
 use crate::parse::{
-    ArgumentMetadata, Constraint, ConstraintKind, ExtractedArgument,
-    GeometryFunction, Initializer, Metadata, Model,
+    ArgumentKind, ArgumentMetadata, Constraint, ConstraintKind,
+    ExtractedArgument, GeometryFunction, Initializer, Metadata, Model,
 };
 
 impl Initializer {
@@ -77,15 +77,41 @@ impl ToTokens for ArgumentMetadata {
         let ArgumentMetadata {
             name,
             default_value,
+            kind,
+            range,
+            description,
         } = self;
 
-        tokens.extend(quote! { fj::models::ArgumentMetadata::new(#name) });
+        tokens.extend(quote! {
+            fj::models::ArgumentMetadata::new(#name).with_kind(#kind)
+        });
 
         if let Some(default_value) = default_value {
             tokens.extend(quote! {
                 .with_default_value(stringify!(#default_value))
             });
         }
+        if let Some((min, max)) = range {
+            tokens.extend(quote! {
+                .with_range((#min) as f64, (#max) as f64)
+            });
+        }
+        if let Some(description) = description {
+            tokens.extend(quote! { .with_description(#description) });
+        }
+    }
+}
+
+impl ToTokens for ArgumentKind {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let kind = match self {
+            ArgumentKind::Integer => quote!(Integer),
+            ArgumentKind::Float => quote!(Float),
+            ArgumentKind::Boolean => quote!(Boolean),
+            ArgumentKind::String => quote!(String),
+        };
+
+        tokens.extend(quote! { fj::models::ArgumentKind::#kind });
     }
 }
 
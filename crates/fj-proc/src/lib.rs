@@ -72,6 +72,21 @@ use syn::{parse_macro_input, FnArg, ItemFn};
 /// }
 /// ```
 ///
+/// The host learns about the arguments from the function signature, so it can
+/// check them before running the model, and show them to the user. Ranges are
+/// only declared to the host, if both `min` and `max` are plain numbers. A
+/// `description` is shown to the user along with the argument.
+///
+/// ```rust
+/// #[fj::model]
+/// pub fn gear(
+///     #[param(default = 12, min = 3, max = 200, description = "Number of teeth")]
+///     teeth: u32,
+/// ) -> fj::Shape {
+///     todo!("Make a gear with {teeth} teeth")
+/// }
+/// ```
+///
 /// For more complex situations, model functions are allowed to return any
 /// error type that converts into a model error.
 ///
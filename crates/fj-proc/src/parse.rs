@@ -1,7 +1,7 @@
 use proc_macro2::Ident;
 use syn::{
-    bracketed, parenthesized, parse::Parse, parse_quote, Expr, ItemFn,
-    ReturnType, Type,
+    bracketed, parenthesized, parse::Parse, parse_quote, Expr, ItemFn, Lit,
+    ReturnType, Type, UnOp,
 };
 
 /// The call to `fj::register_model!()`.
@@ -29,6 +29,17 @@ pub(crate) struct Metadata {
 pub(crate) struct ArgumentMetadata {
     pub(crate) name: String,
     pub(crate) default_value: Option<Expr>,
+    pub(crate) kind: ArgumentKind,
+    pub(crate) range: Option<(Expr, Expr)>,
+    pub(crate) description: Option<Expr>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum ArgumentKind {
+    Integer,
+    Float,
+    Boolean,
+    String,
 }
 
 /// The `<_ as fj::Model>::shape()` function.
@@ -83,6 +94,9 @@ fn parse_model(item: &ItemFn) -> syn::Result<Model> {
             .map(|a| ArgumentMetadata {
                 name: a.ident.to_string(),
                 default_value: a.default(),
+                kind: argument_kind(&a.ty),
+                range: a.range(),
+                description: a.description(),
             })
             .collect(),
     };
@@ -107,6 +121,31 @@ fn parse_model(item: &ItemFn) -> syn::Result<Model> {
     Ok(Model { metadata, geometry })
 }
 
+fn argument_kind(ty: &Type) -> ArgumentKind {
+    let ident = match ty {
+        Type::Path(p) => p.path.segments.last().map(|s| s.ident.to_string()),
+        _ => None,
+    };
+
+    match ident.as_deref() {
+        Some("f32" | "f64") => ArgumentKind::Float,
+        Some(
+            "i8" | "i16" | "i32" | "i64" | "i128" | "isize" | "u8" | "u16"
+            | "u32" | "u64" | "u128" | "usize",
+        ) => ArgumentKind::Integer,
+        Some("bool") => ArgumentKind::Boolean,
+        _ => ArgumentKind::String,
+    }
+}
+
+fn is_number(expr: &Expr) -> bool {
+    match expr {
+        Expr::Lit(e) => matches!(e.lit, Lit::Int(_) | Lit::Float(_)),
+        Expr::Unary(e) => matches!(e.op, UnOp::Neg(_)) && is_number(&e.expr),
+        _ => false,
+    }
+}
+
 fn contains_result(ty: &Type) -> bool {
     match ty {
         Type::Path(p) => p.path.segments.last().unwrap().ident == "Result",
@@ -163,6 +202,29 @@ impl Argument {
             .and_then(|attr| attr.get_default())
             .map(|param| param.val)
     }
+
+    /// The range of valid values, if it is known before the model runs
+    ///
+    /// Constraints can refer to other arguments. A range is only declared, if
+    /// both of them are plain numbers.
+    fn range(&self) -> Option<(Expr, Expr)> {
+        let attr = self.attr.as_ref()?;
+        let min = attr.get_minimum()?.val;
+        let max = attr.get_maximum()?.val;
+
+        if is_number(&min) && is_number(&max) {
+            Some((min, max))
+        } else {
+            None
+        }
+    }
+
+    fn description(&self) -> Option<Expr> {
+        self.attr
+            .as_ref()
+            .and_then(|attr| attr.get_parameter("description"))
+            .map(|param| param.val)
+    }
 }
 
 impl Parse for Argument {
@@ -302,7 +364,9 @@ mod tests {
         let function: ItemFn = syn::parse2(tokens).unwrap();
 
         let Initializer {
-            model: Model { metadata, geometry },
+            model: Model {
+                metadata, geometry, ..
+            },
         } = parse(&function).unwrap();
 
         // Note: we can't #[derive(PartialEq)] on our parsed structs because
@@ -373,6 +437,61 @@ mod tests {
         assert_eq!(constraints, expected_constraints);
     }
 
+    #[test]
+    fn parse_argument_metadata() {
+        let tokens = quote! {
+            pub fn gear(
+                #[param(default = 12, min = 3, max = 200, description = "Number of teeth")]
+                teeth: u32,
+                #[param(min = -1.0, max = inner)] offset: f64,
+                inner: f64,
+                label: String,
+            ) -> fj::Shape {
+                todo!()
+            }
+        };
+        let function: ItemFn = syn::parse2(tokens).unwrap();
+
+        let Initializer {
+            model: Model { metadata, .. },
+        } = parse(&function).unwrap();
+
+        let to_string = |expr: &Expr| expr.to_token_stream().to_string();
+        let arguments: Vec<_> = metadata
+            .arguments
+            .iter()
+            .map(|arg| {
+                (
+                    arg.name.as_str(),
+                    arg.kind,
+                    arg.default_value.as_ref().map(to_string),
+                    arg.range
+                        .as_ref()
+                        .map(|(min, max)| (to_string(min), to_string(max))),
+                    arg.description.as_ref().map(to_string),
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            arguments,
+            [
+                (
+                    "teeth",
+                    ArgumentKind::Integer,
+                    Some("12".to_string()),
+                    Some(("3".to_string(), "200".to_string())),
+                    Some("\"Number of teeth\"".to_string()),
+                ),
+                // The maximum depends on another argument, so there's no
+                // range that's known up front.
+                ("offset", ArgumentKind::Float, None, None, None),
+                ("inner", ArgumentKind::Float, None, None, None),
+                ("label", ArgumentKind::String, None, None, None),
+            ]
+        );
+    }
+
     #[test]
     fn parse_fallible_function() {
         let tokens = quote! {
@@ -1,8 +1,10 @@
 //! Viewer camera module
-use std::f64::consts::FRAC_PI_2;
+use std::f64::consts::{FRAC_PI_2, FRAC_PI_4, PI};
 
 use fj_interop::processed_shape::ProcessedShape;
 use fj_math::{Aabb, Point, Scalar, Transform, Vector};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 use crate::screen::NormalizedPosition;
 
@@ -12,7 +14,7 @@ use crate::screen::NormalizedPosition;
 /// is handled, for example) is not that of a camera freely flying through a
 /// static scene. Instead, the camera is static, and the model is freely
 /// translated and rotated.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct Camera {
     /// The distance to the near plane
     near_plane: f64,
@@ -35,7 +37,38 @@ impl Camera {
 
     /// Returns a new camera aligned for viewing a bounding box
     pub fn new(aabb: &Aabb<3>) -> Self {
-        let initial_distance = {
+        let mut camera = Self {
+            near_plane: Self::DEFAULT_NEAR_PLANE,
+            far_plane: Self::DEFAULT_FAR_PLANE,
+
+            rotation: Transform::identity(),
+            translation: Transform::identity(),
+        };
+
+        camera.fit_to_bounds(aabb);
+        camera
+    }
+
+    /// Snap the camera to one of the standard views
+    ///
+    /// Replaces the current rotation with the canonical orientation of `view`,
+    /// then moves the camera, so the bounding box is framed as it would be by
+    /// [`Camera::new`].
+    pub fn set_view(&mut self, view: StandardView, aabb: &Aabb<3>) {
+        self.rotation = view.rotation();
+        self.fit_to_bounds(aabb);
+    }
+
+    /// Move the camera, so the bounding box is framed by the current rotation
+    pub fn fit_to_bounds(&mut self, aabb: &Aabb<3>) {
+        // The rest of this method works in the rotated space, in which the
+        // camera looks along the negative z-axis.
+        let aabb = Aabb::<3>::from_points(
+            aabb.vertices()
+                .map(|vertex| self.rotation.transform_point(&vertex)),
+        );
+
+        let distance = {
             // Let's make sure we choose a distance, so that the model fills
             // most of the screen.
             //
@@ -64,25 +97,78 @@ impl Camera {
             highest_point + distance_from_model
         };
 
-        let initial_offset = {
+        let offset = {
             let mut offset = aabb.center();
             offset.z = Scalar::ZERO;
             -offset
         };
 
-        Self {
-            near_plane: Self::DEFAULT_NEAR_PLANE,
-            far_plane: Self::DEFAULT_FAR_PLANE,
+        self.translation =
+            Transform::translation([offset.x, offset.y, -distance]);
+    }
 
-            rotation: Transform::identity(),
-            translation: Transform::translation([
-                initial_offset.x,
-                initial_offset.y,
-                -initial_distance,
-            ]),
+    /// Move the camera, so a bounding sphere is framed by the current rotation
+    ///
+    /// Unlike [`Camera::fit_to_bounds`], the resulting distance doesn't depend
+    /// on the rotation, so the model doesn't appear to jump in size when the
+    /// view changes.
+    pub fn fit_to_sphere(&mut self, center: Point<3>, radius: f64) {
+        let center = self.rotation.transform_point(&center);
+
+        // The whole sphere is in view at this distance. Like in
+        // `fit_to_bounds`, double it, so the model doesn't fill the screen.
+        let distance =
+            2. * radius / (Self::INITIAL_FIELD_OF_VIEW_IN_X / 2.).sin();
+
+        self.translation = Transform::translation([
+            -center.x,
+            -center.y,
+            -center.z - Scalar::from_f64(distance),
+        ]);
+    }
+
+    /// Take a snapshot of the camera's view
+    ///
+    /// Applying the snapshot using [`Camera::set_state`] restores the view.
+    pub fn state(&self) -> CameraState {
+        let camera_to_model = self.camera_to_model();
+
+        // The camera looks along the negative z-axis, with positive y pointing
+        // up. Transform both into model space.
+        let to_model = self.rotation.inverse();
+        let forward = to_model.transform_vector(&Vector::from([0., 0., -1.]));
+        let up = to_model.transform_vector(&Vector::from([0., 1., 0.]));
+
+        let position =
+            camera_to_model.inverse_transform_point(&Point::origin());
+        let target = position + forward;
+
+        CameraState {
+            position: position.coords.components.map(Scalar::into_f64),
+            target: target.coords.components.map(Scalar::into_f64),
+            up: up.components.map(Scalar::into_f64),
+            projection: Projection {
+                near_plane: self.near_plane,
+                far_plane: self.far_plane,
+            },
         }
     }
 
+    /// Restore a view from a snapshot taken by [`Camera::state`]
+    pub fn set_state(&mut self, state: &CameraState) {
+        let transform = Transform::look_at(
+            &Point::from(state.position),
+            &Point::from(state.target),
+            &Vector::from(state.up),
+        );
+
+        self.rotation = transform.extract_rotation();
+        self.translation = transform.extract_translation();
+
+        self.near_plane = state.projection.near_plane;
+        self.far_plane = state.projection.far_plane;
+    }
+
     /// Returns the distance between the camera and the minimum distance for rendering.
     pub fn near_plane(&self) -> f64 {
         self.near_plane
@@ -127,6 +213,18 @@ impl Camera {
             .unwrap_or_else(|| FocusPoint(shape.aabb.center()))
     }
 
+    /// Compute the point on the model, that the cursor points to
+    ///
+    /// Returns `None`, if the cursor doesn't point at the model.
+    pub fn pick(
+        &self,
+        cursor: NormalizedPosition,
+        shape: &ProcessedShape,
+    ) -> Option<Point<3>> {
+        self.calculate_focus_point(Some(cursor), shape)
+            .map(|focus_point| focus_point.0)
+    }
+
     fn calculate_focus_point(
         &self,
         cursor: Option<NormalizedPosition>,
@@ -213,9 +311,294 @@ impl Camera {
     }
 }
 
+/// A snapshot of the camera's view
+///
+/// With the `serde` feature, it can be serialized, to restore a view later, for
+/// example to take the same screenshot again. See [`Camera::state`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CameraState {
+    /// The position of the camera, in model space
+    pub position: [f64; 3],
+
+    /// A point that the camera looks at, in model space
+    ///
+    /// Only the direction from `position` to `target` is relevant.
+    pub target: [f64; 3],
+
+    /// The direction that is up on the screen, in model space
+    pub up: [f64; 3],
+
+    /// The projection of the camera
+    pub projection: Projection,
+}
+
+/// The parameters of the camera's perspective projection
+///
+/// The field of view is not included, as it is the same for every camera.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Projection {
+    /// The distance to the near plane
+    pub near_plane: f64,
+
+    /// The distance to the far plane
+    pub far_plane: f64,
+}
+
+/// Convert a maximum on-screen deviation into a world-space tolerance
+///
+/// `pixels` is the deviation between the approximated and the actual geometry
+/// that is acceptable on screen, for a model whose bounding box is `aabb`,
+/// viewed from `camera_distance`. The result is proportional to both.
+///
+/// The size of a pixel is computed for a window that is
+/// [`REFERENCE_SCREEN_WIDTH`] pixels wide, using the camera's field of view.
+/// To keep a camera that is very close to the model from causing excessive
+/// tessellation, the result is never smaller than a thousandth of the smallest
+/// non-zero extent of `aabb`.
+pub fn tolerance_for_screen_error(
+    aabb: &Aabb<3>,
+    camera_distance: f64,
+    pixels: f64,
+) -> f64 {
+    let visible_width =
+        2. * camera_distance * (Camera::INITIAL_FIELD_OF_VIEW_IN_X / 2.).tan();
+    let pixel_size = visible_width / REFERENCE_SCREEN_WIDTH;
+
+    let min_extent = aabb
+        .size()
+        .components
+        .into_iter()
+        .filter(|extent| *extent > Scalar::ZERO)
+        .reduce(Scalar::min)
+        .unwrap_or(Scalar::ONE);
+    let min_tolerance = min_extent.into_f64() / 1000.;
+
+    (pixel_size * pixels).max(min_tolerance)
+}
+
+/// The screen width that [`tolerance_for_screen_error`] assumes, in pixels
+pub const REFERENCE_SCREEN_WIDTH: f64 = 1920.;
+
 /// The point around which camera movement happens.
 ///
 /// This will be the point on the model that the cursor is currently pointing at if such a point exists,
 /// falling back to the center point of the model's bounding volume otherwise.
 #[derive(Clone, Copy)]
 pub struct FocusPoint(pub Point<3>);
+
+/// A canonical orientation of the camera
+///
+/// The names follow the usual CAD conventions for a z-up coordinate system, in
+/// which the front view looks along the positive y-axis.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum StandardView {
+    /// Look down along the negative z-axis
+    Top,
+
+    /// Look up along the positive z-axis
+    Bottom,
+
+    /// Look along the positive x-axis
+    Left,
+
+    /// Look along the negative x-axis
+    Right,
+
+    /// Look along the positive y-axis
+    Front,
+
+    /// Look along the negative y-axis
+    Back,
+
+    /// Look at the model from the front, right, and top at the same time
+    Isometric,
+}
+
+impl StandardView {
+    /// All standard views
+    pub const ALL: [Self; 7] = [
+        Self::Top,
+        Self::Bottom,
+        Self::Left,
+        Self::Right,
+        Self::Front,
+        Self::Back,
+        Self::Isometric,
+    ];
+
+    /// The rotation that aligns the model with this view
+    ///
+    /// Like [`Camera::rotation`], this rotates the model, not the camera.
+    pub fn rotation(&self) -> Transform {
+        // Rotating the model this way turns the top view into the front view.
+        let front = Transform::rotation([-FRAC_PI_2, 0., 0.]);
+
+        match self {
+            Self::Top => Transform::identity(),
+            Self::Bottom => Transform::rotation([PI, 0., 0.]),
+            Self::Left => Transform::rotation([0., FRAC_PI_2, 0.]) * front,
+            Self::Right => Transform::rotation([0., -FRAC_PI_2, 0.]) * front,
+            Self::Front => front,
+            Self::Back => Transform::rotation([0., PI, 0.]) * front,
+            Self::Isometric => {
+                // Turn the model, so the front-right edge faces the camera,
+                // then tilt it, so the camera looks down the space diagonal.
+                let tilt = (1. / 2f64.sqrt()).atan();
+
+                Transform::rotation([tilt, 0., 0.])
+                    * front
+                    * Transform::rotation([0., 0., -FRAC_PI_4])
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Aabb, Point, Scalar, Vector};
+
+    use crate::graphics::transform::Transform;
+
+    use super::{tolerance_for_screen_error, Camera, StandardView};
+
+    #[test]
+    fn standard_view_basis() {
+        let s = 1. / 3f64.sqrt();
+        let u = 1. / 6f64.sqrt();
+
+        let expected = [
+            (StandardView::Top, [0., 0., -1.], [0., 1., 0.]),
+            (StandardView::Bottom, [0., 0., 1.], [0., -1., 0.]),
+            (StandardView::Left, [1., 0., 0.], [0., 0., 1.]),
+            (StandardView::Right, [-1., 0., 0.], [0., 0., 1.]),
+            (StandardView::Front, [0., 1., 0.], [0., 0., 1.]),
+            (StandardView::Back, [0., -1., 0.], [0., 0., 1.]),
+            (StandardView::Isometric, [-s, s, -s], [-u, u, 2. * u]),
+        ];
+
+        for (view, forward, up) in expected {
+            // The rotation maps model space to camera space, in which the
+            // camera looks along negative z, with positive y pointing up.
+            let to_model = view.rotation().inverse();
+
+            let forward = to_model
+                .transform_vector(&Vector::from([0., 0., -1.]))
+                - Vector::from(forward);
+            let up = to_model.transform_vector(&Vector::from([0., 1., 0.]))
+                - Vector::from(up);
+
+            assert!(forward.magnitude() < Scalar::from(1e-12), "{view:?}");
+            assert!(up.magnitude() < Scalar::from(1e-12), "{view:?}");
+        }
+    }
+
+    #[test]
+    fn set_view_keeps_model_framed() {
+        let aabb = Aabb {
+            min: Point::from([1., 2., 3.]),
+            max: Point::from([3., 4., 5.]),
+        };
+        let mut camera = Camera::new(&aabb);
+
+        for view in StandardView::ALL {
+            camera.set_view(view, &aabb);
+
+            // The center of the model is straight ahead of the camera.
+            let center =
+                camera.camera_to_model().transform_point(&aabb.center());
+            assert!(center.coords.xy().magnitude() < Scalar::from(1e-12));
+
+            // And the whole model is in front of it.
+            for vertex in aabb.vertices() {
+                let vertex = camera.camera_to_model().transform_point(&vertex);
+                assert!(vertex.z < Scalar::ZERO);
+            }
+        }
+    }
+
+    #[test]
+    fn fit_to_sphere_is_independent_of_rotation() {
+        let center = Point::from([1., 2., 3.]);
+        let radius = 2.;
+
+        let mut camera = Camera::new(&Aabb {
+            min: Point::origin(),
+            max: Point::origin(),
+        });
+
+        let mut distances = Vec::new();
+        for view in StandardView::ALL {
+            camera.set_view(view, &Aabb::<3>::from_points([center]));
+            camera.fit_to_sphere(center, radius);
+
+            // The center is straight ahead of the camera.
+            let in_view = camera.camera_to_model().transform_point(&center);
+            assert!(in_view.coords.xy().magnitude() < Scalar::from(1e-12));
+            assert!(in_view.z < Scalar::from(-radius));
+
+            distances.push(Point::distance(&camera.position(), &center));
+        }
+
+        for distance in &distances {
+            assert!((*distance - distances[0]).abs() < Scalar::from(1e-12));
+        }
+    }
+
+    #[test]
+    fn tolerance_for_screen_error_scales_with_distance() {
+        let aabb = Aabb {
+            min: Point::from([-1., -1., -1.]),
+            max: Point::from([1., 1., 1.]),
+        };
+
+        let near = tolerance_for_screen_error(&aabb, 10., 0.5);
+        let far = tolerance_for_screen_error(&aabb, 20., 0.5);
+
+        assert!((far - near * 2.).abs() < 1e-12);
+    }
+
+    #[test]
+    fn tolerance_for_screen_error_is_bounded_by_model_size() {
+        let aabb = Aabb {
+            min: Point::from([-1., -1., -1.]),
+            max: Point::from([1., 1., 1.]),
+        };
+
+        let tolerance = tolerance_for_screen_error(&aabb, 1e-6, 0.5);
+
+        assert!((tolerance - 2e-3).abs() < 1e-12);
+    }
+
+    #[test]
+    fn camera_state_round_trip() {
+        let aabb = Aabb {
+            min: Point::from([1., 2., 3.]),
+            max: Point::from([3., 4., 5.]),
+        };
+
+        let mut camera = Camera::new(&aabb);
+        camera.set_view(StandardView::Isometric, &aabb);
+        camera.update_planes(&aabb);
+
+        let state = camera.state();
+        #[cfg(feature = "serde")]
+        let state =
+            serde_json::from_str(&serde_json::to_string(&state).unwrap())
+                .unwrap();
+
+        let mut restored = Camera::new(&Aabb {
+            min: Point::from([-1., -1., -1.]),
+            max: Point::from([1., 1., 1.]),
+        });
+        restored.set_state(&state);
+
+        let expected = Transform::for_vertices(&camera, 16. / 9.).0;
+        let actual = Transform::for_vertices(&restored, 16. / 9.).0;
+
+        for (a, b) in actual.iter().zip(expected) {
+            assert!((a - b).abs() <= f32::EPSILON * b.abs().max(1.));
+        }
+    }
+}
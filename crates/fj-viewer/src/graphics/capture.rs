@@ -0,0 +1,111 @@
+//! Reading rendered frames back from the GPU
+
+use std::f64::consts::TAU;
+
+/// An image with 8-bit RGBA pixels
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RgbaImage {
+    /// The width of the image, in pixels
+    pub width: u32,
+
+    /// The height of the image, in pixels
+    pub height: u32,
+
+    /// The pixels, row by row, starting at the top left
+    pub data: Vec<u8>,
+}
+
+/// The azimuths of the frames of a turntable animation, in radians
+///
+/// The angles are evenly spaced over a full turn. The last frame stops one
+/// step short of the full turn, so the animation loops without repeating the
+/// first frame.
+pub fn turntable_azimuths(frames: usize) -> Vec<f64> {
+    (0..frames)
+        .map(|i| TAU * i as f64 / frames as f64)
+        .collect()
+}
+
+/// The number of bytes per row of a texture that is copied to a buffer
+///
+/// wgpu requires this to be a multiple of
+/// [`wgpu::COPY_BYTES_PER_ROW_ALIGNMENT`].
+pub fn padded_bytes_per_row(width: u32) -> u32 {
+    let align = wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
+    let unpadded = width * 4;
+
+    (unpadded + align - 1) / align * align
+}
+
+/// Convert texture data, as copied to a buffer, into an [`RgbaImage`]
+///
+/// Removes the padding at the end of each row. If the texture format is BGRA,
+/// the channels are swapped.
+pub fn read_image(
+    data: &[u8],
+    width: u32,
+    height: u32,
+    format: wgpu::TextureFormat,
+) -> RgbaImage {
+    let is_bgra = matches!(
+        format,
+        wgpu::TextureFormat::Bgra8Unorm | wgpu::TextureFormat::Bgra8UnormSrgb
+    );
+
+    let padded = padded_bytes_per_row(width) as usize;
+    let unpadded = width as usize * 4;
+
+    let mut pixels = Vec::with_capacity(unpadded * height as usize);
+    for row in data.chunks(padded).take(height as usize) {
+        for pixel in row[..unpadded].chunks_exact(4) {
+            if is_bgra {
+                pixels.extend([pixel[2], pixel[1], pixel[0], pixel[3]]);
+            } else {
+                pixels.extend_from_slice(pixel);
+            }
+        }
+    }
+
+    RgbaImage {
+        width,
+        height,
+        data: pixels,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::{FRAC_PI_2, PI};
+
+    use super::{padded_bytes_per_row, read_image, turntable_azimuths};
+
+    #[test]
+    fn azimuths() {
+        assert_eq!(turntable_azimuths(4), [0., FRAC_PI_2, PI, PI + FRAC_PI_2]);
+        assert!(turntable_azimuths(0).is_empty());
+
+        // The step from the last frame back to the first is a regular step.
+        let azimuths = turntable_azimuths(36);
+        let step = azimuths[1] - azimuths[0];
+        assert!((azimuths[35] + step - std::f64::consts::TAU).abs() < 1e-12);
+    }
+
+    #[test]
+    fn read_padded_bgra() {
+        assert_eq!(padded_bytes_per_row(1), 256);
+        assert_eq!(padded_bytes_per_row(64), 256);
+        assert_eq!(padded_bytes_per_row(65), 512);
+
+        let mut data = vec![0; 2 * 256];
+        data[0..8].copy_from_slice(&[1, 2, 3, 4, 5, 6, 7, 8]);
+        data[256..264].copy_from_slice(&[9, 10, 11, 12, 13, 14, 15, 16]);
+
+        let image =
+            read_image(&data, 2, 2, wgpu::TextureFormat::Bgra8UnormSrgb);
+
+        assert_eq!(
+            image.data,
+            [3, 2, 1, 4, 7, 6, 5, 8, 11, 10, 9, 12, 15, 14, 13, 16]
+        );
+    }
+}
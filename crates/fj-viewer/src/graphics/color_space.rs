@@ -0,0 +1,117 @@
+//! Conversion of colors between sRGB and linear RGB
+
+/// The color space that colors passed to the renderer are in
+///
+/// This applies to vertex colors, as well as the colors of highlights and
+/// debug lines. The renderer converts colors as required by the surface, so a
+/// color looks the same regardless of whether the surface format is sRGB.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ColorSpace {
+    /// Colors are sRGB-encoded, like the hex codes of most color pickers
+    Srgb,
+
+    /// Colors are linear
+    Linear,
+}
+
+impl Default for ColorSpace {
+    fn default() -> Self {
+        Self::Srgb
+    }
+}
+
+/// Convert an sRGB-encoded color component to linear
+pub fn srgb_to_linear(c: f32) -> f32 {
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// Convert a linear color component to sRGB
+pub fn linear_to_srgb(c: f32) -> f32 {
+    if c <= 0.0031308 {
+        c * 12.92
+    } else {
+        1.055 * c.powf(1. / 2.4) - 0.055
+    }
+}
+
+/// The conversion that the shaders apply to colors
+///
+/// The discriminants are passed to the shaders as a uniform. Alpha is always
+/// linear and isn't converted.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum ColorConversion {
+    None = 0,
+    SrgbToLinear = 1,
+    LinearToSrgb = 2,
+}
+
+impl ColorConversion {
+    /// Determine the conversion for a color space and surface format
+    ///
+    /// An sRGB surface expects linear colors, which it encodes when writing
+    /// them. Any other surface stores colors as they are.
+    pub fn new(color_space: ColorSpace, format: wgpu::TextureFormat) -> Self {
+        match (color_space, format.describe().srgb) {
+            (ColorSpace::Srgb, true) => Self::SrgbToLinear,
+            (ColorSpace::Linear, false) => Self::LinearToSrgb,
+            _ => Self::None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{linear_to_srgb, srgb_to_linear, ColorConversion, ColorSpace};
+
+    #[test]
+    fn reference_values() {
+        let values = [
+            (0., 0.),
+            (0.002, 0.02584),
+            (0.214041, 0.5),
+            (0.5, 0.735357),
+            (1., 1.),
+        ];
+
+        for (linear, srgb) in values {
+            assert!((linear_to_srgb(linear) - srgb).abs() < 1e-4);
+            assert!((srgb_to_linear(srgb) - linear).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        for i in 0..=255 {
+            let c = i as f32 / 255.;
+            assert!((linear_to_srgb(srgb_to_linear(c)) - c).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn conversion_depends_on_surface() {
+        let srgb = wgpu::TextureFormat::Bgra8UnormSrgb;
+        let unorm = wgpu::TextureFormat::Bgra8Unorm;
+
+        assert_eq!(
+            ColorConversion::new(ColorSpace::Srgb, srgb),
+            ColorConversion::SrgbToLinear
+        );
+        assert_eq!(
+            ColorConversion::new(ColorSpace::Srgb, unorm),
+            ColorConversion::None
+        );
+        assert_eq!(
+            ColorConversion::new(ColorSpace::Linear, srgb),
+            ColorConversion::None
+        );
+        assert_eq!(
+            ColorConversion::new(ColorSpace::Linear, unorm),
+            ColorConversion::LinearToSrgb
+        );
+    }
+}
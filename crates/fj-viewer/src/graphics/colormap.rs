@@ -0,0 +1,82 @@
+/// A mapping from scalar values to colors
+///
+/// See [`Renderer::set_scalar_field`].
+///
+/// [`Renderer::set_scalar_field`]: super::Renderer::set_scalar_field
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Colormap {
+    /// Perceptually uniform, from dark purple over green to yellow
+    Viridis,
+
+    /// High contrast, from dark blue over green and yellow to dark red
+    Turbo,
+}
+
+impl Colormap {
+    /// Map a value from the range `0.0..=1.0` to a color
+    ///
+    /// Values outside of that range are clamped.
+    pub fn color(&self, value: f32) -> [f32; 4] {
+        let stops = match self {
+            Self::Viridis => &VIRIDIS,
+            Self::Turbo => &TURBO,
+        };
+
+        // Interpolate linearly between the two nearest stops.
+        let position = value.clamp(0., 1.) * (stops.len() - 1) as f32;
+        let i = (position.floor() as usize).min(stops.len() - 2);
+        let t = position - i as f32;
+
+        let [a, b] = [stops[i], stops[i + 1]];
+        let [r, g, b] = [0, 1, 2].map(|c| a[c] + (b[c] - a[c]) * t);
+
+        [r, g, b, 1.]
+    }
+}
+
+/// Samples of the viridis colormap, evenly spaced
+const VIRIDIS: [[f32; 3]; 9] = [
+    [0.267004, 0.004874, 0.329415],
+    [0.282623, 0.140926, 0.457517],
+    [0.229739, 0.322361, 0.545706],
+    [0.172719, 0.448791, 0.557885],
+    [0.127568, 0.566949, 0.550556],
+    [0.157851, 0.683765, 0.501686],
+    [0.369214, 0.788888, 0.382914],
+    [0.678489, 0.863742, 0.189503],
+    [0.993248, 0.906157, 0.143936],
+];
+
+/// Samples of the turbo colormap, evenly spaced
+///
+/// Computed from the polynomial approximation published along with turbo.
+const TURBO: [[f32; 3]; 9] = [
+    [0.1357, 0.0914, 0.1067],
+    [0.2686, 0.4148, 0.9348],
+    [0.1483, 0.7405, 0.8807],
+    [0.2504, 0.9528, 0.5729],
+    [0.5885, 0.9819, 0.3132],
+    [0.9316, 0.8139, 0.1771],
+    [1.0000, 0.5017, 0.1140],
+    [0.7864, 0.1749, 0.0473],
+    [0.5659, 0.0504, 0.0000],
+];
+
+#[cfg(test)]
+mod tests {
+    use super::{Colormap, VIRIDIS};
+
+    #[test]
+    fn interpolate_between_stops() {
+        let [a, b] = [VIRIDIS[0], VIRIDIS[1]];
+        let color = Colormap::Viridis.color(1. / 16.);
+
+        for c in 0..3 {
+            assert!((color[c] - (a[c] + b[c]) / 2.).abs() < 1e-6);
+        }
+
+        // Out-of-range values are clamped.
+        assert_eq!(Colormap::Viridis.color(-1.), Colormap::Viridis.color(0.));
+        assert_eq!(Colormap::Viridis.color(2.), Colormap::Viridis.color(1.));
+    }
+}
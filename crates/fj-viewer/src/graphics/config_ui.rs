@@ -122,11 +122,12 @@ enum Element {
     Model,
     Mesh,
     Debug,
+    Approximation,
 }
 
 impl Element {
-    fn elements() -> [Self; 3] {
-        [Self::Model, Self::Mesh, Self::Debug]
+    fn elements() -> [Self; 4] {
+        [Self::Model, Self::Mesh, Self::Debug, Self::Approximation]
     }
 
     fn name_key(&self) -> (&'static str, &'static str) {
@@ -134,6 +135,7 @@ impl Element {
             Self::Model => ("model", "1"),
             Self::Mesh => ("mesh", "2"),
             Self::Debug => ("debug info", "3"),
+            Self::Approximation => ("approximation", "4"),
         }
     }
 
@@ -142,6 +144,7 @@ impl Element {
             Self::Model => config.draw_model,
             Self::Mesh => config.draw_mesh,
             Self::Debug => config.draw_debug,
+            Self::Approximation => config.draw_approximation,
         }
     }
 }
@@ -1,5 +1,7 @@
 //! High level configuration for graphics rendering
 
+use super::ColorSpace;
+
 /// High level configuration for rendering the active model
 #[derive(Debug)]
 pub struct DrawConfig {
@@ -9,6 +11,20 @@ pub struct DrawConfig {
     pub draw_mesh: bool,
     /// Toggle for displaying model debug information
     pub draw_debug: bool,
+    /// Toggle for displaying the points that approximate the model's edges
+    pub draw_approximation: bool,
+    /// Toggle for displaying the reference mesh, if there is one
+    pub draw_reference: bool,
+    /// Toggle for outlining the model's creases and silhouette
+    pub draw_feature_edges: bool,
+    /// The width of debug lines, in pixels
+    pub line_width: f32,
+    /// Toggle for drawing debug lines with round ends
+    pub round_line_caps: bool,
+    /// The color space of the model's and the debug lines' colors
+    pub color_space: ColorSpace,
+    /// Which faces of the model's triangles are hidden
+    pub cull: CullMode,
 }
 
 impl Default for DrawConfig {
@@ -17,6 +33,45 @@ impl Default for DrawConfig {
             draw_model: true,
             draw_mesh: false,
             draw_debug: false,
+            draw_approximation: false,
+            draw_reference: true,
+            draw_feature_edges: false,
+            line_width: 2.,
+            round_line_caps: true,
+            color_space: ColorSpace::default(),
+            cull: CullMode::default(),
         }
     }
 }
+
+/// Which faces of triangles to cull
+///
+/// A triangle's front face is the one its points wind counter-clockwise
+/// around. Culling back faces hides the inside of closed models, but also
+/// one side of open ones.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CullMode {
+    /// Don't draw back faces
+    Back,
+    /// Don't draw front faces, which reveals the inside of closed models
+    Front,
+    /// Draw all faces
+    None,
+}
+
+impl CullMode {
+    /// The face that wgpu is configured to cull
+    pub fn face(self) -> Option<wgpu::Face> {
+        match self {
+            Self::Back => Some(wgpu::Face::Back),
+            Self::Front => Some(wgpu::Face::Front),
+            Self::None => None,
+        }
+    }
+}
+
+impl Default for CullMode {
+    fn default() -> Self {
+        Self::None
+    }
+}
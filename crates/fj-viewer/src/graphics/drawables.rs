@@ -1,32 +1,82 @@
 use super::{
+    draw_config::CullMode,
+    frustum::Frustum,
     geometries::{Geometries, Geometry},
     pipelines::{Pipeline, Pipelines},
 };
 
 pub struct Drawables<'r> {
     pub model: Drawable<'r>,
+    pub instanced: Vec<Drawable<'r>>,
     pub mesh: Drawable<'r>,
     pub lines: Drawable<'r>,
+    pub approximations: Drawable<'r>,
+    pub highlight: Drawable<'r>,
+    pub measurement: Drawable<'r>,
 }
 
 impl<'r> Drawables<'r> {
-    pub fn new(geometries: &'r Geometries, pipelines: &'r Pipelines) -> Self {
-        let model = Drawable::new(&geometries.mesh, &pipelines.model);
-        let mesh = Drawable::new(&geometries.mesh, &pipelines.mesh);
-        let lines = Drawable::new(&geometries.lines, &pipelines.lines);
+    pub fn new(
+        geometries: &'r Geometries,
+        pipelines: &'r Pipelines,
+        cull_mode: CullMode,
+    ) -> Self {
+        let model =
+            Drawable::new(&geometries.mesh, pipelines.model.get(cull_mode));
+        let instanced = geometries
+            .instanced
+            .iter()
+            .map(|instanced| Drawable {
+                geometry: &instanced.geometry,
+                pipeline: pipelines.instanced.get(cull_mode),
+                instances: Some((
+                    &instanced.instance_buffer,
+                    instanced.num_instances,
+                )),
+            })
+            .collect();
+        let mesh = Drawable::new(&geometries.wireframe, &pipelines.mesh);
+        let lines = Drawable::new(&geometries.lines, &pipelines.wide_lines);
+        let approximations =
+            Drawable::new(&geometries.approximations, &pipelines.wide_lines);
+        let highlight = Drawable::new(
+            &geometries.highlight,
+            pipelines.highlight.get(cull_mode),
+        );
+        let measurement =
+            Drawable::new(&geometries.measurement, &pipelines.wide_lines);
 
-        Self { model, mesh, lines }
+        Self {
+            model,
+            instanced,
+            mesh,
+            lines,
+            approximations,
+            highlight,
+            measurement,
+        }
     }
 }
 
 pub struct Drawable<'r> {
     pub geometry: &'r Geometry,
     pub pipeline: &'r Pipeline,
+
+    /// The instance buffer and number of instances, if this is instanced
+    pub instances: Option<(&'r wgpu::Buffer, u32)>,
 }
 
 impl<'r> Drawable<'r> {
-    fn new(geometry: &'r Geometry, pipeline: &'r Pipeline) -> Self {
-        Self { geometry, pipeline }
+    pub fn new(geometry: &'r Geometry, pipeline: &'r Pipeline) -> Self {
+        Self {
+            geometry,
+            pipeline,
+            instances: None,
+        }
+    }
+
+    pub fn is_visible(&self, frustum: &Frustum) -> bool {
+        self.geometry.is_visible(frustum)
     }
 
     pub fn draw(
@@ -67,6 +117,18 @@ impl<'r> Drawable<'r> {
             wgpu::IndexFormat::Uint32,
         );
 
-        render_pass.draw_indexed(0..self.geometry.num_indices, 0, 0..1);
+        let num_instances = match self.instances {
+            Some((buffer, num_instances)) => {
+                render_pass.set_vertex_buffer(1, buffer.slice(..));
+                num_instances
+            }
+            None => 1,
+        };
+
+        render_pass.draw_indexed(
+            0..self.geometry.num_indices,
+            0,
+            0..num_instances,
+        );
     }
 }
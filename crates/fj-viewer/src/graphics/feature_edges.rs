@@ -0,0 +1,213 @@
+//! Edges that outline a model, for a technical drawing style
+
+use std::collections::BTreeMap;
+
+use fj_interop::mesh::{Index, Mesh};
+use fj_math::{Point, Scalar, Segment, Vector};
+
+use super::vertices::Vertices;
+
+/// The edges of a mesh, each with the normals of the triangles it borders
+#[derive(Debug, Default)]
+pub struct EdgeAdjacency {
+    edges: Vec<AdjacentEdge>,
+}
+
+#[derive(Debug)]
+struct AdjacentEdge {
+    segment: Segment<3>,
+    normals: Vec<Vector<3>>,
+}
+
+impl EdgeAdjacency {
+    /// Build the adjacency from triangles
+    ///
+    /// Each triangle comes with the indices of its vertices, which determine
+    /// which triangles share an edge. Degenerate triangles are ignored.
+    pub fn new(
+        triangles: impl IntoIterator<Item = ([Index; 3], [Point<3>; 3])>,
+    ) -> Self {
+        let mut edges: BTreeMap<_, AdjacentEdge> = BTreeMap::new();
+
+        for (indices, points) in triangles {
+            let normal = match normal(points) {
+                Some(normal) => normal,
+                None => continue,
+            };
+
+            for i in 0..3 {
+                let j = (i + 1) % 3;
+                let key =
+                    (indices[i].min(indices[j]), indices[i].max(indices[j]));
+
+                edges
+                    .entry(key)
+                    .or_insert_with(|| AdjacentEdge {
+                        segment: Segment::from_points([points[i], points[j]]),
+                        normals: Vec::new(),
+                    })
+                    .normals
+                    .push(normal);
+            }
+        }
+
+        Self {
+            edges: edges.into_values().collect(),
+        }
+    }
+
+    /// The number of edges, which no silhouette can exceed
+    pub fn num_edges(&self) -> usize {
+        self.edges.len()
+    }
+
+    /// Find the edges between triangles that meet at a sharp angle
+    ///
+    /// An edge is a crease, if the normals of its triangles differ by more
+    /// than `angle_threshold`, in radians. Edges that don't border exactly two
+    /// triangles, like the boundary of an open mesh, are always included.
+    pub fn creases(&self, angle_threshold: f64) -> Vec<Segment<3>> {
+        self.edges
+            .iter()
+            .filter(|edge| match edge.normals.as_slice() {
+                [a, b] => {
+                    let cos = a.dot(b).into_f64().clamp(-1., 1.);
+                    cos.acos() > angle_threshold
+                }
+                _ => true,
+            })
+            .map(|edge| edge.segment)
+            .collect()
+    }
+
+    /// Find the edges that form the outline of the mesh, as seen from `eye`
+    ///
+    /// These are the edges between a triangle that faces the eye and one that
+    /// faces away from it. They change with the view, unlike creases.
+    pub fn silhouettes(&self, eye: Point<3>) -> Vec<Segment<3>> {
+        self.edges
+            .iter()
+            .filter(|edge| match edge.normals.as_slice() {
+                [a, b] => {
+                    let [point, _] = edge.segment.points();
+                    let view = point - eye;
+
+                    (a.dot(&view) < Scalar::ZERO)
+                        != (b.dot(&view) < Scalar::ZERO)
+                }
+                _ => false,
+            })
+            .map(|edge| edge.segment)
+            .collect()
+    }
+}
+
+impl From<&Mesh<Point<3>>> for EdgeAdjacency {
+    fn from(mesh: &Mesh<Point<3>>) -> Self {
+        let vertices: Vec<_> = mesh.vertices().collect();
+        let normals: Vec<_> = mesh
+            .triangles()
+            .map(|triangle| normal(triangle.inner.points()))
+            .collect();
+
+        let edges = mesh
+            .adjacency()
+            .edges()
+            .map(|(edge, triangles)| AdjacentEdge {
+                segment: Segment::from_points(
+                    edge.map(|vertex| vertices[vertex as usize]),
+                ),
+                normals: triangles.iter().filter_map(|&t| normals[t]).collect(),
+            })
+            // Like in `EdgeAdjacency::new`, degenerate triangles are ignored.
+            .filter(|edge| !edge.normals.is_empty())
+            .collect();
+
+        Self { edges }
+    }
+}
+
+impl From<&Vertices> for EdgeAdjacency {
+    fn from(vertices: &Vertices) -> Self {
+        let sources = vertices.sources();
+        if sources.is_empty() {
+            return Self::default();
+        }
+
+        Self::new(vertices.indices().chunks(3).map(|triangle| {
+            let vertex = |i: usize| triangle[i] as usize;
+
+            (
+                [0, 1, 2].map(|i| sources[vertex(i)]),
+                [0, 1, 2].map(|i| {
+                    let position = vertices.vertices()[vertex(i)].position;
+                    Point::from(position.map(f64::from))
+                }),
+            )
+        }))
+    }
+}
+
+/// Compute the normal of a triangle, unless it is degenerate
+fn normal([a, b, c]: [Point<3>; 3]) -> Option<Vector<3>> {
+    let normal = (b - a).cross(&(c - a));
+    if normal.magnitude() == Scalar::ZERO {
+        return None;
+    }
+
+    Some(normal.normalize())
+}
+
+/// Find the crease edges of a mesh
+///
+/// See [`EdgeAdjacency::creases`].
+pub fn feature_edges(
+    mesh: &Mesh<Point<3>>,
+    angle_threshold: f64,
+) -> Vec<Segment<3>> {
+    EdgeAdjacency::from(mesh).creases(angle_threshold)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::FRAC_PI_4;
+
+    use fj_interop::{fixtures, mesh::Mesh};
+    use fj_math::Point;
+
+    use super::{feature_edges, EdgeAdjacency};
+
+    fn cube() -> Mesh<Point<3>> {
+        fixtures::cube([0., 0., 0.], 1.)
+    }
+
+    #[test]
+    fn cube_creases() {
+        let creases = feature_edges(&cube(), FRAC_PI_4);
+
+        // The diagonals of the sides are flat and not included.
+        assert_eq!(creases.len(), 12);
+        for crease in creases {
+            let [a, b] = crease.points();
+            assert_eq!((b - a).magnitude().into_f64(), 1.);
+        }
+    }
+
+    #[test]
+    fn cube_silhouettes() {
+        let edges = EdgeAdjacency::from(&cube());
+
+        // Looking straight at a side, the outline is that side's four edges.
+        let silhouettes = edges.silhouettes(Point::from([0.5, 0.5, 5.]));
+        assert_eq!(silhouettes.len(), 4);
+        for silhouette in silhouettes {
+            for point in silhouette.points() {
+                assert_eq!(point.z.into_f64(), 1.);
+            }
+        }
+
+        // Looking at a corner, the outline is a hexagon.
+        let silhouettes = edges.silhouettes(Point::from([5., 5., 5.]));
+        assert_eq!(silhouettes.len(), 6);
+    }
+}
@@ -0,0 +1,218 @@
+use fj_math::Aabb;
+
+/// The view frustum, the part of model space that ends up on screen
+#[derive(Debug)]
+pub struct Frustum {
+    planes: [Plane; 6],
+}
+
+impl Frustum {
+    /// Extract the frustum planes from a view-projection matrix
+    ///
+    /// The matrix is expected in column-major order, and to map the frustum to
+    /// the cube from `-1` to `1` along each axis, like
+    /// [`fj_math::Transform::project_to_array`] does.
+    ///
+    /// # Implementation Note
+    ///
+    /// A point `p` is within the frustum, if each component of the projected
+    /// point `M * p` is between `-w` and `w`, with `w` being its fourth
+    /// component. Each of those six conditions can be rearranged into a plane
+    /// equation, made of a sum or difference of the matrix rows (see Gribb and
+    /// Hartmann, "Fast Extraction of Viewing Frustum Planes from the
+    /// World-View-Projection Matrix").
+    pub fn from_view_projection(matrix: &[f64; 16]) -> Self {
+        let row = |i: usize| [0, 1, 2, 3].map(|j| matrix[j * 4 + i]);
+        let [x, y, z, w] = [0, 1, 2, 3].map(row);
+
+        let add = |a: [f64; 4], b: [f64; 4]| [0, 1, 2, 3].map(|i| a[i] + b[i]);
+        let sub = |a: [f64; 4], b: [f64; 4]| [0, 1, 2, 3].map(|i| a[i] - b[i]);
+
+        let planes = [
+            add(w, x),
+            sub(w, x),
+            add(w, y),
+            sub(w, y),
+            add(w, z),
+            sub(w, z),
+        ]
+        .map(Plane::from_coefficients);
+
+        Self { planes }
+    }
+
+    /// Determine where an AABB is, relative to the frustum
+    ///
+    /// This is conservative: An AABB that is outside of the frustum, but near
+    /// one of its corners, might be classified as [`Containment::Intersecting`].
+    pub fn classify(&self, aabb: &Aabb<3>) -> Containment {
+        let min = aabb.min.coords.components.map(|s| s.into_f64());
+        let max = aabb.max.coords.components.map(|s| s.into_f64());
+
+        let mut containment = Containment::Inside;
+
+        for plane in &self.planes {
+            // The corners of the AABB that are the furthest along the plane
+            // normal, and the furthest against it.
+            let mut furthest = [0.; 3];
+            let mut nearest = [0.; 3];
+            for i in 0..3 {
+                if plane.normal[i] >= 0. {
+                    furthest[i] = max[i];
+                    nearest[i] = min[i];
+                } else {
+                    furthest[i] = min[i];
+                    nearest[i] = max[i];
+                }
+            }
+
+            if plane.distance(furthest) < 0. {
+                return Containment::Outside;
+            }
+            if plane.distance(nearest) < 0. {
+                containment = Containment::Intersecting;
+            }
+        }
+
+        containment
+    }
+}
+
+/// Where an AABB is, relative to a [`Frustum`]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Containment {
+    /// The AABB is completely inside of the frustum
+    Inside,
+
+    /// The AABB is completely outside of the frustum
+    Outside,
+
+    /// The AABB is partially inside of the frustum
+    Intersecting,
+}
+
+/// A plane; points on the side its normal points to count as inside
+#[derive(Debug)]
+struct Plane {
+    normal: [f64; 3],
+    offset: f64,
+}
+
+impl Plane {
+    fn from_coefficients([a, b, c, d]: [f64; 4]) -> Self {
+        // Normalizing isn't required to tell the sides apart, but it makes the
+        // distances meaningful, which helps with debugging.
+        let length = (a * a + b * b + c * c).sqrt();
+
+        Self {
+            normal: [a / length, b / length, c / length],
+            offset: d / length,
+        }
+    }
+
+    fn distance(&self, point: [f64; 3]) -> f64 {
+        self.normal
+            .iter()
+            .zip(point)
+            .map(|(n, p)| n * p)
+            .sum::<f64>()
+            + self.offset
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::FRAC_PI_2;
+
+    use fj_math::{Aabb, Point, Transform};
+
+    use super::{Containment, Frustum};
+
+    fn aabb(min: [f64; 3], max: [f64; 3]) -> Aabb<3> {
+        Aabb {
+            min: Point::from(min),
+            max: Point::from(max),
+        }
+    }
+
+    fn frustum() -> Frustum {
+        // Looks down the negative z-axis, from the origin, with a field of view
+        // of 90 degrees. Near plane is at `z = -1`, far plane at `z = -10`.
+        let matrix = Transform::identity()
+            .project_to_array(1., FRAC_PI_2, 1., 10.)
+            .map(|s| s.into_f64());
+
+        Frustum::from_view_projection(&matrix)
+    }
+
+    #[test]
+    fn planes_of_identity_matrix() {
+        let mut identity = [0.; 16];
+        for i in 0..4 {
+            identity[i * 4 + i] = 1.;
+        }
+
+        let frustum = Frustum::from_view_projection(&identity);
+
+        // The identity matrix doesn't project anything, so the frustum is the
+        // cube from -1 to 1.
+        let expected = [
+            ([1., 0., 0.], 1.),
+            ([-1., 0., 0.], 1.),
+            ([0., 1., 0.], 1.),
+            ([0., -1., 0.], 1.),
+            ([0., 0., 1.], 1.),
+            ([0., 0., -1.], 1.),
+        ];
+        for (plane, (normal, offset)) in frustum.planes.iter().zip(expected) {
+            assert_eq!(plane.normal, normal);
+            assert_eq!(plane.offset, offset);
+        }
+    }
+
+    #[test]
+    fn planes_of_perspective_projection() {
+        let frustum = frustum();
+
+        let near = &frustum.planes[4];
+        let far = &frustum.planes[5];
+        assert!(near.distance([0., 0., -1.]).abs() < 1e-9);
+        assert!(far.distance([0., 0., -10.]).abs() < 1e-9);
+
+        // The side planes go through the origin, at 45 degrees.
+        let left = &frustum.planes[0];
+        assert!(left.distance([-5., 0., -5.]).abs() < 1e-9);
+        assert!(left.distance([0., 0., 0.]).abs() < 1e-9);
+        assert!(left.distance([0., 0., -5.]) > 0.);
+    }
+
+    #[test]
+    fn classify_aabb() {
+        let frustum = frustum();
+
+        assert_eq!(
+            frustum.classify(&aabb([-1., -1., -6.], [1., 1., -4.])),
+            Containment::Inside
+        );
+
+        // Behind the camera, beyond the far plane, and off to the side.
+        for outside in [
+            aabb([-1., -1., 1.], [1., 1., 2.]),
+            aabb([-1., -1., -12.], [1., 1., -11.]),
+            aabb([6., -1., -5.], [7., 1., -4.]),
+        ] {
+            assert_eq!(frustum.classify(&outside), Containment::Outside);
+        }
+
+        // Straddles the near plane and the left plane, respectively.
+        for straddling in [
+            aabb([-0.1, -0.1, -2.], [0.1, 0.1, 0.]),
+            aabb([-6., -1., -5.], [-4., 1., -4.]),
+        ] {
+            assert_eq!(
+                frustum.classify(&straddling),
+                Containment::Intersecting
+            );
+        }
+    }
+}
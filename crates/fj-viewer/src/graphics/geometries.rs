@@ -1,14 +1,29 @@
-use std::convert::TryInto;
+use std::{
+    convert::TryInto,
+    mem::{size_of, size_of_val},
+};
 
-use fj_math::Aabb;
+use bytemuck::{Pod, Zeroable};
+use fj_math::{Aabb, Point, Transform};
 use wgpu::util::DeviceExt;
 
-use super::vertices::{Vertex, Vertices};
+use super::{
+    frustum::{Containment, Frustum},
+    vertices::{Vertex, Vertices},
+    wide_lines::{
+        WideLineVertex, WideLines, INDICES_PER_LINE, VERTICES_PER_LINE,
+    },
+};
 
 #[derive(Debug)]
 pub struct Geometries {
     pub mesh: Geometry,
+    pub wireframe: Geometry,
     pub lines: Geometry,
+    pub approximations: Geometry,
+    pub highlight: Geometry,
+    pub measurement: Geometry,
+    pub instanced: Vec<InstancedGeometry>,
     pub aabb: Aabb<3>,
 }
 
@@ -17,13 +32,108 @@ impl Geometries {
         device: &wgpu::Device,
         mesh: &Vertices,
         debug_info: &Vertices,
+        approximations: &Vertices,
         aabb: Aabb<3>,
     ) -> Self {
+        let wireframe = Geometry::wireframe(device, mesh);
         let mesh = Geometry::new(device, mesh.vertices(), mesh.indices());
-        let lines =
-            Geometry::new(device, debug_info.vertices(), debug_info.indices());
 
-        Self { mesh, lines, aabb }
+        Self::with_mesh(
+            device,
+            mesh,
+            wireframe,
+            debug_info,
+            approximations,
+            aabb,
+        )
+    }
+
+    /// Create geometries, with a mesh that is uploaded later
+    ///
+    /// See [`Geometry::for_upload`]. The wireframe is left empty, until it is
+    /// set after the upload.
+    pub fn for_upload(
+        device: &wgpu::Device,
+        mesh: &Vertices,
+        debug_info: &Vertices,
+        approximations: &Vertices,
+        aabb: Aabb<3>,
+    ) -> Self {
+        let wireframe = Geometry::new(device, &[], &[]);
+        let mesh = Geometry::for_upload(device, mesh);
+
+        Self::with_mesh(
+            device,
+            mesh,
+            wireframe,
+            debug_info,
+            approximations,
+            aabb,
+        )
+    }
+
+    fn with_mesh(
+        device: &wgpu::Device,
+        mesh: Geometry,
+        wireframe: Geometry,
+        debug_info: &Vertices,
+        approximations: &Vertices,
+        aabb: Aabb<3>,
+    ) -> Self {
+        let lines = Geometry::wide_lines(device, debug_info);
+        let approximations = Geometry::wide_lines(device, approximations);
+
+        let highlight = Geometry::new(device, &[], &[]);
+        let measurement = Geometry::new(device, &[], &[]);
+
+        Self {
+            mesh,
+            wireframe,
+            lines,
+            approximations,
+            highlight,
+            measurement,
+            instanced: Vec::new(),
+            aabb,
+        }
+    }
+
+    pub fn set_mesh(&mut self, device: &wgpu::Device, mesh: &Vertices) {
+        self.mesh = Geometry::new(device, mesh.vertices(), mesh.indices());
+    }
+
+    pub fn set_wireframe(&mut self, device: &wgpu::Device, mesh: &Vertices) {
+        self.wireframe = Geometry::wireframe(device, mesh);
+    }
+
+    pub fn set_highlight(
+        &mut self,
+        device: &wgpu::Device,
+        highlight: &Vertices,
+    ) {
+        self.highlight =
+            Geometry::new(device, highlight.vertices(), highlight.indices());
+    }
+
+    pub fn set_measurement(&mut self, device: &wgpu::Device, lines: &Vertices) {
+        self.measurement = Geometry::wide_lines(device, lines);
+    }
+
+    /// Replace the instanced geometry
+    ///
+    /// Each mesh is uploaded once, together with one [`Instance`] per
+    /// transform.
+    pub fn set_instanced(
+        &mut self,
+        device: &wgpu::Device,
+        batches: &[(Vertices, Vec<Transform>)],
+    ) {
+        self.instanced = batches
+            .iter()
+            .map(|(mesh, transforms)| {
+                InstancedGeometry::new(device, mesh, transforms)
+            })
+            .collect();
     }
 }
 
@@ -32,13 +142,134 @@ pub struct Geometry {
     pub vertex_buffer: wgpu::Buffer,
     pub index_buffer: wgpu::Buffer,
     pub num_indices: u32,
+
+    /// The bounds of all vertices, used to skip drawing invisible geometry
+    pub aabb: Aabb<3>,
 }
 
 impl Geometry {
-    fn new(
+    pub fn new(
         device: &wgpu::Device,
         vertices: &[Vertex],
         indices: &[u32],
+    ) -> Self {
+        Self::from_raw(device, vertices, indices, bounds(vertices))
+    }
+
+    /// Create the wireframe of a mesh
+    fn wireframe(device: &wgpu::Device, mesh: &Vertices) -> Self {
+        let wireframe = mesh.wireframe();
+        Self::new(device, wireframe.vertices(), wireframe.indices())
+    }
+
+    /// Create geometry with buffers that fit the mesh, but are left empty
+    ///
+    /// Nothing is drawn, until the mesh is written to the buffers and
+    /// `num_indices` is increased accordingly. The AABB already covers the
+    /// whole mesh.
+    fn for_upload(device: &wgpu::Device, mesh: &Vertices) -> Self {
+        let buffer = |size: usize, usage: wgpu::BufferUsages| {
+            device.create_buffer(&wgpu::BufferDescriptor {
+                label: None,
+                size: size as u64,
+                usage: usage | wgpu::BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            })
+        };
+
+        Self {
+            vertex_buffer: buffer(
+                size_of_val(mesh.vertices()),
+                wgpu::BufferUsages::VERTEX,
+            ),
+            index_buffer: buffer(
+                size_of_val(mesh.indices()),
+                wgpu::BufferUsages::INDEX,
+            ),
+            num_indices: 0,
+            aabb: bounds(mesh.vertices()),
+        }
+    }
+
+    /// Create geometry with empty buffers of the given sizes, in bytes
+    ///
+    /// Nothing is drawn, until vertices and indices are written to the buffers
+    /// using [`Geometry::write`].
+    fn with_capacity(
+        device: &wgpu::Device,
+        vertex_bytes: usize,
+        index_bytes: usize,
+        aabb: Aabb<3>,
+    ) -> Self {
+        let buffer = |size: usize, usage: wgpu::BufferUsages| {
+            device.create_buffer(&wgpu::BufferDescriptor {
+                label: None,
+                size: size as u64,
+                usage: usage | wgpu::BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            })
+        };
+
+        Self {
+            vertex_buffer: buffer(vertex_bytes, wgpu::BufferUsages::VERTEX),
+            index_buffer: buffer(index_bytes, wgpu::BufferUsages::INDEX),
+            num_indices: 0,
+            aabb,
+        }
+    }
+
+    /// Write vertices and indices into the buffers, starting at the given
+    /// positions
+    ///
+    /// The indices are drawn, along with all indices before them.
+    fn write<V: Pod>(
+        &mut self,
+        queue: &wgpu::Queue,
+        first_vertex: usize,
+        vertices: &[V],
+        first_index: usize,
+        indices: &[u32],
+    ) {
+        // Writes must not be empty.
+        if !vertices.is_empty() {
+            queue.write_buffer(
+                &self.vertex_buffer,
+                (first_vertex * size_of::<V>()) as u64,
+                bytemuck::cast_slice(vertices),
+            );
+        }
+        if !indices.is_empty() {
+            queue.write_buffer(
+                &self.index_buffer,
+                (first_index * size_of::<u32>()) as u64,
+                bytemuck::cast_slice(indices),
+            );
+        }
+
+        self.num_indices = (first_index + indices.len())
+            .try_into()
+            .expect("`usize` couldn't be cast to `u32`");
+    }
+
+    /// Create geometry to be drawn with the wide lines pipeline
+    ///
+    /// Expects pairs of indices, each describing one line.
+    pub fn wide_lines(device: &wgpu::Device, lines: &Vertices) -> Self {
+        let wide_lines = WideLines::from(lines);
+
+        Self::from_raw(
+            device,
+            wide_lines.vertices(),
+            wide_lines.indices(),
+            bounds(lines.vertices()),
+        )
+    }
+
+    fn from_raw<V: Pod>(
+        device: &wgpu::Device,
+        vertices: &[V],
+        indices: &[u32],
+        aabb: Aabb<3>,
     ) -> Self {
         Self {
             vertex_buffer: device.create_buffer_init(
@@ -59,6 +290,254 @@ impl Geometry {
                 .len()
                 .try_into()
                 .expect("`usize` couldn't be cast to `u32`"),
+            aabb,
+        }
+    }
+
+    /// Determine whether any part of the geometry could be visible
+    pub fn is_visible(&self, frustum: &Frustum) -> bool {
+        // An empty geometry's AABB is meaningless, so check this first.
+        self.num_indices > 0
+            && frustum.classify(&self.aabb) != Containment::Outside
+    }
+}
+
+/// The creases and silhouette of the model, drawn as wide lines
+///
+/// The creases only change with the model, while the silhouette changes with
+/// the view. Both share the same buffers: The creases are written once, at the
+/// start, and the silhouette is written after them for each frame. There's
+/// room for a silhouette made from every edge of the model, so the buffers
+/// never need to grow.
+#[derive(Debug)]
+pub struct FeatureEdges {
+    pub geometry: Geometry,
+
+    num_crease_lines: usize,
+    max_silhouette_lines: usize,
+}
+
+impl FeatureEdges {
+    /// Upload the creases, and make room for the silhouette
+    ///
+    /// Expects pairs of indices in `creases`, each describing one line. The
+    /// silhouette may have up to `max_silhouette_lines` lines. `aabb` must
+    /// contain all of them.
+    pub fn new(
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        creases: &Vertices,
+        max_silhouette_lines: usize,
+        aabb: Aabb<3>,
+    ) -> Self {
+        let creases = WideLines::from(creases);
+        let num_crease_lines = creases.indices().len() / INDICES_PER_LINE;
+        let num_lines = num_crease_lines + max_silhouette_lines;
+
+        let mut geometry = Geometry::with_capacity(
+            device,
+            num_lines * VERTICES_PER_LINE * size_of::<WideLineVertex>(),
+            num_lines * INDICES_PER_LINE * size_of::<u32>(),
+            aabb,
+        );
+        geometry.write(queue, 0, creases.vertices(), 0, creases.indices());
+
+        Self {
+            geometry,
+            num_crease_lines,
+            max_silhouette_lines,
+        }
+    }
+
+    /// Replace the silhouette that is drawn after the creases
+    ///
+    /// Expects pairs of indices, each describing one line. Lines beyond the
+    /// maximum that was passed to [`FeatureEdges::new`] are left out.
+    pub fn set_silhouette(
+        &mut self,
+        queue: &wgpu::Queue,
+        silhouette: &Vertices,
+    ) {
+        let silhouette = WideLines::from(silhouette);
+
+        let num_lines = (silhouette.indices().len() / INDICES_PER_LINE)
+            .min(self.max_silhouette_lines);
+        let vertices = &silhouette.vertices()[..num_lines * VERTICES_PER_LINE];
+
+        // The silhouette's vertices come after those of the creases.
+        let first_vertex = self.num_crease_lines * VERTICES_PER_LINE;
+        let indices: Vec<_> = silhouette.indices()
+            [..num_lines * INDICES_PER_LINE]
+            .iter()
+            .map(|index| index + first_vertex as u32)
+            .collect();
+
+        self.geometry.num_indices =
+            (self.num_crease_lines * INDICES_PER_LINE) as u32;
+        self.geometry.write(
+            queue,
+            first_vertex,
+            vertices,
+            self.geometry.num_indices as usize,
+            &indices,
+        );
+    }
+}
+
+/// Geometry that is drawn once for each of a number of transforms
+#[derive(Debug)]
+pub struct InstancedGeometry {
+    /// The geometry, in the coordinate system of a single instance
+    ///
+    /// Its AABB covers all instances.
+    pub geometry: Geometry,
+
+    pub instance_buffer: wgpu::Buffer,
+    pub num_instances: u32,
+}
+
+impl InstancedGeometry {
+    fn new(
+        device: &wgpu::Device,
+        mesh: &Vertices,
+        transforms: &[Transform],
+    ) -> Self {
+        let mut geometry =
+            Geometry::new(device, mesh.vertices(), mesh.indices());
+        geometry.aabb = instance_bounds(geometry.aabb, transforms);
+
+        let instances = transforms
+            .iter()
+            .map(Instance::from_transform)
+            .collect::<Vec<_>>();
+
+        Self {
+            geometry,
+            instance_buffer: device.create_buffer_init(
+                &wgpu::util::BufferInitDescriptor {
+                    label: None,
+                    contents: bytemuck::cast_slice(&instances),
+                    usage: wgpu::BufferUsages::VERTEX,
+                },
+            ),
+            num_instances: instances
+                .len()
+                .try_into()
+                .expect("`usize` couldn't be cast to `u32`"),
+        }
+    }
+}
+
+/// The per-instance data of an [`InstancedGeometry`]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+#[repr(C)]
+pub struct Instance {
+    /// The model matrix, in column-major order
+    pub model: [[f32; 4]; 4],
+}
+
+impl Instance {
+    pub fn from_transform(transform: &Transform) -> Self {
+        let mut model = [[0.; 4]; 4];
+        for (i, value) in transform.data().iter().enumerate() {
+            model[i / 4][i % 4] = *value as f32;
+        }
+
+        Self { model }
+    }
+
+    pub fn layout() -> wgpu::VertexBufferLayout<'static> {
+        wgpu::VertexBufferLayout {
+            array_stride: size_of::<Self>() as u64,
+            step_mode: wgpu::VertexStepMode::Instance,
+            attributes: &INSTANCE_ATTRIBUTES,
         }
     }
 }
+
+/// One attribute per column of the model matrix
+///
+/// The locations follow those of [`Vertex`].
+const INSTANCE_ATTRIBUTES: [wgpu::VertexAttribute; 4] = wgpu::vertex_attr_array![
+    3 => Float32x4,
+    4 => Float32x4,
+    5 => Float32x4,
+    6 => Float32x4,
+];
+
+fn instance_bounds(aabb: Aabb<3>, transforms: &[Transform]) -> Aabb<3> {
+    transforms
+        .iter()
+        .map(|transform| transform.transform_aabb(&aabb))
+        .reduce(|a, b| a.merged(&b))
+        .unwrap_or(aabb)
+}
+
+fn bounds(vertices: &[Vertex]) -> Aabb<3> {
+    // Computing the AABB of no points would panic.
+    if vertices.is_empty() {
+        let origin = Point::origin();
+        return Aabb {
+            min: origin,
+            max: origin,
+        };
+    }
+
+    Aabb::<3>::from_points(
+        vertices.iter().map(|vertex| vertex.position.map(f64::from)),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Aabb, Point, Transform};
+
+    use crate::graphics::vertices::Vertex;
+
+    use super::{bounds, instance_bounds, Instance};
+
+    #[test]
+    fn bounds_of_vertices() {
+        let vertex = |position| Vertex {
+            position,
+            normal: [0., 0., 1.],
+            color: [1., 0., 0., 1.],
+        };
+
+        let aabb = bounds(&[vertex([0., 1., 2.]), vertex([-1., 3., 0.])]);
+        assert_eq!(aabb.min, Point::from([-1., 1., 0.]));
+        assert_eq!(aabb.max, Point::from([0., 3., 2.]));
+
+        let aabb = bounds(&[]);
+        assert_eq!(aabb.min, aabb.max);
+    }
+
+    #[test]
+    fn instance_from_transform() {
+        let instance =
+            Instance::from_transform(&Transform::translation([1., 2., 3.]));
+
+        assert_eq!(
+            instance.model,
+            [
+                [1., 0., 0., 0.],
+                [0., 1., 0., 0.],
+                [0., 0., 1., 0.],
+                [1., 2., 3., 1.],
+            ]
+        );
+    }
+
+    #[test]
+    fn bounds_of_instances() {
+        let aabb = Aabb {
+            min: Point::from([0., 0., 0.]),
+            max: Point::from([1., 1., 1.]),
+        };
+        let transforms = [0., 4.].map(|x| Transform::translation([x, 0., 0.]));
+
+        let aabb = instance_bounds(aabb, &transforms);
+        assert_eq!(aabb.min, Point::from([0., 0., 0.]));
+        assert_eq!(aabb.max, Point::from([5., 1., 1.]));
+    }
+}
@@ -1,18 +1,30 @@
 //! Rendering primitives, routines, and structures.
 
+mod capture;
+mod color_space;
+mod colormap;
 mod config_ui;
 mod draw_config;
 mod drawables;
+mod feature_edges;
+mod frustum;
 mod geometries;
 mod pipelines;
+mod progressive;
 mod renderer;
 mod shaders;
-mod transform;
+pub(crate) mod transform;
 mod uniforms;
 mod vertices;
+mod wide_lines;
 
 pub use self::{
-    draw_config::DrawConfig,
+    capture::{turntable_azimuths, RgbaImage},
+    color_space::{linear_to_srgb, srgb_to_linear, ColorSpace},
+    colormap::Colormap,
+    draw_config::{CullMode, DrawConfig},
+    feature_edges::{feature_edges, EdgeAdjacency},
+    progressive::UploadHandle,
     renderer::{DrawError, InitError, Renderer},
 };
 
@@ -1,16 +1,19 @@
-use std::mem::size_of;
-
 use super::{
+    draw_config::CullMode,
+    geometries::Instance,
     shaders::{Shader, Shaders},
     vertices::Vertex,
+    wide_lines::WideLineVertex,
     DEPTH_FORMAT,
 };
 
 #[derive(Debug)]
 pub struct Pipelines {
-    pub model: Pipeline,
+    pub model: PerCullMode<Pipeline>,
+    pub instanced: PerCullMode<Pipeline>,
     pub mesh: Pipeline,
-    pub lines: Pipeline,
+    pub wide_lines: Pipeline,
+    pub highlight: PerCullMode<Pipeline>,
 }
 
 impl Pipelines {
@@ -29,30 +32,90 @@ impl Pipelines {
         let shaders = Shaders::new(device);
 
         Self {
-            model: Pipeline::new(
-                device,
-                &pipeline_layout,
-                shaders.model(),
-                wgpu::PrimitiveTopology::TriangleList,
-                wgpu::PolygonMode::Fill,
-                color_format,
-            ),
-            mesh: Pipeline::new(
+            model: PerCullMode::new(|cull_mode| {
+                Pipeline::new(
+                    device,
+                    &pipeline_layout,
+                    shaders.model(),
+                    &[Vertex::layout()],
+                    triangle_primitive(cull_mode),
+                    wgpu::DepthBiasState::default(),
+                    color_format,
+                )
+            }),
+            instanced: PerCullMode::new(|cull_mode| {
+                Pipeline::new(
+                    device,
+                    &pipeline_layout,
+                    shaders.instanced(),
+                    &[Vertex::layout(), Instance::layout()],
+                    triangle_primitive(cull_mode),
+                    wgpu::DepthBiasState::default(),
+                    color_format,
+                )
+            }),
+            mesh: Pipeline::lines(
                 device,
                 &pipeline_layout,
                 shaders.mesh(),
-                wgpu::PrimitiveTopology::TriangleList,
-                wgpu::PolygonMode::Line,
                 color_format,
             ),
-            lines: Pipeline::new(
+            wide_lines: Pipeline::new(
                 device,
                 &pipeline_layout,
-                shaders.lines(),
-                wgpu::PrimitiveTopology::LineList,
-                wgpu::PolygonMode::Line,
+                shaders.wide_lines(),
+                &[WideLineVertex::layout()],
+                triangle_primitive(CullMode::None),
+                wgpu::DepthBiasState::default(),
                 color_format,
             ),
+            highlight: PerCullMode::new(|cull_mode| {
+                Pipeline::new(
+                    device,
+                    &pipeline_layout,
+                    shaders.highlight(),
+                    &[Vertex::layout()],
+                    triangle_primitive(cull_mode),
+                    // The highlighted triangles are drawn on top of the exact
+                    // same triangles of the model. Pull them towards the camera
+                    // a bit, to prevent z-fighting.
+                    wgpu::DepthBiasState {
+                        constant: -2,
+                        slope_scale: -1.,
+                        clamp: 0.,
+                    },
+                    color_format,
+                )
+            }),
+        }
+    }
+}
+
+/// One of something for each [`CullMode`]
+///
+/// The cull mode is part of a render pipeline, so switching it at runtime
+/// requires a pipeline for each mode.
+#[derive(Debug)]
+pub struct PerCullMode<T> {
+    back: T,
+    front: T,
+    none: T,
+}
+
+impl<T> PerCullMode<T> {
+    fn new(mut f: impl FnMut(CullMode) -> T) -> Self {
+        Self {
+            back: f(CullMode::Back),
+            front: f(CullMode::Front),
+            none: f(CullMode::None),
+        }
+    }
+
+    pub fn get(&self, cull_mode: CullMode) -> &T {
+        match cull_mode {
+            CullMode::Back => &self.back,
+            CullMode::Front => &self.front,
+            CullMode::None => &self.none,
         }
     }
 }
@@ -61,12 +124,35 @@ impl Pipelines {
 pub struct Pipeline(pub wgpu::RenderPipeline);
 
 impl Pipeline {
+    /// Create a pipeline for drawing lines
+    ///
+    /// Expects pairs of indices, each describing one line. Lines are depth
+    /// tested, so the model hides any lines behind it.
+    fn lines(
+        device: &wgpu::Device,
+        pipeline_layout: &wgpu::PipelineLayout,
+        shader: Shader,
+        color_format: wgpu::TextureFormat,
+    ) -> Self {
+        Self::new(
+            device,
+            pipeline_layout,
+            shader,
+            &[Vertex::layout()],
+            line_primitive(),
+            // Depth bias is only supported for triangles.
+            wgpu::DepthBiasState::default(),
+            color_format,
+        )
+    }
+
     fn new(
         device: &wgpu::Device,
         pipeline_layout: &wgpu::PipelineLayout,
         shader: Shader,
-        topology: wgpu::PrimitiveTopology,
-        polygon_mode: wgpu::PolygonMode,
+        vertex_layouts: &[wgpu::VertexBufferLayout],
+        primitive: wgpu::PrimitiveState,
+        depth_bias: wgpu::DepthBiasState,
         color_format: wgpu::TextureFormat,
     ) -> Self {
         let pipeline =
@@ -75,26 +161,10 @@ impl Pipeline {
                 layout: Some(pipeline_layout),
                 vertex: wgpu::VertexState {
                     module: shader.module,
-                    entry_point: "vertex",
-                    buffers: &[wgpu::VertexBufferLayout {
-                        array_stride: size_of::<Vertex>() as u64,
-                        step_mode: wgpu::VertexStepMode::Vertex,
-                        attributes: &wgpu::vertex_attr_array![
-                            0 => Float32x3,
-                            1 => Float32x3,
-                            2 => Float32x4,
-                        ],
-                    }],
-                },
-                primitive: wgpu::PrimitiveState {
-                    topology,
-                    strip_index_format: None,
-                    front_face: wgpu::FrontFace::Ccw,
-                    cull_mode: None,
-                    unclipped_depth: false,
-                    polygon_mode,
-                    conservative: false,
+                    entry_point: shader.vert_entry,
+                    buffers: vertex_layouts,
                 },
+                primitive,
                 depth_stencil: Some(wgpu::DepthStencilState {
                     format: DEPTH_FORMAT,
                     depth_write_enabled: true,
@@ -105,7 +175,7 @@ impl Pipeline {
                         read_mask: 0,
                         write_mask: 0,
                     },
-                    bias: wgpu::DepthBiasState::default(),
+                    bias: depth_bias,
                 }),
                 multisample: wgpu::MultisampleState {
                     count: 1,
@@ -129,3 +199,62 @@ impl Pipeline {
         Self(pipeline)
     }
 }
+
+fn triangle_primitive(cull_mode: CullMode) -> wgpu::PrimitiveState {
+    wgpu::PrimitiveState {
+        topology: wgpu::PrimitiveTopology::TriangleList,
+        strip_index_format: None,
+        front_face: wgpu::FrontFace::Ccw,
+        cull_mode: cull_mode.face(),
+        unclipped_depth: false,
+        polygon_mode: wgpu::PolygonMode::Fill,
+        conservative: false,
+    }
+}
+
+fn line_primitive() -> wgpu::PrimitiveState {
+    wgpu::PrimitiveState {
+        topology: wgpu::PrimitiveTopology::LineList,
+        // The polygon mode only affects triangles. Leaving it at `Fill` means
+        // drawing lines doesn't require `Features::POLYGON_MODE_LINE`.
+        polygon_mode: wgpu::PolygonMode::Fill,
+        ..triangle_primitive(CullMode::None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graphics::CullMode;
+
+    use super::{line_primitive, triangle_primitive, PerCullMode};
+
+    #[test]
+    fn line_primitive_draws_line_list() {
+        let primitive = line_primitive();
+
+        assert_eq!(primitive.topology, wgpu::PrimitiveTopology::LineList);
+        assert_eq!(primitive.polygon_mode, wgpu::PolygonMode::Fill);
+    }
+
+    #[test]
+    fn pipeline_is_selected_by_cull_mode() {
+        let mut constructed = Vec::new();
+        let primitives = PerCullMode::new(|cull_mode| {
+            constructed.push(cull_mode);
+            triangle_primitive(cull_mode)
+        });
+
+        assert_eq!(
+            constructed,
+            [CullMode::Back, CullMode::Front, CullMode::None]
+        );
+
+        for (cull_mode, face) in [
+            (CullMode::Back, Some(wgpu::Face::Back)),
+            (CullMode::Front, Some(wgpu::Face::Front)),
+            (CullMode::None, None),
+        ] {
+            assert_eq!(primitives.get(cull_mode).cull_mode, face);
+        }
+    }
+}
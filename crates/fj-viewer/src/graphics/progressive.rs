@@ -0,0 +1,199 @@
+//! Uploading a mesh to the GPU over multiple frames
+
+use std::{
+    collections::VecDeque,
+    mem::size_of,
+    ops::Range,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+use fj_interop::mesh::Index;
+
+use super::{
+    geometries::Geometry,
+    vertices::{Vertex, Vertices},
+};
+
+/// A mesh that is being uploaded in chunks, one per frame
+///
+/// The mesh is drawn as far as it has been uploaded, so it fills in, instead of
+/// the whole upload stalling a single frame.
+#[derive(Debug)]
+pub struct ProgressiveUpload {
+    chunks: VecDeque<UploadChunk>,
+    handle: UploadHandle,
+}
+
+impl ProgressiveUpload {
+    /// Plan the upload of a mesh, with up to `triangles_per_chunk` per frame
+    pub fn new(mesh: &Vertices, triangles_per_chunk: usize) -> Self {
+        Self {
+            chunks: upload_schedule(mesh.indices(), triangles_per_chunk).into(),
+            handle: UploadHandle::default(),
+        }
+    }
+
+    /// Access a handle that reports when the upload is complete
+    pub fn handle(&self) -> UploadHandle {
+        self.handle.clone()
+    }
+
+    /// Upload the next chunk of the mesh into the geometry
+    ///
+    /// The geometry must have been created with [`Geometry::for_upload`], for
+    /// the same mesh that was passed to [`ProgressiveUpload::new`]. Returns
+    /// `true`, once the last chunk has been uploaded.
+    pub fn upload_next(
+        &mut self,
+        queue: &wgpu::Queue,
+        mesh: &Vertices,
+        geometry: &mut Geometry,
+    ) -> bool {
+        if let Some(chunk) = self.chunks.pop_front() {
+            let vertices = &mesh.vertices()[chunk.vertices.clone()];
+            let indices = &mesh.indices()[chunk.indices.clone()];
+
+            if !vertices.is_empty() {
+                queue.write_buffer(
+                    &geometry.vertex_buffer,
+                    (chunk.vertices.start * size_of::<Vertex>()) as u64,
+                    bytemuck::cast_slice(vertices),
+                );
+            }
+            queue.write_buffer(
+                &geometry.index_buffer,
+                (chunk.indices.start * size_of::<Index>()) as u64,
+                bytemuck::cast_slice(indices),
+            );
+
+            geometry.num_indices = chunk.indices.end as u32;
+        }
+
+        if self.chunks.is_empty() {
+            self.finish();
+            return true;
+        }
+
+        false
+    }
+
+    /// Mark the upload as complete, without uploading any more chunks
+    ///
+    /// This is for uploads that are superseded by another one.
+    pub fn finish(&self) {
+        self.handle.complete.store(true, Ordering::Release);
+    }
+}
+
+/// Reports whether an upload started by [`Renderer::upload_progressive`] is
+/// complete
+///
+/// [`Renderer::upload_progressive`]: super::Renderer::upload_progressive
+#[derive(Clone, Debug, Default)]
+pub struct UploadHandle {
+    complete: Arc<AtomicBool>,
+}
+
+impl UploadHandle {
+    /// Whether the mesh has been fully uploaded
+    ///
+    /// Also true, if the upload was cancelled, because other geometry replaced
+    /// the mesh.
+    pub fn is_complete(&self) -> bool {
+        self.complete.load(Ordering::Acquire)
+    }
+}
+
+/// The part of a mesh that is uploaded at once
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct UploadChunk {
+    /// The range of indices that are uploaded
+    ///
+    /// Always covers whole triangles.
+    indices: Range<usize>,
+
+    /// The range of vertices that are uploaded along with the indices
+    ///
+    /// Covers all vertices that the indices refer to, that weren't uploaded
+    /// before.
+    vertices: Range<usize>,
+}
+
+/// Split the upload of a mesh into chunks of triangles
+///
+/// The chunks are in order. Together, they cover all indices, and all
+/// vertices that the indices refer to.
+fn upload_schedule(
+    indices: &[Index],
+    triangles_per_chunk: usize,
+) -> Vec<UploadChunk> {
+    let chunk_len = triangles_per_chunk.max(1) * 3;
+
+    let mut chunks = Vec::new();
+    let mut vertices_uploaded = 0;
+
+    for (i, chunk) in indices.chunks(chunk_len).enumerate() {
+        let start = i * chunk_len;
+
+        let vertices_required = chunk
+            .iter()
+            .map(|&index| index as usize + 1)
+            .max()
+            .unwrap_or(0)
+            .max(vertices_uploaded);
+
+        chunks.push(UploadChunk {
+            indices: start..start + chunk.len(),
+            vertices: vertices_uploaded..vertices_required,
+        });
+
+        vertices_uploaded = vertices_required;
+    }
+
+    chunks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{upload_schedule, UploadChunk};
+
+    #[test]
+    fn schedule() {
+        // Ten triangles in a strip, each sharing an edge with the one before.
+        let indices: Vec<u32> =
+            (0..10).flat_map(|i| [i, i + 1, i + 2]).collect();
+
+        let chunks = upload_schedule(&indices, 4);
+
+        assert_eq!(
+            chunks,
+            [
+                UploadChunk {
+                    indices: 0..12,
+                    vertices: 0..6,
+                },
+                UploadChunk {
+                    indices: 12..24,
+                    vertices: 6..10,
+                },
+                UploadChunk {
+                    indices: 24..30,
+                    vertices: 10..12,
+                },
+            ]
+        );
+
+        // Triangles that only refer to vertices that are already uploaded
+        // don't upload any more vertices.
+        let indices = [0, 1, 2, 2, 1, 0];
+        let chunks = upload_schedule(&indices, 1);
+        assert_eq!(chunks[1].vertices, 3..3);
+
+        // A chunk size of zero still makes progress.
+        assert_eq!(upload_schedule(&indices, 0).len(), 2);
+        assert!(upload_schedule(&[], 4).is_empty());
+    }
+}
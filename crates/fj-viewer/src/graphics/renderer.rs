@@ -1,23 +1,58 @@
-use std::{io, mem::size_of};
+use std::{io, mem::size_of, num::NonZeroU32};
 
-use fj_interop::status_report::StatusReport;
+use fj_interop::{debug::DebugInfo, mesh::Mesh, status_report::StatusReport};
 use fj_math::{Aabb, Point};
+use futures::executor::block_on;
 use thiserror::Error;
 use tracing::debug;
 use wgpu::util::DeviceExt as _;
 use wgpu_glyph::ab_glyph::InvalidFont;
 
 use crate::{
-    camera::Camera,
+    camera::{Camera, FocusPoint},
+    input::CameraController,
+    measurement::Measurement,
+    parameters::ParameterPanel,
     screen::{Screen, Size},
 };
 
 use super::{
-    config_ui::ConfigUi, draw_config::DrawConfig, drawables::Drawables,
-    geometries::Geometries, pipelines::Pipelines, transform::Transform,
-    uniforms::Uniforms, vertices::Vertices, DEPTH_FORMAT,
+    capture::{
+        padded_bytes_per_row, read_image, turntable_azimuths, RgbaImage,
+    },
+    color_space::{ColorConversion, ColorSpace},
+    colormap::Colormap,
+    config_ui::ConfigUi,
+    draw_config::{CullMode, DrawConfig},
+    drawables::{Drawable, Drawables},
+    feature_edges::EdgeAdjacency,
+    frustum::Frustum,
+    geometries::{FeatureEdges, Geometries, Geometry},
+    pipelines::Pipelines,
+    progressive::{ProgressiveUpload, UploadHandle},
+    transform::Transform,
+    uniforms::Uniforms,
+    vertices::Vertices,
+    DEPTH_FORMAT,
 };
 
+/// The color of highlighted triangles
+const HIGHLIGHT_COLOR: [f32; 4] = [1., 0.6, 0., 1.];
+
+/// The color of the line between measured points
+const MEASUREMENT_COLOR: [f32; 4] = [0., 0.5, 1., 1.];
+
+/// The color of the reference mesh
+///
+/// It's translucent, so the model shows through where it's inside of it.
+const REFERENCE_COLOR: [f32; 4] = [0.5, 0.5, 0.5, 0.4];
+
+/// The color of creases and silhouette edges
+const FEATURE_EDGE_COLOR: [f32; 4] = [0., 0., 0., 1.];
+
+/// The angle between triangles, above which their shared edge is a crease
+const CREASE_ANGLE: f64 = 30. * std::f64::consts::PI / 180.;
+
 #[derive(Default)]
 struct EguiOptionsState {
     show_trace: bool,
@@ -45,7 +80,6 @@ impl std::fmt::Debug for EguiState {
 #[derive(Debug)]
 pub struct Renderer {
     surface: wgpu::Surface,
-    features: wgpu::Features,
     device: wgpu::Device,
     queue: wgpu::Queue,
 
@@ -58,14 +92,47 @@ pub struct Renderer {
     geometries: Geometries,
     pipelines: Pipelines,
 
+    /// The vertices of the current model, kept to build highlights from
+    mesh: Vertices,
+
+    /// The edges of the current model, kept to find its silhouette
+    edges: EdgeAdjacency,
+    feature_edges: FeatureEdges,
+
+    /// The upload of the current model's mesh, if it's still in progress
+    upload: Option<ProgressiveUpload>,
+
+    /// A mesh that is displayed next to the model, like a 3D scan
+    reference: Option<Geometry>,
+
+    measurement: Option<Measurement>,
+    processing: bool,
+
     config_ui: ConfigUi,
 
+    /// The panel for editing the arguments of the model
+    pub parameters: ParameterPanel,
+
     /// State required for integration with `egui`.
     pub egui: EguiState,
 }
 
 impl Renderer {
+    /// Returns a new `Renderer`, blocking until it is initialized
+    ///
+    /// This is a wrapper around [`Renderer::new`], for callers that don't run
+    /// an executor. It can't be used where blocking isn't allowed, like on the
+    /// main thread of a web page.
+    pub fn new_blocking(
+        screen: &impl Screen<Window = egui_winit::winit::window::Window>,
+    ) -> Result<Self, InitError> {
+        block_on(Self::new(screen))
+    }
+
     /// Returns a new `Renderer`.
+    ///
+    /// Requesting the adapter and device is awaited, so this doesn't block the
+    /// calling thread.
     pub async fn new(
         screen: &impl Screen<Window = egui_winit::winit::window::Window>,
     ) -> Result<Self, InitError> {
@@ -136,26 +203,11 @@ impl Renderer {
             .await
             .ok_or(InitError::RequestAdapter)?;
 
-        let features = {
-            let desired_features = wgpu::Features::POLYGON_MODE_LINE;
-            let available_features = adapter.features();
-
-            // By requesting the intersection of desired and available features,
-            // we ensure two things:
-            //
-            // 1. That requesting the device doesn't panic, which would happen
-            //    if we requested unavailable features.
-            // 2. That a developer ends up accidentally using features that
-            //    happen to be available on their machine, but that aren't
-            //    necessarily available for all the users.
-            desired_features.intersection(available_features)
-        };
-
         let (device, queue) = adapter
             .request_device(
                 &wgpu::DeviceDescriptor {
                     label: None,
-                    features,
+                    features: wgpu::Features::empty(),
                     limits: wgpu::Limits::default(),
                 },
                 None,
@@ -220,11 +272,19 @@ impl Renderer {
             &device,
             &Vertices::empty(),
             &Vertices::empty(),
+            &Vertices::empty(),
             Aabb {
                 min: Point::from([0.0, 0.0, 0.0]),
                 max: Point::from([0.0, 0.0, 0.0]),
             },
         );
+        let feature_edges = FeatureEdges::new(
+            &device,
+            &queue,
+            &Vertices::empty(),
+            0,
+            geometries.aabb,
+        );
         let pipelines =
             Pipelines::new(&device, &bind_group_layout, color_format);
 
@@ -250,7 +310,6 @@ impl Renderer {
 
         Ok(Self {
             surface,
-            features,
             device,
             queue,
 
@@ -263,8 +322,18 @@ impl Renderer {
             geometries,
             pipelines,
 
+            mesh: Vertices::empty(),
+            edges: EdgeAdjacency::default(),
+            feature_edges,
+            upload: None,
+            reference: None,
+            measurement: None,
+            processing: false,
+
             config_ui,
 
+            parameters: ParameterPanel::new(),
+
             egui: EguiState {
                 context: egui_context,
                 winit_state: egui_winit_state,
@@ -274,23 +343,220 @@ impl Renderer {
         })
     }
 
+    /// The format of the surface that is rendered to
+    ///
+    /// Colors are converted according to [`DrawConfig::color_space`], based on
+    /// whether this is an sRGB format.
+    pub fn surface_format(&self) -> wgpu::TextureFormat {
+        self.surface_config.format
+    }
+
     /// Updates the geometry of the model being rendered.
+    ///
+    /// The measurement is kept, so a reloaded model can be compared to the
+    /// previous one.
     pub fn update_geometry(
         &mut self,
         mesh: Vertices,
-        lines: Vertices,
+        debug_info: &DebugInfo,
+        aabb: Aabb<3>,
+    ) {
+        let lines = Vertices::from(debug_info);
+        let approximations = Vertices::approximations(debug_info);
+
+        // This also removes the highlight and the scalar field, as they belonged
+        // to the previous mesh.
+        self.geometries =
+            Geometries::new(&self.device, &mesh, &lines, &approximations, aabb);
+        self.set_measurement(self.measurement);
+        self.set_mesh(mesh);
+    }
+
+    /// Updates the geometry of the model being rendered, over multiple frames
+    ///
+    /// Like [`Renderer::update_geometry`], except that the mesh is uploaded in
+    /// chunks of `triangles_per_frame`, one chunk for each call to
+    /// [`Renderer::draw`]. Until the upload is complete, only the part that was
+    /// already uploaded is drawn. This avoids a long stall for large meshes.
+    ///
+    /// The upload is cancelled by anything that replaces the mesh, like a
+    /// later call to either method.
+    pub fn upload_progressive(
+        &mut self,
+        mesh: Vertices,
+        debug_info: &DebugInfo,
         aabb: Aabb<3>,
+        triangles_per_frame: usize,
+    ) -> UploadHandle {
+        let lines = Vertices::from(debug_info);
+        let approximations = Vertices::approximations(debug_info);
+
+        self.geometries = Geometries::for_upload(
+            &self.device,
+            &mesh,
+            &lines,
+            &approximations,
+            aabb,
+        );
+
+        let upload = ProgressiveUpload::new(&mesh, triangles_per_frame);
+        let handle = upload.handle();
+
+        self.set_mesh(mesh);
+        self.upload = Some(upload);
+
+        handle
+    }
+
+    fn set_mesh(&mut self, mesh: Vertices) {
+        if let Some(upload) = self.upload.take() {
+            upload.finish();
+        }
+
+        self.edges = EdgeAdjacency::from(&mesh);
+
+        let mut creases = Vertices::empty();
+        for segment in self.edges.creases(CREASE_ANGLE) {
+            creases.push_line(segment.points(), [0.; 3], FEATURE_EDGE_COLOR);
+        }
+        self.feature_edges = FeatureEdges::new(
+            &self.device,
+            &self.queue,
+            &creases,
+            self.edges.num_edges(),
+            self.geometries.aabb,
+        );
+        self.mesh = mesh;
+
+        // The measurement is in model space, so it's still valid.
+        self.set_measurement(self.measurement);
+    }
+
+    /// Upload the next chunk of the mesh, if an upload is in progress
+    fn continue_upload(&mut self) {
+        if let Some(upload) = &mut self.upload {
+            let done = upload.upload_next(
+                &self.queue,
+                &self.mesh,
+                &mut self.geometries.mesh,
+            );
+
+            if done {
+                self.geometries.set_wireframe(&self.device, &self.mesh);
+                self.upload = None;
+            }
+        }
+    }
+
+    /// Stop uploading the mesh in chunks, as it's about to be replaced
+    fn cancel_upload(&mut self) {
+        if let Some(upload) = self.upload.take() {
+            upload.finish();
+            self.geometries.set_wireframe(&self.device, &self.mesh);
+        }
+    }
+
+    /// Draw meshes that appear multiple times in the model
+    ///
+    /// Each mesh is uploaded once and drawn with one instanced draw call, once
+    /// for each of its transforms. This is in addition to the mesh passed to
+    /// [`Renderer::update_geometry`], which removes the instanced meshes.
+    ///
+    /// Wireframe and feature edges are only drawn for the mesh passed to
+    /// [`Renderer::update_geometry`], not for the instanced meshes.
+    pub fn update_instanced_geometry(
+        &mut self,
+        instances: &[(Mesh<Point<3>>, Vec<fj_math::Transform>)],
     ) {
-        self.geometries = Geometries::new(&self.device, &mesh, &lines, aabb);
+        let batches: Vec<_> = instances
+            .iter()
+            .map(|(mesh, transforms)| {
+                (Vertices::from(mesh), transforms.clone())
+            })
+            .collect();
+
+        self.geometries.set_instanced(&self.device, &batches);
+    }
+
+    /// Display a mesh next to the model, for reference
+    ///
+    /// Unlike the model, the reference mesh is drawn in a single, translucent
+    /// color. It's kept when the model is updated. Pass `None` to remove it.
+    pub fn set_reference(&mut self, mesh: Option<&Mesh<Point<3>>>) {
+        self.reference = mesh.map(|mesh| {
+            let triangles: Vec<_> =
+                (0..mesh.triangles().count() as u32).collect();
+            let vertices =
+                Vertices::from(mesh).highlight(&triangles, REFERENCE_COLOR);
+
+            Geometry::new(&self.device, vertices.vertices(), vertices.indices())
+        });
+    }
+
+    /// Show a measurement between two points
+    ///
+    /// Draws a line between the points, labeled with the distance. Pass `None`
+    /// to remove the measurement.
+    pub fn set_measurement(&mut self, measurement: Option<Measurement>) {
+        let mut lines = Vertices::empty();
+        if let Some(Measurement { from, to }) = measurement {
+            if from != to {
+                lines.push_line([from, to], [0.; 3], MEASUREMENT_COLOR);
+            }
+        }
+
+        self.geometries.set_measurement(&self.device, &lines);
+        self.measurement = measurement;
+    }
+
+    /// Show whether a new version of the model is being processed
+    ///
+    /// The geometry that is currently displayed is kept, until it is replaced
+    /// using [`Renderer::update_geometry`].
+    pub fn set_processing(&mut self, processing: bool) {
+        self.processing = processing;
+    }
+
+    /// Color the model according to a scalar field
+    ///
+    /// `values` contains one value per vertex of the mesh that was last passed
+    /// to [`Renderer::update_geometry`]. They are normalized to the range
+    /// between the smallest and largest value, then mapped through `colormap`.
+    /// Vertices without a value keep their color.
+    pub fn set_scalar_field(&mut self, values: &[f32], colormap: Colormap) {
+        self.cancel_upload();
+
+        let mesh = self.mesh.with_scalar_field(values, colormap);
+        self.geometries.set_mesh(&self.device, &mesh);
     }
 
-    /// Resizes the render surface.
+    /// Remove the scalar field, going back to the mesh's own colors
+    pub fn clear_scalar_field(&mut self) {
+        self.cancel_upload();
+        self.geometries.set_mesh(&self.device, &self.mesh);
+    }
+
+    /// Highlight the triangles with the given indices
     ///
-    /// # Arguments
-    /// - `size`: The target size for the render surface.
-    pub fn handle_resize(&mut self, size: Size) {
-        self.surface_config.width = size.width;
-        self.surface_config.height = size.height;
+    /// The indices refer to the triangles of the mesh that was last passed to
+    /// [`Renderer::update_geometry`]. Indices that are out of range are
+    /// ignored. Pass an empty slice to remove the highlight.
+    pub fn set_highlight(&mut self, triangles: &[u32]) {
+        let highlight = self.mesh.highlight(triangles, HIGHLIGHT_COLOR);
+        self.geometries.set_highlight(&self.device, &highlight);
+    }
+
+    /// Resizes the render surface
+    ///
+    /// Reconfigures the surface and recreates the depth buffer. The projection
+    /// uses the new aspect ratio from the next call to [`Renderer::draw`] on.
+    ///
+    /// A surface can't be zero-sized, which is what some platforms report for
+    /// minimized windows. Such sizes are ignored.
+    pub fn resize(&mut self, width: u32, height: u32) {
+        if !resize_surface_config(&mut self.surface_config, width, height) {
+            return;
+        }
 
         self.surface.configure(&self.device, &self.surface_config);
 
@@ -299,19 +565,127 @@ impl Renderer {
         self.depth_view = depth_view;
     }
 
-    /// Draws the renderer, camera, and config state to the window.
-    pub fn draw(
+    /// Render the model into an image
+    ///
+    /// The image has the size of the window. Unlike [`Renderer::draw`], this
+    /// doesn't draw any UI.
+    pub fn capture_frame(
         &mut self,
         camera: &Camera,
-        config: &mut DrawConfig,
-        window: &egui_winit::winit::window::Window,
-        status: &mut StatusReport,
-    ) -> Result<(), DrawError> {
-        let aspect_ratio = self.surface_config.width as f64
-            / self.surface_config.height as f64;
+        config: &DrawConfig,
+    ) -> Result<RgbaImage, DrawError> {
+        let wgpu::SurfaceConfiguration {
+            width,
+            height,
+            format,
+            ..
+        } = self.surface_config;
+        let size = wgpu::Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        };
+
+        // The depth buffer has the size of the surface, so it can be reused.
+        let texture = self.device.create_texture(&wgpu::TextureDescriptor {
+            label: None,
+            size,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT
+                | wgpu::TextureUsages::COPY_SRC,
+        });
+        let color_view =
+            texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+        let bytes_per_row = padded_bytes_per_row(width);
+        let buffer = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: u64::from(bytes_per_row) * u64::from(height),
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+
+        let mut encoder = self.device.create_command_encoder(
+            &wgpu::CommandEncoderDescriptor { label: None },
+        );
+        self.draw_model(&mut encoder, &color_view, camera, config);
+        encoder.copy_texture_to_buffer(
+            texture.as_image_copy(),
+            wgpu::ImageCopyBuffer {
+                buffer: &buffer,
+                layout: wgpu::ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: NonZeroU32::new(bytes_per_row),
+                    rows_per_image: None,
+                },
+            },
+            size,
+        );
+        self.queue.submit(Some(encoder.finish()));
+
+        let slice = buffer.slice(..);
+        let mapped = slice.map_async(wgpu::MapMode::Read);
+        self.device.poll(wgpu::Maintain::Wait);
+        block_on(mapped)?;
+
+        let image =
+            read_image(&slice.get_mapped_range(), width, height, format);
+        buffer.unmap();
+
+        Ok(image)
+    }
+
+    /// Render a full turn of the model around the vertical axis
+    ///
+    /// The model rotates around the center of its AABB, starting from the
+    /// current camera position. See [`turntable_azimuths`] for the angles.
+    pub fn render_turntable(
+        &mut self,
+        camera: &Camera,
+        config: &DrawConfig,
+        frames: usize,
+    ) -> Result<Vec<RgbaImage>, DrawError> {
+        let focus_point = FocusPoint(self.geometries.aabb.center());
+        let mut controller = CameraController::default();
+
+        turntable_azimuths(frames)
+            .into_iter()
+            .map(|azimuth| {
+                let mut camera = camera.clone();
+                controller.on_rotate(0., azimuth, focus_point, &mut camera);
+                self.capture_frame(&camera, config)
+            })
+            .collect()
+    }
+
+    /// Draw the model and its debug information, without any UI
+    fn draw_model(
+        &mut self,
+        encoder: &mut wgpu::CommandEncoder,
+        color_view: &wgpu::TextureView,
+        camera: &Camera,
+        config: &DrawConfig,
+    ) {
         let uniforms = Uniforms {
-            transform: Transform::for_vertices(camera, aspect_ratio),
+            transform: Transform::for_vertices(
+                camera,
+                aspect_ratio(&self.surface_config),
+            ),
             transform_normals: Transform::for_normals(camera),
+            viewport_size: [
+                self.surface_config.width as f32,
+                self.surface_config.height as f32,
+            ],
+            line_width: config.line_width,
+            round_caps: config.round_line_caps.into(),
+            color_conversion: ColorConversion::new(
+                config.color_space,
+                self.surface_config.format,
+            ) as u32,
+            _padding: [0; 3],
         };
 
         self.queue.write_buffer(
@@ -320,49 +694,146 @@ impl Renderer {
             bytemuck::cast_slice(&[uniforms]),
         );
 
-        let surface_texture = self.surface.get_current_texture()?;
-        let color_view = surface_texture
-            .texture
-            .create_view(&wgpu::TextureViewDescriptor::default());
-
-        let mut encoder = self.device.create_command_encoder(
-            &wgpu::CommandEncoderDescriptor { label: None },
-        );
+        // Geometry that is completely outside of the view doesn't need to be
+        // drawn.
+        let frustum =
+            Frustum::from_view_projection(&uniforms.transform.0.map(f64::from));
 
-        self.clear_views(&mut encoder, &color_view);
+        self.clear_views(encoder, color_view);
 
-        let drawables = Drawables::new(&self.geometries, &self.pipelines);
+        let drawables =
+            Drawables::new(&self.geometries, &self.pipelines, config.cull);
 
-        if config.draw_model {
+        if config.draw_model && drawables.model.is_visible(&frustum) {
             drawables.model.draw(
-                &mut encoder,
-                &color_view,
+                encoder,
+                color_view,
                 &self.depth_view,
                 &self.bind_group,
             );
         }
+        for instanced in &drawables.instanced {
+            if config.draw_model && instanced.is_visible(&frustum) {
+                instanced.draw(
+                    encoder,
+                    color_view,
+                    &self.depth_view,
+                    &self.bind_group,
+                );
+            }
+        }
 
-        // NOTE: This does not inform the user if the renderer cannot
-        // use the POLYGON_MODE_LINE feature.
-        if self.features.contains(wgpu::Features::POLYGON_MODE_LINE) {
-            if config.draw_mesh {
-                drawables.mesh.draw(
-                    &mut encoder,
-                    &color_view,
+        // The reference is translucent, so it must be drawn after the model,
+        // which it might hide.
+        if let (true, Some(reference)) =
+            (config.draw_reference, &self.reference)
+        {
+            let reference =
+                Drawable::new(reference, self.pipelines.model.get(config.cull));
+
+            if reference.is_visible(&frustum) {
+                reference.draw(
+                    encoder,
+                    color_view,
                     &self.depth_view,
                     &self.bind_group,
                 );
             }
-            if config.draw_debug {
-                drawables.lines.draw(
-                    &mut encoder,
-                    &color_view,
+        }
+
+        if drawables.highlight.is_visible(&frustum) {
+            drawables.highlight.draw(
+                encoder,
+                color_view,
+                &self.depth_view,
+                &self.bind_group,
+            );
+        }
+
+        if config.draw_mesh && drawables.mesh.is_visible(&frustum) {
+            drawables.mesh.draw(
+                encoder,
+                color_view,
+                &self.depth_view,
+                &self.bind_group,
+            );
+        }
+        if config.draw_debug && drawables.lines.is_visible(&frustum) {
+            drawables.lines.draw(
+                encoder,
+                color_view,
+                &self.depth_view,
+                &self.bind_group,
+            );
+        }
+        if config.draw_approximation
+            && drawables.approximations.is_visible(&frustum)
+        {
+            drawables.approximations.draw(
+                encoder,
+                color_view,
+                &self.depth_view,
+                &self.bind_group,
+            );
+        }
+
+        if config.draw_feature_edges {
+            // The silhouette depends on the view, so it's found anew for each
+            // frame.
+            let silhouettes = self.edges.silhouettes(camera.position());
+
+            let mut lines = Vertices::empty();
+            for segment in silhouettes {
+                lines.push_line(segment.points(), [0.; 3], FEATURE_EDGE_COLOR);
+            }
+            self.feature_edges.set_silhouette(&self.queue, &lines);
+
+            let feature_edges = Drawable::new(
+                &self.feature_edges.geometry,
+                &self.pipelines.wide_lines,
+            );
+
+            if feature_edges.is_visible(&frustum) {
+                feature_edges.draw(
+                    encoder,
+                    color_view,
                     &self.depth_view,
                     &self.bind_group,
                 );
             }
         }
 
+        if drawables.measurement.is_visible(&frustum) {
+            drawables.measurement.draw(
+                encoder,
+                color_view,
+                &self.depth_view,
+                &self.bind_group,
+            );
+        }
+    }
+
+    /// Draws the renderer, camera, and config state to the window.
+    pub fn draw(
+        &mut self,
+        camera: &Camera,
+        config: &mut DrawConfig,
+        window: &egui_winit::winit::window::Window,
+        status: &mut StatusReport,
+    ) -> Result<(), DrawError> {
+        self.continue_upload();
+
+        let surface_texture = self.surface.get_current_texture()?;
+        let color_view = surface_texture
+            .texture
+            .create_view(&wgpu::TextureViewDescriptor::default());
+
+        let mut encoder = self.device.create_command_encoder(
+            &wgpu::CommandEncoderDescriptor { label: None },
+        );
+
+        self.draw_model(&mut encoder, &color_view, camera, config);
+
         if self.egui.options.show_original_ui {
             self.config_ui
                 .draw(
@@ -426,24 +897,51 @@ impl Renderer {
             info
         }
 
-        let line_drawing_available = self.is_line_drawing_available();
-
         egui::SidePanel::left("fj-left-panel").show(&self.egui.context, |ui| {
             ui.add_space(16.0);
 
             ui.group(|ui| {
                 ui.checkbox(&mut config.draw_model, "Render model")
                     .on_hover_text_at_pointer("Toggle with 1");
-                ui.add_enabled(line_drawing_available, egui::Checkbox::new(&mut config.draw_mesh, "Render mesh"))
-                    .on_hover_text_at_pointer("Toggle with 2")
-                    .on_disabled_hover_text(
-                        "Rendering device does not have line rendering feature support",
+                ui.checkbox(&mut config.draw_mesh, "Render mesh")
+                    .on_hover_text_at_pointer("Toggle with 2");
+                ui.checkbox(&mut config.draw_debug, "Render debug")
+                    .on_hover_text_at_pointer("Toggle with 3");
+                ui.checkbox(
+                    &mut config.draw_approximation,
+                    "Render approximation",
+                )
+                .on_hover_text_at_pointer("Toggle with 4");
+                ui.checkbox(&mut config.draw_reference, "Render reference")
+                    .on_hover_text_at_pointer("Toggle with 5");
+                ui.checkbox(
+                    &mut config.draw_feature_edges,
+                    "Render feature edges",
+                );
+                ui.add(
+                    egui::Slider::new(&mut config.line_width, 1.0..=10.0)
+                        .text("Line width"),
+                );
+                ui.checkbox(&mut config.round_line_caps, "Round line caps");
+                ui.horizontal(|ui| {
+                    ui.label("Colors:");
+                    ui.radio_value(
+                        &mut config.color_space,
+                        ColorSpace::Srgb,
+                        "sRGB",
                     );
-                ui.add_enabled(line_drawing_available, egui::Checkbox::new(&mut config.draw_debug, "Render debug"))
-                    .on_hover_text_at_pointer("Toggle with 3")
-                    .on_disabled_hover_text(
-                        "Rendering device does not have line rendering feature support"
+                    ui.radio_value(
+                        &mut config.color_space,
+                        ColorSpace::Linear,
+                        "Linear",
                     );
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Cull:");
+                    ui.radio_value(&mut config.cull, CullMode::Back, "Back");
+                    ui.radio_value(&mut config.cull, CullMode::Front, "Front");
+                    ui.radio_value(&mut config.cull, CullMode::None, "None");
+                });
                 ui.checkbox(
                     &mut self.egui.options.show_original_ui,
                     "Render original UI",
@@ -571,15 +1069,62 @@ impl Renderer {
             ui.add_space(16.0);
         });
 
+        if !self.parameters.is_empty() {
+            egui::SidePanel::right("fj-parameters").show(
+                &self.egui.context,
+                |ui| {
+                    ui.add_space(16.0);
+                    ui.strong("Parameters");
+                    ui.group(|ui| self.parameters.ui(ui));
+                },
+            );
+        }
+
         egui::Area::new("fj-status-message").show(&self.egui.context, |ui| {
             ui.group(|ui| {
                 ui.add(egui::Label::new(
-                    egui::RichText::new(format!("Status:{}", status.status()))
-                        .color(egui::Color32::BLACK),
+                    egui::RichText::new(format!(
+                        "Status:{}{}",
+                        status.status(),
+                        if self.processing {
+                            "\nProcessing model..."
+                        } else {
+                            ""
+                        }
+                    ))
+                    .color(egui::Color32::BLACK),
                 ))
             })
         });
 
+        if let Some(measurement) = self.measurement {
+            let midpoint = measurement.from + measurement.deltas() / 2.;
+            let transform = Transform::for_vertices(
+                camera,
+                aspect_ratio(&self.surface_config),
+            );
+            let position = project_to_screen(
+                &transform,
+                midpoint,
+                &self.surface_config,
+                window.scale_factor() as f32,
+            );
+
+            if let Some([x, y]) = position {
+                egui::Area::new("fj-measurement").fixed_pos([x, y]).show(
+                    &self.egui.context,
+                    |ui| {
+                        ui.group(|ui| {
+                            ui.label(
+                                egui::RichText::new(measurement.to_string())
+                                    .color(egui::Color32::BLACK),
+                            )
+                        })
+                    },
+                );
+            }
+        }
+
         // End the UI frame. We could now handle the output and draw the UI with the backend.
         let egui_output = self.egui.context.end_frame();
         let egui_paint_jobs = self.egui.context.tessellate(egui_output.shapes);
@@ -655,11 +1200,58 @@ impl Renderer {
             ),
         });
     }
+}
+
+/// Update the size in a surface configuration
+///
+/// Returns `false`, leaving the configuration unchanged, if either dimension is
+/// zero.
+fn resize_surface_config(
+    config: &mut wgpu::SurfaceConfiguration,
+    width: u32,
+    height: u32,
+) -> bool {
+    if width == 0 || height == 0 {
+        return false;
+    }
 
-    /// Returns true if the renderer's adapter can draw lines
-    pub fn is_line_drawing_available(&self) -> bool {
-        self.features.contains(wgpu::Features::POLYGON_MODE_LINE)
+    config.width = width;
+    config.height = height;
+
+    true
+}
+
+/// Compute where a point appears on the screen, in `egui` points
+///
+/// Returns `None`, if the point is behind the camera.
+fn project_to_screen(
+    transform: &Transform,
+    point: Point<3>,
+    config: &wgpu::SurfaceConfiguration,
+    pixels_per_point: f32,
+) -> Option<[f32; 2]> {
+    let m = &transform.0;
+    let [x, y, z] = point.coords.components.map(|s| s.into_f32());
+
+    // The matrix is stored in column-major order.
+    let clip = [0, 1, 3]
+        .map(|row| m[row] * x + m[4 + row] * y + m[8 + row] * z + m[12 + row]);
+    let [clip_x, clip_y, w] = clip;
+    if w <= 0. {
+        return None;
     }
+
+    let width = config.width as f32 / pixels_per_point;
+    let height = config.height as f32 / pixels_per_point;
+
+    Some([
+        (clip_x / w + 1.) / 2. * width,
+        (1. - clip_y / w) / 2. * height,
+    ])
+}
+
+fn aspect_ratio(config: &wgpu::SurfaceConfiguration) -> f64 {
+    config.width as f64 / config.height as f64
 }
 
 /// Error describing the set of render surface initialization errors
@@ -689,17 +1281,76 @@ pub enum InitError {
 /// Graphics rendering error
 ///
 /// Describes errors related to non initialization graphics errors.
+///
+/// [`DrawError::Outdated`] and [`DrawError::Lost`] can be recovered from, by
+/// reconfiguring the surface and drawing again. [`DrawError::OutOfMemory`] is
+/// fatal.
+///
+/// ```
+/// use fj_viewer::graphics::DrawError;
+///
+/// fn should_retry(err: &DrawError) -> bool {
+///     match err {
+///         DrawError::Outdated | DrawError::Lost => true,
+///         DrawError::Timeout => false,
+///         DrawError::OutOfMemory | DrawError::Text(_) => false,
+///         DrawError::ReadBack(_) => false,
+///     }
+/// }
+///
+/// let err = DrawError::from(wgpu::SurfaceError::Outdated);
+///
+/// assert!(matches!(err, DrawError::Outdated));
+/// assert!(should_retry(&err));
+/// assert!(err.is_recoverable());
+/// ```
 #[derive(Error, Debug)]
 pub enum DrawError {
-    #[error("Error acquiring output surface: {0}")]
-    /// Surface drawing error.
+    #[error("Timed out acquiring output surface")]
+    /// Acquiring the next surface texture took too long
+    ///
+    /// Drawing can be attempted again with the next frame.
+    Timeout,
+
+    #[error("Output surface is outdated")]
+    /// The surface no longer matches the window, e.g. after a resize
     ///
-    /// See - [wgpu::SurfaceError](https://docs.rs/wgpu/latest/wgpu/enum.SurfaceError.html)
-    Surface(#[from] wgpu::SurfaceError),
+    /// The surface needs to be reconfigured, before drawing again.
+    Outdated,
+
+    #[error("Output surface was lost")]
+    /// The surface was lost and needs to be reconfigured
+    Lost,
+
+    #[error("Out of memory while acquiring output surface")]
+    /// There is not enough memory left to acquire a surface texture
+    OutOfMemory,
 
     #[error("Error drawing text: {0}")]
     /// Text rasterisation error.
     Text(String),
+
+    #[error("Error reading back a captured frame")]
+    /// The buffer that a captured frame was copied into couldn't be mapped
+    ReadBack(#[from] wgpu::BufferAsyncError),
+}
+
+impl DrawError {
+    /// Indicate whether reconfiguring the surface can fix this error
+    pub fn is_recoverable(&self) -> bool {
+        matches!(self, Self::Outdated | Self::Lost)
+    }
+}
+
+impl From<wgpu::SurfaceError> for DrawError {
+    fn from(err: wgpu::SurfaceError) -> Self {
+        match err {
+            wgpu::SurfaceError::Timeout => Self::Timeout,
+            wgpu::SurfaceError::Outdated => Self::Outdated,
+            wgpu::SurfaceError::Lost => Self::Lost,
+            wgpu::SurfaceError::OutOfMemory => Self::OutOfMemory,
+        }
+    }
 }
 
 impl Renderer {
@@ -795,3 +1446,75 @@ impl Renderer {
         );
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Aabb, Point};
+
+    use crate::{camera::Camera, graphics::transform::Transform};
+
+    use super::{aspect_ratio, project_to_screen, resize_surface_config};
+
+    fn surface_config(width: u32, height: u32) -> wgpu::SurfaceConfiguration {
+        wgpu::SurfaceConfiguration {
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            format: wgpu::TextureFormat::Bgra8UnormSrgb,
+            width,
+            height,
+            present_mode: wgpu::PresentMode::Mailbox,
+        }
+    }
+
+    fn projection(
+        camera: &Camera,
+        config: &wgpu::SurfaceConfiguration,
+    ) -> [f32; 16] {
+        Transform::for_vertices(camera, aspect_ratio(config)).0
+    }
+
+    #[test]
+    fn resize_updates_projection() {
+        let camera = Camera::new(&Aabb {
+            min: Point::from([-1., -1., -1.]),
+            max: Point::from([1., 1., 1.]),
+        });
+        let mut config = surface_config(800, 600);
+        let before = projection(&camera, &config);
+
+        assert!(resize_surface_config(&mut config, 600, 800));
+
+        assert_eq!(aspect_ratio(&config), 0.75);
+        assert_ne!(projection(&camera, &config), before);
+    }
+
+    #[test]
+    fn resize_to_zero_is_ignored() {
+        let mut config = surface_config(800, 600);
+
+        assert!(!resize_surface_config(&mut config, 800, 0));
+        assert!(!resize_surface_config(&mut config, 0, 600));
+
+        assert_eq!((config.width, config.height), (800, 600));
+    }
+
+    #[test]
+    fn project_center_of_view() {
+        let aabb = Aabb {
+            min: Point::from([-1., -1., -1.]),
+            max: Point::from([1., 1., 1.]),
+        };
+        let camera = Camera::new(&aabb);
+        let config = surface_config(800, 600);
+        let transform = Transform::for_vertices(&camera, aspect_ratio(&config));
+
+        // The camera looks at the center of the model. With 2 pixels per
+        // point, the screen is 400x300 points large.
+        let [x, y] =
+            project_to_screen(&transform, aabb.center(), &config, 2.).unwrap();
+        assert!((x - 200.).abs() < 1e-3);
+        assert!((y - 150.).abs() < 1e-3);
+
+        let behind = camera.position() + (camera.position() - aabb.center());
+        assert_eq!(project_to_screen(&transform, behind, &config, 2.), None);
+    }
+}
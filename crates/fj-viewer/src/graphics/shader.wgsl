@@ -7,11 +7,41 @@ struct VertexOutput {
 struct Uniforms {
     transform: mat4x4<f32>;
     transform_normals: mat4x4<f32>;
+    viewport_size: vec2<f32>;
+    line_width: f32;
+    round_caps: u32;
+    color_conversion: u32;
 };
 
 [[group(0), binding(0)]]
 var<uniform> uniforms: Uniforms;
 
+fn srgb_to_linear(c: vec3<f32>) -> vec3<f32> {
+    let low = c / 12.92;
+    let high = pow((c + 0.055) / 1.055, vec3<f32>(2.4));
+    return select(high, low, c <= vec3<f32>(0.04045));
+}
+
+fn linear_to_srgb(c: vec3<f32>) -> vec3<f32> {
+    let low = c * 12.92;
+    let high = 1.055 * pow(c, vec3<f32>(1.0 / 2.4)) - 0.055;
+    return select(high, low, c <= vec3<f32>(0.0031308));
+}
+
+// Converts a color as described by `ColorConversion`, then premultiplies its
+// alpha, as we use premultiplied alpha blending.
+fn prepare_color(color: vec4<f32>) -> vec4<f32> {
+    var rgb: vec3<f32> = color.rgb;
+    if (uniforms.color_conversion == 1u) {
+        rgb = srgb_to_linear(rgb);
+    }
+    if (uniforms.color_conversion == 2u) {
+        rgb = linear_to_srgb(rgb);
+    }
+
+    return vec4<f32>(rgb * color.a, color.a);
+}
+
 [[stage(vertex)]]
 fn vertex(
     [[location(0)]] position: vec3<f32>,
@@ -23,12 +53,110 @@ fn vertex(
     var out: VertexOutput;
     out.normal = (uniforms.transform_normals * vec4<f32>(normal, 0.0)).xyz;
     out.position = uniforms.transform * vec4<f32>(position, 1.0);
-    // We use premultiplied alpha blending.
-    out.color = vec4<f32>(color.rgb * color.a, color.a);
+    out.color = prepare_color(color);
 
     return out;
 }
 
+[[stage(vertex)]]
+fn vertex_instanced(
+    [[location(0)]] position: vec3<f32>,
+    [[location(1)]] normal: vec3<f32>,
+    [[location(2)]] color: vec4<f32>,
+    [[location(3)]] model_0: vec4<f32>,
+    [[location(4)]] model_1: vec4<f32>,
+    [[location(5)]] model_2: vec4<f32>,
+    [[location(6)]] model_3: vec4<f32>,
+)
+    -> VertexOutput
+{
+    let model = mat4x4<f32>(model_0, model_1, model_2, model_3);
+
+    // The model matrix doesn't shear or scale non-uniformly, so it can
+    // transform normals too. A uniform scale still changes their length.
+    let model_normal = normalize((model * vec4<f32>(normal, 0.0)).xyz);
+
+    var out: VertexOutput;
+    out.normal =
+        (uniforms.transform_normals * vec4<f32>(model_normal, 0.0)).xyz;
+    out.position = uniforms.transform * model * vec4<f32>(position, 1.0);
+    out.color = prepare_color(color);
+
+    return out;
+}
+
+struct WideLineOutput {
+    [[builtin(position)]] position: vec4<f32>;
+    [[location(0)]] color: vec4<f32>;
+    // The position within the line, in pixels: `x` along the line, from its
+    // start; `y` across the line, from its center. `z` is the line's length.
+    [[location(1)]] line: vec3<f32>;
+};
+
+// Lines are widened by this many pixels on each side, to leave room for
+// antialiasing.
+let antialiasing_margin: f32 = 1.0;
+
+[[stage(vertex)]]
+fn vertex_wide_line(
+    [[location(0)]] position: vec3<f32>,
+    [[location(1)]] other: vec3<f32>,
+    [[location(2)]] color: vec4<f32>,
+    [[location(3)]] corner: vec2<f32>,
+)
+    -> WideLineOutput
+{
+    let clip = uniforms.transform * vec4<f32>(position, 1.0);
+    let clip_other = uniforms.transform * vec4<f32>(other, 1.0);
+
+    // Screen space, in pixels, relative to the center of the viewport.
+    let half_viewport = uniforms.viewport_size * 0.5;
+    let screen = clip.xy / clip.w * half_viewport;
+    let screen_other = clip_other.xy / clip_other.w * half_viewport;
+
+    let is_end = corner.x > 0.5;
+    let start = select(screen, screen_other, is_end);
+    let end = select(screen_other, screen, is_end);
+
+    let length = distance(start, end);
+    let dir = (end - start) / max(length, 0.0001);
+    let normal = vec2<f32>(-dir.y, dir.x);
+
+    let half_width = uniforms.line_width * 0.5 + antialiasing_margin;
+    let cap = select(0.0, half_width, uniforms.round_caps != 0u);
+
+    let along = select(-cap, cap, is_end);
+    let across = corner.y * half_width;
+    let offset = dir * along + normal * across;
+
+    var out: WideLineOutput;
+    out.position = vec4<f32>(
+        clip.xy + offset / half_viewport * clip.w,
+        clip.z,
+        clip.w,
+    );
+    out.color = prepare_color(color);
+    out.line = vec3<f32>(select(0.0, length, is_end) + along, across, length);
+
+    return out;
+}
+
+[[stage(fragment)]]
+fn frag_wide_line(in: WideLineOutput) -> [[location(0)]] vec4<f32> {
+    // Distance from the line, in pixels. With round caps, that's the distance
+    // from the segment; otherwise only the distance across the line matters.
+    var d: f32 = abs(in.line.y);
+    if (uniforms.round_caps != 0u) {
+        let nearest = vec2<f32>(clamp(in.line.x, 0.0, in.line.z), 0.0);
+        d = distance(in.line.xy, nearest);
+    }
+
+    let coverage = clamp(uniforms.line_width * 0.5 + 0.5 - d, 0.0, 1.0);
+
+    // The color is premultiplied, so all components need to be scaled.
+    return in.color * coverage;
+}
+
 let pi: f32 = 3.14159265359;
 
 [[stage(fragment)]]
@@ -51,6 +179,6 @@ fn frag_mesh(in: VertexOutput) -> [[location(0)]] vec4<f32> {
 }
 
 [[stage(fragment)]]
-fn frag_lines(in: VertexOutput) -> [[location(0)]] vec4<f32> {
-    return vec4<f32>(in.color.rgb, in.color.a);
+fn frag_highlight(in: VertexOutput) -> [[location(0)]] vec4<f32> {
+    return in.color;
 }
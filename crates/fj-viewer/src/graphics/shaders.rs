@@ -18,6 +18,15 @@ impl Shaders {
     pub fn model(&self) -> Shader {
         Shader {
             module: &self.0,
+            vert_entry: "vertex",
+            frag_entry: "frag_model",
+        }
+    }
+
+    pub fn instanced(&self) -> Shader {
+        Shader {
+            module: &self.0,
+            vert_entry: "vertex_instanced",
             frag_entry: "frag_model",
         }
     }
@@ -25,14 +34,24 @@ impl Shaders {
     pub fn mesh(&self) -> Shader {
         Shader {
             module: &self.0,
+            vert_entry: "vertex",
             frag_entry: "frag_mesh",
         }
     }
 
-    pub fn lines(&self) -> Shader {
+    pub fn wide_lines(&self) -> Shader {
+        Shader {
+            module: &self.0,
+            vert_entry: "vertex_wide_line",
+            frag_entry: "frag_wide_line",
+        }
+    }
+
+    pub fn highlight(&self) -> Shader {
         Shader {
             module: &self.0,
-            frag_entry: "frag_lines",
+            vert_entry: "vertex",
+            frag_entry: "frag_highlight",
         }
     }
 }
@@ -40,5 +59,22 @@ impl Shaders {
 #[derive(Clone, Copy)]
 pub struct Shader<'r> {
     pub module: &'r wgpu::ShaderModule,
+    pub vert_entry: &'static str,
     pub frag_entry: &'static str,
 }
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn shader_is_valid() {
+        let module =
+            naga::front::wgsl::parse_str(include_str!("shader.wgsl")).unwrap();
+
+        naga::valid::Validator::new(
+            naga::valid::ValidationFlags::all(),
+            naga::valid::Capabilities::empty(),
+        )
+        .validate(&module)
+        .unwrap();
+    }
+}
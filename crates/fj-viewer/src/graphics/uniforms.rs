@@ -7,6 +7,23 @@ use super::transform::Transform;
 pub struct Uniforms {
     pub transform: Transform,
     pub transform_normals: Transform,
+
+    /// The size of the viewport, in pixels
+    pub viewport_size: [f32; 2],
+
+    /// The width of wide lines, in pixels
+    pub line_width: f32,
+
+    /// Whether wide lines have round caps; `0` means they don't
+    pub round_caps: u32,
+
+    /// A [`ColorConversion`] discriminant
+    ///
+    /// [`ColorConversion`]: super::color_space::ColorConversion
+    pub color_conversion: u32,
+
+    /// Pads the struct to a multiple of 16 bytes, as WGSL requires
+    pub _padding: [u32; 3],
 }
 
 impl Default for Uniforms {
@@ -14,6 +31,11 @@ impl Default for Uniforms {
         Self {
             transform: Transform::identity(),
             transform_normals: Transform::identity(),
+            viewport_size: [1., 1.],
+            line_width: 1.,
+            round_caps: 0,
+            color_conversion: 0,
+            _padding: [0; 3],
         }
     }
 }
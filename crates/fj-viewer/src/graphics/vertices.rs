@@ -1,3 +1,5 @@
+use std::{collections::BTreeSet, mem::size_of};
+
 use bytemuck::{Pod, Zeroable};
 use fj_interop::{
     debug::DebugInfo,
@@ -5,10 +7,17 @@ use fj_interop::{
 };
 use fj_math::{Point, Vector};
 
+use super::colormap::Colormap;
+
 #[derive(Debug)]
 pub struct Vertices {
     vertices: Vec<Vertex>,
     indices: Vec<Index>,
+
+    /// The index of the mesh vertex that each vertex was created from
+    ///
+    /// Empty, if these vertices weren't created from a [`Mesh`].
+    sources: Vec<Index>,
 }
 
 impl Vertices {
@@ -16,6 +25,7 @@ impl Vertices {
         Self {
             vertices: Vec::new(),
             indices: Vec::new(),
+            sources: Vec::new(),
         }
     }
 
@@ -27,6 +37,11 @@ impl Vertices {
         self.indices.as_slice()
     }
 
+    /// The index of the mesh vertex that each vertex was created from
+    pub fn sources(&self) -> &[Index] {
+        self.sources.as_slice()
+    }
+
     pub fn push_line(
         &mut self,
         line: [Point<3>; 2],
@@ -45,6 +60,98 @@ impl Vertices {
         self.indices.push(self.indices.len() as u32);
     }
 
+    /// Copy the given triangles, replacing their color
+    ///
+    /// `triangles` are indices into the triangles of these vertices, as they
+    /// were created from a [`Mesh`]. Indices that are out of range are ignored.
+    pub fn highlight(&self, triangles: &[u32], color: [f32; 4]) -> Self {
+        let mut self_ = Self::empty();
+
+        for &triangle in triangles {
+            let start = triangle as usize * 3;
+            let indices = match self.indices.get(start..start + 3) {
+                Some(indices) => indices,
+                None => continue,
+            };
+
+            for &index in indices {
+                let vertex = self.vertices[index as usize];
+
+                self_.indices.push(self_.vertices.len() as u32);
+                self_.vertices.push(Vertex { color, ..vertex });
+            }
+        }
+
+        self_
+    }
+
+    /// Create the edges of all triangles, to be drawn as lines
+    ///
+    /// Shares the vertices of the triangles. Edges that are part of multiple
+    /// triangles are only included once.
+    pub fn wireframe(&self) -> Self {
+        let mut edges = BTreeSet::new();
+
+        for triangle in self.indices.chunks(3) {
+            for (i, &a) in triangle.iter().enumerate() {
+                let b = triangle[(i + 1) % triangle.len()];
+                edges.insert((a.min(b), a.max(b)));
+            }
+        }
+
+        Self {
+            vertices: self.vertices.clone(),
+            indices: edges.into_iter().flat_map(|(a, b)| [a, b]).collect(),
+            sources: self.sources.clone(),
+        }
+    }
+
+    /// Copy the vertices, coloring them according to a scalar field
+    ///
+    /// `values` contains one value per vertex of the [`Mesh`] these vertices
+    /// were created from. The values are normalized to the range between the
+    /// smallest and the largest of them, then mapped through `colormap`.
+    /// Vertices without a value keep their color.
+    pub fn with_scalar_field(
+        &self,
+        values: &[f32],
+        colormap: Colormap,
+    ) -> Self {
+        let finite = values.iter().copied().filter(|value| value.is_finite());
+        let min = finite.clone().fold(f32::INFINITY, f32::min);
+        let max = finite.fold(f32::NEG_INFINITY, f32::max);
+
+        let vertices = self
+            .vertices
+            .iter()
+            .zip(&self.sources)
+            .map(|(&vertex, &source)| {
+                let value = match values.get(source as usize) {
+                    Some(value) if value.is_finite() => value,
+                    _ => return vertex,
+                };
+
+                // A constant field maps to the start of the colormap.
+                let normalized = if max > min {
+                    (value - min) / (max - min)
+                } else {
+                    0.
+                };
+
+                Vertex {
+                    color: colormap.color(normalized),
+                    ..vertex
+                }
+            })
+            .collect();
+
+        Self {
+            vertices,
+            indices: self.indices.clone(),
+            sources: self.sources.clone(),
+        }
+    }
+
     pub fn push_cross(
         &mut self,
         position: Point<3>,
@@ -76,29 +183,59 @@ impl From<&Mesh<fj_math::Point<3>>> for Vertices {
     fn from(mesh: &Mesh<fj_math::Point<3>>) -> Self {
         let mut m = Mesh::new();
 
-        for triangle in mesh.triangles() {
+        let sources: Vec<_> = mesh.indices().collect();
+
+        for (triangle, sources) in mesh.triangles().zip(sources.chunks(3)) {
             let [a, b, c] = triangle.inner.points();
 
             let normal = (b - a).cross(&(c - a)).normalize();
             let color = triangle.color;
 
-            m.push_vertex((a, normal, color));
-            m.push_vertex((b, normal, color));
-            m.push_vertex((c, normal, color));
+            m.push_vertex((a, normal, color, sources[0]));
+            m.push_vertex((b, normal, color, sources[1]));
+            m.push_vertex((c, normal, color, sources[2]));
         }
 
         let vertices = m
             .vertices()
-            .map(|(vertex, normal, color)| Vertex {
+            .map(|(vertex, normal, color, _)| Vertex {
                 position: vertex.into(),
                 normal: normal.into(),
-                color: color.0.map(|v| f32::from(v) / 255.0),
+                color: color.to_linear(),
             })
             .collect();
 
         let indices = m.indices().collect();
+        let sources = m.vertices().map(|(_, _, _, source)| source).collect();
 
-        Self { vertices, indices }
+        Self {
+            vertices,
+            indices,
+            sources,
+        }
+    }
+}
+
+impl Vertices {
+    /// Create markers for all approximation points, connected by lines
+    pub fn approximations(debug_info: &DebugInfo) -> Self {
+        let mut self_ = Self::empty();
+        let normal = [0.; 3];
+
+        for approximation in &debug_info.approximations {
+            for &point in &approximation.points {
+                self_.push_cross(point, normal, APPROX_POINT_COLOR);
+            }
+            for segment in approximation.points.windows(2) {
+                self_.push_line(
+                    [segment[0], segment[1]],
+                    normal,
+                    APPROX_SEGMENT_COLOR,
+                );
+            }
+        }
+
+        self_
     }
 }
 
@@ -132,6 +269,12 @@ impl From<&DebugInfo> for Vertices {
     }
 }
 
+/// The color of the markers for approximation points
+const APPROX_POINT_COLOR: [f32; 4] = [0., 0., 1., 1.];
+
+/// The color of the lines between approximation points
+const APPROX_SEGMENT_COLOR: [f32; 4] = [1., 1., 0., 1.];
+
 #[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
 #[repr(C)]
 pub struct Vertex {
@@ -139,3 +282,137 @@ pub struct Vertex {
     pub normal: [f32; 3],
     pub color: [f32; 4],
 }
+
+impl Vertex {
+    pub fn layout() -> wgpu::VertexBufferLayout<'static> {
+        wgpu::VertexBufferLayout {
+            array_stride: size_of::<Self>() as u64,
+            step_mode: wgpu::VertexStepMode::Vertex,
+            attributes: &ATTRIBUTES,
+        }
+    }
+}
+
+const ATTRIBUTES: [wgpu::VertexAttribute; 3] = wgpu::vertex_attr_array![
+    0 => Float32x3,
+    1 => Float32x3,
+    2 => Float32x4,
+];
+
+#[cfg(test)]
+mod tests {
+    use fj_interop::{
+        debug::{Approximation, DebugInfo},
+        mesh::{Color, Mesh},
+    };
+    use fj_math::{Point, Triangle};
+
+    use crate::graphics::colormap::Colormap;
+
+    use super::{Vertices, APPROX_POINT_COLOR};
+
+    #[test]
+    fn highlight() {
+        let mut mesh = Mesh::new();
+        let [a, b, c, d] =
+            [[0., 0., 0.], [1., 0., 0.], [1., 1., 0.], [0., 1., 0.]]
+                .map(Point::from);
+        mesh.push_triangle(Triangle::from([a, b, c]), Color::default());
+        mesh.push_triangle(Triangle::from([a, c, d]), Color::default());
+        let vertices = Vertices::from(&mesh);
+
+        let color = [1., 1., 0., 1.];
+
+        let highlight = vertices.highlight(&[1], color);
+        assert_eq!(highlight.indices(), &[0, 1, 2]);
+        assert!(highlight.vertices().iter().all(|v| v.color == color));
+        assert_eq!(
+            highlight
+                .vertices()
+                .iter()
+                .map(|vertex| vertex.position)
+                .collect::<Vec<_>>(),
+            [a, c, d].map(<[f32; 3]>::from),
+        );
+
+        // Out-of-range triangles are ignored.
+        let highlight = vertices.highlight(&[0, 1, 2], color);
+        assert_eq!(highlight.indices().len(), 6);
+        assert_eq!(highlight.vertices().len(), 6);
+    }
+
+    #[test]
+    fn wireframe() {
+        let mut mesh = Mesh::new();
+        let [a, b, c, d] =
+            [[0., 0., 0.], [1., 0., 0.], [1., 1., 0.], [0., 1., 0.]]
+                .map(Point::from);
+        mesh.push_triangle(Triangle::from([a, b, c]), Color::default());
+        mesh.push_triangle(Triangle::from([a, c, d]), Color::default());
+        let vertices = Vertices::from(&mesh);
+
+        let wireframe = vertices.wireframe();
+
+        // The diagonal is shared by both triangles, but only drawn once.
+        assert_eq!(wireframe.indices(), &[0, 1, 0, 2, 0, 3, 1, 2, 2, 3]);
+        assert_eq!(wireframe.vertices(), vertices.vertices());
+    }
+
+    #[test]
+    fn approximations() {
+        let mut debug_info = DebugInfo::new();
+        debug_info.approximations.push(Approximation {
+            points: [[0., 0., 0.], [1., 0., 0.], [1., 1., 0.], [0., 0., 0.]]
+                .map(Point::from)
+                .into(),
+        });
+        debug_info.approximations.push(Approximation {
+            points: [[0., 0., 1.], [1., 0., 1.]].map(Point::from).into(),
+        });
+
+        let vertices = Vertices::approximations(&debug_info);
+
+        // Each marker is a cross, made from two lines.
+        let marker_vertices = vertices
+            .vertices()
+            .iter()
+            .filter(|vertex| vertex.color == APPROX_POINT_COLOR)
+            .count();
+        assert_eq!(marker_vertices / 4, 6);
+    }
+
+    #[test]
+    fn scalar_field() {
+        let mut mesh = Mesh::new();
+        let [a, b, c, d] =
+            [[0., 0., 0.], [1., 0., 0.], [1., 1., 0.], [0., 1., 0.]]
+                .map(Point::from);
+        mesh.push_triangle(Triangle::from([a, b, c]), Color::default());
+        mesh.push_triangle(Triangle::from([a, c, d]), Color::default());
+        let vertices = Vertices::from(&mesh);
+
+        // A linear field along the x-axis.
+        let values: Vec<_> = mesh
+            .vertices()
+            .map(|vertex| vertex.x.into_f32() * 0.5 + 2.)
+            .collect();
+
+        let colormap = Colormap::Viridis;
+        let colored = vertices.with_scalar_field(&values, colormap);
+
+        for vertex in colored.vertices() {
+            let expected = if vertex.position[0] == 0. {
+                colormap.color(0.)
+            } else {
+                colormap.color(1.)
+            };
+            assert_eq!(vertex.color, expected);
+        }
+        assert_eq!(colored.indices(), vertices.indices());
+
+        let start = colormap.color(0.);
+        let end = colormap.color(1.);
+        assert_eq!(start, [0.267004, 0.004874, 0.329415, 1.]);
+        assert_eq!(end, [0.993248, 0.906157, 0.143936, 1.]);
+    }
+}
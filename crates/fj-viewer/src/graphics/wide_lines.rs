@@ -0,0 +1,180 @@
+use std::mem::size_of;
+
+use bytemuck::{Pod, Zeroable};
+use fj_interop::mesh::Index;
+
+use super::vertices::Vertices;
+
+/// The number of vertices that each line is expanded into
+pub const VERTICES_PER_LINE: usize = 4;
+
+/// The number of indices that each line is expanded into
+pub const INDICES_PER_LINE: usize = 6;
+
+/// Lines that are expanded into quads, to give them a width
+///
+/// Each line becomes a quad made from two triangles. The vertex shader moves
+/// the corners of the quad apart in screen space, so the line has the same
+/// width in pixels, regardless of its distance from the camera.
+#[derive(Debug)]
+pub struct WideLines {
+    vertices: Vec<WideLineVertex>,
+    indices: Vec<Index>,
+}
+
+impl WideLines {
+    pub fn vertices(&self) -> &[WideLineVertex] {
+        self.vertices.as_slice()
+    }
+
+    pub fn indices(&self) -> &[Index] {
+        self.indices.as_slice()
+    }
+}
+
+impl From<&Vertices> for WideLines {
+    /// Expects pairs of indices, each describing one line
+    fn from(lines: &Vertices) -> Self {
+        let mut vertices = Vec::new();
+        let mut indices = Vec::new();
+
+        for line in lines.indices().chunks_exact(2) {
+            let [a, b] =
+                [line[0], line[1]].map(|i| lines.vertices()[i as usize]);
+
+            let first = vertices.len() as Index;
+
+            for (end, [this, other]) in [(0., [a, b]), (1., [b, a])] {
+                for side in [-1., 1.] {
+                    vertices.push(WideLineVertex {
+                        position: this.position,
+                        other: other.position,
+                        color: this.color,
+                        corner: [end, side],
+                    });
+                }
+            }
+
+            indices.extend([0, 1, 2, 2, 1, 3].map(|i| first + i));
+        }
+
+        Self { vertices, indices }
+    }
+}
+
+/// A corner of the quad that a wide line is expanded into
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+#[repr(C)]
+pub struct WideLineVertex {
+    /// The end point of the line this corner belongs to
+    pub position: [f32; 3],
+
+    /// The other end point of the line
+    pub other: [f32; 3],
+
+    pub color: [f32; 4],
+
+    /// Which corner of the quad this is
+    ///
+    /// The first component is `0.` for the start of the line and `1.` for its
+    /// end. The second component is `-1.` or `1.`, depending on the side of
+    /// the line.
+    pub corner: [f32; 2],
+}
+
+impl WideLineVertex {
+    pub fn layout() -> wgpu::VertexBufferLayout<'static> {
+        wgpu::VertexBufferLayout {
+            array_stride: size_of::<Self>() as u64,
+            step_mode: wgpu::VertexStepMode::Vertex,
+            attributes: &ATTRIBUTES,
+        }
+    }
+}
+
+const ATTRIBUTES: [wgpu::VertexAttribute; 4] = wgpu::vertex_attr_array![
+    0 => Float32x3,
+    1 => Float32x3,
+    2 => Float32x4,
+    3 => Float32x2,
+];
+
+#[cfg(test)]
+mod tests {
+    use fj_interop::mesh::{Color, Mesh};
+    use fj_math::Point;
+
+    use crate::graphics::vertices::Vertices;
+
+    use super::{
+        WideLineVertex, WideLines, INDICES_PER_LINE, VERTICES_PER_LINE,
+    };
+
+    #[test]
+    fn layout_matches_shader() {
+        let module =
+            naga::front::wgsl::parse_str(include_str!("shader.wgsl")).unwrap();
+        let entry_point = module
+            .entry_points
+            .iter()
+            .find(|entry_point| entry_point.name == "vertex_wide_line")
+            .unwrap();
+
+        let inputs: Vec<_> = entry_point
+            .function
+            .arguments
+            .iter()
+            .map(|argument| {
+                let location = match argument.binding {
+                    Some(naga::Binding::Location { location, .. }) => location,
+                    _ => panic!("Expected vertex input"),
+                };
+                let components = match module.types[argument.ty].inner {
+                    naga::TypeInner::Vector {
+                        size,
+                        kind: naga::ScalarKind::Float,
+                        width: 4,
+                    } => size as u64,
+                    _ => panic!("Expected vector of `f32`"),
+                };
+
+                (location, components)
+            })
+            .collect();
+
+        let attributes: Vec<_> = WideLineVertex::layout()
+            .attributes
+            .iter()
+            .map(|attribute| {
+                (attribute.shader_location, attribute.format.size() / 4)
+            })
+            .collect();
+
+        assert_eq!(attributes, inputs);
+    }
+
+    #[test]
+    fn quad_per_line() {
+        let mut mesh = Mesh::new();
+        mesh.push_triangle(
+            [[0., 0., 0.], [1., 0., 0.], [0., 1., 0.]].map(Point::from),
+            Color::default(),
+        );
+        let lines = Vertices::from(&mesh).wireframe();
+
+        let wide = WideLines::from(&lines);
+
+        assert_eq!(wide.vertices().len(), 3 * VERTICES_PER_LINE);
+        assert_eq!(wide.indices().len(), 3 * INDICES_PER_LINE);
+
+        // Each quad covers both ends and both sides of its line.
+        for quad in wide.vertices().chunks(VERTICES_PER_LINE) {
+            let corners: Vec<_> =
+                quad.iter().map(|vertex| vertex.corner).collect();
+            assert_eq!(corners, [[0., -1.], [0., 1.], [1., -1.], [1., 1.]]);
+
+            assert_eq!(quad[0].position, quad[2].other);
+            assert_eq!(quad[0].other, quad[2].position);
+        }
+    }
+}
@@ -0,0 +1,263 @@
+use crate::{
+    camera::{Camera, FocusPoint},
+    screen::NormalizedPosition,
+};
+
+use super::{
+    movement::Movement,
+    rotation::{OrbitMode, Rotation},
+    zoom::Zoom,
+};
+
+/// Moves the camera in response to user input
+///
+/// This is independent of any windowing library. It is up to the caller to
+/// convert their input events into calls to the methods of this type. See
+/// [`Handler`] for a controller that is driven by [`Event`]s.
+///
+/// [`Handler`]: super::Handler
+/// [`Event`]: super::Event
+#[derive(Debug)]
+pub struct CameraController {
+    movement: Movement,
+    rotation: Rotation,
+    zoom: Zoom,
+    min_distance: f64,
+    orbit_mode: OrbitMode,
+}
+
+impl CameraController {
+    /// The default value for [`CameraController::with_min_distance`]
+    pub const DEFAULT_MIN_DISTANCE: f64 = 0.1;
+
+    /// How far the camera can look up or down in [`OrbitMode::Constrained`]
+    ///
+    /// This is the angle between the horizontal plane and the direction from
+    /// the focus point to the camera, in radians.
+    pub const MAX_PITCH: f64 = Rotation::MAX_PITCH;
+
+    /// Set how close zooming can get the camera to the focus point
+    pub fn with_min_distance(mut self, min_distance: f64) -> Self {
+        self.min_distance = min_distance;
+        self
+    }
+
+    /// Set how rotating the camera treats the model's orientation
+    pub fn with_orbit_mode(mut self, orbit_mode: OrbitMode) -> Self {
+        self.orbit_mode = orbit_mode;
+        self
+    }
+
+    /// Access the current orbit mode
+    pub fn orbit_mode(&self) -> OrbitMode {
+        self.orbit_mode
+    }
+
+    /// Switch to another orbit mode
+    ///
+    /// Switching to [`OrbitMode::Constrained`] removes any roll with the next
+    /// rotation.
+    pub fn set_orbit_mode(&mut self, orbit_mode: OrbitMode) {
+        self.orbit_mode = orbit_mode;
+    }
+
+    /// Rotate the model around the focus point
+    ///
+    /// `angle_x` and `angle_y` are the angles (in radians) around the screen's
+    /// x and y axes, respectively. In [`OrbitMode::Constrained`], they are the
+    /// changes in pitch and yaw instead. See [`CameraController::MAX_PITCH`].
+    pub fn on_rotate(
+        &mut self,
+        angle_x: f64,
+        angle_y: f64,
+        focus_point: FocusPoint,
+        camera: &mut Camera,
+    ) {
+        self.rotation.apply(
+            angle_x,
+            angle_y,
+            self.orbit_mode,
+            focus_point,
+            camera,
+        )
+    }
+
+    /// Move the model, so the focus point follows the cursor
+    pub fn on_pan(
+        &mut self,
+        previous: NormalizedPosition,
+        current: NormalizedPosition,
+        focus_point: FocusPoint,
+        camera: &mut Camera,
+    ) {
+        self.movement.apply(previous, current, focus_point, camera)
+    }
+
+    /// Move the view forwards and backwards
+    ///
+    /// The distance moved is relative to the distance to the focus point. The
+    /// camera never gets closer to the focus point than the minimum distance.
+    pub fn on_zoom(
+        &mut self,
+        zoom_delta: f64,
+        focus_point: FocusPoint,
+        camera: &mut Camera,
+    ) {
+        self.zoom
+            .apply(zoom_delta, self.min_distance, focus_point, camera)
+    }
+}
+
+impl Default for CameraController {
+    fn default() -> Self {
+        Self {
+            movement: Movement,
+            rotation: Rotation,
+            zoom: Zoom,
+            min_distance: Self::DEFAULT_MIN_DISTANCE,
+            orbit_mode: OrbitMode::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::{FRAC_PI_4, PI};
+
+    use fj_math::{Aabb, Point, Transform, Vector};
+
+    use crate::{
+        camera::{Camera, FocusPoint, StandardView},
+        input::OrbitMode,
+    };
+
+    use super::CameraController;
+
+    fn camera() -> Camera {
+        let mut camera = Camera::new(&Aabb::<3>::from_points([
+            Point::from([-1., -1., -1.]),
+            Point::from([1., 1., 1.]),
+        ]));
+        camera.rotation = Transform::identity();
+        camera.translation =
+            Transform::translation(Vector::from([0., 0., -10.]));
+        camera
+    }
+
+    fn distance(camera: &Camera, focus_point: FocusPoint) -> f64 {
+        (focus_point.0 - camera.position()).magnitude().into_f64()
+    }
+
+    #[test]
+    fn rotate_horizontally() {
+        let mut camera = camera();
+        let focus_point = FocusPoint(Point::origin());
+
+        CameraController::default().on_rotate(
+            0.,
+            FRAC_PI_4,
+            focus_point,
+            &mut camera,
+        );
+
+        // Rotating around the focus point doesn't change its distance.
+        assert!((distance(&camera, focus_point) - 10.).abs() < 1e-12);
+
+        let expected = Transform::rotation(Vector::from([0., FRAC_PI_4, 0.]));
+        for (a, b) in camera.rotation.data().iter().zip(expected.data()) {
+            assert!((a - b).abs() < 1e-12);
+        }
+    }
+
+    /// The angle between the horizontal plane and the reversed view direction
+    fn pitch(camera: &Camera) -> f64 {
+        let view_direction = camera
+            .rotation
+            .inverse()
+            .transform_vector(&Vector::from([0., 0., -1.]));
+        (-view_direction.z.into_f64()).asin()
+    }
+
+    /// The screen's right and up vectors in model space
+    fn screen_axes(camera: &Camera) -> [Vector<3>; 2] {
+        let model_rotation = camera.rotation.inverse();
+        [[1., 0., 0.], [0., 1., 0.]]
+            .map(|axis| model_rotation.transform_vector(&Vector::from(axis)))
+    }
+
+    #[test]
+    fn constrained_pitch_clamps_at_limits() {
+        let mut camera = camera();
+        camera.rotation = StandardView::Front.rotation();
+        let focus_point = FocusPoint(Point::origin());
+
+        let mut controller =
+            CameraController::default().with_orbit_mode(OrbitMode::Constrained);
+
+        controller.on_rotate(PI, 0., focus_point, &mut camera);
+        assert!((pitch(&camera) - CameraController::MAX_PITCH).abs() < 1e-9);
+
+        // Further rotation in the same direction has no effect.
+        controller.on_rotate(0.5, 0., focus_point, &mut camera);
+        assert!((pitch(&camera) - CameraController::MAX_PITCH).abs() < 1e-9);
+
+        controller.on_rotate(-2. * PI, 0., focus_point, &mut camera);
+        assert!((pitch(&camera) + CameraController::MAX_PITCH).abs() < 1e-9);
+
+        assert!((distance(&camera, focus_point) - 10.).abs() < 1e-9);
+    }
+
+    #[test]
+    fn constrained_yaw_then_pitch_keeps_world_up() {
+        let focus_point = FocusPoint(Point::from([1., 2., 3.]));
+
+        let mut camera = camera();
+        camera.rotation = StandardView::Front.rotation();
+
+        let mut controller =
+            CameraController::default().with_orbit_mode(OrbitMode::Constrained);
+        controller.on_rotate(0., 0.7, focus_point, &mut camera);
+        controller.on_rotate(0.3, 0., focus_point, &mut camera);
+        controller.on_rotate(0., -1.9, focus_point, &mut camera);
+        controller.on_rotate(-0.8, 0., focus_point, &mut camera);
+
+        // No roll: The screen's right vector stays horizontal, and the model's
+        // z-axis points up on the screen.
+        let [right, up] = screen_axes(&camera);
+        assert!(right.z.into_f64().abs() < 1e-9);
+        assert!(up.z.into_f64() > 0.);
+        assert!((pitch(&camera) - (0.3 - 0.8)).abs() < 1e-9);
+
+        // Whereas the same rotations in free mode roll the camera.
+        let mut camera = self::camera();
+        camera.rotation = StandardView::Front.rotation();
+
+        let mut controller = CameraController::default();
+        controller.on_rotate(0., 0.7, focus_point, &mut camera);
+        controller.on_rotate(0.3, 0., focus_point, &mut camera);
+        controller.on_rotate(0., -1.9, focus_point, &mut camera);
+
+        let [right, _] = screen_axes(&camera);
+        assert!(right.z.into_f64().abs() > 1e-3);
+    }
+
+    #[test]
+    fn zoom_clamps_to_min_distance() {
+        let mut camera = camera();
+        let focus_point = FocusPoint(Point::origin());
+
+        let mut controller = CameraController::default().with_min_distance(1.);
+
+        // Moving forward by 95% of the distance would end up too close.
+        controller.on_zoom(-0.95, focus_point, &mut camera);
+        assert!((distance(&camera, focus_point) - 1.).abs() < 1e-12);
+
+        // Once at the minimum distance, zooming in has no effect.
+        controller.on_zoom(-0.5, focus_point, &mut camera);
+        assert!((distance(&camera, focus_point) - 1.).abs() < 1e-12);
+
+        // Zooming out is still possible.
+        controller.on_zoom(1., focus_point, &mut camera);
+        assert!((distance(&camera, focus_point) - 2.).abs() < 1e-12);
+    }
+}
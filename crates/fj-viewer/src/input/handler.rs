@@ -1,16 +1,25 @@
-use super::{movement::Movement, rotation::Rotation, zoom::Zoom, Event};
+use super::{CameraController, Event};
 use crate::camera::{Camera, FocusPoint};
 
 /// Input handling abstraction
 ///
 /// Takes user input and applies them to application state.
+#[derive(Debug, Default)]
 pub struct Handler {
-    movement: Movement,
-    rotation: Rotation,
-    zoom: Zoom,
+    controller: CameraController,
 }
 
 impl Handler {
+    /// Create a handler that moves the camera using the given controller
+    pub fn new(controller: CameraController) -> Self {
+        Self { controller }
+    }
+
+    /// Access the controller, to change its settings
+    pub fn controller_mut(&mut self) -> &mut CameraController {
+        &mut self.controller
+    }
+
     /// Handle an input event
     pub fn handle_event(
         &mut self,
@@ -20,24 +29,16 @@ impl Handler {
     ) {
         match event {
             Event::Translate { previous, current } => {
-                self.movement.apply(previous, current, focus_point, camera)
+                self.controller
+                    .on_pan(previous, current, focus_point, camera)
             }
             Event::Rotation { angle_x, angle_y } => {
-                self.rotation.apply(angle_x, angle_y, focus_point, camera)
+                self.controller
+                    .on_rotate(angle_x, angle_y, focus_point, camera)
             }
             Event::Zoom(zoom_delta) => {
-                self.zoom.apply(zoom_delta, focus_point, camera)
+                self.controller.on_zoom(zoom_delta, focus_point, camera)
             }
         }
     }
 }
-
-impl Default for Handler {
-    fn default() -> Self {
-        Self {
-            movement: Movement,
-            rotation: Rotation,
-            zoom: Zoom,
-        }
-    }
-}
@@ -1,9 +1,13 @@
 //! User input parsing and propagation.
 
+mod controller;
 mod event;
 mod handler;
 mod movement;
 mod rotation;
 mod zoom;
 
-pub use self::{event::Event, handler::Handler};
+pub use self::{
+    controller::CameraController, event::Event, handler::Handler,
+    rotation::OrbitMode,
+};
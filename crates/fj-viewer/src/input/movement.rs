@@ -5,6 +5,7 @@ use crate::{
     screen::NormalizedPosition,
 };
 
+#[derive(Debug)]
 pub struct Movement;
 
 impl Movement {
@@ -1,26 +1,59 @@
+use std::f64::consts::FRAC_PI_2;
+
 use fj_math::{Transform, Vector};
 
 use crate::camera::{Camera, FocusPoint};
 
+/// How rotating the camera treats the model's orientation
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum OrbitMode {
+    /// Rotate freely around the screen's axes, like a trackball
+    ///
+    /// Successive rotations can roll the model, so its z-axis no longer points
+    /// up on the screen.
+    Free,
+
+    /// Keep the model's z-axis pointing up on the screen
+    ///
+    /// Horizontal rotation turns the model around its z-axis, vertical rotation
+    /// tilts it towards or away from the camera. The camera never rolls, and
+    /// never passes over the poles, where the model would flip upside down.
+    Constrained,
+}
+
+impl Default for OrbitMode {
+    fn default() -> Self {
+        Self::Free
+    }
+}
+
+#[derive(Debug)]
 pub struct Rotation;
 
 impl Rotation {
+    /// How far the camera can look up or down in [`OrbitMode::Constrained`]
+    ///
+    /// This is the angle between the horizontal plane and the direction from
+    /// the focus point to the camera. It stays just short of a right angle, as
+    /// yaw and roll can't be told apart straight above or below the model.
+    pub const MAX_PITCH: f64 = FRAC_PI_2 - 0.01;
+
     pub fn apply(
         &self,
         angle_x: f64,
         angle_y: f64,
+        mode: OrbitMode,
         focus_point: FocusPoint,
         camera: &mut Camera,
     ) {
         let rotate_around = Transform::translation(focus_point.0.coords);
 
-        // the model rotates not the camera, so invert the transform
-        let camera_rotation = camera.rotation.inverse();
-        let right_vector = right_vector(&camera_rotation);
-        let up_vector = up_vector(&camera_rotation);
-
-        let rotation = Transform::rotation(right_vector * angle_x)
-            * Transform::rotation(up_vector * angle_y);
+        let rotation = match mode {
+            OrbitMode::Free => free_rotation(angle_x, angle_y, camera),
+            OrbitMode::Constrained => {
+                constrained_rotation(angle_x, angle_y, camera)
+            }
+        };
 
         let transform = camera.camera_to_model()
             * rotate_around
@@ -32,6 +65,46 @@ impl Rotation {
     }
 }
 
+fn free_rotation(angle_x: f64, angle_y: f64, camera: &Camera) -> Transform {
+    // the model rotates not the camera, so invert the transform
+    let camera_rotation = camera.rotation.inverse();
+    let right_vector = right_vector(&camera_rotation);
+    let up_vector = up_vector(&camera_rotation);
+
+    Transform::rotation(right_vector * angle_x)
+        * Transform::rotation(up_vector * angle_y)
+}
+
+fn constrained_rotation(
+    angle_x: f64,
+    angle_y: f64,
+    camera: &Camera,
+) -> Transform {
+    // A camera rotation without roll is a rotation around the model's z-axis
+    // (yaw), followed by a rotation around the screen's x-axis (tilt). A tilt
+    // of zero is the top view, minus a right angle the front view.
+    let d = camera.rotation.data();
+
+    // The model's z-axis in camera space is `[0, -sin(tilt), cos(tilt)]`. If
+    // the camera is rolled, the roll is dropped.
+    let tilt = (-d[9]).atan2(d[10]);
+
+    // The model's x-axis in camera space, with the tilt undone, is
+    // `[cos(yaw), sin(yaw), 0]`.
+    let yaw = (d[1] * tilt.cos() + d[2] * tilt.sin()).atan2(d[0]);
+
+    let pitch = (tilt + FRAC_PI_2 + angle_x)
+        .clamp(-Rotation::MAX_PITCH, Rotation::MAX_PITCH);
+    let tilt = pitch - FRAC_PI_2;
+    let yaw = yaw + angle_y;
+
+    let target = Transform::rotation(Vector::from([tilt, 0., 0.]))
+        * Transform::rotation(Vector::from([0., 0., yaw]));
+
+    // The rotation is applied in model space, after the current one.
+    camera.rotation.inverse() * target
+}
+
 fn up_vector(rotation: &Transform) -> Vector<3> {
     let d = rotation.data();
     Vector::from_components_f64([d[4], d[5], d[6]])
@@ -2,17 +2,49 @@ use fj_math::{Transform, Vector};
 
 use crate::camera::{Camera, FocusPoint};
 
+#[derive(Debug)]
 pub struct Zoom;
 
 impl Zoom {
     pub fn apply(
         &mut self,
         zoom_delta: f64,
+        min_distance: f64,
         focus_point: FocusPoint,
         camera: &mut Camera,
     ) {
-        let distance = (focus_point.0 - camera.position()).magnitude();
-        let displacement = zoom_delta * distance.into_f64();
+        // The focus point, relative to the camera. The camera looks along the
+        // negative z-axis, and moving it along that axis is what zooming does.
+        let focus = camera.camera_to_model().transform_point(&focus_point.0);
+        let [x, y, z] = [focus.x, focus.y, focus.z].map(|c| c.into_f64());
+
+        let distance = (x * x + y * y + z * z).sqrt();
+        let mut displacement = zoom_delta * distance;
+
+        // Moving the camera would end up closer than the minimum distance, if
+        // the focus point ends up within `radius` of the xy-plane.
+        let radius_squared = min_distance * min_distance - x * x - y * y;
+        if radius_squared > 0. {
+            let radius = radius_squared.sqrt();
+            let new_z = z - displacement;
+
+            if new_z.abs() < radius {
+                displacement = if distance >= min_distance {
+                    // Stop at the edge of the forbidden range.
+                    if z - radius >= 0. {
+                        z - radius
+                    } else {
+                        z + radius
+                    }
+                } else if new_z.abs() < z.abs() {
+                    // We're already too close. Don't get any closer.
+                    0.
+                } else {
+                    displacement
+                };
+            }
+        }
+
         camera.translation = camera.translation
             * Transform::translation(Vector::from([0.0, 0.0, -displacement]));
     }
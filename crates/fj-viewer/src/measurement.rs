@@ -0,0 +1,136 @@
+//! Measuring distances on the model
+
+use std::fmt;
+
+use fj_math::{Point, Scalar, Vector};
+
+/// The distance between two points on the model
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Measurement {
+    /// The point that was picked first
+    pub from: Point<3>,
+
+    /// The point that was picked second
+    pub to: Point<3>,
+}
+
+impl Measurement {
+    /// The straight-line distance between the points
+    pub fn distance(&self) -> Scalar {
+        self.deltas().magnitude()
+    }
+
+    /// The distance between the points along each axis
+    ///
+    /// The deltas are signed. They point from `from` to `to`.
+    pub fn deltas(&self) -> Vector<3> {
+        self.to - self.from
+    }
+}
+
+impl fmt::Display for Measurement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let [dx, dy, dz] = self.deltas().components;
+        write!(
+            f,
+            "{:.3}\ndx {:.3} dy {:.3} dz {:.3}",
+            self.distance(),
+            dx,
+            dy,
+            dz
+        )
+    }
+}
+
+/// Tracks the points picked for a measurement
+///
+/// The first call to [`MeasureTool::pick`] picks the start point. Until the
+/// second call picks the end point, the measurement follows the points passed
+/// to [`MeasureTool::hover`]. Picking again after that starts a new
+/// measurement.
+#[derive(Debug, Default)]
+pub struct MeasureTool {
+    from: Option<Point<3>>,
+    to: Option<Point<3>>,
+    done: bool,
+}
+
+impl MeasureTool {
+    /// Pick a point on the model
+    pub fn pick(&mut self, point: Point<3>) {
+        match (self.from, self.done) {
+            (Some(_), false) => {
+                self.to = Some(point);
+                self.done = true;
+            }
+            _ => {
+                self.from = Some(point);
+                self.to = None;
+                self.done = false;
+            }
+        }
+    }
+
+    /// Update the point that is under the cursor
+    ///
+    /// Pass `None`, if the cursor isn't over the model.
+    pub fn hover(&mut self, point: Option<Point<3>>) {
+        if self.from.is_some() && !self.done {
+            self.to = point;
+        }
+    }
+
+    /// Discard all picked points
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    /// The current measurement, if there is one
+    pub fn measurement(&self) -> Option<Measurement> {
+        Some(Measurement {
+            from: self.from?,
+            to: self.to?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Point, Scalar, Vector};
+
+    use super::{MeasureTool, Measurement};
+
+    #[test]
+    fn distance_and_deltas() {
+        let measurement = Measurement {
+            from: Point::from([1., 2., 3.]),
+            to: Point::from([4., -2., 3.]),
+        };
+
+        assert_eq!(measurement.distance(), Scalar::from(5.));
+        assert_eq!(measurement.deltas(), Vector::from([3., -4., 0.]));
+    }
+
+    #[test]
+    fn measure_tool() {
+        let [a, b, c] =
+            [[0., 0., 0.], [1., 0., 0.], [2., 0., 0.]].map(Point::from);
+        let mut tool = MeasureTool::default();
+
+        tool.hover(Some(a));
+        assert_eq!(tool.measurement(), None);
+
+        // The measurement follows the cursor, until the second point is picked.
+        tool.pick(a);
+        tool.hover(Some(b));
+        assert_eq!(tool.measurement().map(|m| m.to), Some(b));
+
+        tool.pick(c);
+        tool.hover(Some(b));
+        assert_eq!(tool.measurement().map(|m| m.to), Some(c));
+
+        // Picking again starts over.
+        tool.pick(b);
+        assert_eq!(tool.measurement(), None);
+    }
+}
@@ -0,0 +1,293 @@
+//! Editing the arguments of a model
+
+use std::collections::HashMap;
+
+use fj::models::{ArgumentKind, ArgumentMetadata};
+
+/// A panel that lets the user edit the arguments of a model
+///
+/// Shows a widget for each argument that the model's metadata describes. Models
+/// that don't describe any arguments don't get a panel.
+///
+/// While the user is still editing a value, by dragging a slider or typing,
+/// the value is kept separately. Only once the user is done with it, is it
+/// committed to the arguments, and the model re-run.
+#[derive(Debug, Default)]
+pub struct ParameterPanel {
+    parameters: Vec<ArgumentMetadata>,
+    arguments: HashMap<String, String>,
+    edits: HashMap<String, String>,
+    changed: bool,
+}
+
+impl ParameterPanel {
+    /// Create an empty panel
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the metadata of the model's arguments, and the arguments it was run
+    /// with
+    ///
+    /// Parameters without an argument show their default value.
+    pub fn set_parameters(
+        &mut self,
+        parameters: Vec<ArgumentMetadata>,
+        arguments: &HashMap<String, String>,
+    ) {
+        self.parameters = parameters;
+        self.arguments = arguments.clone();
+        self.edits.clear();
+        self.changed = false;
+    }
+
+    /// Take the arguments, if the user has edited any since the last call
+    ///
+    /// Returns all arguments, not just the edited ones, so they can directly
+    /// replace the ones the model was run with.
+    pub fn take_changes(&mut self) -> Option<HashMap<String, String>> {
+        if !self.changed {
+            return None;
+        }
+
+        self.changed = false;
+        Some(self.arguments.clone())
+    }
+
+    /// The value the model uses for a parameter
+    pub fn value<'a>(
+        &'a self,
+        parameter: &'a ArgumentMetadata,
+    ) -> Option<&'a str> {
+        self.arguments
+            .get(&parameter.name)
+            .or(parameter.default_value.as_ref())
+            .map(String::as_str)
+    }
+
+    /// The value the panel shows for a parameter
+    ///
+    /// This is the value the user is editing, if there is one. Otherwise, it's
+    /// the value the model uses.
+    pub fn edited_value<'a>(
+        &'a self,
+        parameter: &'a ArgumentMetadata,
+    ) -> Option<&'a str> {
+        self.edits
+            .get(&parameter.name)
+            .map(String::as_str)
+            .or_else(|| self.value(parameter))
+    }
+
+    /// Set the value of a parameter, as if edited by the user
+    pub fn set_value(&mut self, name: &str, value: impl ToString) {
+        let value = value.to_string();
+        self.edits.remove(name);
+
+        if self.arguments.get(name) != Some(&value) {
+            self.arguments.insert(name.to_owned(), value);
+            self.changed = true;
+        }
+    }
+
+    /// Update the value of a parameter that the user is still editing
+    ///
+    /// The panel shows the new value, but the model keeps using the previous
+    /// one, until the edit is committed.
+    pub fn edit(&mut self, name: &str, value: impl ToString) {
+        self.edits.insert(name.to_owned(), value.to_string());
+    }
+
+    /// Commit the edit of a parameter, if there is one
+    pub fn commit(&mut self, name: &str) {
+        if let Some(value) = self.edits.remove(name) {
+            self.set_value(name, value);
+        }
+    }
+
+    /// Whether there are parameters to show
+    pub fn is_empty(&self) -> bool {
+        self.parameters.is_empty()
+    }
+
+    pub(crate) fn ui(&mut self, ui: &mut egui::Ui) {
+        for parameter in self.parameters.clone() {
+            let value =
+                self.edited_value(&parameter).unwrap_or_default().to_owned();
+
+            // Re-running the model on every frame of a drag would be wasteful.
+            // Only commit the new value, once the user is done editing it.
+            let is_done = |response: &egui::Response| {
+                response.drag_released()
+                    || response.lost_focus()
+                    || (response.changed() && !response.dragged())
+            };
+
+            let response = match parameter.kind {
+                ArgumentKind::Integer => {
+                    let mut number = value.parse::<i64>().unwrap_or_default();
+                    let response = match parameter.range {
+                        Some(range) => ui.add(
+                            egui::Slider::new(
+                                &mut number,
+                                range.min as i64..=range.max as i64,
+                            )
+                            .text(&parameter.name),
+                        ),
+                        None => {
+                            ui.horizontal(|ui| {
+                                let response =
+                                    ui.add(egui::DragValue::new(&mut number));
+                                ui.label(&parameter.name);
+                                response
+                            })
+                            .inner
+                        }
+                    };
+                    if response.changed() {
+                        self.edit(&parameter.name, number);
+                    }
+                    if is_done(&response) {
+                        self.commit(&parameter.name);
+                    }
+                    response
+                }
+                ArgumentKind::Float => {
+                    let mut number = value.parse::<f64>().unwrap_or_default();
+                    let response = match parameter.range {
+                        Some(range) => ui.add(
+                            egui::Slider::new(
+                                &mut number,
+                                range.min..=range.max,
+                            )
+                            .text(&parameter.name),
+                        ),
+                        None => {
+                            ui.horizontal(|ui| {
+                                let response = ui.add(
+                                    egui::DragValue::new(&mut number)
+                                        .speed(0.1),
+                                );
+                                ui.label(&parameter.name);
+                                response
+                            })
+                            .inner
+                        }
+                    };
+                    if response.changed() {
+                        self.edit(&parameter.name, number);
+                    }
+                    if is_done(&response) {
+                        self.commit(&parameter.name);
+                    }
+                    response
+                }
+                ArgumentKind::Boolean => {
+                    let mut flag = value.parse::<bool>().unwrap_or_default();
+                    let response = ui.checkbox(&mut flag, &parameter.name);
+                    if response.changed() {
+                        self.set_value(&parameter.name, flag);
+                    }
+                    response
+                }
+                ArgumentKind::String => {
+                    let mut text = value;
+                    let response = ui
+                        .horizontal(|ui| {
+                            let response = ui.text_edit_singleline(&mut text);
+                            ui.label(&parameter.name);
+                            response
+                        })
+                        .inner;
+                    if response.changed() {
+                        self.edit(&parameter.name, text);
+                    }
+                    if response.lost_focus() {
+                        self.commit(&parameter.name);
+                    }
+                    response
+                }
+            };
+
+            if let Some(description) = &parameter.description {
+                response.on_hover_text(description);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use fj::models::{ArgumentKind, ArgumentMetadata};
+
+    use super::ParameterPanel;
+
+    #[test]
+    fn edited_arguments_replace_previous_ones() {
+        let mut panel = ParameterPanel::new();
+
+        let teeth = ArgumentMetadata::new("teeth")
+            .with_kind(ArgumentKind::Integer)
+            .with_default_value("12")
+            .with_range(3., 200.);
+        let width =
+            ArgumentMetadata::new("width").with_kind(ArgumentKind::Float);
+
+        let arguments = HashMap::from([("width".to_owned(), "2.5".to_owned())]);
+        panel.set_parameters(vec![teeth.clone(), width.clone()], &arguments);
+
+        assert_eq!(panel.value(&teeth), Some("12"));
+        assert_eq!(panel.value(&width), Some("2.5"));
+        assert_eq!(panel.take_changes(), None);
+
+        // Setting a value that doesn't change anything isn't an edit.
+        panel.set_value("width", "2.5");
+        assert_eq!(panel.take_changes(), None);
+
+        panel.set_value("teeth", 24);
+        assert_eq!(
+            panel.take_changes(),
+            Some(HashMap::from([
+                ("teeth".to_owned(), "24".to_owned()),
+                ("width".to_owned(), "2.5".to_owned()),
+            ]))
+        );
+        assert_eq!(panel.take_changes(), None);
+    }
+
+    #[test]
+    fn edits_are_kept_until_committed() {
+        let mut panel = ParameterPanel::new();
+
+        let name = ArgumentMetadata::new("name")
+            .with_kind(ArgumentKind::String)
+            .with_default_value("gear");
+        panel.set_parameters(vec![name.clone()], &HashMap::new());
+
+        // While the user is typing, the panel shows what they typed, but the
+        // model keeps its previous argument.
+        panel.edit("name", "sp");
+        panel.edit("name", "spur");
+        assert_eq!(panel.edited_value(&name), Some("spur"));
+        assert_eq!(panel.value(&name), Some("gear"));
+        assert_eq!(panel.take_changes(), None);
+
+        panel.commit("name");
+        assert_eq!(panel.edited_value(&name), Some("spur"));
+        assert_eq!(
+            panel.take_changes(),
+            Some(HashMap::from([("name".to_owned(), "spur".to_owned())]))
+        );
+
+        // Committing without an edit doesn't change anything.
+        panel.commit("name");
+        assert_eq!(panel.take_changes(), None);
+
+        // New parameters discard edits that weren't committed.
+        panel.edit("name", "helical");
+        panel.set_parameters(vec![name.clone()], &HashMap::new());
+        assert_eq!(panel.edited_value(&name), Some("gear"));
+    }
+}
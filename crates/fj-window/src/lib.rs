@@ -15,3 +15,5 @@
 
 pub mod run;
 pub mod window;
+
+mod worker;
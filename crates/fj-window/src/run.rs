@@ -5,17 +5,19 @@
 
 use std::error;
 
-use fj_host::Watcher;
-use fj_interop::status_report::StatusReport;
-use fj_operations::shape_processor::ShapeProcessor;
+use fj_host::{Parameters, Watcher};
+use fj_interop::{mesh::Mesh, status_report::StatusReport};
+use fj_kernel::algorithms::Tolerance;
+use fj_math::{Point, Scalar};
+use fj_operations::{shape_processor::ShapeProcessor, Shape as _};
 use fj_viewer::{
-    camera::Camera,
-    graphics::{self, DrawConfig, Renderer},
-    input,
+    camera::{tolerance_for_screen_error, Camera, StandardView},
+    graphics::{self, DrawConfig, DrawError, Renderer},
+    input::{self, OrbitMode},
+    measurement::MeasureTool,
     screen::{NormalizedPosition, Screen as _, Size},
 };
-use futures::executor::block_on;
-use tracing::{trace, warn};
+use tracing::{error, trace, warn};
 use winit::{
     dpi::PhysicalPosition,
     event::{
@@ -25,13 +27,26 @@ use winit::{
     event_loop::{ControlFlow, EventLoop},
 };
 
-use crate::window::{self, Window};
+use crate::{
+    window::{self, Window},
+    worker::ShapeWorker,
+};
 
 /// Initializes a model viewer for a given model and enters its process loop.
+///
+/// If a `reference` mesh is provided, it is displayed next to the model.
+///
+/// If `screen_error` is provided, the tolerance of `shape_processor` is derived
+/// from it, once the first model is loaded. It is the maximum deviation, in
+/// pixels, between the model and its approximation, as seen from the initial
+/// camera. See [`tolerance_for_screen_error`]. Switching the approximation
+/// quality goes back to the presets.
 pub fn run(
-    watcher: Watcher,
-    shape_processor: ShapeProcessor,
+    mut watcher: Watcher,
+    mut shape_processor: ShapeProcessor,
     mut status: StatusReport,
+    reference: Option<Mesh<Point<3>>>,
+    mut screen_error: Option<f64>,
 ) -> Result<(), Error> {
     let event_loop = EventLoop::new();
     let window = Window::new(&event_loop)?;
@@ -41,24 +56,114 @@ pub fn run(
     let mut focus_point = None;
 
     let mut input_handler = input::Handler::default();
-    let mut renderer = block_on(Renderer::new(&window))?;
+    let mut renderer = Renderer::new_blocking(&window)?;
+    renderer.set_reference(reference.as_ref());
 
     let mut draw_config = DrawConfig::default();
 
+    // Only available while measuring.
+    let mut measure_tool: Option<MeasureTool> = None;
+
+    // Processing a complex model can take a while. Until a new version is
+    // ready, the last one stays on screen.
+    let mut worker = ShapeWorker::spawn();
+    let mut model_shape = None;
+
     let mut shape = None;
     let mut camera = None;
 
+    let mut tolerance_from_screen = false;
+
     event_loop.run(move |event, _, control_flow| {
         trace!("Handling event: {:?}", event);
 
+        let mut process_shape = false;
+
+        if let Some(arguments) = renderer.parameters.take_changes() {
+            watcher.set_parameters(Parameters(arguments));
+        }
         if let Some(new_shape) = watcher.receive(&mut status) {
-            match shape_processor.process(&new_shape) {
-                Ok(new_shape) => {
+            renderer.parameters.set_parameters(
+                watcher.argument_metadata().to_vec(),
+                watcher.parameters(),
+            );
+
+            if let Some(pixels) = screen_error.take() {
+                // Derive the tolerance from the camera that the model is going
+                // to be viewed from initially.
+                let aabb = new_shape.bounding_volume();
+                let camera = Camera::new(&aabb);
+                let distance =
+                    Point::distance(&camera.position(), &aabb.center());
+
+                let tolerance = tolerance_for_screen_error(
+                    &aabb,
+                    distance.into_f64(),
+                    pixels,
+                );
+                match Tolerance::from_scalar(Scalar::from_f64(tolerance)) {
+                    Ok(tolerance) => {
+                        shape_processor.tolerance = Some(tolerance);
+                        tolerance_from_screen = true;
+                    }
+                    Err(err) => {
+                        warn!("Can't derive tolerance from screen: {err}");
+                    }
+                }
+            }
+
+            model_shape = Some(new_shape);
+            process_shape = true;
+        }
+        if let Event::WindowEvent {
+            event:
+                WindowEvent::KeyboardInput {
+                    input:
+                        KeyboardInput {
+                            state: ElementState::Pressed,
+                            virtual_keycode: Some(VirtualKeyCode::Q),
+                            ..
+                        },
+                    ..
+                },
+            ..
+        } = event
+        {
+            // The tolerance derived from the screen would override the preset.
+            if tolerance_from_screen {
+                shape_processor.tolerance = None;
+                tolerance_from_screen = false;
+            }
+
+            shape_processor.quality = shape_processor.quality.next();
+            status.update_status(&format!(
+                "Approximation quality: {:?}",
+                shape_processor.quality
+            ));
+            process_shape = true;
+        }
+
+        if let (true, Some(model_shape)) = (process_shape, &model_shape) {
+            worker.process(&shape_processor, model_shape);
+        }
+
+        if let Some(result) = worker.receive() {
+            match result {
+                Ok((new_shape, instanced)) => {
+                    // Repeated parts are drawn with instancing. The rest of
+                    // the model is drawn as usual.
+                    let mesh = match &instanced {
+                        Some(instanced) => &instanced.unique,
+                        None => &new_shape.mesh,
+                    };
                     renderer.update_geometry(
-                        (&new_shape.mesh).into(),
-                        (&new_shape.debug_info).into(),
+                        mesh.into(),
+                        &new_shape.debug_info,
                         new_shape.aabb,
                     );
+                    if let Some(instanced) = &instanced {
+                        renderer.update_instanced_geometry(&instanced.repeated);
+                    }
 
                     if camera.is_none() {
                         camera = Some(Camera::new(&new_shape.aabb));
@@ -83,6 +188,7 @@ pub fn run(
                 }
             }
         }
+        renderer.set_processing(worker.is_processing());
 
         //
 
@@ -139,26 +245,60 @@ pub fn run(
                     draw_config.draw_model = !draw_config.draw_model
                 }
                 VirtualKeyCode::Key2 => {
-                    if renderer.is_line_drawing_available() {
-                        draw_config.draw_mesh = !draw_config.draw_mesh
-                    }
+                    draw_config.draw_mesh = !draw_config.draw_mesh
                 }
                 VirtualKeyCode::Key3 => {
-                    if renderer.is_line_drawing_available() {
-                        draw_config.draw_debug = !draw_config.draw_debug
+                    draw_config.draw_debug = !draw_config.draw_debug
+                }
+                VirtualKeyCode::Key4 => {
+                    draw_config.draw_approximation =
+                        !draw_config.draw_approximation
+                }
+                VirtualKeyCode::Key5 => {
+                    draw_config.draw_reference = !draw_config.draw_reference
+                }
+                VirtualKeyCode::O => {
+                    let controller = input_handler.controller_mut();
+                    let (mode, message) = match controller.orbit_mode() {
+                        OrbitMode::Free => (
+                            OrbitMode::Constrained,
+                            "Orbit: Keeping the model upright",
+                        ),
+                        OrbitMode::Constrained => {
+                            (OrbitMode::Free, "Orbit: Rotating freely")
+                        }
+                    };
+                    controller.set_orbit_mode(mode);
+                    status.update_status(message);
+                }
+                VirtualKeyCode::M => {
+                    measure_tool = match measure_tool {
+                        Some(_) => {
+                            status.clear_status();
+                            None
+                        }
+                        None => {
+                            status.update_status(
+                                "Measuring: Click two points on the model",
+                            );
+                            Some(MeasureTool::default())
+                        }
+                    };
+                    renderer.set_measurement(None);
+                }
+                key => {
+                    if let (Some(view), Some(shape), Some(camera)) =
+                        (standard_view(key), &shape, &mut camera)
+                    {
+                        camera.set_view(view, &shape.aabb);
                     }
                 }
-                _ => {}
             },
             Event::WindowEvent {
                 event: WindowEvent::Resized(size),
                 ..
             } => {
-                let size = Size {
-                    width: size.width,
-                    height: size.height,
-                };
-                renderer.handle_resize(size);
+                renderer.resize(size.width, size.height);
             }
             Event::WindowEvent {
                 event: WindowEvent::MouseInput { state, button, .. },
@@ -176,13 +316,26 @@ pub fn run(
                 if let (Some(shape), Some(camera)) = (&shape, &mut camera) {
                     camera.update_planes(&shape.aabb);
 
-                    if let Err(err) = renderer.draw(
+                    match renderer.draw(
                         camera,
                         &mut draw_config,
                         window.window(),
                         &mut status,
                     ) {
-                        warn!("Draw error: {}", err);
+                        Ok(()) => {}
+                        Err(err) if err.is_recoverable() => {
+                            // Reconfiguring the surface is all it takes. The
+                            // next frame will be drawn to the new one.
+                            let Size { width, height } = window.size();
+                            renderer.resize(width, height);
+                        }
+                        Err(err @ DrawError::OutOfMemory) => {
+                            error!("Draw error: {}", err);
+                            *control_flow = ControlFlow::Exit;
+                        }
+                        Err(err) => {
+                            warn!("Draw error: {}", err);
+                        }
                     }
                 }
             }
@@ -217,6 +370,39 @@ pub fn run(
         {
             input_handler.handle_event(input_event, fp, camera);
         }
+
+        if let (Some(tool), Some(shape), Some(camera), Some(cursor)) =
+            (&mut measure_tool, &shape, &camera, previous_cursor)
+        {
+            let changed = match &event {
+                Event::WindowEvent {
+                    event: WindowEvent::CursorMoved { .. },
+                    ..
+                } => {
+                    tool.hover(camera.pick(cursor, shape));
+                    true
+                }
+                Event::WindowEvent {
+                    event:
+                        WindowEvent::MouseInput {
+                            state: ElementState::Pressed,
+                            button: MouseButton::Left,
+                            ..
+                        },
+                    ..
+                } => {
+                    if let Some(point) = camera.pick(cursor, shape) {
+                        tool.pick(point);
+                    }
+                    true
+                }
+                _ => false,
+            };
+
+            if changed {
+                renderer.set_measurement(tool.measurement());
+            }
+        }
     });
 }
 
@@ -273,6 +459,24 @@ fn input_event(
     }
 }
 
+/// Returns the standard view that is bound to a key, if any
+///
+/// Loosely follows the numpad layout that other CAD applications use.
+fn standard_view(key: VirtualKeyCode) -> Option<StandardView> {
+    let view = match key {
+        VirtualKeyCode::Numpad1 => StandardView::Front,
+        VirtualKeyCode::Numpad2 => StandardView::Back,
+        VirtualKeyCode::Numpad3 => StandardView::Right,
+        VirtualKeyCode::Numpad4 => StandardView::Left,
+        VirtualKeyCode::Numpad5 => StandardView::Isometric,
+        VirtualKeyCode::Numpad7 => StandardView::Top,
+        VirtualKeyCode::Numpad9 => StandardView::Bottom,
+        _ => return None,
+    };
+
+    Some(view)
+}
+
 /// Returns true/false if focus point point should be created/removed
 /// None means no change to focus point is needed
 fn focus_event(event: &Event<()>) -> Option<bool> {
@@ -0,0 +1,115 @@
+//! Shape processing in the background
+
+use std::{sync::mpsc, thread};
+
+use fj_interop::processed_shape::ProcessedShape;
+use fj_operations::shape_processor::{
+    Error, InstancedMeshes, ProcessedShapeCache, ShapeProcessor,
+};
+
+/// A processed shape, and its parts for instanced rendering, if any repeat
+///
+/// See [`ShapeProcessor::process_instances`].
+pub type WorkerResult =
+    Result<(ProcessedShape, Option<InstancedMeshes>), Error>;
+
+/// Processes shapes on a separate thread, so the window stays responsive
+pub struct ShapeWorker {
+    requests: mpsc::Sender<(u64, ShapeProcessor, fj::Shape)>,
+    results: mpsc::Receiver<(u64, WorkerResult)>,
+
+    latest_request: u64,
+    latest_result: u64,
+}
+
+impl ShapeWorker {
+    /// Start the thread that processes shapes
+    ///
+    /// The thread stops, once the `ShapeWorker` is dropped.
+    pub fn spawn() -> Self {
+        let (requests, request_rx) =
+            mpsc::channel::<(u64, ShapeProcessor, fj::Shape)>();
+        let (result_tx, results) = mpsc::channel();
+
+        thread::spawn(move || {
+            // Switching between quality presets shouldn't require re-running
+            // the kernel every time. Make sure all of them fit into the cache.
+            let mut cache = ProcessedShapeCache::new(4);
+
+            while let Ok(mut request) = request_rx.recv() {
+                // Only the most recent request matters. Don't bother with the
+                // ones that were superseded while the last one was processed.
+                while let Ok(newer) = request_rx.try_recv() {
+                    request = newer;
+                }
+
+                let (id, processor, shape) = request;
+                let result = cache
+                    .process(&processor, "model", &shape)
+                    .cloned()
+                    .and_then(|processed| {
+                        let instanced = cache
+                            .process_instances(&processor, "model", &shape)?
+                            .cloned();
+                        Ok((processed, instanced))
+                    });
+
+                if result_tx.send((id, result)).is_err() {
+                    // Nobody is waiting for results anymore.
+                    break;
+                }
+            }
+        });
+
+        Self {
+            requests,
+            results,
+            latest_request: 0,
+            latest_result: 0,
+        }
+    }
+
+    /// Start processing a shape
+    ///
+    /// Replaces any shape that is still being processed.
+    pub fn process(&mut self, processor: &ShapeProcessor, shape: &fj::Shape) {
+        self.latest_request += 1;
+
+        // If this fails, the thread has panicked. `receive` reports that.
+        let _ = self.requests.send((
+            self.latest_request,
+            processor.clone(),
+            shape.clone(),
+        ));
+    }
+
+    /// Receive the most recently processed shape, if there is a new one
+    ///
+    /// Doesn't block. Returns `None`, if no shape has been processed since the
+    /// last call.
+    pub fn receive(&mut self) -> Option<WorkerResult> {
+        let mut latest = None;
+
+        loop {
+            match self.results.try_recv() {
+                Ok((id, result)) => {
+                    self.latest_result = id;
+                    latest = Some(result);
+                }
+                Err(mpsc::TryRecvError::Empty) => break,
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    // The thread only stops on its own, if processing a shape
+                    // panicked. Not much we can do here.
+                    panic!("Shape processing thread has stopped");
+                }
+            }
+        }
+
+        latest
+    }
+
+    /// Whether a shape is still being processed
+    pub fn is_processing(&self) -> bool {
+        self.latest_result != self.latest_request
+    }
+}
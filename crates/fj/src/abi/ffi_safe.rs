@@ -11,7 +11,7 @@ use crate::models::Error;
 
 /// A FFI-safe version of `Vec<T>`.
 #[repr(C)]
-pub(crate) struct Vec<T> {
+pub struct Vec<T> {
     ptr: NonNull<T>,
     len: usize,
 }
@@ -44,10 +44,15 @@ impl<T> From<std::vec::Vec<T>> for Vec<T> {
             items.set_len(0);
 
             // Use the system allocator to create some space for our
-            // FfiSafeVec's buffer.
-            let layout = Layout::array::<T>(len).unwrap();
-            let ptr: *mut T = System::default().alloc(layout).cast();
-            let ptr = NonNull::new(ptr).expect("Allocation failed");
+            // FfiSafeVec's buffer. Allocating zero bytes isn't allowed, but
+            // an empty buffer doesn't need an allocation anyway.
+            let ptr = if len == 0 {
+                NonNull::dangling()
+            } else {
+                let layout = Layout::array::<T>(len).unwrap();
+                let ptr: *mut T = System::default().alloc(layout).cast();
+                NonNull::new(ptr).expect("Allocation failed")
+            };
 
             // Now, we can copy the items across
             std::ptr::copy_nonoverlapping(first_item, ptr.as_ptr(), len);
@@ -78,6 +83,12 @@ impl<T: Copy> From<Vec<T>> for Box<[T]> {
     }
 }
 
+impl<T> Default for Vec<T> {
+    fn default() -> Self {
+        std::vec::Vec::new().into()
+    }
+}
+
 impl<T> FromIterator<T> for Vec<T> {
     fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
         let vec: std::vec::Vec<T> = iter.into_iter().collect();
@@ -113,6 +124,11 @@ impl<T> Drop for Vec<T> {
             }
         }
 
+        // An empty vec doesn't have an allocation.
+        if len == 0 {
+            return;
+        }
+
         // Safety: This vec is immutable, so we're using the same layout as the
         // original allocation. It's also not possible to touch the allocation
         // after Drop completes.
@@ -127,6 +143,29 @@ impl<T> Drop for Vec<T> {
 unsafe impl<T: Send> Send for Vec<T> {}
 unsafe impl<T: Sync> Sync for Vec<T> {}
 
+#[cfg(feature = "serde")]
+impl<T: serde::Serialize> serde::Serialize for Vec<T> {
+    fn serialize<S>(
+        &self,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        (**self).serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: serde::Deserialize<'de>> serde::Deserialize<'de> for Vec<T> {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        std::vec::Vec::deserialize(deserializer).map(Into::into)
+    }
+}
+
 /// A FFI-safe version of `Box<str>`.
 #[repr(transparent)]
 #[derive(Debug, PartialEq, Clone)]
@@ -138,6 +177,29 @@ impl From<std::string::String> for String {
     }
 }
 
+#[cfg(feature = "serde")]
+impl serde::Serialize for String {
+    fn serialize<S>(
+        &self,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        (**self).serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for String {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        std::string::String::deserialize(deserializer).map(Into::into)
+    }
+}
+
 impl From<String> for std::string::String {
     fn from(s: String) -> Self {
         s.to_string()
@@ -106,6 +106,8 @@ pub struct ArgumentMetadata {
     name: ffi_safe::String,
     description: ffi_safe::Option<ffi_safe::String>,
     default_value: ffi_safe::Option<ffi_safe::String>,
+    kind: crate::models::ArgumentKind,
+    range: ffi_safe::Option<crate::models::ArgumentRange>,
 }
 
 impl From<crate::models::ArgumentMetadata> for ArgumentMetadata {
@@ -114,12 +116,16 @@ impl From<crate::models::ArgumentMetadata> for ArgumentMetadata {
             name,
             description,
             default_value,
+            kind,
+            range,
         } = meta;
 
         ArgumentMetadata {
             name: name.into(),
             description: description.into(),
             default_value: default_value.into(),
+            kind,
+            range: range.into(),
         }
     }
 }
@@ -130,12 +136,16 @@ impl From<ArgumentMetadata> for crate::models::ArgumentMetadata {
             name,
             description,
             default_value,
+            kind,
+            range,
         } = meta;
 
         crate::models::ArgumentMetadata {
             name: name.into(),
             description: description.map(Into::into).into(),
             default_value: default_value.map(Into::into).into(),
+            kind,
+            range: range.into(),
         }
     }
 }
@@ -0,0 +1,42 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::Shape;
+
+/// A difference between two 3-dimensional shapes
+///
+/// The second shape is subtracted from the first. Unlike [`Difference2d`], the
+/// second shape doesn't need to be contained in the first.
+///
+/// # Limitations
+///
+/// The shapes are combined using the boolean operations of the kernel, which
+/// come with some limitations. See the [documentation of the `boolean` module]
+/// in `fj-kernel`.
+///
+/// [documentation of the `boolean` module]: https://github.com/hannobraun/Fornjot/blob/main/crates/fj-kernel/src/algorithms/boolean/mod.rs
+/// [`Difference2d`]: crate::Difference2d
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(C)]
+pub struct Difference3d {
+    shapes: [Shape; 2],
+}
+
+impl Difference3d {
+    /// Create a `Difference3d` from two shapes
+    pub fn from_shapes(shapes: [Shape; 2]) -> Self {
+        Self { shapes }
+    }
+
+    /// Access the shapes that make up the difference
+    pub fn shapes(&self) -> &[Shape; 2] {
+        &self.shapes
+    }
+}
+
+impl From<Difference3d> for Shape {
+    fn from(shape: Difference3d) -> Self {
+        Self::Difference3d(Box::new(shape))
+    }
+}
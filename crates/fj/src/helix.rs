@@ -0,0 +1,94 @@
+use std::f64::consts::TAU;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{Shape, Shape2d};
+
+/// A helix around the z-axis
+///
+/// The helix starts at `[radius, 0, 0]` and winds counterclockwise around the
+/// z-axis, as seen from above. A positive pitch results in a right-handed helix
+/// that moves up, a negative one in a left-handed helix that moves down.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(C)]
+pub struct Helix {
+    /// The distance of the helix from the z-axis
+    pub radius: f64,
+
+    /// The distance the helix moves along the z-axis per turn
+    pub pitch: f64,
+
+    /// The number of turns
+    pub turns: f64,
+}
+
+impl Helix {
+    /// Compute a point on the helix
+    ///
+    /// `t` goes from `0` at the start of the helix to `1` at its end. This can
+    /// be used with [`PathSweep::from_curve`].
+    ///
+    /// [`PathSweep::from_curve`]: crate::PathSweep::from_curve
+    pub fn point(&self, t: f64) -> [f64; 3] {
+        let angle = TAU * self.turns * t;
+        let (sin, cos) = angle.sin_cos();
+
+        [
+            self.radius * cos,
+            self.radius * sin,
+            self.pitch * self.turns * t,
+        ]
+    }
+
+    /// The height of the helix along the z-axis
+    pub fn height(&self) -> f64 {
+        self.pitch * self.turns
+    }
+}
+
+/// A sweep of a 2-dimensional shape along a [`Helix`]
+///
+/// The shape is placed in the plane that contains the z-axis and the start of
+/// the helix: Its x-axis points away from the z-axis, its y-axis along it, and
+/// its origin is at the start of the helix. Then, it is rotated around the
+/// z-axis while it moves along the helix. This is how screw threads are formed.
+///
+/// # Limitations
+///
+/// The shape must be small enough, compared to the pitch and radius of the
+/// helix, to not intersect itself. This is not currently checked.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(C)]
+pub struct HelicalSweep {
+    /// The 2-dimensional shape being swept
+    shape: Shape2d,
+
+    /// The helix that the shape is swept along
+    helix: Helix,
+}
+
+impl HelicalSweep {
+    /// Create a `HelicalSweep` along the given helix
+    pub fn from_helix(shape: Shape2d, helix: Helix) -> Self {
+        Self { shape, helix }
+    }
+
+    /// Access the shape being swept
+    pub fn shape(&self) -> &Shape2d {
+        &self.shape
+    }
+
+    /// Access the helix that the shape is swept along
+    pub fn helix(&self) -> Helix {
+        self.helix
+    }
+}
+
+impl From<HelicalSweep> for Shape {
+    fn from(shape: HelicalSweep) -> Self {
+        Self::HelicalSweep(shape)
+    }
+}
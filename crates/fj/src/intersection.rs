@@ -0,0 +1,32 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::Shape;
+
+/// The intersection of two 3-dimensional shapes
+///
+/// Only the volume that is inside of both shapes is kept.
+///
+/// # Limitations
+///
+/// The shapes are combined using the boolean operations of the kernel, which
+/// come with some limitations. See the [documentation of the `boolean` module]
+/// in `fj-kernel`.
+///
+/// [documentation of the `boolean` module]: https://github.com/hannobraun/Fornjot/blob/main/crates/fj-kernel/src/algorithms/boolean/mod.rs
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(C)]
+pub struct Intersection {
+    /// The first of the shapes
+    pub a: Shape,
+
+    /// The second of the shapes
+    pub b: Shape,
+}
+
+impl From<Intersection> for Shape {
+    fn from(shape: Intersection) -> Self {
+        Self::Intersection(Box::new(shape))
+    }
+}
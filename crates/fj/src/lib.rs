@@ -23,14 +23,38 @@ pub mod syntax;
 #[doc(hidden)]
 pub mod abi;
 mod angle;
+mod difference_3d;
 mod group;
+mod helix;
+mod intersection;
+mod loft;
+mod mirror;
 pub mod models;
+mod path_sweep;
+mod pattern;
+mod revolve;
 mod shape_2d;
 mod sweep;
+mod text;
 mod transform;
+mod union;
 
 pub use self::{
-    angle::*, group::Group, shape_2d::*, sweep::Sweep, transform::Transform,
+    angle::*,
+    difference_3d::Difference3d,
+    group::Group,
+    helix::{HelicalSweep, Helix},
+    intersection::Intersection,
+    loft::{Loft, LoftProfile},
+    mirror::Mirror,
+    path_sweep::PathSweep,
+    pattern::{CircularPattern, LinearPattern},
+    revolve::Revolve,
+    shape_2d::*,
+    sweep::Sweep,
+    text::Text,
+    transform::Transform,
+    union::Union,
 };
 pub use fj_proc::*;
 #[cfg(feature = "serde")]
@@ -42,9 +66,36 @@ use serde::{Deserialize, Serialize};
 #[repr(C)]
 #[allow(improper_ctypes)] // Box isn't FFI-safe
 pub enum Shape {
+    /// A 3-dimensional shape, repeated around an axis
+    CircularPattern(Box<CircularPattern>),
+
+    /// A difference between two 3-dimensional shapes
+    Difference3d(Box<Difference3d>),
+
     /// A group of two 3-dimensional shapes
     Group(Box<Group>),
 
+    /// A sweep of a 2-dimensional shape along a helix
+    HelicalSweep(HelicalSweep),
+
+    /// The intersection of two 3-dimensional shapes
+    Intersection(Box<Intersection>),
+
+    /// A 3-dimensional shape, repeated along a straight line
+    LinearPattern(Box<LinearPattern>),
+
+    /// A shape that connects a series of profiles
+    Loft(Loft),
+
+    /// A 3-dimensional shape, reflected across a plane
+    Mirror(Box<Mirror>),
+
+    /// A sweep of a 2-dimensional shape along an arbitrary path
+    PathSweep(PathSweep),
+
+    /// A revolution of a 2-dimensional shape around an axis
+    Revolve(Revolve),
+
     /// A 2D shape
     Shape2d(Shape2d),
 
@@ -53,4 +104,7 @@ pub enum Shape {
 
     /// A transformed 3-dimensional shape
     Transform(Box<Transform>),
+
+    /// The union of two 3-dimensional shapes
+    Union(Box<Union>),
 }
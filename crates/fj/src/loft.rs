@@ -0,0 +1,96 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{abi::ffi_safe, Angle, Shape, Sketch};
+
+/// A shape that connects a series of profiles
+///
+/// The profiles are connected in order. The first and last profile cap off the
+/// resulting shape.
+///
+/// # Limitations
+///
+/// All profiles must face the same way, and the shape must not intersect
+/// itself. Neither of that is currently checked.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(C)]
+pub struct Loft {
+    /// The profiles that are connected
+    profiles: ffi_safe::Vec<LoftProfile>,
+
+    /// Whether the profiles are connected by a smooth surface
+    smooth: bool,
+}
+
+impl Loft {
+    /// Create a `Loft` that connects the profiles with straight lines
+    pub fn ruled(profiles: Vec<LoftProfile>) -> Self {
+        Self {
+            profiles: profiles.into(),
+            smooth: false,
+        }
+    }
+
+    /// Create a `Loft` that connects the profiles with a smooth surface
+    ///
+    /// The surface passes through all profiles. With only two profiles, this is
+    /// the same as [`Loft::ruled`].
+    pub fn smooth(profiles: Vec<LoftProfile>) -> Self {
+        Self {
+            profiles: profiles.into(),
+            smooth: true,
+        }
+    }
+
+    /// Access the profiles
+    pub fn profiles(&self) -> &[LoftProfile] {
+        &self.profiles
+    }
+
+    /// Indicate whether the profiles are connected by a smooth surface
+    pub fn is_smooth(&self) -> bool {
+        self.smooth
+    }
+}
+
+impl From<Loft> for Shape {
+    fn from(shape: Loft) -> Self {
+        Self::Loft(shape)
+    }
+}
+
+/// A profile of a [`Loft`]
+///
+/// The sketch is rotated around the origin, then moved by `offset`, just like
+/// a [`Transform`] would.
+///
+/// [`Transform`]: crate::Transform
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(C)]
+pub struct LoftProfile {
+    /// The sketch that defines the profile
+    pub sketch: Sketch,
+
+    /// The axis of the rotation
+    pub axis: [f64; 3],
+
+    /// The angle of the rotation
+    pub angle: Angle,
+
+    /// The offset of the profile
+    pub offset: [f64; 3],
+}
+
+impl LoftProfile {
+    /// Create a `LoftProfile` that is parallel to the xy-plane
+    pub fn at_height(sketch: Sketch, z: f64) -> Self {
+        Self {
+            sketch,
+            axis: [1., 0., 0.],
+            angle: Angle::from_rad(0.),
+            offset: [0., 0., z],
+        }
+    }
+}
@@ -0,0 +1,28 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::Shape;
+
+/// A 3-dimensional shape, reflected across a plane
+///
+/// The plane is defined by its normal and its distance from the origin, along
+/// that normal.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(C)]
+pub struct Mirror {
+    /// The shape being reflected
+    pub shape: Shape,
+
+    /// The normal of the plane
+    pub normal: [f64; 3],
+
+    /// The distance of the plane from the origin
+    pub offset: f64,
+}
+
+impl From<Mirror> for Shape {
+    fn from(shape: Mirror) -> Self {
+        Self::Mirror(Box::new(shape))
+    }
+}
@@ -4,7 +4,7 @@ use std::{
     str::FromStr,
 };
 
-use crate::models::Error;
+use crate::models::{ArgumentMetadata, ArgumentRange, Error};
 
 /// Contextual information passed to a [`Model`][crate::models::Model] when it
 /// is being initialized.
@@ -13,36 +13,62 @@ use crate::models::Error;
 pub trait Context {
     /// Get an argument that was passed to this model.
     fn get_argument(&self, name: &str) -> Option<&str>;
+
+    /// Iterate over the names and values of all arguments.
+    ///
+    /// Contexts that can't list their arguments, like the one a model receives
+    /// from the host application, yield nothing.
+    fn arguments(&self) -> Box<dyn Iterator<Item = (&str, &str)> + '_> {
+        Box::new(std::iter::empty())
+    }
 }
 
 impl<C: Context + ?Sized> Context for &'_ C {
     fn get_argument(&self, name: &str) -> Option<&str> {
         (**self).get_argument(name)
     }
+    fn arguments(&self) -> Box<dyn Iterator<Item = (&str, &str)> + '_> {
+        (**self).arguments()
+    }
 }
 
 impl<C: Context + ?Sized> Context for Box<C> {
     fn get_argument(&self, name: &str) -> Option<&str> {
         (**self).get_argument(name)
     }
+    fn arguments(&self) -> Box<dyn Iterator<Item = (&str, &str)> + '_> {
+        (**self).arguments()
+    }
 }
 
 impl<C: Context + ?Sized> Context for std::rc::Rc<C> {
     fn get_argument(&self, name: &str) -> Option<&str> {
         (**self).get_argument(name)
     }
+    fn arguments(&self) -> Box<dyn Iterator<Item = (&str, &str)> + '_> {
+        (**self).arguments()
+    }
 }
 
 impl<C: Context + ?Sized> Context for std::sync::Arc<C> {
     fn get_argument(&self, name: &str) -> Option<&str> {
         (**self).get_argument(name)
     }
+    fn arguments(&self) -> Box<dyn Iterator<Item = (&str, &str)> + '_> {
+        (**self).arguments()
+    }
 }
 
 impl Context for HashMap<String, String> {
     fn get_argument(&self, name: &str) -> Option<&str> {
         self.get(name).map(|s| s.as_str())
     }
+    fn arguments(&self) -> Box<dyn Iterator<Item = (&str, &str)> + '_> {
+        Box::new(
+            self.iter()
+                .map(|(name, value)| (name.as_str(), value.as_str())),
+        )
+    }
 }
 
 /// Extension methods for the [`Context`] type.
@@ -131,6 +157,8 @@ pub enum ContextError {
     /// An argument was present, but we were unable to parse it into the final
     /// type.
     ParseFailed(ParseFailed),
+    /// An argument was outside of the range its parameter allows.
+    OutOfRange(OutOfRange),
 }
 
 impl From<MissingArgument> for ContextError {
@@ -145,6 +173,12 @@ impl From<ParseFailed> for ContextError {
     }
 }
 
+impl From<OutOfRange> for ContextError {
+    fn from(o: OutOfRange) -> Self {
+        ContextError::OutOfRange(o)
+    }
+}
+
 impl Display for ContextError {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         match self {
@@ -154,6 +188,9 @@ impl Display for ContextError {
             ContextError::ParseFailed(_) => {
                 write!(f, "Unable to parse an argument")
             }
+            ContextError::OutOfRange(_) => {
+                write!(f, "An argument was out of range")
+            }
         }
     }
 }
@@ -163,10 +200,120 @@ impl std::error::Error for ContextError {
         match self {
             ContextError::MissingArgument(m) => Some(m),
             ContextError::ParseFailed(p) => Some(p),
+            ContextError::OutOfRange(o) => Some(o),
+        }
+    }
+}
+
+/// Collects errors from reading multiple arguments, to report them together.
+///
+/// [`ContextExt::parse_argument()`] fails on the first problematic argument,
+/// which means users have to fix them one at a time. This type reads each
+/// argument, remembering any errors instead of returning them right away.
+///
+/// ```rust
+/// # use std::collections::HashMap;
+/// # use fj::models::{ContextErrors, Error};
+/// # fn shape(ctx: &HashMap<String, String>) -> Result<(), Error> {
+/// let mut errors = ContextErrors::new();
+/// let width: Option<f64> = errors.require(ctx, "width");
+/// let height: Option<f64> = errors.require(ctx, "height");
+/// errors.into_result()?;
+///
+/// // If there were no errors, all required arguments are available.
+/// let (width, height) = (width.unwrap(), height.unwrap());
+/// # let _ = (width, height);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default)]
+pub struct ContextErrors {
+    /// The errors that occurred so far.
+    pub errors: Vec<ContextError>,
+}
+
+impl ContextErrors {
+    /// Create an empty list of errors.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parse a required argument, remembering the error if that fails.
+    pub fn require<T>(&mut self, ctx: &dyn Context, name: &str) -> Option<T>
+    where
+        T: FromStr,
+        T::Err: std::error::Error + Send + Sync + 'static,
+    {
+        match ctx.parse_argument(name) {
+            Ok(value) => Some(value),
+            Err(err) => {
+                self.errors.push(err);
+                None
+            }
+        }
+    }
+
+    /// Parse an optional argument, remembering the error if that fails.
+    pub fn optional<T>(&mut self, ctx: &dyn Context, name: &str) -> Option<T>
+    where
+        T: FromStr,
+        T::Err: std::error::Error + Send + Sync + 'static,
+    {
+        match ctx.parse_optional_argument(name) {
+            Ok(value) => value,
+            Err(err) => {
+                self.errors.push(err.into());
+                None
+            }
         }
     }
+
+    /// Check arguments against the metadata a model declares, remembering
+    /// all the errors.
+    ///
+    /// See [`ArgumentMetadata::validate()`].
+    pub fn validate(
+        &mut self,
+        ctx: &dyn Context,
+        metadata: &[ArgumentMetadata],
+    ) {
+        for argument in metadata {
+            if let Err(err) = argument.validate(ctx) {
+                self.errors.push(err);
+            }
+        }
+    }
+
+    /// Return `Ok`, if no errors occurred, or all of them otherwise.
+    pub fn into_result(self) -> Result<(), ContextErrors> {
+        if self.errors.is_empty() {
+            Ok(())
+        } else {
+            Err(self)
+        }
+    }
+}
+
+impl Display for ContextErrors {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "Problems with {} argument(s):", self.errors.len())?;
+
+        for err in &self.errors {
+            match err {
+                ContextError::MissingArgument(m) => write!(f, "\n- {m}")?,
+                ContextError::ParseFailed(p) => {
+                    write!(f, "\n- {p}: {}", p.error)?
+                }
+                ContextError::OutOfRange(o) => write!(f, "\n- {o}")?,
+            }
+        }
+
+        Ok(())
+    }
 }
 
+impl std::error::Error for ContextErrors {}
+
 /// The error returned when a required argument wasn't provided.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct MissingArgument {
@@ -210,6 +357,32 @@ impl std::error::Error for ParseFailed {
     }
 }
 
+/// The error returned when an argument is outside of the range its
+/// [`ArgumentMetadata`] allows.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutOfRange {
+    /// The argument's name.
+    pub name: String,
+    /// The actual value.
+    pub value: f64,
+    /// The range of valid values.
+    pub range: ArgumentRange,
+}
+
+impl Display for OutOfRange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let OutOfRange { name, value, range } = self;
+
+        write!(
+            f,
+            "The \"{name}\" argument ({value}) must be between {} and {}",
+            range.min, range.max
+        )
+    }
+}
+
+impl std::error::Error for OutOfRange {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -218,4 +391,29 @@ mod tests {
     fn context_is_object_safe() {
         let _: &dyn Context;
     }
+
+    #[test]
+    fn aggregate_errors() {
+        let mut ctx = HashMap::new();
+        ctx.insert("b".to_string(), "2".to_string());
+        ctx.insert("d".to_string(), "four".to_string());
+
+        let mut errors = ContextErrors::new();
+        let a: Option<u32> = errors.require(&ctx, "a");
+        let b: Option<u32> = errors.require(&ctx, "b");
+        let c: Option<u32> = errors.require(&ctx, "c");
+        let d: Option<u32> = errors.optional(&ctx, "d");
+
+        assert_eq!((a, b, c, d), (None, Some(2), None, None));
+
+        let errors = errors.into_result().unwrap_err();
+        assert_eq!(errors.errors.len(), 3);
+
+        let message = errors.to_string();
+        assert!(message.contains("The \"a\" argument was missing"));
+        assert!(message.contains("The \"c\" argument was missing"));
+        assert!(message.contains("Unable to parse the \"d\" argument"));
+
+        assert!(ContextErrors::new().into_result().is_ok());
+    }
 }
@@ -0,0 +1,84 @@
+use std::collections::HashMap;
+
+use crate::models::Context;
+
+/// A [`Context`] that reads its arguments from environment variables.
+///
+/// Only variables that start with the prefix are used. The rest of the
+/// variable's name is converted to lowercase and `__` is replaced with `.`, so
+/// with the prefix `FJ_`, `FJ_GEAR__TEETH` becomes the `gear.teeth` argument.
+///
+/// The variables are read once, when the context is constructed.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct EnvContext {
+    arguments: HashMap<String, String>,
+}
+
+impl EnvContext {
+    /// Read the arguments from the environment variables of this process.
+    ///
+    /// Variables whose name or value isn't valid Unicode are ignored.
+    pub fn new(prefix: &str) -> Self {
+        Self::from_vars(
+            prefix,
+            std::env::vars_os().filter_map(|(k, v)| {
+                Some((k.into_string().ok()?, v.into_string().ok()?))
+            }),
+        )
+    }
+
+    /// Read the arguments from the provided variables.
+    pub fn from_vars(
+        prefix: &str,
+        vars: impl IntoIterator<Item = (String, String)>,
+    ) -> Self {
+        let arguments = vars
+            .into_iter()
+            .filter_map(|(name, value)| {
+                let name = name.strip_prefix(prefix)?;
+                if name.is_empty() {
+                    return None;
+                }
+
+                Some((name.to_lowercase().replace("__", "."), value))
+            })
+            .collect();
+
+        Self { arguments }
+    }
+}
+
+impl Context for EnvContext {
+    fn get_argument(&self, name: &str) -> Option<&str> {
+        self.arguments.get_argument(name)
+    }
+
+    fn arguments(&self) -> Box<dyn Iterator<Item = (&str, &str)> + '_> {
+        self.arguments.arguments()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::models::Context;
+
+    use super::EnvContext;
+
+    #[test]
+    fn prefixed_vars() {
+        let ctx = EnvContext::from_vars(
+            "FJ_",
+            [
+                ("FJ_GEAR__TEETH", "12"),
+                ("FJ_OUTER_RADIUS", "5"),
+                ("HOME", "/root"),
+                ("FJ_", "empty"),
+            ]
+            .map(|(name, value)| (name.to_string(), value.to_string())),
+        );
+
+        assert_eq!(ctx.get_argument("gear.teeth"), Some("12"));
+        assert_eq!(ctx.get_argument("outer_radius"), Some("5"));
+        assert_eq!(ctx.arguments().count(), 2);
+    }
+}
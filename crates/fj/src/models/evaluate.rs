@@ -0,0 +1,132 @@
+use std::{
+    any::Any,
+    fmt::{self, Display, Formatter},
+    panic::{self, AssertUnwindSafe},
+};
+
+use crate::{
+    models::{Context, Error, Model},
+    Shape,
+};
+
+/// Evaluate a model, returning the shape it defines
+///
+/// This runs the model in-process, without any of the machinery the Fornjot
+/// application uses to load models. It is meant for embedding models in other
+/// tools, which do their own meshing or export.
+///
+/// Errors returned by the model (like a [`ContextError`] for a missing
+/// argument) are passed on. If the model panics, the panic is caught and
+/// returned as a [`ModelPanicked`] error.
+///
+/// [`ContextError`]: crate::models::ContextError
+pub fn evaluate_model(
+    model: &dyn Model,
+    context: &dyn Context,
+) -> Result<Shape, Error> {
+    match panic::catch_unwind(AssertUnwindSafe(|| model.shape(context))) {
+        Ok(result) => result,
+        Err(payload) => Err(Box::new(ModelPanicked::from_payload(payload))),
+    }
+}
+
+/// The error returned by [`evaluate_model`], if the model panicked
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModelPanicked {
+    /// The panic message, if it was a string
+    pub message: Option<String>,
+}
+
+impl ModelPanicked {
+    fn from_payload(payload: Box<dyn Any + Send>) -> Self {
+        let message = if let Some(s) = payload.downcast_ref::<String>() {
+            Some(s.clone())
+        } else {
+            payload.downcast_ref::<&str>().map(|s| s.to_string())
+        };
+
+        Self { message }
+    }
+}
+
+impl Display for ModelPanicked {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match &self.message {
+            Some(message) => write!(f, "The model panicked: {message}"),
+            None => write!(f, "The model panicked"),
+        }
+    }
+}
+
+impl std::error::Error for ModelPanicked {}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::{
+        models::{
+            Context, ContextError, ContextExt, Error, Model, ModelMetadata,
+        },
+        Shape, Sketch,
+    };
+
+    use super::{evaluate_model, ModelPanicked};
+
+    struct Square;
+
+    impl Model for Square {
+        fn shape(&self, ctx: &dyn Context) -> Result<Shape, Error> {
+            let size: f64 = ctx.parse_argument("size")?;
+
+            if size < 0. {
+                panic!("Negative size");
+            }
+
+            let points = vec![[0., 0.], [size, 0.], [size, size], [0., size]];
+            Ok(Sketch::from_points(points).into())
+        }
+
+        fn metadata(&self) -> ModelMetadata {
+            ModelMetadata::new("square")
+        }
+    }
+
+    fn context(size: Option<&str>) -> HashMap<String, String> {
+        size.map(|size| ("size".to_string(), size.to_string()))
+            .into_iter()
+            .collect()
+    }
+
+    #[test]
+    fn evaluate() {
+        let shape = evaluate_model(&Square, &context(Some("2"))).unwrap();
+
+        let expected: Shape =
+            Sketch::from_points(vec![[0., 0.], [2., 0.], [2., 2.], [0., 2.]])
+                .into();
+        assert_eq!(shape, expected);
+    }
+
+    #[test]
+    fn evaluate_with_context_error() {
+        let err = evaluate_model(&Square, &context(None)).unwrap_err();
+
+        assert!(matches!(
+            err.downcast_ref::<ContextError>(),
+            Some(ContextError::MissingArgument(_))
+        ));
+    }
+
+    #[test]
+    fn evaluate_with_panic() {
+        let err = evaluate_model(&Square, &context(Some("-1"))).unwrap_err();
+
+        assert_eq!(
+            err.downcast_ref::<ModelPanicked>(),
+            Some(&ModelPanicked {
+                message: Some("Negative size".to_string())
+            })
+        );
+    }
+}
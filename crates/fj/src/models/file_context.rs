@@ -0,0 +1,252 @@
+use std::{
+    collections::HashMap,
+    fmt::{self, Display, Formatter},
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::models::Context;
+
+/// A [`Context`] that reads its arguments from a TOML or JSON file.
+///
+/// Nested tables are flattened into dotted keys, so `teeth` in a `[gear]`
+/// table becomes the `gear.teeth` argument. All values are converted into
+/// strings, to be parsed by [`ContextExt`][crate::models::ContextExt] like any
+/// other argument. Arrays and `null` have no string representation that could
+/// be parsed like that, and are rejected.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FileContext {
+    arguments: HashMap<String, String>,
+}
+
+impl FileContext {
+    /// Load the arguments from a file.
+    ///
+    /// The format is determined by the file extension, which must be `toml`
+    /// or `json`.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, FileContextError> {
+        let path = path.as_ref();
+
+        let parse = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("toml") => Self::from_toml,
+            Some("json") => Self::from_json,
+            _ => {
+                return Err(FileContextError::UnknownFormat(path.to_path_buf()))
+            }
+        };
+
+        parse(&fs::read_to_string(path)?)
+    }
+
+    /// Read the arguments from a TOML document.
+    pub fn from_toml(toml: &str) -> Result<Self, FileContextError> {
+        let value: toml::Value = toml.parse()?;
+
+        let mut arguments = HashMap::new();
+        flatten_toml(String::new(), value, &mut arguments)?;
+
+        Ok(Self { arguments })
+    }
+
+    /// Read the arguments from a JSON document.
+    pub fn from_json(json: &str) -> Result<Self, FileContextError> {
+        let value: serde_json::Value = serde_json::from_str(json)?;
+
+        let mut arguments = HashMap::new();
+        flatten_json(String::new(), value, &mut arguments)?;
+
+        Ok(Self { arguments })
+    }
+}
+
+impl Context for FileContext {
+    fn get_argument(&self, name: &str) -> Option<&str> {
+        self.arguments.get(name).map(|s| s.as_str())
+    }
+    fn arguments(&self) -> Box<dyn Iterator<Item = (&str, &str)> + '_> {
+        self.arguments.arguments()
+    }
+}
+
+fn flatten_toml(
+    key: String,
+    value: toml::Value,
+    arguments: &mut HashMap<String, String>,
+) -> Result<(), FileContextError> {
+    let value = match value {
+        toml::Value::Table(table) => {
+            for (name, value) in table {
+                flatten_toml(nested_key(&key, &name), value, arguments)?;
+            }
+            return Ok(());
+        }
+        toml::Value::Array(_) => {
+            return Err(FileContextError::NonScalar { key });
+        }
+        toml::Value::String(value) => value,
+        toml::Value::Integer(value) => value.to_string(),
+        toml::Value::Float(value) => value.to_string(),
+        toml::Value::Boolean(value) => value.to_string(),
+        toml::Value::Datetime(value) => value.to_string(),
+    };
+
+    arguments.insert(key, value);
+    Ok(())
+}
+
+fn flatten_json(
+    key: String,
+    value: serde_json::Value,
+    arguments: &mut HashMap<String, String>,
+) -> Result<(), FileContextError> {
+    let value = match value {
+        serde_json::Value::Object(object) => {
+            for (name, value) in object {
+                flatten_json(nested_key(&key, &name), value, arguments)?;
+            }
+            return Ok(());
+        }
+        serde_json::Value::Array(_) | serde_json::Value::Null => {
+            return Err(FileContextError::NonScalar { key });
+        }
+        serde_json::Value::String(value) => value,
+        serde_json::Value::Number(value) => value.to_string(),
+        serde_json::Value::Bool(value) => value.to_string(),
+    };
+
+    arguments.insert(key, value);
+    Ok(())
+}
+
+fn nested_key(parent: &str, name: &str) -> String {
+    if parent.is_empty() {
+        name.to_string()
+    } else {
+        format!("{parent}.{name}")
+    }
+}
+
+/// An error that can occur when loading a [`FileContext`].
+#[derive(Debug)]
+pub enum FileContextError {
+    /// The file could not be read.
+    Io(io::Error),
+
+    /// The file extension is neither `toml` nor `json`.
+    UnknownFormat(PathBuf),
+
+    /// The file is not valid TOML.
+    Toml(toml::de::Error),
+
+    /// The file is not valid JSON.
+    Json(serde_json::Error),
+
+    /// A value can't be used as an argument, because it's an array or `null`.
+    NonScalar {
+        /// The dotted key of the value.
+        key: String,
+    },
+}
+
+impl From<io::Error> for FileContextError {
+    fn from(err: io::Error) -> Self {
+        FileContextError::Io(err)
+    }
+}
+
+impl From<toml::de::Error> for FileContextError {
+    fn from(err: toml::de::Error) -> Self {
+        FileContextError::Toml(err)
+    }
+}
+
+impl From<serde_json::Error> for FileContextError {
+    fn from(err: serde_json::Error) -> Self {
+        FileContextError::Json(err)
+    }
+}
+
+impl Display for FileContextError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            FileContextError::Io(_) => write!(f, "Unable to read the file"),
+            FileContextError::UnknownFormat(path) => write!(
+                f,
+                "Unable to determine the format of {}, expected a `.toml` or \
+                `.json` file",
+                path.display()
+            ),
+            FileContextError::Toml(_) => write!(f, "Invalid TOML"),
+            FileContextError::Json(_) => write!(f, "Invalid JSON"),
+            FileContextError::NonScalar { key } => write!(
+                f,
+                "The \"{key}\" argument must be a string, number, or boolean"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for FileContextError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            FileContextError::Io(err) => Some(err),
+            FileContextError::Toml(err) => Some(err),
+            FileContextError::Json(err) => Some(err),
+            FileContextError::UnknownFormat(_)
+            | FileContextError::NonScalar { .. } => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::models::{Context, ContextExt};
+
+    use super::{FileContext, FileContextError};
+
+    #[test]
+    fn nested_toml() {
+        let ctx = FileContext::from_toml(
+            r#"
+            name = "spur"
+
+            [gear]
+            teeth = 12
+            module = 0.5
+
+            [gear.hub]
+            keyed = true
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(ctx.get_argument("name"), Some("spur"));
+        assert_eq!(ctx.parse_argument::<u32>("gear.teeth").unwrap(), 12);
+        assert_eq!(ctx.parse_argument::<f64>("gear.module").unwrap(), 0.5);
+        assert!(ctx.parse_argument::<bool>("gear.hub.keyed").unwrap());
+        assert_eq!(ctx.get_argument("gear"), None);
+    }
+
+    #[test]
+    fn nested_json() {
+        let ctx =
+            FileContext::from_json(r#"{ "gear": { "teeth": 12 } }"#).unwrap();
+
+        assert_eq!(ctx.get_argument("gear.teeth"), Some("12"));
+    }
+
+    #[test]
+    fn reject_non_scalar_values() {
+        let err = FileContext::from_toml("[gear]\nteeth = [1, 2]").unwrap_err();
+        assert!(
+            matches!(&err, FileContextError::NonScalar { key } if key == "gear.teeth")
+        );
+        assert_eq!(
+            err.to_string(),
+            "The \"gear.teeth\" argument must be a string, number, or boolean"
+        );
+
+        let err = FileContext::from_json(r#"{ "a": null }"#).unwrap_err();
+        assert!(matches!(err, FileContextError::NonScalar { .. }));
+    }
+}
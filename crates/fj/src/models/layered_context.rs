@@ -0,0 +1,109 @@
+use std::collections::HashSet;
+
+use crate::models::Context;
+
+/// A [`Context`] that combines multiple sources of arguments.
+///
+/// Sources are queried in order, so an argument from an earlier source
+/// overrides the same argument from later ones. To have environment variables
+/// override a file, which in turn overrides some defaults, add them in that
+/// order:
+///
+/// ```rust
+/// # use std::collections::HashMap;
+/// # use fj::models::{Context, EnvContext, LayeredContext};
+/// let mut defaults = HashMap::new();
+/// defaults.insert("teeth".to_string(), "12".to_string());
+///
+/// let ctx = LayeredContext::new()
+///     .with_source(EnvContext::new("FJ_"))
+///     .with_source(defaults);
+/// # let _ = ctx.get_argument("teeth");
+/// ```
+#[derive(Default)]
+pub struct LayeredContext {
+    sources: Vec<Box<dyn Context>>,
+}
+
+impl LayeredContext {
+    /// Construct a context without any sources.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a source, with lower precedence than the ones added before.
+    pub fn with_source(mut self, source: impl Context + 'static) -> Self {
+        self.sources.push(Box::new(source));
+        self
+    }
+}
+
+impl Context for LayeredContext {
+    fn get_argument(&self, name: &str) -> Option<&str> {
+        self.sources
+            .iter()
+            .find_map(|source| source.get_argument(name))
+    }
+
+    fn arguments(&self) -> Box<dyn Iterator<Item = (&str, &str)> + '_> {
+        let mut seen = HashSet::new();
+
+        Box::new(
+            self.sources
+                .iter()
+                .flat_map(|source| source.arguments())
+                .filter(move |(name, _)| seen.insert(*name)),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{BTreeMap, HashMap};
+
+    use crate::models::{Context, EnvContext};
+
+    use super::LayeredContext;
+
+    fn map(arguments: &[(&str, &str)]) -> HashMap<String, String> {
+        arguments
+            .iter()
+            .map(|&(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn precedence() {
+        let env = EnvContext::from_vars(
+            "FJ_",
+            [("FJ_GEAR__TEETH".to_string(), "20".to_string())],
+        );
+        let file = map(&[("gear.teeth", "16"), ("gear.module", "0.5")]);
+        let defaults = map(&[
+            ("gear.teeth", "12"),
+            ("gear.module", "1"),
+            ("gear.width", "3"),
+        ]);
+
+        let ctx = LayeredContext::new()
+            .with_source(env)
+            .with_source(file)
+            .with_source(defaults);
+
+        assert_eq!(ctx.get_argument("gear.teeth"), Some("20"));
+        assert_eq!(ctx.get_argument("gear.module"), Some("0.5"));
+        assert_eq!(ctx.get_argument("gear.width"), Some("3"));
+        assert_eq!(ctx.get_argument("gear.hub"), None);
+
+        let arguments: Vec<_> = ctx.arguments().collect();
+        assert_eq!(arguments.len(), 3);
+        assert_eq!(
+            arguments.into_iter().collect::<BTreeMap<_, _>>(),
+            BTreeMap::from([
+                ("gear.module", "0.5"),
+                ("gear.teeth", "20"),
+                ("gear.width", "3"),
+            ])
+        );
+    }
+}
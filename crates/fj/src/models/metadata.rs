@@ -1,3 +1,9 @@
+use std::fmt;
+
+use crate::models::{
+    Context, ContextError, Error, MissingArgument, OutOfRange, ParseFailed,
+};
+
 /// Information about a particular module that can be used by the host for
 /// things like introspection and search.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -167,6 +173,9 @@ impl ModelMetadata {
 }
 
 /// Metadata describing a model's argument.
+///
+/// Hosts can use this to present the arguments to the user, for example as
+/// widgets or as `--help` output, and to check them before running the model.
 #[derive(Debug, Clone, PartialEq)]
 pub struct ArgumentMetadata {
     /// The name used to refer to this argument.
@@ -176,11 +185,18 @@ pub struct ArgumentMetadata {
     pub description: Option<String>,
     /// Something that could be used as a default if no value was provided.
     pub default_value: Option<String>,
+    /// The type of value the argument is parsed into.
+    pub kind: ArgumentKind,
+    /// The range of valid values, for numeric arguments.
+    pub range: Option<ArgumentRange>,
 }
 
 impl ArgumentMetadata {
     /// Create a new [`ArgumentMetadata`].
     ///
+    /// The argument is of kind [`ArgumentKind::String`], until specified
+    /// otherwise.
+    ///
     /// # Panics
     ///
     /// The `name` must not be empty.
@@ -191,6 +207,8 @@ impl ArgumentMetadata {
             name,
             description: None,
             default_value: None,
+            kind: ArgumentKind::String,
+            range: None,
         }
     }
 
@@ -213,6 +231,70 @@ impl ArgumentMetadata {
         self.default_value = Some(default_value.into());
         self
     }
+
+    /// Set the [`ArgumentMetadata::kind`].
+    pub fn with_kind(mut self, kind: ArgumentKind) -> Self {
+        self.kind = kind;
+        self
+    }
+
+    /// Set the [`ArgumentMetadata::range`].
+    pub fn with_range(mut self, min: f64, max: f64) -> Self {
+        self.range = Some(ArgumentRange { min, max });
+        self
+    }
+
+    /// Check the value of this argument, before the model runs.
+    ///
+    /// The argument may only be missing, if there is a default value. If it is
+    /// present, it must parse into the argument's kind, and be within its
+    /// range.
+    pub fn validate(&self, ctx: &dyn Context) -> Result<(), ContextError> {
+        let value = match ctx.get_argument(&self.name) {
+            Some(value) => value,
+            None if self.default_value.is_some() => return Ok(()),
+            None => {
+                return Err(MissingArgument {
+                    name: self.name.clone(),
+                }
+                .into())
+            }
+        };
+
+        let parse_failed = |error: Error| ParseFailed {
+            name: self.name.clone(),
+            value: value.to_owned(),
+            error,
+        };
+        let number = match self.kind {
+            ArgumentKind::Integer => value
+                .parse::<i64>()
+                .map(|number| number as f64)
+                .map_err(|err| parse_failed(err.into()))?,
+            ArgumentKind::Float => value
+                .parse::<f64>()
+                .map_err(|err| parse_failed(err.into()))?,
+            ArgumentKind::Boolean => {
+                value
+                    .parse::<bool>()
+                    .map_err(|err| parse_failed(err.into()))?;
+                return Ok(());
+            }
+            ArgumentKind::String => return Ok(()),
+        };
+
+        match self.range {
+            Some(range) if number < range.min || number > range.max => {
+                Err(OutOfRange {
+                    name: self.name.clone(),
+                    value: number,
+                    range,
+                }
+                .into())
+            }
+            _ => Ok(()),
+        }
+    }
 }
 
 impl From<&str> for ArgumentMetadata {
@@ -220,3 +302,125 @@ impl From<&str> for ArgumentMetadata {
         ArgumentMetadata::new(name)
     }
 }
+
+/// Formats the argument as a line of help text
+impl fmt::Display for ArgumentMetadata {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} ({}", self.name, self.kind)?;
+        if let Some(default_value) = &self.default_value {
+            write!(f, ", default: {default_value}")?;
+        }
+        if let Some(ArgumentRange { min, max }) = self.range {
+            write!(f, ", {min} to {max}")?;
+        }
+        write!(f, ")")?;
+
+        if let Some(description) = &self.description {
+            write!(f, ": {description}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The type of value an argument is parsed into
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(C)]
+pub enum ArgumentKind {
+    /// An integer, like `u32` or `i64`
+    Integer,
+    /// A floating-point number, like `f64`
+    Float,
+    /// A `bool`
+    Boolean,
+    /// Any other value, parsed from its string representation
+    String,
+}
+
+impl fmt::Display for ArgumentKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            ArgumentKind::Integer => "integer",
+            ArgumentKind::Float => "float",
+            ArgumentKind::Boolean => "boolean",
+            ArgumentKind::String => "string",
+        };
+
+        write!(f, "{name}")
+    }
+}
+
+/// The range of valid values of a numeric argument
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(C)]
+pub struct ArgumentRange {
+    /// The smallest valid value
+    pub min: f64,
+    /// The largest valid value
+    pub max: f64,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::models::ContextErrors;
+
+    use super::{ArgumentKind, ArgumentMetadata};
+
+    #[test]
+    fn validate() {
+        let metadata = [
+            ArgumentMetadata::new("teeth")
+                .with_kind(ArgumentKind::Integer)
+                .with_range(3., 200.),
+            ArgumentMetadata::new("width")
+                .with_kind(ArgumentKind::Float)
+                .with_default_value("1.0"),
+            ArgumentMetadata::new("hollow").with_kind(ArgumentKind::Boolean),
+            ArgumentMetadata::new("label"),
+        ];
+
+        let arguments = |arguments: &[(&str, &str)]| {
+            let ctx: HashMap<_, _> = arguments
+                .iter()
+                .map(|&(name, value)| (name.to_owned(), value.to_owned()))
+                .collect();
+
+            let mut errors = ContextErrors::new();
+            errors.validate(&ctx, &metadata);
+            errors.into_result().map_err(|errors| errors.to_string())
+        };
+
+        assert!(arguments(&[
+            ("teeth", "12"),
+            ("hollow", "true"),
+            ("label", "Gear")
+        ])
+        .is_ok());
+
+        let message =
+            arguments(&[("teeth", "2"), ("width", "wide"), ("label", "Gear")])
+                .unwrap_err();
+        assert!(message.contains("Problems with 3 argument(s)"));
+        assert!(message.contains("\"teeth\" argument (2) must be between"));
+        assert!(message.contains("Unable to parse the \"width\" argument"));
+        assert!(message.contains("The \"hollow\" argument was missing"));
+    }
+
+    #[test]
+    fn help_text() {
+        let teeth = ArgumentMetadata::new("teeth")
+            .with_kind(ArgumentKind::Integer)
+            .with_default_value("12")
+            .with_range(3., 200.)
+            .with_description("Number of teeth");
+        assert_eq!(
+            teeth.to_string(),
+            "teeth (integer, default: 12, 3 to 200): Number of teeth"
+        );
+
+        let label = ArgumentMetadata::new("label");
+        assert_eq!(label.to_string(), "label (string)");
+    }
+}
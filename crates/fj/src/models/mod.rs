@@ -1,18 +1,32 @@
 //! Interfaces used when defining models.
 
 mod context;
+mod env_context;
+mod evaluate;
+#[cfg(feature = "file-context")]
+mod file_context;
 mod host;
+mod layered_context;
 mod metadata;
 mod model;
 
 pub use self::{
     context::{
-        Context, ContextError, ContextExt, MissingArgument, ParseFailed,
+        Context, ContextError, ContextErrors, ContextExt, MissingArgument,
+        OutOfRange, ParseFailed,
     },
+    env_context::EnvContext,
+    evaluate::{evaluate_model, ModelPanicked},
     host::{Host, HostExt},
-    metadata::{ArgumentMetadata, Metadata, ModelMetadata},
+    layered_context::LayeredContext,
+    metadata::{
+        ArgumentKind, ArgumentMetadata, ArgumentRange, Metadata, ModelMetadata,
+    },
     model::Model,
 };
 
+#[cfg(feature = "file-context")]
+pub use self::file_context::{FileContext, FileContextError};
+
 /// A generic error used when defining a model.
 pub type Error = Box<dyn std::error::Error + Send + Sync>;
@@ -14,10 +14,57 @@ pub trait Model: Send + Sync {
 
 #[cfg(test)]
 mod tests {
+    use crate::models::{ArgumentKind, ArgumentMetadata, ArgumentRange};
+
     use super::*;
 
     #[test]
     fn model_is_object_safe() {
         let _: &dyn Model;
     }
+
+    struct Gear;
+
+    impl Model for Gear {
+        fn shape(&self, _: &dyn Context) -> Result<Shape, Error> {
+            Ok(crate::Sketch::from_points(vec![]).into())
+        }
+
+        fn metadata(&self) -> ModelMetadata {
+            ModelMetadata::new("gear")
+                .with_argument(
+                    ArgumentMetadata::new("teeth")
+                        .with_kind(ArgumentKind::Integer)
+                        .with_default_value("12")
+                        .with_description("Number of teeth")
+                        .with_range(3., 200.),
+                )
+                .with_argument("name")
+        }
+    }
+
+    #[test]
+    fn argument_metadata() {
+        let expected = [
+            ArgumentMetadata {
+                name: "teeth".into(),
+                description: Some("Number of teeth".into()),
+                default_value: Some("12".into()),
+                kind: ArgumentKind::Integer,
+                range: Some(ArgumentRange { min: 3., max: 200. }),
+            },
+            ArgumentMetadata {
+                name: "name".into(),
+                description: None,
+                default_value: None,
+                kind: ArgumentKind::String,
+                range: None,
+            },
+        ];
+        assert_eq!(Gear.metadata().arguments, expected);
+
+        // Argument metadata survives the trip across the FFI boundary.
+        let model = crate::abi::Model::from(Box::new(Gear) as Box<dyn Model>);
+        assert_eq!(model.metadata().arguments, expected);
+    }
 }
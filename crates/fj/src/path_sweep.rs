@@ -0,0 +1,67 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{abi::ffi_safe, Shape, Shape2d};
+
+/// A sweep of a 2-dimensional shape along an arbitrary path
+///
+/// The path is a polyline. The shape is moved along it, starting at its first
+/// point, and turned with it at every corner.
+///
+/// # Limitations
+///
+/// The path must not reverse its direction. The side faces of the resulting
+/// shape are always approximated, even if the path is straight.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(C)]
+pub struct PathSweep {
+    /// The 2-dimensional shape being swept
+    shape: Shape2d,
+
+    /// The points of the path
+    path: ffi_safe::Vec<[f64; 3]>,
+}
+
+impl PathSweep {
+    /// Create a `PathSweep` along a polyline
+    pub fn from_points(shape: Shape2d, path: Vec<[f64; 3]>) -> Self {
+        Self {
+            shape,
+            path: path.into(),
+        }
+    }
+
+    /// Create a `PathSweep` along a curve
+    ///
+    /// The curve is evaluated at `segments + 1` evenly spaced parameters from
+    /// `0` to `1`, and the shape is swept along the resulting polyline.
+    pub fn from_curve(
+        shape: Shape2d,
+        segments: usize,
+        curve: impl Fn(f64) -> [f64; 3],
+    ) -> Self {
+        let segments = segments.max(1);
+        let path = (0..=segments)
+            .map(|i| curve(i as f64 / segments as f64))
+            .collect();
+
+        Self::from_points(shape, path)
+    }
+
+    /// Access the shape being swept
+    pub fn shape(&self) -> &Shape2d {
+        &self.shape
+    }
+
+    /// Access the points of the path
+    pub fn path(&self) -> &[[f64; 3]] {
+        &self.path
+    }
+}
+
+impl From<PathSweep> for Shape {
+    fn from(shape: PathSweep) -> Self {
+        Self::PathSweep(shape)
+    }
+}
@@ -0,0 +1,59 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{Angle, Shape};
+
+/// A 3-dimensional shape, repeated along a straight line
+///
+/// The first instance is the shape itself. Each further instance is offset from
+/// the previous one by `spacing`.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(C)]
+pub struct LinearPattern {
+    /// The shape being repeated
+    pub shape: Shape,
+
+    /// The number of instances
+    pub count: u32,
+
+    /// The offset between two neighboring instances
+    pub spacing: [f64; 3],
+}
+
+impl From<LinearPattern> for Shape {
+    fn from(shape: LinearPattern) -> Self {
+        Self::LinearPattern(Box::new(shape))
+    }
+}
+
+/// A 3-dimensional shape, repeated around an axis
+///
+/// The axis goes through `origin`, in the direction of `axis`. The first
+/// instance is the shape itself. Each further instance is rotated from the
+/// previous one by `angle`.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(C)]
+pub struct CircularPattern {
+    /// The shape being repeated
+    pub shape: Shape,
+
+    /// The number of instances
+    pub count: u32,
+
+    /// A point on the axis
+    pub origin: [f64; 3],
+
+    /// The direction of the axis
+    pub axis: [f64; 3],
+
+    /// The angle between two neighboring instances
+    pub angle: Angle,
+}
+
+impl From<CircularPattern> for Shape {
+    fn from(shape: CircularPattern) -> Self {
+        Self::CircularPattern(Box::new(shape))
+    }
+}
@@ -0,0 +1,81 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{Angle, Shape, Shape2d};
+
+/// A revolution of a 2-dimensional shape around an axis
+///
+/// The shape is rotated counterclockwise around the axis, as seen from the
+/// axis' tip. Unless it is rotated by a full revolution, its start and end
+/// positions cap off the resulting shape.
+///
+/// # Limitations
+///
+/// The shape must not cross the axis, but it may touch it. Since [`Angle`]
+/// wraps around, a full revolution is represented by an angle of zero.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(C)]
+pub struct Revolve {
+    /// The 2-dimensional shape being revolved
+    shape: Shape2d,
+
+    /// A point on the axis of revolution
+    axis_origin: [f64; 3],
+
+    /// The direction of the axis of revolution
+    axis: [f64; 3],
+
+    /// The angle of the revolution
+    angle: Angle,
+}
+
+impl Revolve {
+    /// Create a `Revolve` by the given angle
+    pub fn from_angle(
+        shape: Shape2d,
+        axis_origin: [f64; 3],
+        axis: [f64; 3],
+        angle: Angle,
+    ) -> Self {
+        Self {
+            shape,
+            axis_origin,
+            axis,
+            angle,
+        }
+    }
+
+    /// Create a `Revolve` by a full revolution
+    pub fn full(shape: Shape2d, axis_origin: [f64; 3], axis: [f64; 3]) -> Self {
+        Self::from_angle(shape, axis_origin, axis, Angle::from_rad(0.))
+    }
+
+    /// Access the shape being revolved
+    pub fn shape(&self) -> &Shape2d {
+        &self.shape
+    }
+
+    /// Access the point on the axis of revolution
+    pub fn axis_origin(&self) -> [f64; 3] {
+        self.axis_origin
+    }
+
+    /// Access the direction of the axis of revolution
+    pub fn axis(&self) -> [f64; 3] {
+        self.axis
+    }
+
+    /// Access the angle of the revolution
+    ///
+    /// An angle of zero means a full revolution.
+    pub fn angle(&self) -> Angle {
+        self.angle
+    }
+}
+
+impl From<Revolve> for Shape {
+    fn from(shape: Revolve) -> Self {
+        Self::Revolve(shape)
+    }
+}
@@ -3,7 +3,7 @@ use serde::{de, ser, Deserialize, Serialize};
 use std::mem;
 use std::sync::atomic;
 
-use crate::Shape;
+use crate::{abi::ffi_safe, Shape, Text};
 
 /// A 2-dimensional shape
 #[derive(Clone, Debug, PartialEq)]
@@ -15,6 +15,9 @@ pub enum Shape2d {
 
     /// A sketch
     Sketch(Sketch),
+
+    /// The outlines of a text
+    Text(Text),
 }
 
 impl Shape2d {
@@ -23,6 +26,7 @@ impl Shape2d {
         match &self {
             Shape2d::Sketch(s) => s.color(),
             Shape2d::Difference(d) => d.color(),
+            Shape2d::Text(t) => t.color(),
         }
     }
 }
@@ -66,18 +70,22 @@ impl From<Difference2d> for Shape2d {
 
 /// A sketch
 ///
-/// Sketches are currently limited to a single cycle of straight lines,
-/// represented by a number of points. For example, if the points a, b, and c
-/// are provided, the edges ab, bc, and ca are assumed.
+/// Sketches are currently limited to a single exterior cycle, plus any number
+/// of holes. Each cycle can be a circle, an ellipse, a number of points that are
+/// connected
+/// by straight lines, or a chain of lines, arcs, and curves. For example, if the
+/// points a, b, and c are provided, the edges ab, bc, and ca are assumed.
 ///
 /// Nothing about these edges is checked right now, but algorithms might assume
-/// that the edges are non-overlapping. If you create a `Sketch` with
-/// overlapping edges, you're on your own.
+/// that the edges are non-overlapping, and that the holes are within the
+/// exterior cycle, without overlapping each other. If you create a `Sketch`
+/// that violates this, you're on your own.
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[repr(C)]
 pub struct Sketch {
     chain: Chain,
+    holes: ffi_safe::Vec<Chain>,
 
     // The color of the sketch in RGBA
     color: [u8; 4],
@@ -88,6 +96,7 @@ impl Sketch {
     pub fn from_points(points: Vec<[f64; 2]>) -> Self {
         Self {
             chain: Chain::PolyChain(PolyChain::from_points(points)),
+            holes: ffi_safe::Vec::default(),
             color: [255, 0, 0, 255],
         }
     }
@@ -96,10 +105,48 @@ impl Sketch {
     pub fn from_circle(circle: Circle) -> Self {
         Self {
             chain: Chain::Circle(circle),
+            holes: ffi_safe::Vec::default(),
+            color: [255, 0, 0, 255],
+        }
+    }
+
+    /// Create a sketch from an ellipse
+    pub fn from_ellipse(ellipse: Ellipse) -> Self {
+        Self {
+            chain: Chain::Ellipse(ellipse),
+            holes: ffi_safe::Vec::default(),
             color: [255, 0, 0, 255],
         }
     }
 
+    /// Create a sketch from a chain of lines, arcs, and curves
+    ///
+    /// See [`SegmentChain`].
+    pub fn from_segments(
+        start: [f64; 2],
+        segments: Vec<SketchSegment>,
+    ) -> Self {
+        Self {
+            chain: Chain::Segments(SegmentChain::from_segments(
+                start, segments,
+            )),
+            holes: ffi_safe::Vec::default(),
+            color: [255, 0, 0, 255],
+        }
+    }
+
+    /// Add a hole to the sketch
+    ///
+    /// The hole is removed from the area within the sketch's chain. Its
+    /// winding doesn't matter.
+    pub fn with_hole(mut self, hole: impl Into<Chain>) -> Self {
+        let mut holes = self.holes.to_vec();
+        holes.push(hole.into());
+
+        self.holes = holes.into();
+        self
+    }
+
     /// Set the rendering color of the sketch in RGBA
     pub fn with_color(mut self, color: [u8; 4]) -> Self {
         self.color = color;
@@ -111,6 +158,11 @@ impl Sketch {
         &self.chain
     }
 
+    /// Access the holes of the sketch
+    pub fn holes(&self) -> &[Chain] {
+        &self.holes
+    }
+
     /// Get the rendering color of the sketch in RGBA
     pub fn color(&self) -> [u8; 4] {
         self.color
@@ -125,8 +177,38 @@ pub enum Chain {
     /// The chain is a circle
     Circle(Circle),
 
+    /// The chain is an ellipse
+    Ellipse(Ellipse),
+
     /// The chain is a polygonal chain
     PolyChain(PolyChain),
+
+    /// The chain is made up of lines, arcs, and curves
+    Segments(SegmentChain),
+}
+
+impl From<Circle> for Chain {
+    fn from(circle: Circle) -> Self {
+        Self::Circle(circle)
+    }
+}
+
+impl From<Ellipse> for Chain {
+    fn from(ellipse: Ellipse) -> Self {
+        Self::Ellipse(ellipse)
+    }
+}
+
+impl From<PolyChain> for Chain {
+    fn from(poly_chain: PolyChain) -> Self {
+        Self::PolyChain(poly_chain)
+    }
+}
+
+impl From<SegmentChain> for Chain {
+    fn from(chain: SegmentChain) -> Self {
+        Self::Segments(chain)
+    }
 }
 
 /// A circle that is part of a [`Sketch`]
@@ -150,6 +232,168 @@ impl Circle {
     }
 }
 
+/// An ellipse that is part of a [`Sketch`]
+///
+/// The axes of the ellipse are aligned with the x and y axes.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(C)]
+pub struct Ellipse {
+    center: [f64; 2],
+    radii: [f64; 2],
+}
+
+impl Ellipse {
+    /// Construct a new ellipse around the origin, from its x and y radii
+    pub fn from_radii(radii: [f64; 2]) -> Self {
+        Self {
+            center: [0., 0.],
+            radii,
+        }
+    }
+
+    /// Move the ellipse to a different center
+    pub fn with_center(mut self, center: [f64; 2]) -> Self {
+        self.center = center;
+        self
+    }
+
+    /// Access the center of the ellipse
+    pub fn center(&self) -> [f64; 2] {
+        self.center
+    }
+
+    /// Access the x and y radii of the ellipse
+    pub fn radii(&self) -> [f64; 2] {
+        self.radii
+    }
+}
+
+/// A chain of lines, arcs, and curves that is part of a [`Sketch`]
+///
+/// The chain begins at its start point, and every segment continues from where
+/// the previous one ended. If the last segment doesn't end at the start point,
+/// a straight line closes the chain.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(C)]
+pub struct SegmentChain {
+    start: [f64; 2],
+    segments: ffi_safe::Vec<SketchSegment>,
+}
+
+impl SegmentChain {
+    /// Construct an instance from a start point and a list of segments
+    pub fn from_segments(
+        start: [f64; 2],
+        segments: Vec<SketchSegment>,
+    ) -> Self {
+        Self {
+            start,
+            segments: segments.into(),
+        }
+    }
+
+    /// Access the start point of the chain
+    pub fn start(&self) -> [f64; 2] {
+        self.start
+    }
+
+    /// Access the segments of the chain
+    pub fn segments(&self) -> &[SketchSegment] {
+        &self.segments
+    }
+}
+
+/// A segment of a [`SegmentChain`]
+///
+/// Each segment starts where the previous one ended, so only its end point is
+/// stored.
+///
+/// Bézier curves and B-splines are approximated by straight lines, according
+/// to the tolerance that the model is processed with.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(C)]
+pub enum SketchSegment {
+    /// A straight line
+    Line {
+        /// The end point of the line
+        end: [f64; 2],
+    },
+
+    /// A circular arc, defined by its center
+    ///
+    /// The start point of the arc must have the same distance from the center
+    /// as its end point.
+    Arc {
+        /// The end point of the arc
+        end: [f64; 2],
+
+        /// The center of the arc
+        center: [f64; 2],
+
+        /// Whether the arc goes counterclockwise around the center
+        counterclockwise: bool,
+    },
+
+    /// A circular arc, defined by a point it passes through
+    ArcThrough {
+        /// The end point of the arc
+        end: [f64; 2],
+
+        /// A point on the arc, between its start and end points
+        via: [f64; 2],
+    },
+
+    /// A parabolic segment, defined like a quadratic Bézier curve
+    Parabola {
+        /// The end point of the segment
+        end: [f64; 2],
+
+        /// The control point that pulls the segment towards it
+        control: [f64; 2],
+    },
+
+    /// A cubic Bézier curve
+    Bezier {
+        /// The end point of the curve
+        end: [f64; 2],
+
+        /// The control points that pull the curve towards them
+        controls: [[f64; 2]; 2],
+    },
+
+    /// A cubic B-spline
+    ///
+    /// The curve passes through its start and end points, and is pulled
+    /// towards the control points in between. With fewer than two control
+    /// points, the degree of the curve is reduced accordingly.
+    BSpline {
+        /// The end point of the curve
+        end: [f64; 2],
+
+        /// The control points between the start and end points
+        ///
+        /// Can be created from a `Vec`, using `.into()`.
+        controls: ffi_safe::Vec<[f64; 2]>,
+    },
+}
+
+impl SketchSegment {
+    /// Access the end point of the segment
+    pub fn end(&self) -> [f64; 2] {
+        match self {
+            Self::Line { end } => *end,
+            Self::Arc { end, .. } => *end,
+            Self::ArcThrough { end, .. } => *end,
+            Self::Parabola { end, .. } => *end,
+            Self::Bezier { end, .. } => *end,
+            Self::BSpline { end, .. } => *end,
+        }
+    }
+}
+
 /// A polygonal chain that is part of a [`Sketch`]
 #[derive(Debug)]
 #[repr(C)]
@@ -28,6 +28,34 @@ where
     }
 }
 
+/// Convenient syntax to create an [`fj::Difference3d`]
+///
+/// [`fj::Difference3d`]: crate::Difference3d
+pub trait Difference3d {
+    /// Create a difference between `self` and `other`
+    ///
+    /// This is named differently from [`Difference::difference`], as sketches
+    /// can be used in both kinds of differences.
+    fn difference_3d<Other>(&self, other: &Other) -> crate::Difference3d
+    where
+        Other: Clone + Into<crate::Shape>;
+}
+
+impl<T> Difference3d for T
+where
+    T: Clone + Into<crate::Shape>,
+{
+    fn difference_3d<Other>(&self, other: &Other) -> crate::Difference3d
+    where
+        Other: Clone + Into<crate::Shape>,
+    {
+        let a = self.clone().into();
+        let b = other.clone().into();
+
+        crate::Difference3d::from_shapes([a, b])
+    }
+}
+
 /// Convenient syntax to create an [`fj::Group`]
 ///
 /// [`fj::Group`]: crate::Group
@@ -53,6 +81,31 @@ where
     }
 }
 
+/// Convenient syntax to create an [`fj::Intersection`]
+///
+/// [`fj::Intersection`]: crate::Intersection
+pub trait Intersection {
+    /// Create an intersection of `self` and `other`
+    fn intersection<Other>(&self, other: &Other) -> crate::Intersection
+    where
+        Other: Clone + Into<crate::Shape>;
+}
+
+impl<T> Intersection for T
+where
+    T: Clone + Into<crate::Shape>,
+{
+    fn intersection<Other>(&self, other: &Other) -> crate::Intersection
+    where
+        Other: Clone + Into<crate::Shape>,
+    {
+        let a = self.clone().into();
+        let b = other.clone().into();
+
+        crate::Intersection { a, b }
+    }
+}
+
 /// Convenient syntax to create an [`fj::Sketch`]
 ///
 /// [`fj::Sketch`]: crate::Sketch
@@ -73,12 +126,133 @@ where
     }
 }
 
-/// Convenient syntax to create an [`fj::Sweep`]
+/// Convenient syntax to create an [`fj::Mirror`]
+///
+/// [`fj::Mirror`]: crate::Mirror
+pub trait Mirror {
+    /// Reflect a shape across a plane
+    ///
+    /// The plane is defined by its `normal` and its distance from the origin,
+    /// `offset`.
+    fn mirror(&self, normal: [f64; 3], offset: f64) -> crate::Mirror;
+}
+
+impl<T> Mirror for T
+where
+    T: Clone + Into<crate::Shape>,
+{
+    fn mirror(&self, normal: [f64; 3], offset: f64) -> crate::Mirror {
+        let shape = self.clone().into();
+        crate::Mirror {
+            shape,
+            normal,
+            offset,
+        }
+    }
+}
+
+/// Convenient syntax to create an [`fj::LinearPattern`] or
+/// [`fj::CircularPattern`]
+///
+/// [`fj::LinearPattern`]: crate::LinearPattern
+/// [`fj::CircularPattern`]: crate::CircularPattern
+pub trait Pattern {
+    /// Repeat a shape `count` times, each instance offset by `spacing`
+    fn linear_pattern(
+        &self,
+        count: u32,
+        spacing: [f64; 3],
+    ) -> crate::LinearPattern;
+
+    /// Repeat a shape `count` times around an axis
+    ///
+    /// The axis goes through `origin`, in the direction of `axis`. Each
+    /// instance is rotated by `angle` from the previous one.
+    fn circular_pattern(
+        &self,
+        count: u32,
+        origin: [f64; 3],
+        axis: [f64; 3],
+        angle: crate::Angle,
+    ) -> crate::CircularPattern;
+}
+
+impl<T> Pattern for T
+where
+    T: Clone + Into<crate::Shape>,
+{
+    fn linear_pattern(
+        &self,
+        count: u32,
+        spacing: [f64; 3],
+    ) -> crate::LinearPattern {
+        let shape = self.clone().into();
+        crate::LinearPattern {
+            shape,
+            count,
+            spacing,
+        }
+    }
+
+    fn circular_pattern(
+        &self,
+        count: u32,
+        origin: [f64; 3],
+        axis: [f64; 3],
+        angle: crate::Angle,
+    ) -> crate::CircularPattern {
+        let shape = self.clone().into();
+        crate::CircularPattern {
+            shape,
+            count,
+            origin,
+            axis,
+            angle,
+        }
+    }
+}
+
+/// Convenient syntax to create an [`fj::Revolve`]
+///
+/// [`fj::Revolve`]: crate::Revolve
+pub trait Revolve {
+    /// Revolve `self` by `angle` around an axis
+    ///
+    /// The axis goes through `origin`, in the direction of `axis`. An angle of
+    /// zero means a full revolution.
+    fn revolve(
+        &self,
+        origin: [f64; 3],
+        axis: [f64; 3],
+        angle: crate::Angle,
+    ) -> crate::Revolve;
+}
+
+impl<T> Revolve for T
+where
+    T: Clone + Into<crate::Shape2d>,
+{
+    fn revolve(
+        &self,
+        origin: [f64; 3],
+        axis: [f64; 3],
+        angle: crate::Angle,
+    ) -> crate::Revolve {
+        let shape = self.clone().into();
+        crate::Revolve::from_angle(shape, origin, axis, angle)
+    }
+}
+
+/// Convenient syntax to create an [`fj::Sweep`] or [`fj::PathSweep`]
 ///
 /// [`fj::Sweep`]: crate::Sweep
+/// [`fj::PathSweep`]: crate::PathSweep
 pub trait Sweep {
     /// Sweep `self` along a straight path
     fn sweep(&self, path: [f64; 3]) -> crate::Sweep;
+
+    /// Sweep `self` along a polyline
+    fn sweep_along(&self, path: &[[f64; 3]]) -> crate::PathSweep;
 }
 
 impl<T> Sweep for T
@@ -89,6 +263,11 @@ where
         let shape = self.clone().into();
         crate::Sweep::from_path(shape, path)
     }
+
+    fn sweep_along(&self, path: &[[f64; 3]]) -> crate::PathSweep {
+        let shape = self.clone().into();
+        crate::PathSweep::from_points(shape, path.to_vec())
+    }
 }
 
 /// Convenient syntax to create an [`fj::Transform`]
@@ -105,6 +284,11 @@ pub trait Transform {
     ///
     /// Create a translation that translates `shape` by `offset`.
     fn translate(&self, offset: [f64; 3]) -> crate::Transform;
+
+    /// Create a scaling
+    ///
+    /// Create a scaling that scales `shape` uniformly by `factor`.
+    fn scale(&self, factor: f64) -> crate::Transform;
 }
 
 impl<T> Transform for T
@@ -118,6 +302,7 @@ where
             axis,
             angle,
             offset: [0.; 3],
+            scale: 1.,
         }
     }
 
@@ -128,6 +313,43 @@ where
             axis: [1., 0., 0.],
             angle: crate::Angle::from_rad(0.),
             offset,
+            scale: 1.,
+        }
+    }
+
+    fn scale(&self, factor: f64) -> crate::Transform {
+        let shape = self.clone().into();
+        crate::Transform {
+            shape,
+            axis: [1., 0., 0.],
+            angle: crate::Angle::from_rad(0.),
+            offset: [0.; 3],
+            scale: factor,
         }
     }
 }
+
+/// Convenient syntax to create an [`fj::Union`]
+///
+/// [`fj::Union`]: crate::Union
+pub trait Union {
+    /// Create a union of `self` and `other`
+    fn union<Other>(&self, other: &Other) -> crate::Union
+    where
+        Other: Clone + Into<crate::Shape>;
+}
+
+impl<T> Union for T
+where
+    T: Clone + Into<crate::Shape>,
+{
+    fn union<Other>(&self, other: &Other) -> crate::Union
+    where
+        Other: Clone + Into<crate::Shape>,
+    {
+        let a = self.clone().into();
+        let b = other.clone().into();
+
+        crate::Union { a, b }
+    }
+}
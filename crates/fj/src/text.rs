@@ -0,0 +1,84 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{abi::ffi_safe, Shape, Shape2d};
+
+/// A line of text, converted into the outlines of its glyphs
+///
+/// The text starts at the origin, with the baseline of the first line on the
+/// x-axis. A newline moves the following text down by one line.
+///
+/// The font is provided as the contents of a TrueType or OpenType file, for
+/// example by using `include_bytes!`.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(C)]
+pub struct Text {
+    text: ffi_safe::String,
+    font: ffi_safe::Vec<u8>,
+
+    size: f64,
+
+    // The color of the text in RGBA
+    color: [u8; 4],
+}
+
+impl Text {
+    /// Create text from a string and a font
+    ///
+    /// `size` is the height of the font's em square, which is roughly the
+    /// distance between the lowest descender and the highest ascender.
+    pub fn new(
+        text: impl Into<String>,
+        font: impl Into<Vec<u8>>,
+        size: f64,
+    ) -> Self {
+        let text: String = text.into();
+        let font: Vec<u8> = font.into();
+
+        Self {
+            text: text.into(),
+            font: font.into(),
+            size,
+            color: [255, 0, 0, 255],
+        }
+    }
+
+    /// Set the rendering color of the text in RGBA
+    pub fn with_color(mut self, color: [u8; 4]) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Access the text
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Access the contents of the font file
+    pub fn font(&self) -> &[u8] {
+        &self.font
+    }
+
+    /// Access the size of the text
+    pub fn size(&self) -> f64 {
+        self.size
+    }
+
+    /// Get the rendering color of the text in RGBA
+    pub fn color(&self) -> [u8; 4] {
+        self.color
+    }
+}
+
+impl From<Text> for Shape {
+    fn from(shape: Text) -> Self {
+        Self::Shape2d(shape.into())
+    }
+}
+
+impl From<Text> for Shape2d {
+    fn from(shape: Text) -> Self {
+        Self::Text(shape)
+    }
+}
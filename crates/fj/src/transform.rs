@@ -5,13 +5,12 @@ use crate::{Angle, Shape};
 
 /// A transformed 3-dimensional shape
 ///
-/// # Limitations
+/// The shape is scaled first, then rotated, then translated.
 ///
-/// Transformations are currently limited to a rotation, followed by a
-/// translation.
+/// # Limitations
 ///
-/// See issue:
-/// <https://github.com/hannobraun/Fornjot/issues/101>
+/// Only uniform scaling is supported, as circles would turn into ellipses
+/// otherwise, which the kernel can't represent.
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[repr(C)]
@@ -27,6 +26,12 @@ pub struct Transform {
 
     /// The offset of the translation
     pub offset: [f64; 3],
+
+    /// The factor of the scaling
+    ///
+    /// Must not be zero. A negative factor mirrors the shape through the
+    /// origin.
+    pub scale: f64,
 }
 
 impl From<Transform> for Shape {
@@ -0,0 +1,34 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::Shape;
+
+/// The union of two 3-dimensional shapes
+///
+/// Unlike a [`Group`], the shapes may overlap. Where they do, they are merged
+/// into a single solid.
+///
+/// # Limitations
+///
+/// The shapes are combined using the boolean operations of the kernel, which
+/// come with some limitations. See the [documentation of the `boolean` module]
+/// in `fj-kernel`.
+///
+/// [documentation of the `boolean` module]: https://github.com/hannobraun/Fornjot/blob/main/crates/fj-kernel/src/algorithms/boolean/mod.rs
+/// [`Group`]: crate::Group
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(C)]
+pub struct Union {
+    /// The first of the shapes
+    pub a: Shape,
+
+    /// The second of the shapes
+    pub b: Shape,
+}
+
+impl From<Union> for Shape {
+    fn from(shape: Union) -> Self {
+        Self::Union(Box::new(shape))
+    }
+}